@@ -0,0 +1,256 @@
+//! Generic proxy for the daemon's REST API. A new `/api/v1` route shows
+//! up in the UI as soon as it's added to its prefix's allowlist entry
+//! below, without a bespoke Tauri command for every endpoint.
+//!
+//! The three commands with bespoke wrappers below also implement
+//! [`crate::offline::GraphSource`] on [`DaemonState`] and route through
+//! [`crate::offline::OfflineStore`] when the daemon can't be reached —
+//! see that module for the offline story.
+
+use crate::offline::{self, GraphSource, OfflineStore};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::State;
+
+const ALLOWED_PREFIXES: &[&str] =
+    &["/api/v1/proposals", "/api/v1/nodes", "/api/v1/edges", "/api/v1/search", "/api/v1/status", "/api/v1/matter"];
+
+pub struct DaemonState {
+    base_url: Mutex<String>,
+    /// Which project the daemon should serve requests for, sent as
+    /// [`snps_daemon::PROJECT_HEADER`] on every request — see
+    /// `lifecycle::resolve_workspace_root`, which is what sets this.
+    workspace_root: Mutex<PathBuf>,
+    client: reqwest::Client,
+    /// The daemon process we spawned, if any — used as a last resort by
+    /// `stop_daemon` when the graceful shutdown endpoint isn't reachable.
+    pub(crate) child: Mutex<Option<std::process::Child>>,
+}
+
+impl DaemonState {
+    pub fn new(base_url: impl Into<String>, workspace_root: PathBuf) -> Self {
+        Self {
+            base_url: Mutex::new(base_url.into()),
+            workspace_root: Mutex::new(workspace_root),
+            client: reqwest::Client::builder().timeout(Duration::from_secs(10)).build().expect("reqwest client"),
+            child: Mutex::new(None),
+        }
+    }
+
+    fn base_url(&self) -> String {
+        self.base_url.lock().unwrap().clone()
+    }
+
+    pub(crate) fn set_base_url(&self, base_url: impl Into<String>) {
+        *self.base_url.lock().unwrap() = base_url.into();
+    }
+
+    fn workspace_root(&self) -> PathBuf {
+        self.workspace_root.lock().unwrap().clone()
+    }
+
+    pub(crate) fn set_workspace_root(&self, workspace_root: PathBuf) {
+        *self.workspace_root.lock().unwrap() = workspace_root;
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DaemonResponse {
+    pub status: u16,
+    pub body: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DaemonError {
+    pub code: String,
+    pub message: String,
+}
+
+fn is_allowed(path: &str) -> bool {
+    ALLOWED_PREFIXES.iter().any(|prefix| path.starts_with(prefix))
+}
+
+/// Forward `method path body_json` to the daemon and return its response
+/// verbatim as `{status, body}`. `path` must start with an allowed
+/// `/api/v1/...` prefix; anything else is rejected before it ever hits
+/// the network.
+#[tauri::command]
+pub async fn daemon_request(
+    state: State<'_, DaemonState>,
+    method: String,
+    path: String,
+    body_json: Option<String>,
+) -> Result<DaemonResponse, DaemonError> {
+    send(&state, &method, &path, body_json).await
+}
+
+/// The core of [`daemon_request`], taking `&DaemonState` directly rather
+/// than a `State` wrapper so [`crate::offline::GraphSource`]'s daemon
+/// implementation can call it too, without every offline-aware command
+/// needing to go through the generic proxy command.
+pub(crate) async fn send(state: &DaemonState, method: &str, path: &str, body_json: Option<String>) -> Result<DaemonResponse, DaemonError> {
+    if !is_allowed(path) {
+        return Err(DaemonError { code: "path_not_allowed".into(), message: format!("'{path}' is not an allowed daemon endpoint") });
+    }
+
+    let method: reqwest::Method = method
+        .parse()
+        .map_err(|_| DaemonError { code: "bad_method".into(), message: format!("unsupported HTTP method '{method}'") })?;
+
+    let url = format!("{}{}", state.base_url(), path);
+    // Lets the daemon credit any graph node this request creates or
+    // imports to "desktop" rather than a bare "daemon-api" source.
+    let mut request = state
+        .client
+        .request(method, &url)
+        .header("x-pmsynapse-client", "desktop")
+        .header(snps_daemon::PROJECT_HEADER, state.workspace_root().to_string_lossy().into_owned());
+    if let Some(body) = body_json {
+        request = request.header("content-type", "application/json").body(body);
+    }
+
+    let response = request.send().await.map_err(|e| map_request_error(&e))?;
+    let status = response.status().as_u16();
+    let body: serde_json::Value = response.json().await.unwrap_or(serde_json::Value::Null);
+    Ok(DaemonResponse { status, body })
+}
+
+/// Timeouts and connection-refused get their own codes so the UI can
+/// react (e.g. prompt to start the daemon) instead of showing raw text.
+pub(crate) fn map_request_error(err: &reqwest::Error) -> DaemonError {
+    if err.is_timeout() {
+        DaemonError { code: "timeout".into(), message: "daemon did not respond in time".into() }
+    } else if err.is_connect() {
+        DaemonError { code: "connection_refused".into(), message: "could not reach the daemon — is it running?".into() }
+    } else {
+        DaemonError { code: "request_failed".into(), message: err.to_string() }
+    }
+}
+
+/// Typed wrapper over the paginated node list, one of the hot paths the
+/// UI already had a bespoke command for. Falls back to the embedded
+/// offline mirror when the daemon can't be reached — see
+/// [`crate::offline`] for what "falls back" means here.
+#[tauri::command]
+pub async fn get_knowledge_graph(
+    state: State<'_, DaemonState>,
+    offline: State<'_, OfflineStore>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+    order_by: Option<String>,
+    node_type: Option<String>,
+) -> Result<DaemonResponse, DaemonError> {
+    offline::replay_pending(&state, &offline).await;
+    match state.list_nodes(limit, offset, order_by.as_deref(), node_type.as_deref()).await {
+        Ok(resp) => {
+            offline::remember_nodes(&offline, limit, offset, order_by.as_deref(), node_type.as_deref(), &resp.body);
+            Ok(resp)
+        }
+        Err(e) if offline::should_fall_back(&e) => offline.list_nodes(limit, offset, order_by.as_deref(), node_type.as_deref()).await,
+        Err(e) => Err(e),
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CreateNodeRequest {
+    pub node_type: String,
+    pub title: String,
+    pub content: String,
+}
+
+/// Create a node through the daemon, queuing it in the offline mirror
+/// instead when the daemon can't be reached. See [`crate::offline`].
+#[tauri::command]
+pub async fn create_node(
+    state: State<'_, DaemonState>,
+    offline: State<'_, OfflineStore>,
+    node: CreateNodeRequest,
+) -> Result<DaemonResponse, DaemonError> {
+    offline::replay_pending(&state, &offline).await;
+    match state.create_node(&node).await {
+        Ok(resp) => Ok(resp),
+        Err(e) if offline::should_fall_back(&e) => offline.create_node(&node).await,
+        Err(e) => Err(e),
+    }
+}
+
+/// Typed wrapper over `GET /api/v1/idlc/items`, with the same
+/// daemon-then-offline-mirror routing as [`get_knowledge_graph`].
+#[tauri::command]
+pub async fn list_idlc_items(
+    state: State<'_, DaemonState>,
+    offline: State<'_, OfflineStore>,
+    team: Option<String>,
+) -> Result<DaemonResponse, DaemonError> {
+    offline::replay_pending(&state, &offline).await;
+    match state.list_idlc_items(team.as_deref()).await {
+        Ok(resp) => {
+            offline::remember_idlc_items(&offline, team.as_deref(), &resp.body);
+            Ok(resp)
+        }
+        Err(e) if offline::should_fall_back(&e) => offline.list_idlc_items(team.as_deref()).await,
+        Err(e) => Err(e),
+    }
+}
+
+/// Typed wrapper over `GET /api/v1/matter`, for the documents panel.
+#[tauri::command]
+pub async fn search_matter(
+    state: State<'_, DaemonState>,
+    query: Option<String>,
+    matter_type: Option<String>,
+    tags: Option<String>,
+    limit: Option<u32>,
+) -> Result<DaemonResponse, DaemonError> {
+    let mut params = Vec::new();
+    if let Some(query) = query {
+        params.push(format!("query={}", urlencoding_lite(&query)));
+    }
+    if let Some(matter_type) = matter_type {
+        params.push(format!("type={matter_type}"));
+    }
+    if let Some(tags) = tags {
+        params.push(format!("tags={}", urlencoding_lite(&tags)));
+    }
+    if let Some(limit) = limit {
+        params.push(format!("limit={limit}"));
+    }
+
+    let path = if params.is_empty() { "/api/v1/matter".to_string() } else { format!("/api/v1/matter?{}", params.join("&")) };
+    daemon_request(state, "GET".into(), path, None).await
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateMatterRequest {
+    #[serde(rename = "type")]
+    pub matter_type: String,
+    pub title: String,
+    pub author: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub context: Option<String>,
+    pub content: Option<String>,
+}
+
+#[tauri::command]
+pub async fn create_matter(state: State<'_, DaemonState>, document: CreateMatterRequest) -> Result<DaemonResponse, DaemonError> {
+    let body =
+        serde_json::to_string(&document).map_err(|e| DaemonError { code: "invalid_request".into(), message: e.to_string() })?;
+    daemon_request(state, "POST".into(), "/api/v1/matter".into(), Some(body)).await
+}
+
+/// Minimal query-param escaping — just enough for the free-text search
+/// and tag values this command sends; not a general URL encoder.
+fn urlencoding_lite(raw: &str) -> String {
+    raw.bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~' | b',') {
+                (b as char).to_string()
+            } else {
+                format!("%{b:02X}")
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,224 @@
+//! Start/stop the daemon from the desktop UI instead of requiring a
+//! terminal. PID-file bookkeeping is `snps_daemon::pid`, not duplicated
+//! here — this module only locates the binary, spawns/polls it, and
+//! prefers the graceful HTTP shutdown route over killing the process.
+
+use crate::daemon::{DaemonError, DaemonState};
+use serde::Serialize;
+use snps_daemon::pid::{self, DaemonPid};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::State;
+
+/// Resolve which project the daemon should serve. There's no profile
+/// registry yet (that lands with a dedicated multi-profile backlog
+/// item) — for now a profile is just treated as a path, defaulting to
+/// the current directory.
+pub(crate) fn resolve_workspace_root(profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+    }
+}
+
+fn pmsynapse_dir(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(".pmsynapse")
+}
+
+/// The profile in effect for this invocation: an explicit argument wins,
+/// falling back to `PMSYNAPSE_PROFILE` (set by whatever launched the dev
+/// daemon this session should talk to).
+fn effective_profile(profile: Option<String>) -> Option<String> {
+    profile.or_else(|| std::env::var("PMSYNAPSE_PROFILE").ok())
+}
+
+/// Where the daemon's port comes from, for the "which source won" log
+/// line — there's no logging framework wired up yet, so this prints to
+/// stderr directly, consistent with the rest of this crate's diagnostics.
+enum PortSource {
+    ProfiledPidFile,
+    DefaultPidFile,
+    EnvVar,
+}
+
+impl PortSource {
+    fn describe(&self) -> &'static str {
+        match self {
+            PortSource::ProfiledPidFile => "profiled daemon.pid",
+            PortSource::DefaultPidFile => "default daemon.pid",
+            PortSource::EnvVar => "PMSYNAPSE_DAEMON_PORT",
+        }
+    }
+}
+
+/// Resolve the daemon's URL for `profile`, in priority order:
+/// `daemon-<profile>.pid`, then the unprofiled `daemon.pid`, then the
+/// `PMSYNAPSE_DAEMON_PORT` env var. Logs which source won so a
+/// mismatched profile is obvious instead of a silent connection refusal.
+pub fn get_daemon_url(workspace_root: &Path, profile: Option<&str>) -> Option<String> {
+    let dir = pmsynapse_dir(workspace_root);
+
+    if let Some(p) = profile {
+        if let Some(daemon) = pid::read_pid_file_for_profile(&dir, Some(p)) {
+            eprintln!("resolved daemon port from {} (profile '{p}')", PortSource::ProfiledPidFile.describe());
+            return Some(format!("http://127.0.0.1:{}", daemon.port));
+        }
+    }
+
+    if let Some(daemon) = pid::read_pid_file_for_profile(&dir, None) {
+        eprintln!("resolved daemon port from {}", PortSource::DefaultPidFile.describe());
+        return Some(format!("http://127.0.0.1:{}", daemon.port));
+    }
+
+    if let Ok(port) = std::env::var("PMSYNAPSE_DAEMON_PORT") {
+        eprintln!("resolved daemon port from {}", PortSource::EnvVar.describe());
+        return Some(format!("http://127.0.0.1:{port}"));
+    }
+
+    None
+}
+
+#[derive(Debug, Serialize)]
+pub struct DaemonProfileInfo {
+    /// `None` is rendered as `"default"` for the UI's profile picker,
+    /// since a bare `null` label reads as a bug rather than "unprofiled".
+    profile: String,
+    port: u16,
+    pid: u32,
+    alive: bool,
+}
+
+/// Enumerate every daemon PID file for the current workspace, so the UI
+/// can offer a profile picker instead of guessing which one is live.
+#[tauri::command]
+pub fn list_daemon_profiles(profile: Option<String>) -> Vec<DaemonProfileInfo> {
+    let workspace_root = resolve_workspace_root(profile.as_deref());
+    let dir = pmsynapse_dir(&workspace_root);
+    pid::list_pid_files(&dir)
+        .into_iter()
+        .map(|(profile, daemon): (Option<String>, DaemonPid)| DaemonProfileInfo {
+            alive: pid::DaemonPidFile::new(&dir, profile.as_deref()).is_live(),
+            profile: profile.unwrap_or_else(|| "default".to_string()),
+            port: daemon.port,
+            pid: daemon.pid,
+        })
+        .collect()
+}
+
+fn locate_snps_binary() -> Result<PathBuf, DaemonError> {
+    if let Ok(path) = std::env::var("PMSYNAPSE_SNPS_BIN") {
+        return Ok(PathBuf::from(path));
+    }
+
+    let binary_name = if cfg!(windows) { "snps.exe" } else { "snps" };
+
+    if let Ok(exe) = std::env::current_exe() {
+        if let Some(dir) = exe.parent() {
+            let candidate = dir.join(binary_name);
+            if candidate.is_file() {
+                return Ok(candidate);
+            }
+        }
+    }
+
+    if let Some(path_var) = std::env::var_os("PATH") {
+        for dir in std::env::split_paths(&path_var) {
+            let candidate = dir.join(binary_name);
+            if candidate.is_file() {
+                return Ok(candidate);
+            }
+        }
+    }
+
+    Err(DaemonError {
+        code: "snps_binary_not_found".into(),
+        message: "could not find the snps binary (bundled sidecar or PATH) — install the CLI or set PMSYNAPSE_SNPS_BIN".into(),
+    })
+}
+
+async fn is_healthy(port: u16) -> bool {
+    reqwest::Client::new()
+        .get(format!("http://127.0.0.1:{port}/health"))
+        .timeout(Duration::from_secs(1))
+        .send()
+        .await
+        .map(|r| r.status().is_success())
+        .unwrap_or(false)
+}
+
+async fn wait_for_health(port: u16) -> Result<(), DaemonError> {
+    for _ in 0..50 {
+        if is_healthy(port).await {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    Err(DaemonError { code: "daemon_start_timeout".into(), message: "daemon did not become healthy within 5s".into() })
+}
+
+/// Start the daemon for `profile` if it isn't already running, and
+/// return the port it's listening on. If a healthy daemon is already up
+/// for this profile, its existing port is returned instead of erroring.
+#[tauri::command]
+pub async fn start_daemon(state: State<'_, DaemonState>, profile: Option<String>, port: u16) -> Result<u16, DaemonError> {
+    let profile = effective_profile(profile);
+    let workspace_root = resolve_workspace_root(profile.as_deref());
+
+    if let Some(existing) = pid::read_pid_file_for_profile(&pmsynapse_dir(&workspace_root), profile.as_deref()) {
+        if is_healthy(existing.port).await {
+            state.set_base_url(format!("http://127.0.0.1:{}", existing.port));
+            state.set_workspace_root(workspace_root);
+            return Ok(existing.port);
+        }
+    }
+
+    let binary = locate_snps_binary()?;
+    let mut args = vec!["daemon".to_string(), "start".to_string(), "--port".to_string(), port.to_string()];
+    if let Some(profile) = &profile {
+        args.push("--profile".to_string());
+        args.push(profile.clone());
+    }
+    let child = std::process::Command::new(&binary)
+        .args(&args)
+        .current_dir(&workspace_root)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| DaemonError { code: "spawn_failed".into(), message: format!("failed to launch {}: {e}", binary.display()) })?;
+
+    *state.child.lock().unwrap() = Some(child);
+
+    wait_for_health(port).await?;
+    state.set_base_url(format!("http://127.0.0.1:{port}"));
+    state.set_workspace_root(workspace_root);
+    Ok(port)
+}
+
+/// Stop the daemon for `profile`, preferring its graceful shutdown
+/// endpoint; if that isn't reachable, kill the process this session
+/// itself spawned. A no-op if nothing is running.
+#[tauri::command]
+pub async fn stop_daemon(state: State<'_, DaemonState>, profile: Option<String>) -> Result<(), DaemonError> {
+    let profile = effective_profile(profile);
+    let workspace_root = resolve_workspace_root(profile.as_deref());
+    let pid_file = pid::DaemonPidFile::new(&pmsynapse_dir(&workspace_root), profile.as_deref());
+
+    let Some(running) = pid_file.read() else {
+        return Ok(());
+    };
+    if !pid_file.is_live() {
+        return Ok(());
+    }
+
+    let shutdown_url = format!("http://127.0.0.1:{}/api/v1/shutdown", running.port);
+    let graceful = reqwest::Client::new().post(&shutdown_url).timeout(Duration::from_secs(2)).send().await.is_ok();
+
+    if !graceful {
+        if let Some(mut child) = state.child.lock().unwrap().take() {
+            let _ = child.kill();
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+//! PMSynapse desktop app: a thin Tauri shell over the `snps-daemon` HTTP
+//! API. Graph/proposal/session logic all lives in `snps-core` and
+//! `snps-daemon`; this binary runs the native window, proxies requests to
+//! whichever daemon it's pointed at, and — via `offline` — keeps a small
+//! embedded mirror so the three commands with bespoke wrappers still work
+//! (read-only, plus queued writes) when no daemon answers.
+
+mod daemon;
+mod lifecycle;
+mod notify;
+mod offline;
+
+use daemon::{create_matter, create_node, daemon_request, get_knowledge_graph, list_idlc_items, search_matter, DaemonState};
+use lifecycle::{list_daemon_profiles, resolve_workspace_root, start_daemon, stop_daemon};
+use offline::{sync_status, OfflineStore};
+use notify::notify_completion;
+
+fn main() {
+    // Unprofiled at startup, same as `DaemonState::new`'s default port —
+    // `start_daemon`/`stop_daemon` resolve a profile-specific workspace
+    // root per call, but the offline mirror is opened once up front, so
+    // it lives at the unprofiled workspace's `.pmsynapse/offline.db`
+    // until multi-profile offline mirrors get their own backlog item.
+    let workspace_root = resolve_workspace_root(None);
+    let offline_store = OfflineStore::open(&workspace_root).expect("failed to open offline store");
+
+    tauri::Builder::default()
+        .plugin(tauri_plugin_notification::init())
+        .manage(DaemonState::new("http://127.0.0.1:4884", workspace_root))
+        .manage(offline_store)
+        .invoke_handler(tauri::generate_handler![
+            daemon_request,
+            get_knowledge_graph,
+            create_node,
+            list_idlc_items,
+            search_matter,
+            create_matter,
+            start_daemon,
+            stop_daemon,
+            list_daemon_profiles,
+            notify_completion,
+            sync_status
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
@@ -0,0 +1,41 @@
+//! Desktop notification when a slow daemon-backed action finishes. The CLI
+//! has its own equivalent in `snps-cli`'s `notify` module, built on
+//! `notify-rust`; a Tauri webview isn't a CLI process with a lifetime for
+//! that crate to hook, so this goes through `tauri_plugin_notification`
+//! instead. The frontend already measures how long its own `invoke()` call
+//! took, so it decides whether the elapsed time crossed
+//! `defaults.notify_after_seconds` and calls this command only then, rather
+//! than this module re-deriving timing it doesn't have.
+
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+#[derive(Debug, Serialize)]
+pub struct NotifyError {
+    pub message: String,
+}
+
+#[tauri::command]
+pub fn notify_completion(app: AppHandle, command: String, succeeded: bool, elapsed_secs: u64) -> Result<(), NotifyError> {
+    let body = if succeeded {
+        format!("finished in {}", format_elapsed(elapsed_secs))
+    } else {
+        format!("failed after {}", format_elapsed(elapsed_secs))
+    };
+
+    app.notification()
+        .builder()
+        .title(format!("snps {command}"))
+        .body(body)
+        .show()
+        .map_err(|e| NotifyError { message: e.to_string() })
+}
+
+fn format_elapsed(secs: u64) -> String {
+    if secs < 60 {
+        format!("{secs}s")
+    } else {
+        format!("{}m{}s", secs / 60, secs % 60)
+    }
+}
@@ -0,0 +1,324 @@
+//! Offline fallback for the desktop app: an embedded SQLite mirror of
+//! the daemon's node/IDLC-item reads, plus a queue of writes made while
+//! the daemon was unreachable.
+//!
+//! [`GraphSource`] is the interface [`DaemonState`] (the live HTTP path)
+//! and [`OfflineStore`] (this embedded path) both implement, so
+//! `create_node`/`get_knowledge_graph`/`list_idlc_items` in `daemon.rs`
+//! can pick whichever answered rather than hardcoding the daemon. There's
+//! no bespoke edge command today (`/api/v1/edges` is only ever reached
+//! through `daemon_request`'s generic proxy — see its module doc comment)
+//! so edges aren't mirrored here; a `create_edge`/`list_edges` pair can
+//! join `GraphSource` the same way once bespoke commands for them exist.
+//!
+//! "Last-write-wins" only has teeth where there's something to overwrite.
+//! `create_node` has no update counterpart in this codebase yet (no
+//! `PATCH /api/v1/nodes/:id`), so a queued create can't actually collide
+//! with a newer remote write the way an update could — the closest honest
+//! analog is the daemon durably rejecting a replayed write (validation,
+//! not a transient network blip). That's what [`replay_pending`] logs to
+//! `conflict_log`: a permanent rejection, distinct from "still offline,
+//! try again later".
+
+use crate::daemon::{send, CreateNodeRequest, DaemonError, DaemonResponse, DaemonState};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn now_unix() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Cache key for a `get_knowledge_graph` call, so the mirror can serve
+/// the same filtered view offline that was last fetched online instead
+/// of only ever caching the unfiltered list.
+fn node_cache_key(limit: Option<u32>, offset: Option<u32>, order_by: Option<&str>, node_type: Option<&str>) -> String {
+    format!("{}:{}:{}:{}", limit.unwrap_or(0), offset.unwrap_or(0), order_by.unwrap_or(""), node_type.unwrap_or(""))
+}
+
+/// Same three operations as the daemon's node/IDLC-item read/write
+/// endpoints, so callers can be generic over "the daemon" vs "the
+/// embedded mirror" and route between them without duplicating logic.
+pub trait GraphSource {
+    async fn create_node(&self, node: &CreateNodeRequest) -> Result<DaemonResponse, DaemonError>;
+    async fn list_nodes(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        order_by: Option<&str>,
+        node_type: Option<&str>,
+    ) -> Result<DaemonResponse, DaemonError>;
+    async fn list_idlc_items(&self, team: Option<&str>) -> Result<DaemonResponse, DaemonError>;
+}
+
+impl GraphSource for DaemonState {
+    async fn create_node(&self, node: &CreateNodeRequest) -> Result<DaemonResponse, DaemonError> {
+        let body = serde_json::to_string(node).map_err(|e| DaemonError { code: "invalid_request".into(), message: e.to_string() })?;
+        send(self, "POST", "/api/v1/nodes", Some(body)).await
+    }
+
+    async fn list_nodes(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        order_by: Option<&str>,
+        node_type: Option<&str>,
+    ) -> Result<DaemonResponse, DaemonError> {
+        let mut query = Vec::new();
+        if let Some(limit) = limit {
+            query.push(format!("limit={limit}"));
+        }
+        if let Some(offset) = offset {
+            query.push(format!("offset={offset}"));
+        }
+        if let Some(order_by) = order_by {
+            query.push(format!("order_by={order_by}"));
+        }
+        if let Some(node_type) = node_type {
+            query.push(format!("node_type={node_type}"));
+        }
+        let path = if query.is_empty() { "/api/v1/nodes".to_string() } else { format!("/api/v1/nodes?{}", query.join("&")) };
+        send(self, "GET", &path, None).await
+    }
+
+    async fn list_idlc_items(&self, team: Option<&str>) -> Result<DaemonResponse, DaemonError> {
+        let path = match team {
+            Some(team) => format!("/api/v1/idlc/items?team={team}"),
+            None => "/api/v1/idlc/items".to_string(),
+        };
+        send(self, "GET", &path, None).await
+    }
+}
+
+/// One write made while the daemon was unreachable, waiting to be
+/// replayed. Only `create_node` is covered — see the module doc comment.
+struct QueuedWrite {
+    id: i64,
+    payload: String,
+}
+
+/// Outcome of a single [`replay_pending`] pass, for `sync_status` to
+/// report to the UI.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayResult {
+    pub attempted: u32,
+    pub applied: u32,
+    pub conflicts: u32,
+    pub at_unix: u64,
+}
+
+/// What the `sync_status` command reports.
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatus {
+    pub queued_writes: u32,
+    pub last_replay: Option<ReplayResult>,
+}
+
+pub struct OfflineStore {
+    conn: Mutex<Connection>,
+    last_replay: Mutex<Option<ReplayResult>>,
+}
+
+impl OfflineStore {
+    /// Open (creating if needed) the embedded mirror at
+    /// `<workspace>/.pmsynapse/offline.db`, alongside the daemon's own
+    /// `daemon.pid` and the CLI's `synapse.db`.
+    pub fn open(workspace_root: &Path) -> rusqlite::Result<Self> {
+        let dir = workspace_root.join(".pmsynapse");
+        let _ = std::fs::create_dir_all(&dir);
+        let conn = Connection::open(dir.join("offline.db"))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS node_cache (key TEXT PRIMARY KEY, body TEXT NOT NULL, cached_at INTEGER NOT NULL);
+             CREATE TABLE IF NOT EXISTS idlc_cache (team TEXT PRIMARY KEY, body TEXT NOT NULL, cached_at INTEGER NOT NULL);
+             CREATE TABLE IF NOT EXISTS queued_writes (id INTEGER PRIMARY KEY AUTOINCREMENT, payload TEXT NOT NULL, queued_at INTEGER NOT NULL);
+             CREATE TABLE IF NOT EXISTS conflict_log (id INTEGER PRIMARY KEY AUTOINCREMENT, detail TEXT NOT NULL, occurred_at INTEGER NOT NULL);",
+        )?;
+        Ok(Self { conn: Mutex::new(conn), last_replay: Mutex::new(None) })
+    }
+
+    fn queue_create_node(&self, node: &CreateNodeRequest) {
+        let Ok(payload) = serde_json::to_string(node) else { return };
+        let _ = self.conn.lock().unwrap().execute(
+            "INSERT INTO queued_writes (payload, queued_at) VALUES (?1, ?2)",
+            params![payload, now_unix()],
+        );
+    }
+
+    fn queued_writes(&self) -> Vec<QueuedWrite> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = match conn.prepare("SELECT id, payload FROM queued_writes ORDER BY queued_at ASC") {
+            Ok(stmt) => stmt,
+            Err(_) => return Vec::new(),
+        };
+        stmt.query_map([], |row| Ok(QueuedWrite { id: row.get(0)?, payload: row.get(1)? }))
+            .map(|rows| rows.flatten().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn queued_count(&self) -> u32 {
+        self.conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM queued_writes", [], |row| row.get::<_, i64>(0))
+            .unwrap_or(0) as u32
+    }
+
+    fn dequeue(&self, id: i64) {
+        let _ = self.conn.lock().unwrap().execute("DELETE FROM queued_writes WHERE id = ?1", params![id]);
+    }
+
+    fn log_conflict(&self, detail: &str) {
+        let _ = self
+            .conn
+            .lock()
+            .unwrap()
+            .execute("INSERT INTO conflict_log (detail, occurred_at) VALUES (?1, ?2)", params![detail, now_unix()]);
+    }
+
+    fn cache_nodes(&self, key: &str, body: &serde_json::Value) {
+        let _ = self.conn.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO node_cache (key, body, cached_at) VALUES (?1, ?2, ?3)",
+            params![key, body.to_string(), now_unix()],
+        );
+    }
+
+    fn cache_idlc(&self, team: &str, body: &serde_json::Value) {
+        let _ = self.conn.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO idlc_cache (team, body, cached_at) VALUES (?1, ?2, ?3)",
+            params![team, body.to_string(), now_unix()],
+        );
+    }
+
+    fn record_replay(&self, result: ReplayResult) {
+        *self.last_replay.lock().unwrap() = Some(result);
+    }
+
+    pub fn last_replay(&self) -> Option<ReplayResult> {
+        self.last_replay.lock().unwrap().clone()
+    }
+}
+
+impl GraphSource for OfflineStore {
+    /// Queue the write and hand the UI back a synthetic, unsynced
+    /// response — there's no local id-assignment scheme to reconcile
+    /// against the daemon's on replay, so the node this returns has no
+    /// `id` yet; the frontend already treats it as pending via
+    /// `synced: false` and should refresh once [`replay_pending`] applies it.
+    async fn create_node(&self, node: &CreateNodeRequest) -> Result<DaemonResponse, DaemonError> {
+        self.queue_create_node(node);
+        Ok(DaemonResponse {
+            status: 202,
+            body: serde_json::json!({
+                "node_type": node.node_type,
+                "title": node.title,
+                "content": node.content,
+                "synced": false,
+            }),
+        })
+    }
+
+    async fn list_nodes(
+        &self,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        order_by: Option<&str>,
+        node_type: Option<&str>,
+    ) -> Result<DaemonResponse, DaemonError> {
+        let key = node_cache_key(limit, offset, order_by, node_type);
+        let body = self
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT body FROM node_cache WHERE key = ?1", params![key], |row| row.get::<_, String>(0))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_else(|| serde_json::json!({"items": [], "total": 0}));
+        Ok(DaemonResponse { status: 200, body })
+    }
+
+    async fn list_idlc_items(&self, team: Option<&str>) -> Result<DaemonResponse, DaemonError> {
+        let team = team.unwrap_or("default");
+        let body = self
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT body FROM idlc_cache WHERE team = ?1", params![team], |row| row.get::<_, String>(0))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_else(|| serde_json::json!([]));
+        Ok(DaemonResponse { status: 200, body })
+    }
+}
+
+/// Cache a successful daemon read so it's available offline next time.
+pub(crate) fn remember_nodes(offline: &OfflineStore, limit: Option<u32>, offset: Option<u32>, order_by: Option<&str>, node_type: Option<&str>, body: &serde_json::Value) {
+    offline.cache_nodes(&node_cache_key(limit, offset, order_by, node_type), body);
+}
+
+pub(crate) fn remember_idlc_items(offline: &OfflineStore, team: Option<&str>, body: &serde_json::Value) {
+    offline.cache_idlc(team.unwrap_or("default"), body);
+}
+
+/// Whether `err` reflects "the daemon is down right now" rather than a
+/// real rejection — the only case worth falling back to the embedded
+/// store for. See [`crate::daemon::map_request_error`].
+fn is_daemon_unreachable(err: &DaemonError) -> bool {
+    matches!(err.code.as_str(), "connection_refused" | "timeout")
+}
+
+pub(crate) fn should_fall_back(err: &DaemonError) -> bool {
+    is_daemon_unreachable(err)
+}
+
+/// Best-effort replay of every queued write against `daemon`, oldest
+/// first. Called opportunistically at the top of each routed command
+/// (see `daemon.rs`) rather than on a timer, so replay happens exactly
+/// when there's evidence the daemon might be back: a caller is about to
+/// talk to it anyway.
+pub async fn replay_pending(daemon: &DaemonState, offline: &OfflineStore) -> ReplayResult {
+    let pending = offline.queued_writes();
+    let mut applied = 0u32;
+    let mut conflicts = 0u32;
+
+    for write in &pending {
+        let Ok(node) = serde_json::from_str::<CreateNodeRequest>(&write.payload) else {
+            offline.log_conflict(&format!("dropped an unreadable queued write (id {})", write.id));
+            offline.dequeue(write.id);
+            conflicts += 1;
+            continue;
+        };
+
+        match daemon.create_node(&node).await {
+            Ok(resp) if resp.status < 400 => {
+                offline.dequeue(write.id);
+                applied += 1;
+            }
+            Ok(resp) if resp.status < 500 => {
+                // A durable rejection (validation, etc.) — replaying it
+                // again won't change the outcome, so this is the
+                // "conflict" case: log it and give up on this write
+                // rather than retrying it forever.
+                offline.log_conflict(&format!("daemon rejected queued node '{}' (status {})", node.title, resp.status));
+                offline.dequeue(write.id);
+                conflicts += 1;
+            }
+            _ => {
+                // Still unreachable, or a transient server error — leave
+                // it queued for the next attempt.
+            }
+        }
+    }
+
+    let result = ReplayResult { attempted: pending.len() as u32, applied, conflicts, at_unix: now_unix() };
+    offline.record_replay(result.clone());
+    result
+}
+
+/// `sync_status` — how many writes are still waiting to reach the
+/// daemon, and how the last replay attempt went.
+#[tauri::command]
+pub fn sync_status(offline: tauri::State<'_, OfflineStore>) -> SyncStatus {
+    SyncStatus { queued_writes: offline.queued_count(), last_replay: offline.last_replay() }
+}
@@ -0,0 +1,32 @@
+//! Reading Claude Code's own session transcripts and turning them into
+//! PMSynapse artifacts (thought summaries, and later graph/import features).
+
+pub mod export;
+pub mod paths;
+pub mod projects;
+pub mod redact;
+pub mod report;
+pub mod session;
+pub mod session_index;
+pub mod summarize;
+pub mod titles;
+
+pub use export::{
+    export_session_json, export_session_jsonl_chat, html_document, parse_thread_data, thread_to_html, thread_to_markdown,
+    write_session_export, write_session_export_streaming, ExportFormat, JsonlChatOptions, SessionExporter, StreamingExportStats, ThreadData,
+    ThreadMessage,
+};
+pub use redact::RedactionSummary;
+pub use paths::{
+    claude_projects_dir, decode_claude_project_dir, path_to_claude_project_dir, project_slug, resolve_project_dir_for_cwd, session_file_path,
+    ProjectDirResolution,
+};
+pub use projects::{list_claude_projects, list_sessions_for_project, ClaudeProjectSummary, SessionSummary};
+pub use report::{render_sessions_report, SessionReportRow};
+pub use session::{
+    iter_session_events, parse_session_file, parse_session_file_strict, parse_session_file_with_options, FileChange, MessageRole,
+    ParseIssue, ParseOptions, Session, SessionEvent, SessionMessage, SessionStatistics, ToolCall,
+};
+pub use session_index::{has_flat_sessions, migrate_flat_sessions, regenerate_index, MigrationStats};
+pub use summarize::{extractive_summary, narrative_summary, render_summary_markdown, write_session_summary, ExtractiveSummary};
+pub use titles::{resolved_title, title_from_summary, titles_path, TitleStore};
@@ -0,0 +1,169 @@
+//! Locating Claude Code's on-disk session files, which live under
+//! `~/.claude/projects/<encoded-project-path>/<session-id>.jsonl`.
+
+use std::path::{Path, PathBuf};
+
+fn claude_home() -> PathBuf {
+    std::env::var_os("HOME").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."))
+}
+
+pub fn claude_projects_dir() -> PathBuf {
+    claude_home().join(".claude").join("projects")
+}
+
+/// Encode a project path the way Claude Code names its project directory:
+/// every `/` becomes `-`.
+pub fn path_to_claude_project_dir(project_path: &Path) -> String {
+    project_path.to_string_lossy().replace('/', "-")
+}
+
+pub fn session_file_path(project_path: &Path, session_id: &str) -> PathBuf {
+    claude_projects_dir().join(path_to_claude_project_dir(project_path)).join(format!("{session_id}.jsonl"))
+}
+
+/// Reverse [`path_to_claude_project_dir`]. The encoding is lossy — a `-`
+/// could be an original path separator or a literal dash in a directory
+/// name — so this walks the string left to right, greedily treating each
+/// `-` as a separator whenever the path built so far actually exists as a
+/// directory, and as a literal character otherwise. Falls back to a plain
+/// `/`-for-`-` swap if the real filesystem can't disambiguate (moved or
+/// deleted project directories).
+pub fn decode_claude_project_dir(encoded: &str) -> PathBuf {
+    let mut resolved = PathBuf::from("/");
+    let mut segment = String::new();
+
+    for ch in encoded.chars() {
+        if ch == '-' {
+            let candidate = if segment.is_empty() { resolved.clone() } else { resolved.join(&segment) };
+            if candidate.is_dir() {
+                resolved = candidate;
+                segment.clear();
+                continue;
+            }
+        }
+        segment.push(ch);
+    }
+
+    if segment.is_empty() { resolved } else { resolved.join(segment) }
+}
+
+/// Directory-safe project slug used to namespace exports/summaries under
+/// `sessions/<project-slug>/`, derived from the decoded project path.
+/// Lowercases and collapses everything but ASCII alphanumerics into a
+/// single `-` (same shape as the slugify helpers in `matter`/`thoughts`),
+/// so it's stable and readable rather than round-trippable — a slug is
+/// only ever used as a lookup key back into `list_claude_projects`, never
+/// decoded on its own.
+pub fn project_slug(project_path: &Path) -> String {
+    let mut slug = String::new();
+    let mut last_dash = false;
+    for c in project_path.to_string_lossy().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+            last_dash = false;
+        } else if !last_dash && !slug.is_empty() {
+            slug.push('-');
+            last_dash = true;
+        }
+    }
+    let slug = slug.trim_matches('-').to_string();
+    if slug.is_empty() {
+        "root".to_string()
+    } else {
+        slug
+    }
+}
+
+/// The result of mapping a working directory to its Claude project
+/// directory, plus whether that mapping could actually be confirmed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectDirResolution {
+    pub dir: PathBuf,
+    /// `true` if a session inside `dir` recorded a `cwd` that matches, or
+    /// no session was available to check; `false` if a session recorded a
+    /// *different* cwd, meaning the exact-encoded candidate is probably
+    /// the wrong directory (a dash in the real path collided with the
+    /// path-separator encoding). Callers should warn rather than silently
+    /// trust the mapping when this is `false`.
+    pub verified: bool,
+}
+
+/// Map `cwd` to its Claude project directory, verified against the
+/// recorded `cwd` field of a session inside the candidate directory
+/// where possible. `path_to_claude_project_dir` alone is ambiguous for
+/// paths containing dashes (`/a/b-c` and `/a-b/c` both encode to
+/// `-a-b-c`), so this cross-checks with real transcript data instead of
+/// trusting the encoding blindly.
+pub fn resolve_project_dir_for_cwd(cwd: &Path) -> ProjectDirResolution {
+    let dir = claude_projects_dir().join(path_to_claude_project_dir(cwd));
+    let verified = cwd_matches_recorded(first_session_cwd_in(&dir).as_deref(), cwd);
+    ProjectDirResolution { dir, verified }
+}
+
+/// No session to check against means there's nothing to contradict the
+/// mapping, so treat it as verified rather than warning on every fresh
+/// project with no history yet.
+fn cwd_matches_recorded(recorded: Option<&Path>, cwd: &Path) -> bool {
+    match recorded {
+        Some(r) => r == cwd,
+        None => true,
+    }
+}
+
+fn first_session_cwd_in(dir: &Path) -> Option<PathBuf> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir).ok()?.flatten().collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    entries.into_iter().find_map(|entry| {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+            return None;
+        }
+        super::session::first_recorded_cwd(&path)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_slashes_as_dashes() {
+        assert_eq!(path_to_claude_project_dir(Path::new("/home/user/my-project")), "-home-user-my-project");
+    }
+
+    #[test]
+    fn project_slug_lowercases_and_collapses_punctuation() {
+        assert_eq!(project_slug(Path::new("/Users/joe/My Project")), "users-joe-my-project");
+    }
+
+    #[test]
+    fn project_slug_of_root_is_a_stable_fallback() {
+        assert_eq!(project_slug(Path::new("/")), "root");
+    }
+
+    #[test]
+    fn decodes_dashed_directory_names_by_checking_real_directories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project = tmp.path().join("my-project");
+        std::fs::create_dir_all(&project).unwrap();
+
+        let encoded = path_to_claude_project_dir(&project);
+        assert_eq!(decode_claude_project_dir(&encoded), project);
+    }
+
+    #[test]
+    fn first_session_cwd_in_reads_the_recorded_cwd() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("sess.jsonl"), r#"{"type":"user","cwd":"/somewhere/else","message":{"role":"user","content":"hi"}}"#).unwrap();
+
+        assert_eq!(first_session_cwd_in(tmp.path()), Some(PathBuf::from("/somewhere/else")));
+    }
+
+    #[test]
+    fn cwd_matches_recorded_treats_no_session_as_verified() {
+        assert!(cwd_matches_recorded(None, Path::new("/whatever")));
+        assert!(cwd_matches_recorded(Some(Path::new("/a")), Path::new("/a")));
+        assert!(!cwd_matches_recorded(Some(Path::new("/a")), Path::new("/b")));
+    }
+}
@@ -0,0 +1,154 @@
+//! Aggregate stats across every project Claude Code has ever recorded
+//! sessions for, so `snps claude projects` (and the desktop app's
+//! project switcher) don't have to re-implement the scan.
+
+use super::paths::{claude_projects_dir, decode_claude_project_dir, path_to_claude_project_dir};
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+#[derive(Debug, Clone)]
+pub struct ClaudeProjectSummary {
+    pub path: PathBuf,
+    pub session_count: usize,
+    pub total_size_bytes: u64,
+    /// Unix seconds of the most recently modified session file, or `None`
+    /// if the project directory has no session files at all.
+    pub most_recent_activity: Option<u64>,
+}
+
+/// Scan `~/.claude/projects` and summarize each project directory found
+/// there. Sorted most-recently-active first; projects with no recorded
+/// activity sort last.
+pub fn list_claude_projects() -> std::io::Result<Vec<ClaudeProjectSummary>> {
+    let mut summaries = Vec::new();
+    let entries = match std::fs::read_dir(claude_projects_dir()) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(summaries),
+        Err(e) => return Err(e),
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let Some(encoded) = entry.file_name().to_str().map(str::to_string) else { continue };
+        summaries.push(summarize_project(&encoded, &entry.path())?);
+    }
+
+    summaries.sort_by(|a, b| b.most_recent_activity.cmp(&a.most_recent_activity));
+    Ok(summaries)
+}
+
+fn summarize_project(encoded: &str, dir: &std::path::Path) -> std::io::Result<ClaudeProjectSummary> {
+    let mut session_count = 0;
+    let mut total_size_bytes = 0;
+    let mut most_recent_activity = None;
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+            continue;
+        }
+
+        let metadata = entry.metadata()?;
+        session_count += 1;
+        total_size_bytes += metadata.len();
+
+        if let Ok(modified) = metadata.modified() {
+            let secs = modified.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+            most_recent_activity = Some(most_recent_activity.map_or(secs, |current: u64| current.max(secs)));
+        }
+    }
+
+    Ok(ClaudeProjectSummary { path: decode_claude_project_dir(encoded), session_count, total_size_bytes, most_recent_activity })
+}
+
+/// One session file's on-disk facts, cheap enough to gather for every
+/// session in a project without fully parsing any of them — used by
+/// `snps claude list`/`browse` to render a listing before the user picks
+/// a session to actually parse.
+#[derive(Debug, Clone)]
+pub struct SessionSummary {
+    pub id: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub modified_unix: Option<u64>,
+    /// Best-effort: true if any line in the transcript carries Claude
+    /// Code's `isSidechain` marker, meaning at least part of the session
+    /// is a subagent (Task tool) transcript rather than a top-level
+    /// conversation with the user. Sniffed line-by-line without a full
+    /// parse, same tradeoff as the rest of this module.
+    pub is_agent_session: bool,
+}
+
+/// List every session recorded for `project_path`, most recently modified
+/// first. Empty (not an error) if the project has no recorded sessions.
+pub fn list_sessions_for_project(project_path: &Path) -> std::io::Result<Vec<SessionSummary>> {
+    let dir = claude_projects_dir().join(path_to_claude_project_dir(project_path));
+    let mut sessions = Vec::new();
+
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(sessions),
+        Err(e) => return Err(e),
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()).map(str::to_string) else { continue };
+        if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+            continue;
+        }
+
+        let metadata = entry.metadata()?;
+        let modified_unix = metadata.modified().ok().map(|m| m.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0));
+        sessions.push(SessionSummary { id, path: path.clone(), size_bytes: metadata.len(), modified_unix, is_agent_session: sniff_is_agent_session(&path)? });
+    }
+
+    sessions.sort_by(|a, b| b.modified_unix.cmp(&a.modified_unix));
+    Ok(sessions)
+}
+
+fn sniff_is_agent_session(path: &Path) -> std::io::Result<bool> {
+    let file = std::fs::File::open(path)?;
+    for line in std::io::BufReader::new(file).lines() {
+        if line?.contains("\"isSidechain\":true") {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarize_project_counts_sessions_and_total_size() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("a.jsonl"), "1234").unwrap();
+        std::fs::write(tmp.path().join("b.jsonl"), "12345678").unwrap();
+        std::fs::write(tmp.path().join("notes.txt"), "ignored").unwrap();
+
+        let summary = summarize_project("-tmp-my-project", tmp.path()).unwrap();
+        assert_eq!(summary.session_count, 2);
+        assert_eq!(summary.total_size_bytes, 12);
+        assert!(summary.most_recent_activity.is_some());
+    }
+
+    #[test]
+    fn sniff_is_agent_session_detects_the_sidechain_marker() {
+        let tmp = tempfile::tempdir().unwrap();
+        let normal = tmp.path().join("normal.jsonl");
+        std::fs::write(&normal, "{\"type\":\"message\",\"isSidechain\":false}\n").unwrap();
+        assert!(!sniff_is_agent_session(&normal).unwrap());
+
+        let agent = tmp.path().join("agent.jsonl");
+        std::fs::write(&agent, "{\"type\":\"message\"}\n{\"type\":\"message\",\"isSidechain\":true}\n").unwrap();
+        assert!(sniff_is_agent_session(&agent).unwrap());
+    }
+}
@@ -0,0 +1,146 @@
+//! Directory-level HTML report over every session recorded for a
+//! project, for `snps claude list --format html`. Reuses
+//! [`html_document`]'s bare shell rather than inventing a second one; a
+//! plain `<table>` sorts fine in a browser's built-in column-click
+//! behavior once a tiny bit of JS is attached, so this doesn't pull in a
+//! table-sorting library for one report.
+//!
+//! There's no per-session tool-call count, duration, or parent/child
+//! session linkage recorded anywhere in [`super::session::Session`] or
+//! [`super::projects::SessionSummary`] today — a session only knows its
+//! own message count and whether it's a subagent ("agent") transcript —
+//! so this reports what's actually tracked (messages, age, agent flag)
+//! rather than fabricating numbers. Agent sessions get their own
+//! collapsible section instead of a fabricated parent/child tree, since
+//! nothing on disk records which primary session spawned which agent one.
+
+use super::export::html_document;
+
+/// One row of the report table — the same fields `snps claude list`
+/// already prints as text/JSON, reused here rather than re-deriving them
+/// from [`super::projects::SessionSummary`] a second time.
+pub struct SessionReportRow {
+    pub id: String,
+    pub title: String,
+    pub message_count: usize,
+    pub age: String,
+    pub is_agent_session: bool,
+}
+
+/// Render an HTML report for `project_label` (usually the project's path)
+/// covering `rows`. Primary sessions are listed first in a sortable
+/// table; agent sessions, if any, follow in a collapsed `<details>`
+/// section so they don't dominate the report by count.
+pub fn render_sessions_report(project_label: &str, rows: &[SessionReportRow]) -> String {
+    let primary: Vec<&SessionReportRow> = rows.iter().filter(|r| !r.is_agent_session).collect();
+    let agent: Vec<&SessionReportRow> = rows.iter().filter(|r| r.is_agent_session).collect();
+
+    let mut body = format!(
+        "<h1>Sessions for {}</h1>\n<p>{} session(s): {} primary, {} agent</p>\n",
+        escape_html(project_label),
+        rows.len(),
+        primary.len(),
+        agent.len()
+    );
+
+    body.push_str(&render_table(&primary));
+
+    if !agent.is_empty() {
+        body.push_str("<details>\n<summary>Agent sessions</summary>\n");
+        body.push_str(&render_table(&agent));
+        body.push_str("</details>\n");
+    }
+
+    body.push_str(SORT_SCRIPT);
+
+    html_document(&format!("Sessions for {project_label}"), &body)
+}
+
+fn render_table(rows: &[&SessionReportRow]) -> String {
+    if rows.is_empty() {
+        return "<p>(none)</p>\n".to_string();
+    }
+
+    let mut out = "<table class=\"sortable\">\n<thead><tr><th>ID</th><th>Title</th><th>Messages</th><th>Age</th></tr></thead>\n<tbody>\n".to_string();
+    for row in rows {
+        out.push_str(&format!(
+            "<tr><td><a href=\"exports/{}.html\">{}</a></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&row.id),
+            escape_html(&row.id),
+            escape_html(&row.title),
+            row.message_count,
+            escape_html(&row.age)
+        ));
+    }
+    out.push_str("</tbody>\n</table>\n");
+    out
+}
+
+/// Minimal click-a-header-to-sort behavior. No dependency, no CSS
+/// framework — just enough for a table with a handful of columns.
+const SORT_SCRIPT: &str = r#"<script>
+document.querySelectorAll("table.sortable th").forEach((th, index) => {
+  th.style.cursor = "pointer";
+  th.addEventListener("click", () => {
+    const table = th.closest("table");
+    const tbody = table.querySelector("tbody");
+    const rows = Array.from(tbody.querySelectorAll("tr"));
+    const ascending = th.dataset.sortAsc !== "true";
+    rows.sort((a, b) => {
+      const av = a.children[index].textContent.trim();
+      const bv = b.children[index].textContent.trim();
+      const an = Number(av), bn = Number(bv);
+      const cmp = !isNaN(an) && !isNaN(bn) ? an - bn : av.localeCompare(bv);
+      return ascending ? cmp : -cmp;
+    });
+    rows.forEach((row) => tbody.appendChild(row));
+    th.dataset.sortAsc = ascending;
+  });
+});
+</script>
+"#;
+
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(id: &str, agent: bool) -> SessionReportRow {
+        SessionReportRow { id: id.to_string(), title: format!("title for {id}"), message_count: 3, age: "2h ago".to_string(), is_agent_session: agent }
+    }
+
+    #[test]
+    fn starts_with_html_doctype_and_contains_session_ids() {
+        let html = render_sessions_report("/home/user/proj", &[row("abc", false), row("def", true)]);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("abc"));
+        assert!(html.contains("def"));
+    }
+
+    #[test]
+    fn agent_sessions_are_grouped_separately_from_primary() {
+        let html = render_sessions_report("/home/user/proj", &[row("abc", false), row("def", true)]);
+        assert!(html.contains("<details>"));
+        assert!(html.contains("Agent sessions"));
+    }
+
+    #[test]
+    fn empty_session_list_still_renders_a_valid_document() {
+        let html = render_sessions_report("/home/user/proj", &[]);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("(none)"));
+    }
+}
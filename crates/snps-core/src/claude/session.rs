@@ -0,0 +1,719 @@
+//! Parsing Claude Code's per-session JSONL transcripts into a structured
+//! [`Session`].
+//!
+//! [`parse_session_file`] materializes the whole session, which is the
+//! simplest thing to hand a summarizer or exporter that already reads the
+//! full transcript into memory. For the sessions where that's the problem
+//! — 200MB+ of JSONL with large tool outputs — [`iter_session_events`]
+//! walks the file line by line via a [`BufReader`](std::io::BufReader)
+//! instead of reading it into one `String` up front, and never retains a
+//! line's parsed content past the [`SessionEvent`] it produces.
+//! [`parse_session_file_with_options`] is itself just that iterator
+//! collected into a [`Session`] — one code path for both.
+
+use crate::error::{CoreError, CoreResult};
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageRole {
+    User,
+    Assistant,
+    System,
+}
+
+impl MessageRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageRole::User => "user",
+            MessageRole::Assistant => "assistant",
+            MessageRole::System => "system",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionMessage {
+    pub role: MessageRole,
+    pub text: String,
+    pub timestamp: Option<String>,
+    /// `true` when this message's only content was one or more
+    /// `tool_result` blocks — Claude Code's synthetic "next turn" carrying
+    /// a tool's output back to the assistant, recorded as a `User`
+    /// message alongside anything the human actually typed. Distinguishing
+    /// the two matters to a caller pairing messages into conversational
+    /// turns (`claude::export`'s `jsonl-chat` format) that would otherwise
+    /// see two consecutive `User` messages and no way to tell them apart.
+    pub is_tool_result: bool,
+    /// Tool calls the assistant made in this message, in transcript order.
+    /// Empty for anything but an assistant message that used a tool.
+    pub tool_calls: Vec<ToolCall>,
+}
+
+/// One `tool_use` block from an assistant message. Recorded for every
+/// tool, not just [`EDIT_TOOLS`] (which additionally get a [`FileChange`])
+/// — a caller rendering tool calls back out (`claude::export`'s
+/// `jsonl-chat --include-tools`) needs the call regardless of which tool
+/// it was.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    pub name: String,
+    pub input: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub tool: String,
+    pub path: String,
+}
+
+/// A line of a session's JSONL transcript that couldn't be turned into a
+/// [`SessionMessage`] — either it wasn't valid JSON, or it parsed but had a
+/// shape [`parse_role`] doesn't recognize (e.g. a record type from a newer
+/// Claude Code version). Collected rather than treated as fatal, since
+/// transcripts are append-only logs where a single bad line (commonly a
+/// truncated final line from a process killed mid-write) shouldn't cost the
+/// rest of the session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseIssue {
+    /// 1-based line number within the file.
+    pub line: usize,
+    /// Byte offset of the line's first character within the file.
+    pub byte_offset: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: String,
+    pub source_path: PathBuf,
+    pub messages: Vec<SessionMessage>,
+    pub file_changes: Vec<FileChange>,
+    /// Lines that were skipped while parsing. Empty for a clean transcript.
+    pub parse_issues: Vec<ParseIssue>,
+}
+
+impl Session {
+    pub fn first_user_message(&self) -> Option<&str> {
+        self.messages.iter().find(|m| m.role == MessageRole::User).map(|m| m.text.as_str())
+    }
+
+    pub fn final_assistant_message(&self) -> Option<&str> {
+        self.messages.iter().rev().find(|m| m.role == MessageRole::Assistant).map(|m| m.text.as_str())
+    }
+
+    /// Plain-text transcript suitable as LLM context: role-prefixed lines.
+    pub fn transcript_text(&self) -> String {
+        self.messages.iter().map(|m| format!("{:?}: {}", m.role, m.text)).collect::<Vec<_>>().join("\n\n")
+    }
+}
+
+#[derive(Deserialize)]
+struct RawEntry {
+    #[serde(rename = "type")]
+    entry_type: Option<String>,
+    message: Option<RawMessage>,
+    timestamp: Option<String>,
+    cwd: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawMessage {
+    role: Option<String>,
+    content: serde_json::Value,
+}
+
+const EDIT_TOOLS: &[&str] = &["Edit", "Write", "MultiEdit", "NotebookEdit"];
+
+/// Knobs shared by [`parse_session_file`] and [`iter_session_events`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions {
+    /// Fail on the first malformed or unrecognized line instead of
+    /// recording a [`ParseIssue`] and continuing.
+    pub strict: bool,
+    /// Truncate any single tool result's text to this many bytes, past
+    /// which it's replaced with a `"(truncated N bytes)"` marker. `None`
+    /// keeps tool output text as recorded. This is the main lever for
+    /// bounding memory on a transcript with a handful of huge tool
+    /// results, since [`iter_session_events`] otherwise still has to hold
+    /// one line's worth of content in memory to parse it.
+    pub max_tool_output_bytes: Option<usize>,
+}
+
+/// One line's worth of session content, produced by [`iter_session_events`]
+/// without retaining anything from the line once it's returned.
+#[derive(Debug, Clone)]
+pub enum SessionEvent {
+    Message(SessionMessage),
+    FileChange(FileChange),
+    Issue(ParseIssue),
+}
+
+/// Walk `path` line by line via a buffered reader, yielding a
+/// [`SessionEvent`] per message/file-change/issue without ever holding the
+/// whole file — or more than one line's parsed JSON — in memory at once.
+/// [`parse_session_file_with_options`] is this, collected into a
+/// [`Session`]; use this directly (see [`SessionStatistics::compute`] and
+/// `SessionExporter::write_streaming`) when even the collected `Vec`s
+/// would be too big to hold.
+///
+/// With `options.strict`, the iterator yields one `Err` for the first bad
+/// line and then ends, mirroring [`parse_session_file_strict`].
+pub fn iter_session_events(path: &Path, options: ParseOptions) -> CoreResult<impl Iterator<Item = CoreResult<SessionEvent>>> {
+    let file = File::open(path)?;
+    Ok(SessionEventIter {
+        lines: BufReader::new(file).lines(),
+        path: path.to_path_buf(),
+        line_number: 0,
+        byte_offset: 0,
+        options,
+        done: false,
+        pending: std::collections::VecDeque::new(),
+    })
+}
+
+struct SessionEventIter {
+    lines: std::io::Lines<BufReader<File>>,
+    path: PathBuf,
+    line_number: usize,
+    byte_offset: usize,
+    options: ParseOptions,
+    done: bool,
+    /// A line can produce more than one event (a text block plus a
+    /// tool_use block, say); everything past the first gets queued here
+    /// and drained before reading the next line.
+    pending: std::collections::VecDeque<SessionEvent>,
+}
+
+impl Iterator for SessionEventIter {
+    type Item = CoreResult<SessionEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+            if self.done {
+                return None;
+            }
+
+            let raw_line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(CoreError::from(e)));
+                }
+            };
+
+            self.line_number += 1;
+            let this_offset = self.byte_offset;
+            self.byte_offset += raw_line.len() + 1; // +1 for the newline `lines()` strips
+            let line = raw_line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let entry = match serde_json::from_str::<RawEntry>(line) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    if self.options.strict {
+                        self.done = true;
+                        return Some(Err(CoreError::Parse { path: self.path.clone(), message: e.to_string() }));
+                    }
+                    return Some(Ok(SessionEvent::Issue(ParseIssue {
+                        line: self.line_number,
+                        byte_offset: this_offset,
+                        message: e.to_string(),
+                    })));
+                }
+            };
+            let Some(message) = entry.message else { continue };
+            let Some(role) = parse_role(entry.entry_type.as_deref(), message.role.as_deref()) else {
+                let issue_message = format!("unrecognized record type {:?} / role {:?}", entry.entry_type, message.role);
+                if self.options.strict {
+                    self.done = true;
+                    return Some(Err(CoreError::Parse { path: self.path.clone(), message: issue_message }));
+                }
+                return Some(Ok(SessionEvent::Issue(ParseIssue { line: self.line_number, byte_offset: this_offset, message: issue_message })));
+            };
+
+            let mut text_parts = Vec::new();
+            let mut file_changes = Vec::new();
+            let mut tool_calls = Vec::new();
+            let is_tool_result =
+                collect_content(&message.content, &mut text_parts, &mut file_changes, &mut tool_calls, self.options.max_tool_output_bytes);
+
+            self.pending.extend(file_changes.into_iter().map(SessionEvent::FileChange));
+            if !text_parts.is_empty() || !tool_calls.is_empty() {
+                self.pending.push_back(SessionEvent::Message(SessionMessage {
+                    role,
+                    text: text_parts.join("\n"),
+                    timestamp: entry.timestamp,
+                    is_tool_result,
+                    tool_calls,
+                }));
+            }
+        }
+    }
+}
+
+/// Parse a session's JSONL file, tolerating malformed lines: each one that
+/// isn't valid JSON, or doesn't match a recognized record shape, is
+/// recorded as a [`ParseIssue`] on the returned [`Session`] instead of
+/// aborting the parse. Real transcripts occasionally have a truncated
+/// final line (the process was killed mid-write) or, from a newer Claude
+/// Code version, a record shape this binary doesn't know yet — neither
+/// should cost every message that came before it.
+///
+/// Only fails outright if every non-blank line was an issue, i.e. nothing
+/// at all could be recovered. Use [`parse_session_file_strict`] to fail on
+/// the first bad line instead, or [`iter_session_events`] to avoid
+/// materializing the whole session in the first place.
+pub fn parse_session_file(path: &Path) -> CoreResult<Session> {
+    parse_session_file_with_options(path, ParseOptions::default())
+}
+
+/// Like [`parse_session_file`], but fails on the first malformed or
+/// unrecognized line instead of skipping it. This is the parser's
+/// pre-[`ParseIssue`] behavior, kept around for callers (or CI checks)
+/// that would rather know immediately than get a session silently missing
+/// some of its history.
+pub fn parse_session_file_strict(path: &Path) -> CoreResult<Session> {
+    parse_session_file_with_options(path, ParseOptions { strict: true, ..ParseOptions::default() })
+}
+
+/// [`parse_session_file`]/[`parse_session_file_strict`] with control over
+/// [`ParseOptions::max_tool_output_bytes`] as well as strictness.
+pub fn parse_session_file_with_options(path: &Path, options: ParseOptions) -> CoreResult<Session> {
+    let id = path.file_stem().and_then(|s| s.to_str()).unwrap_or("unknown").to_string();
+
+    let mut messages = Vec::new();
+    let mut file_changes = Vec::new();
+    let mut issues = Vec::new();
+
+    for event in iter_session_events(path, options)? {
+        match event? {
+            SessionEvent::Message(message) => messages.push(message),
+            SessionEvent::FileChange(change) => file_changes.push(change),
+            SessionEvent::Issue(issue) => issues.push(issue),
+        }
+    }
+
+    if messages.is_empty() && !issues.is_empty() {
+        return Err(CoreError::Parse {
+            path: path.to_path_buf(),
+            message: format!("no records could be parsed ({} line(s) skipped)", issues.len()),
+        });
+    }
+
+    Ok(Session { id, source_path: path.to_path_buf(), messages, file_changes, parse_issues: issues })
+}
+
+/// Per-role message counts and totals computed in a single pass over
+/// [`iter_session_events`], without retaining any message body — the
+/// counterpart to [`Session`] for a transcript too large to hold in
+/// memory at once (`snps claude stats --streaming` uses this).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionStatistics {
+    pub user_messages: usize,
+    pub assistant_messages: usize,
+    pub system_messages: usize,
+    pub file_changes: usize,
+    pub parse_issues: usize,
+    /// Sum of `text.len()` across every message, i.e. after any
+    /// `max_tool_output_bytes` truncation has already been applied.
+    pub total_text_bytes: u64,
+}
+
+impl SessionStatistics {
+    pub fn total_messages(&self) -> usize {
+        self.user_messages + self.assistant_messages + self.system_messages
+    }
+
+    /// Stream `path` and fold it into a [`SessionStatistics`] one event at
+    /// a time. Peak memory is bounded by one line's parsed JSON, not the
+    /// transcript's total size.
+    pub fn compute(path: &Path, options: ParseOptions) -> CoreResult<SessionStatistics> {
+        let mut stats = SessionStatistics::default();
+        for event in iter_session_events(path, options)? {
+            match event? {
+                SessionEvent::Message(message) => {
+                    stats.total_text_bytes += message.text.len() as u64;
+                    match message.role {
+                        MessageRole::User => stats.user_messages += 1,
+                        MessageRole::Assistant => stats.assistant_messages += 1,
+                        MessageRole::System => stats.system_messages += 1,
+                    }
+                }
+                SessionEvent::FileChange(_) => stats.file_changes += 1,
+                SessionEvent::Issue(_) => stats.parse_issues += 1,
+            }
+        }
+        Ok(stats)
+    }
+}
+
+/// The `cwd` Claude Code recorded on the first line of `path` that has
+/// one, without parsing the whole transcript. Used to verify a project
+/// directory actually belongs to the workspace it was encoded from,
+/// since the encoding (`/` -> `-`) is lossy for paths that already
+/// contain dashes.
+pub fn first_recorded_cwd(path: &Path) -> Option<PathBuf> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    contents.lines().find_map(|line| {
+        let entry: RawEntry = serde_json::from_str(line.trim()).ok()?;
+        entry.cwd.map(PathBuf::from)
+    })
+}
+
+fn parse_role(entry_type: Option<&str>, role: Option<&str>) -> Option<MessageRole> {
+    match role.or(entry_type)? {
+        "user" => Some(MessageRole::User),
+        "assistant" => Some(MessageRole::Assistant),
+        "system" => Some(MessageRole::System),
+        _ => None,
+    }
+}
+
+/// Returns `true` when the content was one or more `tool_result` blocks
+/// and nothing else — see [`SessionMessage::is_tool_result`]. A `"thinking"`
+/// block type exists in real transcripts but has no case below, so it's
+/// silently dropped along with anything else this binary doesn't
+/// recognize yet; that's also how thinking is kept out of every export
+/// format, `jsonl-chat` included, without that format needing its own
+/// filter for it.
+fn collect_content(
+    content: &serde_json::Value,
+    text_parts: &mut Vec<String>,
+    file_changes: &mut Vec<FileChange>,
+    tool_calls: &mut Vec<ToolCall>,
+    max_tool_output_bytes: Option<usize>,
+) -> bool {
+    let mut saw_text = false;
+    let mut saw_tool_result = false;
+    match content {
+        serde_json::Value::String(s) => {
+            text_parts.push(s.clone());
+            saw_text = true;
+        }
+        serde_json::Value::Array(blocks) => {
+            for block in blocks {
+                let block_type = block.get("type").and_then(|t| t.as_str()).unwrap_or_default();
+                match block_type {
+                    "text" => {
+                        if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
+                            text_parts.push(text.to_string());
+                            saw_text = true;
+                        }
+                    }
+                    "tool_use" => {
+                        let tool = block.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+                        let input = block.get("input").cloned().unwrap_or(serde_json::Value::Null);
+                        tool_calls.push(ToolCall { name: tool.to_string(), input });
+                        if EDIT_TOOLS.contains(&tool) {
+                            if let Some(path) = block.get("input").and_then(|i| i.get("file_path")).and_then(|p| p.as_str()) {
+                                file_changes.push(FileChange { tool: tool.to_string(), path: path.to_string() });
+                            }
+                        }
+                    }
+                    "tool_result" => {
+                        saw_tool_result = true;
+                        if let Some(text) = tool_result_text(block) {
+                            text_parts.push(truncate_tool_output(&text, max_tool_output_bytes));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+    saw_tool_result && !saw_text
+}
+
+/// A `tool_result` block's `content` is either a plain string, or (like a
+/// message's own `content`) an array of blocks — in practice just `text`
+/// ones for a CLI tool's stdout/stderr. Joins multiple text blocks with
+/// blank lines the same way [`Session::transcript_text`] joins messages.
+fn tool_result_text(block: &serde_json::Value) -> Option<String> {
+    match block.get("content")? {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Array(blocks) => {
+            let parts: Vec<&str> = blocks.iter().filter_map(|b| b.get("text").and_then(|t| t.as_str())).collect();
+            if parts.is_empty() {
+                None
+            } else {
+                Some(parts.join("\n"))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Cut `text` down to `max_bytes` on a `char` boundary and append a
+/// `"(truncated N bytes)"` marker, so a single 50MB tool result doesn't
+/// dominate the session's memory footprint. `None` means no limit.
+fn truncate_tool_output(text: &str, max_bytes: Option<usize>) -> String {
+    let Some(max_bytes) = max_bytes else { return text.to_string() };
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+
+    let mut cut = max_bytes;
+    while cut > 0 && !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    format!("{}\n... (truncated {} bytes)", &text[..cut], text.len() - cut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_messages_and_file_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        std::fs::write(
+            &path,
+            [
+                r#"{"type":"user","message":{"role":"user","content":"add a test"}}"#,
+                r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"done"},{"type":"tool_use","name":"Edit","input":{"file_path":"src/lib.rs"}}]}}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let session = parse_session_file(&path).unwrap();
+        assert_eq!(session.id, "abc");
+        assert_eq!(session.first_user_message(), Some("add a test"));
+        assert_eq!(session.final_assistant_message(), Some("done"));
+        assert_eq!(session.file_changes, vec![FileChange { tool: "Edit".into(), path: "src/lib.rs".into() }]);
+        assert!(session.parse_issues.is_empty());
+    }
+
+    #[test]
+    fn skips_malformed_lines_without_failing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        std::fs::write(&path, "not json\n{\"type\":\"user\",\"message\":{\"role\":\"user\",\"content\":\"hi\"}}\n").unwrap();
+
+        let session = parse_session_file(&path).unwrap();
+        assert_eq!(session.messages.len(), 1);
+        assert_eq!(session.parse_issues.len(), 1);
+        assert_eq!(session.parse_issues[0].line, 1);
+        assert_eq!(session.parse_issues[0].byte_offset, 0);
+    }
+
+    #[test]
+    fn tolerates_a_truncated_final_line() {
+        // Simulates a process killed mid-write: the last line is cut off
+        // partway through the JSON object.
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        std::fs::write(
+            &path,
+            [
+                r#"{"type":"user","message":{"role":"user","content":"add a test"}}"#,
+                r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"wo"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let session = parse_session_file(&path).unwrap();
+        assert_eq!(session.messages.len(), 1);
+        assert_eq!(session.parse_issues.len(), 1);
+        assert_eq!(session.parse_issues[0].line, 2);
+    }
+
+    #[test]
+    fn records_an_unrecognized_record_type_as_an_issue_instead_of_dropping_it_silently() {
+        // A shape this binary doesn't know yet, e.g. from a newer Claude
+        // Code version — `message` is present but its role isn't one of
+        // "user"/"assistant"/"system".
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        std::fs::write(
+            &path,
+            [
+                r#"{"type":"user","message":{"role":"user","content":"add a test"}}"#,
+                r#"{"type":"tool_result","message":{"role":"tool","content":"ok"}}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let session = parse_session_file(&path).unwrap();
+        assert_eq!(session.messages.len(), 1);
+        assert_eq!(session.parse_issues.len(), 1);
+        assert!(session.parse_issues[0].message.contains("tool"));
+    }
+
+    #[test]
+    fn fails_when_every_line_is_unparseable() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        std::fs::write(&path, "not json\nalso not json\n").unwrap();
+
+        assert!(parse_session_file(&path).is_err());
+    }
+
+    #[test]
+    fn captures_non_edit_tool_calls_that_edit_tools_alone_would_drop() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        std::fs::write(
+            &path,
+            [
+                r#"{"type":"user","message":{"role":"user","content":"run the tests"}}"#,
+                r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{"command":"cargo test"}}]}}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let session = parse_session_file(&path).unwrap();
+        assert_eq!(session.messages.len(), 2);
+        let assistant = &session.messages[1];
+        assert_eq!(assistant.text, "");
+        assert_eq!(assistant.tool_calls, vec![ToolCall { name: "Bash".into(), input: serde_json::json!({"command": "cargo test"}) }]);
+        assert!(session.file_changes.is_empty());
+    }
+
+    #[test]
+    fn marks_a_tool_result_only_message_distinct_from_a_real_user_message() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        std::fs::write(
+            &path,
+            [
+                r#"{"type":"user","message":{"role":"user","content":"run the tests"}}"#,
+                r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","content":"all green"}]}}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let session = parse_session_file(&path).unwrap();
+        assert_eq!(session.messages.len(), 2);
+        assert!(!session.messages[0].is_tool_result);
+        assert!(session.messages[1].is_tool_result);
+    }
+
+    #[test]
+    fn drops_thinking_blocks_like_any_other_unrecognized_block_type() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"thinking","thinking":"let me consider this"},{"type":"text","text":"done"}]}}"#,
+        )
+        .unwrap();
+
+        let session = parse_session_file(&path).unwrap();
+        assert_eq!(session.messages.len(), 1);
+        assert_eq!(session.messages[0].text, "done");
+    }
+
+    #[test]
+    fn strict_mode_fails_on_the_first_malformed_line() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        std::fs::write(&path, "not json\n{\"type\":\"user\",\"message\":{\"role\":\"user\",\"content\":\"hi\"}}\n").unwrap();
+
+        assert!(parse_session_file_strict(&path).is_err());
+    }
+
+    #[test]
+    fn max_tool_output_bytes_truncates_a_giant_tool_result() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        let huge = "x".repeat(1000);
+        let line = serde_json::json!({
+            "type": "user",
+            "message": {
+                "role": "user",
+                "content": [{"type": "tool_result", "tool_use_id": "t1", "content": huge}],
+            },
+        })
+        .to_string();
+        std::fs::write(&path, line).unwrap();
+
+        let options = ParseOptions { max_tool_output_bytes: Some(100), ..ParseOptions::default() };
+        let session = parse_session_file_with_options(&path, options).unwrap();
+        assert_eq!(session.messages.len(), 1);
+        let text = &session.messages[0].text;
+        assert!(text.starts_with(&"x".repeat(100)));
+        assert!(text.contains("(truncated 900 bytes)"));
+    }
+
+    #[test]
+    fn iter_session_events_yields_one_event_at_a_time_without_a_session() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        std::fs::write(
+            &path,
+            [
+                r#"{"type":"user","message":{"role":"user","content":"add a test"}}"#,
+                r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"done"},{"type":"tool_use","name":"Edit","input":{"file_path":"src/lib.rs"}}]}}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let events: Vec<SessionEvent> = iter_session_events(&path, ParseOptions::default()).unwrap().collect::<CoreResult<Vec<_>>>().unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], SessionEvent::Message(_)));
+        assert!(matches!(events[1], SessionEvent::Message(_)));
+        assert!(matches!(events[2], SessionEvent::FileChange(_)));
+    }
+
+    #[test]
+    fn statistics_match_a_fully_materialized_session_on_a_large_synthetic_fixture() {
+        // 100k messages is the scale the streaming path exists for; this
+        // exercises it end to end and checks it agrees with the
+        // fully-materialized parse, without needing to measure memory
+        // directly (not something a unit test can do portably).
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("big.jsonl");
+        let mut file = std::io::BufWriter::new(std::fs::File::create(&path).unwrap());
+        use std::io::Write;
+        for i in 0..100_000 {
+            let role = if i % 2 == 0 { "user" } else { "assistant" };
+            writeln!(file, r#"{{"type":"{role}","message":{{"role":"{role}","content":"message number {i}"}}}}"#).unwrap();
+        }
+        file.flush().unwrap();
+        drop(file);
+
+        let stats = SessionStatistics::compute(&path, ParseOptions::default()).unwrap();
+        assert_eq!(stats.total_messages(), 100_000);
+        assert_eq!(stats.user_messages, 50_000);
+        assert_eq!(stats.assistant_messages, 50_000);
+        assert_eq!(stats.parse_issues, 0);
+
+        let session = parse_session_file(&path).unwrap();
+        assert_eq!(session.messages.len(), stats.total_messages());
+        let total_bytes: u64 = session.messages.iter().map(|m| m.text.len() as u64).sum();
+        assert_eq!(total_bytes, stats.total_text_bytes);
+    }
+
+    #[test]
+    fn first_recorded_cwd_reads_the_first_line_that_has_one() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        std::fs::write(
+            &path,
+            [
+                r#"{"type":"user","message":{"role":"user","content":"hi"}}"#,
+                r#"{"type":"user","cwd":"/home/me/my-project","message":{"role":"user","content":"more"}}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        assert_eq!(first_recorded_cwd(&path), Some(PathBuf::from("/home/me/my-project")));
+    }
+}
@@ -0,0 +1,237 @@
+//! Per-project session directory layout: everything `snps claude
+//! export`/`summarize` writes lives under
+//! `thoughts/shared/sessions/<project-slug>/` instead of one flat
+//! directory shared by every project, so `session-<id>.md` from two
+//! different projects can no longer collide (or just pile up together
+//! into an unbrowsable folder). This module owns those paths, the
+//! regenerated `index.md` per project, and the one-time migration of
+//! files written before this layout existed.
+
+use super::paths::project_slug;
+use super::projects::list_claude_projects;
+use crate::error::CoreResult;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+pub fn sessions_root(thoughts_dir: &Path) -> PathBuf {
+    thoughts_dir.join("shared").join("sessions")
+}
+
+pub fn project_sessions_dir(thoughts_dir: &Path, project_path: &Path) -> PathBuf {
+    sessions_root(thoughts_dir).join(project_slug(project_path))
+}
+
+pub fn exports_dir(thoughts_dir: &Path, project_path: &Path) -> PathBuf {
+    project_sessions_dir(thoughts_dir, project_path).join("exports")
+}
+
+pub fn summaries_dir(thoughts_dir: &Path, project_path: &Path) -> PathBuf {
+    project_sessions_dir(thoughts_dir, project_path).join("summaries")
+}
+
+/// Regenerate `<project dir>/index.md` from whatever export/summary
+/// files currently exist for `project_path`. Cheap enough (a directory
+/// listing plus a frontmatter line grep, no transcript parsing) to call
+/// after every write rather than maintaining it incrementally.
+pub fn regenerate_index(thoughts_dir: &Path, project_path: &Path) -> CoreResult<PathBuf> {
+    let dir = project_sessions_dir(thoughts_dir, project_path);
+    std::fs::create_dir_all(&dir)?;
+
+    let mut sessions: BTreeMap<String, (Option<String>, Option<u64>)> = BTreeMap::new();
+    collect_entries(&exports_dir(thoughts_dir, project_path), &mut sessions);
+    collect_entries(&summaries_dir(thoughts_dir, project_path), &mut sessions);
+
+    let mut out = format!("# Sessions for {}\n\n", project_path.display());
+    if sessions.is_empty() {
+        out.push_str("(none exported or summarized yet)\n");
+    } else {
+        // Most recently touched first, same ordering `claude list` uses.
+        let mut rows: Vec<(&String, &(Option<String>, Option<u64>))> = sessions.iter().collect();
+        rows.sort_by(|a, b| b.1 .1.cmp(&a.1 .1));
+        for (id, (title, modified)) in rows {
+            let date = modified.map(crate::time::date_string).unwrap_or_else(|| "unknown date".to_string());
+            match title {
+                Some(t) => out.push_str(&format!("- {date} — {id}: {t}\n")),
+                None => out.push_str(&format!("- {date} — {id}\n")),
+            }
+        }
+    }
+
+    let path = dir.join("index.md");
+    std::fs::write(&path, out)?;
+    Ok(path)
+}
+
+/// Best-effort session id + title + mtime for every file directly
+/// inside `dir` (summary filenames are `<date>-<session-id>.md`, export
+/// filenames are `<session-id>.<ext>`), merged into `sessions`. Missing
+/// directories (a project with only exports and no summaries, or vice
+/// versa) are treated as empty rather than an error.
+fn collect_entries(dir: &Path, sessions: &mut BTreeMap<String, (Option<String>, Option<u64>)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else { continue };
+        let id = session_id_from_stem(stem);
+        let modified = entry
+            .metadata()
+            .ok()
+            .and_then(|m| m.modified().ok())
+            .map(|t| t.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0));
+        let title = extract_frontmatter_title(&path);
+
+        let record = sessions.entry(id).or_insert((None, None));
+        if record.0.is_none() {
+            record.0 = title;
+        }
+        record.1 = match (record.1, modified) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+/// Strip a leading `YYYY-MM-DD-` date prefix, as written by
+/// [`super::summarize::write_session_summary`]; export filenames have no
+/// such prefix so they pass through unchanged. A session id (a UUID)
+/// never itself starts with a 10-digit dashed date, so this is
+/// unambiguous.
+fn session_id_from_stem(stem: &str) -> String {
+    let bytes = stem.as_bytes();
+    let has_date_prefix = bytes.len() > 11
+        && bytes[0..4].iter().all(u8::is_ascii_digit)
+        && bytes[4] == b'-'
+        && bytes[5..7].iter().all(u8::is_ascii_digit)
+        && bytes[7] == b'-'
+        && bytes[8..10].iter().all(u8::is_ascii_digit)
+        && bytes[10] == b'-';
+    if has_date_prefix { stem[11..].to_string() } else { stem.to_string() }
+}
+
+fn extract_frontmatter_title(path: &Path) -> Option<String> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    contents.lines().find_map(|line| line.trim().strip_prefix("title:").map(|rest| rest.trim().trim_matches('"').to_string()))
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MigrationStats {
+    pub moved: usize,
+    pub unresolved: Vec<String>,
+}
+
+/// One-time move of files written under the old flat
+/// `sessions/exports/`/`sessions/summaries/` directories into
+/// `sessions/<project-slug>/...`. A session id alone doesn't encode
+/// which project it belongs to (it's a UUID Claude Code assigns, not a
+/// path-derived key), so ownership is resolved by checking which
+/// recorded Claude project actually has a transcript with that id —
+/// the same source of truth `claude list`/`export` already trust.
+/// Files whose id can't be resolved this way (the project directory in
+/// `~/.claude/projects` has since been deleted) are left in place and
+/// reported in `unresolved` rather than dropped.
+pub fn migrate_flat_sessions(thoughts_dir: &Path) -> CoreResult<MigrationStats> {
+    let root = sessions_root(thoughts_dir);
+    let mut stats = MigrationStats::default();
+
+    let owner_by_session_id = build_session_ownership_index()?;
+
+    for (flat_dir_name, dest_subdir) in [("exports", "exports"), ("summaries", "summaries")] {
+        let flat_dir = root.join(flat_dir_name);
+        let Ok(entries) = std::fs::read_dir(&flat_dir) else { continue };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else { continue };
+            let id = session_id_from_stem(stem);
+
+            match owner_by_session_id.get(&id) {
+                Some(project) => {
+                    let dest_dir = root.join(project_slug(project)).join(dest_subdir);
+                    std::fs::create_dir_all(&dest_dir)?;
+                    let dest = dest_dir.join(path.file_name().expect("file_name checked by is_file above"));
+                    std::fs::rename(&path, &dest)?;
+                    stats.moved += 1;
+                }
+                None => stats.unresolved.push(path.display().to_string()),
+            }
+        }
+    }
+
+    let touched_projects: std::collections::BTreeSet<&PathBuf> = owner_by_session_id.values().collect();
+    for project in touched_projects {
+        regenerate_index(thoughts_dir, project)?;
+    }
+
+    Ok(stats)
+}
+
+fn build_session_ownership_index() -> CoreResult<BTreeMap<String, PathBuf>> {
+    let mut index = BTreeMap::new();
+    for project in list_claude_projects()? {
+        for session in super::projects::list_sessions_for_project(&project.path)? {
+            index.insert(session.id, project.path.clone());
+        }
+    }
+    Ok(index)
+}
+
+/// Whether `thoughts_dir` still has anything under the pre-migration
+/// flat layout, so callers can skip the (project-directory-scanning)
+/// migration attempt entirely once it's done.
+pub fn has_flat_sessions(thoughts_dir: &Path) -> bool {
+    let root = sessions_root(thoughts_dir);
+    for name in ["exports", "summaries"] {
+        if let Ok(mut entries) = std::fs::read_dir(root.join(name)) {
+            if entries.any(|e| e.map(|e| e.path().is_file()).unwrap_or(false)) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_id_from_stem_strips_a_date_prefix_but_not_a_bare_id() {
+        assert_eq!(session_id_from_stem("2026-08-01-abc123"), "abc123");
+        assert_eq!(session_id_from_stem("abc123"), "abc123");
+    }
+
+    #[test]
+    fn regenerate_index_lists_exports_and_summaries_together() {
+        let tmp = tempfile::tempdir().unwrap();
+        let thoughts = tmp.path().join("thoughts");
+        let project = PathBuf::from("/home/user/proj");
+
+        std::fs::create_dir_all(exports_dir(&thoughts, &project)).unwrap();
+        std::fs::write(exports_dir(&thoughts, &project).join("abc.html"), "<html></html>").unwrap();
+
+        std::fs::create_dir_all(summaries_dir(&thoughts, &project)).unwrap();
+        std::fs::write(
+            summaries_dir(&thoughts, &project).join("2026-08-01-def.md"),
+            "---\ntitle: \"Fix the bug\"\n---\n\nbody",
+        )
+        .unwrap();
+
+        let index_path = regenerate_index(&thoughts, &project).unwrap();
+        let contents = std::fs::read_to_string(index_path).unwrap();
+        assert!(contents.contains("abc"));
+        assert!(contents.contains("def: Fix the bug"));
+    }
+
+    #[test]
+    fn has_flat_sessions_is_false_for_a_fresh_thoughts_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(!has_flat_sessions(&tmp.path().join("thoughts")));
+    }
+}
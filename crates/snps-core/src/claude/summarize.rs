@@ -0,0 +1,125 @@
+//! Building a research-type thought document that summarizes a Claude
+//! Code session, either purely extractively or with an LLM-written
+//! narrative layered on top.
+
+use super::session::Session;
+use crate::error::CoreResult;
+use crate::llm::{CompletionRequest, LlmProvider};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default)]
+pub struct ExtractiveSummary {
+    pub first_user_message: Option<String>,
+    pub file_changes: Vec<String>,
+    pub final_assistant_message: Option<String>,
+}
+
+pub fn extractive_summary(session: &Session) -> ExtractiveSummary {
+    ExtractiveSummary {
+        first_user_message: session.first_user_message().map(str::to_string),
+        file_changes: session.file_changes.iter().map(|c| c.path.clone()).collect(),
+        final_assistant_message: session.final_assistant_message().map(str::to_string),
+    }
+}
+
+/// Ask the configured provider for a goals/decisions/open-questions
+/// narrative. Callers decide whether to invoke this at all (`--no-llm`).
+pub async fn narrative_summary(session: &Session, provider: &dyn LlmProvider) -> CoreResult<String> {
+    let prompt = format!(
+        "Summarize this coding session for a teammate. Cover: goals, decisions made, and open questions. \
+         Be concise (under 200 words).\n\n{}",
+        session.transcript_text()
+    );
+    let request = CompletionRequest::new(prompt, "claude-3-5-sonnet-20241022");
+    let completion = provider.complete(&request).await?;
+    Ok(completion.text)
+}
+
+/// Render the thought document body. `narrative` is `None` in `--no-llm` mode.
+pub fn render_summary_markdown(session: &Session, extractive: &ExtractiveSummary, narrative: Option<&str>) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("# Session summary: {}\n\n", session.id));
+
+    if let Some(text) = narrative {
+        out.push_str("## Summary\n\n");
+        out.push_str(text.trim());
+        out.push_str("\n\n");
+    }
+
+    out.push_str("## Goals\n\n");
+    out.push_str(extractive.first_user_message.as_deref().unwrap_or("(no user message recorded)"));
+    out.push_str("\n\n## Files changed\n\n");
+    if extractive.file_changes.is_empty() {
+        out.push_str("(none)\n\n");
+    } else {
+        for path in &extractive.file_changes {
+            out.push_str(&format!("- {path}\n"));
+        }
+        out.push('\n');
+    }
+
+    out.push_str("## Final state\n\n");
+    out.push_str(extractive.final_assistant_message.as_deref().unwrap_or("(no assistant message recorded)"));
+    out.push('\n');
+
+    out
+}
+
+/// Write the summary as a research-type thought document under
+/// `thoughts/shared/sessions/<project-slug>/summaries/`, with
+/// frontmatter linking back to the session id. Returns the written path.
+pub fn write_session_summary(thoughts_dir: &Path, project_path: &Path, today: &str, session: &Session, body: &str) -> CoreResult<PathBuf> {
+    let dir = super::session_index::summaries_dir(thoughts_dir, project_path);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{today}-{}.md", session.id));
+
+    let contents = format!(
+        "---\ntitle: \"Session summary: {}\"\ntype: research\nsession_id: \"{}\"\ncreated: {today}\n---\n\n{body}",
+        session.id, session.id
+    );
+    std::fs::write(&path, contents)?;
+    super::session_index::regenerate_index(thoughts_dir, project_path)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude::session::{FileChange, MessageRole, SessionMessage};
+
+    fn sample_session() -> Session {
+        Session {
+            id: "sess-1".into(),
+            source_path: PathBuf::from("/tmp/sess-1.jsonl"),
+            messages: vec![
+                SessionMessage { role: MessageRole::User, text: "add tests".into(), timestamp: None, is_tool_result: false, tool_calls: vec![] },
+                SessionMessage { role: MessageRole::Assistant, text: "done, all green".into(), timestamp: None, is_tool_result: false, tool_calls: vec![] },
+            ],
+            file_changes: vec![FileChange { tool: "Edit".into(), path: "src/lib.rs".into() }],
+            parse_issues: vec![],
+        }
+    }
+
+    #[test]
+    fn extractive_summary_pulls_first_and_last_messages() {
+        let summary = extractive_summary(&sample_session());
+        assert_eq!(summary.first_user_message.as_deref(), Some("add tests"));
+        assert_eq!(summary.final_assistant_message.as_deref(), Some("done, all green"));
+        assert_eq!(summary.file_changes, vec!["src/lib.rs".to_string()]);
+    }
+
+    #[test]
+    fn write_session_summary_includes_session_id_in_frontmatter() {
+        let tmp = tempfile::tempdir().unwrap();
+        let session = sample_session();
+        let extractive = extractive_summary(&session);
+        let body = render_summary_markdown(&session, &extractive, None);
+        let project = PathBuf::from("/home/user/proj");
+        let path = write_session_summary(tmp.path(), &project, "2026-08-08", &session, &body).unwrap();
+
+        assert_eq!(path, tmp.path().join("shared/sessions/home-user-proj/summaries/2026-08-08-sess-1.md"));
+        let contents = std::fs::read_to_string(path).unwrap();
+        assert!(contents.contains("session_id: \"sess-1\""));
+        assert!(contents.contains("src/lib.rs"));
+    }
+}
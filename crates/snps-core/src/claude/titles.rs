@@ -0,0 +1,103 @@
+//! User-assigned session titles, stored outside `~/.claude/projects/*`
+//! so they survive Claude Code moving a session's transcript between
+//! project directories — the sidecar keys on session id, never on path.
+
+use crate::error::CoreResult;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+pub fn titles_path() -> Option<PathBuf> {
+    home_dir().map(|home| home.join(".pmsynapse").join("claude-titles.json"))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TitleStore {
+    titles: BTreeMap<String, String>,
+}
+
+impl TitleStore {
+    pub fn load(path: &std::path::Path) -> CoreResult<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> CoreResult<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, session_id: &str) -> Option<&str> {
+        self.titles.get(session_id).map(String::as_str)
+    }
+
+    pub fn set(&mut self, session_id: &str, title: &str) {
+        self.titles.insert(session_id.to_string(), title.to_string());
+    }
+}
+
+/// The title cmd_claude_list and the exporters should show: the
+/// user-assigned title if one was ever recorded, otherwise `inferred`
+/// (typically the raw first user message).
+pub fn resolved_title<'a>(store: &'a TitleStore, session_id: &str, inferred: &'a str) -> &'a str {
+    store.get(session_id).unwrap_or(inferred)
+}
+
+/// Derive a title from an extractive summary's first user message,
+/// trimmed to a single line and capped so it reads like a title rather
+/// than a pasted prompt.
+pub fn title_from_summary(first_user_message: Option<&str>) -> String {
+    const MAX_LEN: usize = 72;
+    let raw = first_user_message.unwrap_or("Untitled session");
+    let first_line = raw.lines().next().unwrap_or(raw).trim();
+    if first_line.chars().count() > MAX_LEN {
+        let truncated: String = first_line.chars().take(MAX_LEN).collect();
+        format!("{}...", truncated.trim_end())
+    } else {
+        first_line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolved_title_prefers_user_title_over_inferred() {
+        let mut store = TitleStore::default();
+        store.set("sess-1", "Fix the login bug");
+        assert_eq!(resolved_title(&store, "sess-1", "raw first message"), "Fix the login bug");
+        assert_eq!(resolved_title(&store, "sess-2", "raw first message"), "raw first message");
+    }
+
+    #[test]
+    fn title_store_round_trips_through_disk() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("claude-titles.json");
+
+        let mut store = TitleStore::load(&path).unwrap();
+        store.set("sess-1", "Fix the login bug");
+        store.save(&path).unwrap();
+
+        let reloaded = TitleStore::load(&path).unwrap();
+        assert_eq!(reloaded.get("sess-1"), Some("Fix the login bug"));
+    }
+
+    #[test]
+    fn title_from_summary_truncates_long_first_lines() {
+        let long = "a".repeat(100);
+        let title = title_from_summary(Some(&long));
+        assert!(title.ends_with("..."));
+        assert!(title.chars().count() <= 75);
+    }
+}
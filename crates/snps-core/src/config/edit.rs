@@ -0,0 +1,187 @@
+//! `snps config get`/`set`: scripted single-key reads and writes against
+//! one config layer file.
+//!
+//! Files are re-serialized as `serde_yaml::Value` rather than edited
+//! in-place, so unknown keys survive but comments do not — there's no
+//! comment-preserving YAML writer in the current dependency set. If that
+//! becomes a problem, swap in a CST-based editor (e.g. `yaml-rust2`'s
+//! layout-preserving mode) without changing this module's signatures.
+
+use crate::error::{CoreError, CoreResult};
+use crate::workspace::Workspace;
+use std::path::PathBuf;
+
+/// Which config file a `get`/`set` targets. Deliberately excludes `local`
+/// and `env` — those aren't meant to be edited by a scripted command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigScope {
+    Global,
+    Team,
+    Project,
+}
+
+impl std::str::FromStr for ConfigScope {
+    type Err = CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "global" => Ok(ConfigScope::Global),
+            "team" => Ok(ConfigScope::Team),
+            "project" => Ok(ConfigScope::Project),
+            other => Err(CoreError::InvalidInput(format!("unknown config scope '{other}'"))),
+        }
+    }
+}
+
+/// Known dotted keys and whether they hold a list (vs. a scalar), used to
+/// validate `set` values against `GlobalConfig`'s schema.
+const LIST_KEYS: &[&str] = &["search.exclude_patterns", "redaction.patterns", "knowledge.precedence"];
+const SCALAR_KEYS: &[&str] = &[
+    "defaults.editor",
+    "search.index_db",
+    "llm.default_provider",
+    "llm.api_key",
+    "repositories_root",
+    "active_team",
+];
+/// Scalar keys that hold a number rather than a string, so `set` stores a
+/// YAML number `PartialConfig` can deserialize straight into their `u64`
+/// field instead of a string that would fail to parse.
+const NUMERIC_KEYS: &[&str] = &["defaults.notify_after_seconds"];
+/// Scalar keys that hold a bool, same reasoning as `NUMERIC_KEYS`.
+const BOOL_KEYS: &[&str] = &["require_share_review", "defaults.telemetry_enabled"];
+
+pub fn scope_path(workspace: &Workspace, scope: ConfigScope) -> PathBuf {
+    match scope {
+        ConfigScope::Global => super::home_dir()
+            .map(|h| h.join(".pmsynapse").join("config.yaml"))
+            .unwrap_or_else(|| workspace.pmsynapse_dir().join("config.yaml")),
+        ConfigScope::Team => workspace.teams_dir().join(crate::team::active_team_id(workspace)).join("config.yaml"),
+        ConfigScope::Project => workspace.config_path(),
+    }
+}
+
+fn load_raw(path: &PathBuf) -> CoreResult<serde_yaml::Value> {
+    if !path.exists() {
+        return Ok(serde_yaml::Value::Mapping(Default::default()));
+    }
+    let contents = std::fs::read_to_string(path)?;
+    serde_yaml::from_str(&contents)
+        .map_err(|e| CoreError::Parse { path: path.clone(), message: e.to_string() })
+}
+
+fn key_path(key: &str) -> Vec<&str> {
+    key.split('.').collect()
+}
+
+/// Read a dotted key (e.g. `search.index_db`) out of `scope`'s file.
+pub fn get_value(workspace: &Workspace, scope: ConfigScope, key: &str) -> CoreResult<Option<serde_yaml::Value>> {
+    let path = scope_path(workspace, scope);
+    let root = load_raw(&path)?;
+    Ok(navigate(&root, &key_path(key)).cloned())
+}
+
+fn navigate<'a>(root: &'a serde_yaml::Value, path: &[&str]) -> Option<&'a serde_yaml::Value> {
+    let mut current = root;
+    for segment in path {
+        current = current.as_mapping()?.get(serde_yaml::Value::String(segment.to_string()))?;
+    }
+    Some(current)
+}
+
+/// Parse the CLI-supplied `value` string for `key`: a JSON array for list
+/// keys (`search.exclude_patterns`), a plain string otherwise.
+pub fn parse_value(key: &str, raw: &str) -> CoreResult<serde_yaml::Value> {
+    if LIST_KEYS.contains(&key) {
+        let items: Vec<String> = serde_json::from_str(raw)
+            .map_err(|e| CoreError::InvalidInput(format!("'{key}' expects a JSON array of strings: {e}")))?;
+        return Ok(serde_yaml::Value::Sequence(items.into_iter().map(serde_yaml::Value::String).collect()));
+    }
+    if NUMERIC_KEYS.contains(&key) {
+        let n: u64 = raw
+            .parse()
+            .map_err(|_| CoreError::InvalidInput(format!("'{key}' expects a non-negative integer, got '{raw}'")))?;
+        return Ok(serde_yaml::Value::Number(n.into()));
+    }
+    if BOOL_KEYS.contains(&key) {
+        let b: bool = raw
+            .parse()
+            .map_err(|_| CoreError::InvalidInput(format!("'{key}' expects 'true' or 'false', got '{raw}'")))?;
+        return Ok(serde_yaml::Value::Bool(b));
+    }
+    if SCALAR_KEYS.contains(&key) {
+        return Ok(serde_yaml::Value::String(raw.to_string()));
+    }
+    Err(CoreError::InvalidInput(format!("unknown config key '{key}'")))
+}
+
+/// Write `value` at `key` in `scope`'s file, creating the file (and its
+/// parent directory) if it doesn't exist yet. Returns the previous value,
+/// if any.
+pub fn set_value(
+    workspace: &Workspace,
+    scope: ConfigScope,
+    key: &str,
+    value: serde_yaml::Value,
+) -> CoreResult<Option<serde_yaml::Value>> {
+    let path = scope_path(workspace, scope);
+    let mut root = load_raw(&path)?;
+    let segments = key_path(key);
+    let old = navigate(&root, &segments).cloned();
+
+    insert(&mut root, &segments, value);
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_yaml::to_string(&root)?.into_bytes())?;
+
+    Ok(old)
+}
+
+fn insert(root: &mut serde_yaml::Value, path: &[&str], value: serde_yaml::Value) {
+    if !root.is_mapping() {
+        *root = serde_yaml::Value::Mapping(Default::default());
+    }
+    let mapping = root.as_mapping_mut().expect("just ensured this is a mapping");
+    let key = serde_yaml::Value::String(path[0].to_string());
+
+    if path.len() == 1 {
+        mapping.insert(key, value);
+        return;
+    }
+
+    let child = mapping.entry(key).or_insert_with(|| serde_yaml::Value::Mapping(Default::default()));
+    insert(child, &path[1..], value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_value_rejects_unknown_key() {
+        assert!(parse_value("nonexistent.key", "x").is_err());
+    }
+
+    #[test]
+    fn parse_value_parses_a_numeric_key() {
+        assert_eq!(parse_value("defaults.notify_after_seconds", "45").unwrap(), serde_yaml::Value::Number(45.into()));
+        assert!(parse_value("defaults.notify_after_seconds", "soon").is_err());
+    }
+
+    #[test]
+    fn parse_value_parses_a_bool_key() {
+        assert_eq!(parse_value("require_share_review", "true").unwrap(), serde_yaml::Value::Bool(true));
+        assert!(parse_value("require_share_review", "sure").is_err());
+    }
+
+    #[test]
+    fn parse_value_parses_json_array_for_list_key() {
+        let value = parse_value("search.exclude_patterns", r#"["*.lock", "target/"]"#).unwrap();
+        assert_eq!(value, serde_yaml::Value::Sequence(vec![
+            serde_yaml::Value::String("*.lock".into()),
+            serde_yaml::Value::String("target/".into()),
+        ]));
+    }
+}
@@ -0,0 +1,152 @@
+//! Environment variable overrides, the highest-precedence config layer.
+//!
+//! Documented mapping (prefix `PMSYNAPSE_`, section and key joined by `_`):
+//!
+//! | variable                       | field                          |
+//! |---------------------------------|---------------------------------|
+//! | `PMSYNAPSE_DEFAULTS_EDITOR`     | `defaults.editor`               |
+//! | `PMSYNAPSE_DEFAULTS_NOTIFY_AFTER_SECONDS` | `defaults.notify_after_seconds` |
+//! | `PMSYNAPSE_DEFAULTS_TELEMETRY_ENABLED` | `defaults.telemetry_enabled` (`true`/`false`) |
+//! | `PMSYNAPSE_SEARCH_INDEX_DB`     | `search.index_db`               |
+//! | `PMSYNAPSE_SEARCH_EXCLUDE_PATTERNS` | `search.exclude_patterns` (comma-separated) |
+//! | `PMSYNAPSE_LLM_DEFAULT_PROVIDER` | `llm.default_provider`         |
+//! | `PMSYNAPSE_LLM_API_KEY`         | `llm.api_key`                    |
+//! | `PMSYNAPSE_REDACTION_PATTERNS`  | `redaction.patterns` (comma-separated) |
+//! | `PMSYNAPSE_KNOWLEDGE_PRECEDENCE` | `knowledge.precedence` (comma-separated) |
+//! | `PMSYNAPSE_REPOSITORIES_ROOT`   | `repositories_root`             |
+//! | `PMSYNAPSE_REQUIRE_SHARE_REVIEW` | `require_share_review` (`true`/`false`) |
+
+use super::partial::{DefaultsSection, KnowledgeSection, LlmSection, PartialConfig, RedactionSection, SearchSection};
+
+/// Build the env layer by reading the documented `PMSYNAPSE_*` variables
+/// from the process environment.
+pub fn env_layer() -> PartialConfig {
+    from_lookup(|name| std::env::var(name).ok())
+}
+
+fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> PartialConfig {
+    let editor = lookup("PMSYNAPSE_DEFAULTS_EDITOR");
+    let notify_after_seconds = lookup("PMSYNAPSE_DEFAULTS_NOTIFY_AFTER_SECONDS").and_then(|v| v.parse().ok());
+    let telemetry_enabled = lookup("PMSYNAPSE_DEFAULTS_TELEMETRY_ENABLED").and_then(|v| v.parse().ok());
+    let index_db = lookup("PMSYNAPSE_SEARCH_INDEX_DB");
+    let exclude_patterns = lookup("PMSYNAPSE_SEARCH_EXCLUDE_PATTERNS")
+        .map(|v| v.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect());
+    let default_provider = lookup("PMSYNAPSE_LLM_DEFAULT_PROVIDER");
+    let api_key = lookup("PMSYNAPSE_LLM_API_KEY");
+    let redaction_patterns = lookup("PMSYNAPSE_REDACTION_PATTERNS")
+        .map(|v| v.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect());
+    let repositories_root = lookup("PMSYNAPSE_REPOSITORIES_ROOT");
+    let require_share_review = lookup("PMSYNAPSE_REQUIRE_SHARE_REVIEW").and_then(|v| v.parse().ok());
+    let knowledge_precedence = lookup("PMSYNAPSE_KNOWLEDGE_PRECEDENCE")
+        .map(|v| v.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect());
+
+    PartialConfig {
+        defaults: (editor.is_some() || notify_after_seconds.is_some() || telemetry_enabled.is_some())
+            .then(|| DefaultsSection { editor, notify_after_seconds, telemetry_enabled }),
+        search: (index_db.is_some() || exclude_patterns.is_some())
+            .then(|| SearchSection { index_db, exclude_patterns }),
+        llm: (default_provider.is_some() || api_key.is_some())
+            .then(|| LlmSection { default_provider, api_key }),
+        redaction: redaction_patterns.is_some().then(|| RedactionSection { patterns: redaction_patterns }),
+        knowledge: knowledge_precedence.is_some().then(|| KnowledgeSection { precedence: knowledge_precedence }),
+        repositories_root,
+        require_share_review,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_documented_variables_into_sections() {
+        let layer = from_lookup(|name| match name {
+            "PMSYNAPSE_DEFAULTS_EDITOR" => Some("helix".to_string()),
+            "PMSYNAPSE_SEARCH_EXCLUDE_PATTERNS" => Some("*.lock, target/".to_string()),
+            _ => None,
+        });
+        assert_eq!(layer.defaults_editor(), Some("helix".to_string()));
+        assert_eq!(layer.search_exclude_patterns(), Some(vec!["*.lock".to_string(), "target/".to_string()]));
+        assert!(layer.llm.is_none());
+        assert!(layer.redaction.is_none());
+    }
+
+    #[test]
+    fn maps_redaction_patterns_variable() {
+        let layer = from_lookup(|name| match name {
+            "PMSYNAPSE_REDACTION_PATTERNS" => Some("sk-live-[a-z0-9]+, internal-.*".to_string()),
+            _ => None,
+        });
+        assert_eq!(layer.redaction_patterns(), Some(vec!["sk-live-[a-z0-9]+".to_string(), "internal-.*".to_string()]));
+    }
+
+    #[test]
+    fn maps_notify_after_seconds_variable() {
+        let layer = from_lookup(|name| match name {
+            "PMSYNAPSE_DEFAULTS_NOTIFY_AFTER_SECONDS" => Some("45".to_string()),
+            _ => None,
+        });
+        assert_eq!(layer.defaults_notify_after_seconds(), Some(45));
+    }
+
+    #[test]
+    fn ignores_an_unparseable_notify_after_seconds_variable() {
+        let layer = from_lookup(|name| match name {
+            "PMSYNAPSE_DEFAULTS_NOTIFY_AFTER_SECONDS" => Some("soon".to_string()),
+            _ => None,
+        });
+        assert_eq!(layer.defaults_notify_after_seconds(), None);
+    }
+
+    #[test]
+    fn maps_telemetry_enabled_variable() {
+        let layer = from_lookup(|name| match name {
+            "PMSYNAPSE_DEFAULTS_TELEMETRY_ENABLED" => Some("true".to_string()),
+            _ => None,
+        });
+        assert_eq!(layer.defaults_telemetry_enabled(), Some(true));
+    }
+
+    #[test]
+    fn ignores_an_unparseable_telemetry_enabled_variable() {
+        let layer = from_lookup(|name| match name {
+            "PMSYNAPSE_DEFAULTS_TELEMETRY_ENABLED" => Some("sure".to_string()),
+            _ => None,
+        });
+        assert_eq!(layer.defaults_telemetry_enabled(), None);
+    }
+
+    #[test]
+    fn maps_knowledge_precedence_variable() {
+        let layer = from_lookup(|name| match name {
+            "PMSYNAPSE_KNOWLEDGE_PRECEDENCE" => Some("team, project, user".to_string()),
+            _ => None,
+        });
+        assert_eq!(layer.knowledge_precedence(), Some(vec!["team".to_string(), "project".to_string(), "user".to_string()]));
+    }
+
+    #[test]
+    fn maps_require_share_review_variable() {
+        let layer = from_lookup(|name| match name {
+            "PMSYNAPSE_REQUIRE_SHARE_REVIEW" => Some("true".to_string()),
+            _ => None,
+        });
+        assert_eq!(layer.require_share_review, Some(true));
+    }
+
+    #[test]
+    fn ignores_an_unparseable_require_share_review_variable() {
+        let layer = from_lookup(|name| match name {
+            "PMSYNAPSE_REQUIRE_SHARE_REVIEW" => Some("sure".to_string()),
+            _ => None,
+        });
+        assert_eq!(layer.require_share_review, None);
+    }
+
+    #[test]
+    fn empty_environment_produces_empty_layer() {
+        let layer = from_lookup(|_| None);
+        assert_eq!(layer, PartialConfig::default());
+    }
+}
@@ -0,0 +1,290 @@
+//! Layered configuration: global (user), team, project, project-local, and
+//! environment overrides, merged into a single [`GlobalConfig`] with
+//! per-field source tracking for `snps config show --source`.
+//!
+//! Precedence, highest to lowest: env > local > project > team > global.
+
+mod edit;
+mod env;
+mod partial;
+mod shadow;
+mod validate;
+
+pub use edit::{get_value, parse_value, scope_path, set_value, ConfigScope};
+pub use env::env_layer;
+pub use partial::PartialConfig;
+pub use shadow::{config_push, config_sync, ChangedFile, PushOutcome};
+pub use validate::{validate_config, ValidationIssue};
+
+use crate::error::CoreResult;
+use crate::workspace::Workspace;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Where a resolved config value came from, in precedence order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigSource {
+    Default,
+    Global,
+    Team,
+    Project,
+    Local,
+    Env,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ConfigSource::Default => "default",
+            ConfigSource::Global => "global",
+            ConfigSource::Team => "team",
+            ConfigSource::Project => "project",
+            ConfigSource::Local => "local",
+            ConfigSource::Env => "env",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Fully resolved configuration, with every field defaulted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalConfig {
+    pub defaults_editor: String,
+    /// `defaults.notify_after_seconds` — a command that runs at least this
+    /// long fires a desktop notification on completion, via `snps-cli`'s
+    /// `notify` module (`notify-rust` on the CLI, `tauri-plugin-notification`
+    /// in the desktop app). `None` (the default) means notifications are
+    /// off; this is opt-in, not opt-out.
+    pub notify_after_seconds: Option<u64>,
+    /// `defaults.telemetry_enabled` — whether `snps-cli`'s `telemetry`
+    /// module appends a record to `~/.pmsynapse/metrics.jsonl` after each
+    /// invocation. `false` (the default) means nothing is written; this is
+    /// opt-in, not opt-out, same as `notify_after_seconds`.
+    pub telemetry_enabled: bool,
+    pub search_index_db: String,
+    pub search_exclude_patterns: Vec<String>,
+    pub llm_default_provider: String,
+    pub llm_api_key: Option<String>,
+    pub redaction_patterns: Vec<String>,
+    /// `thoughts.categories`, resolved via
+    /// [`crate::thoughts::ThoughtsLayout::from_config`] rather than used
+    /// directly — see that function for the empty-falls-back-to-defaults
+    /// rule. Unlike the other fields here, this one is intentionally not
+    /// exposed through `snps config get/set` or `PMSYNAPSE_*` env vars:
+    /// both are built for flat scalar/list-of-string values, and a list of
+    /// `{name, dir, template}` records doesn't fit either shape without
+    /// inventing a delimiter-encoded mini-format. Edit the YAML directly.
+    pub thoughts_categories: Vec<crate::thoughts::CategoryConfig>,
+    /// `sync.schedules` — see [`crate::scheduler`]. Not exposed through
+    /// `snps config get/set`/env vars, same reasoning as
+    /// `thoughts_categories` above.
+    pub sync_schedules: Vec<crate::scheduler::ScheduleConfig>,
+    /// `knowledge.precedence` — context names highest-winning-first, e.g.
+    /// `["team", "project", "user"]`. Consulted by
+    /// [`crate::knowledge::context_precedence`]; empty means the built-in
+    /// `project > team > user` order.
+    pub knowledge_precedence: Vec<String>,
+    pub repositories_root: String,
+    pub active_team: String,
+    pub require_share_review: bool,
+}
+
+impl Default for GlobalConfig {
+    fn default() -> Self {
+        Self {
+            defaults_editor: "vi".to_string(),
+            notify_after_seconds: None,
+            telemetry_enabled: false,
+            search_index_db: ".pmsynapse/search-index.json".to_string(),
+            search_exclude_patterns: Vec::new(),
+            llm_default_provider: "anthropic".to_string(),
+            llm_api_key: None,
+            redaction_patterns: Vec::new(),
+            thoughts_categories: Vec::new(),
+            sync_schedules: Vec::new(),
+            knowledge_precedence: Vec::new(),
+            repositories_root: ".".to_string(),
+            active_team: crate::team::DEFAULT_TEAM_ID.to_string(),
+            require_share_review: false,
+        }
+    }
+}
+
+/// The result of merging every config layer: resolved values plus which
+/// layer each one came from.
+#[derive(Debug, Clone)]
+pub struct MergedConfig {
+    pub config: GlobalConfig,
+    pub sources: HashMap<&'static str, ConfigSource>,
+    /// Path each non-default layer was read from, in precedence order,
+    /// for diagnostics (`snps config show --source` and future `validate`).
+    pub layer_paths: Vec<(ConfigSource, PathBuf)>,
+}
+
+/// One field name per `GlobalConfig` field, matched to the merge loop below.
+const FIELDS: &[&str] = &[
+    "defaults.editor",
+    "defaults.notify_after_seconds",
+    "defaults.telemetry_enabled",
+    "search.index_db",
+    "search.exclude_patterns",
+    "llm.default_provider",
+    "llm.api_key",
+    "redaction.patterns",
+    "thoughts.categories",
+    "sync.schedules",
+    "knowledge.precedence",
+    "repositories_root",
+    "active_team",
+    "require_share_review",
+];
+
+/// Load and merge every layer for `workspace`: global (`~/.pmsynapse/config.yaml`),
+/// team (`<workspace>/.pmsynapse/teams/config.yaml`), project
+/// (`<workspace>/.pmsynapse/config.yaml`), project-local
+/// (`<workspace>/.pmsynapse/config.local.yaml`, gitignored), and environment
+/// variables prefixed `PMSYNAPSE_` (see [`env_layer`]).
+pub fn load_merged_config(workspace: &Workspace) -> CoreResult<MergedConfig> {
+    let mut layers: Vec<(ConfigSource, Option<PathBuf>, PartialConfig)> = Vec::new();
+
+    if let Some(home) = home_dir() {
+        let path = home.join(".pmsynapse").join("config.yaml");
+        layers.push((ConfigSource::Global, Some(path.clone()), PartialConfig::load(&path)?));
+    }
+
+    let team_path = workspace.teams_dir().join(crate::team::active_team_id(workspace)).join("config.yaml");
+    layers.push((ConfigSource::Team, Some(team_path.clone()), PartialConfig::load(&team_path)?));
+
+    let project_path = workspace.config_path();
+    layers.push((ConfigSource::Project, Some(project_path.clone()), PartialConfig::load(&project_path)?));
+
+    let local_path = local_config_path(workspace);
+    layers.push((ConfigSource::Local, Some(local_path.clone()), PartialConfig::load(&local_path)?));
+
+    layers.push((ConfigSource::Env, None, env_layer()));
+
+    Ok(merge(layers))
+}
+
+/// Path of the gitignored project-local override file.
+pub fn local_config_path(workspace: &Workspace) -> PathBuf {
+    workspace.pmsynapse_dir().join("config.local.yaml")
+}
+
+fn merge(layers: Vec<(ConfigSource, Option<PathBuf>, PartialConfig)>) -> MergedConfig {
+    let mut config = GlobalConfig::default();
+    let mut sources: HashMap<&'static str, ConfigSource> = FIELDS.iter().map(|f| (*f, ConfigSource::Default)).collect();
+    let mut layer_paths = Vec::new();
+
+    // Layers are pushed lowest-precedence first; walk them in that order so
+    // a later (higher-precedence) layer's `Some` overwrites an earlier one.
+    for (source, path, partial) in layers {
+        if let Some(path) = path {
+            layer_paths.push((source, path));
+        }
+        if let Some(v) = partial.defaults_editor() {
+            config.defaults_editor = v;
+            sources.insert("defaults.editor", source);
+        }
+        if let Some(v) = partial.defaults_notify_after_seconds() {
+            config.notify_after_seconds = Some(v);
+            sources.insert("defaults.notify_after_seconds", source);
+        }
+        if let Some(v) = partial.defaults_telemetry_enabled() {
+            config.telemetry_enabled = v;
+            sources.insert("defaults.telemetry_enabled", source);
+        }
+        if let Some(v) = partial.search_index_db() {
+            config.search_index_db = v;
+            sources.insert("search.index_db", source);
+        }
+        if let Some(v) = partial.search_exclude_patterns() {
+            config.search_exclude_patterns = v;
+            sources.insert("search.exclude_patterns", source);
+        }
+        if let Some(v) = partial.llm_default_provider() {
+            config.llm_default_provider = v;
+            sources.insert("llm.default_provider", source);
+        }
+        if let Some(v) = partial.llm_api_key() {
+            config.llm_api_key = Some(v);
+            sources.insert("llm.api_key", source);
+        }
+        if let Some(v) = partial.redaction_patterns() {
+            config.redaction_patterns = v;
+            sources.insert("redaction.patterns", source);
+        }
+        if let Some(v) = partial.thoughts_categories() {
+            config.thoughts_categories = v;
+            sources.insert("thoughts.categories", source);
+        }
+        if let Some(v) = partial.sync_schedules() {
+            config.sync_schedules = v;
+            sources.insert("sync.schedules", source);
+        }
+        if let Some(v) = partial.knowledge_precedence() {
+            config.knowledge_precedence = v;
+            sources.insert("knowledge.precedence", source);
+        }
+        if let Some(v) = partial.repositories_root {
+            config.repositories_root = v;
+            sources.insert("repositories_root", source);
+        }
+        if let Some(v) = partial.active_team {
+            config.active_team = v;
+            sources.insert("active_team", source);
+        }
+        if let Some(v) = partial.require_share_review {
+            config.require_share_review = v;
+            sources.insert("require_share_review", source);
+        }
+    }
+
+    MergedConfig { config, sources, layer_paths }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use partial::DefaultsSection;
+
+    fn layer(source: ConfigSource, editor: &str) -> (ConfigSource, Option<PathBuf>, PartialConfig) {
+        let partial = PartialConfig {
+            defaults: Some(DefaultsSection { editor: Some(editor.to_string()), ..Default::default() }),
+            ..Default::default()
+        };
+        (source, None, partial)
+    }
+
+    #[test]
+    fn later_layer_wins_and_is_attributed() {
+        let merged = merge(vec![layer(ConfigSource::Global, "nano"), layer(ConfigSource::Project, "vim")]);
+        assert_eq!(merged.config.defaults_editor, "vim");
+        assert_eq!(merged.sources["defaults.editor"], ConfigSource::Project);
+    }
+
+    #[test]
+    fn env_outranks_every_file_layer() {
+        let merged = merge(vec![
+            layer(ConfigSource::Global, "nano"),
+            layer(ConfigSource::Team, "emacs"),
+            layer(ConfigSource::Project, "vim"),
+            layer(ConfigSource::Local, "helix"),
+            layer(ConfigSource::Env, "ed"),
+        ]);
+        assert_eq!(merged.config.defaults_editor, "ed");
+        assert_eq!(merged.sources["defaults.editor"], ConfigSource::Env);
+    }
+
+    #[test]
+    fn unset_field_falls_back_to_default_source() {
+        let merged = merge(vec![(ConfigSource::Global, None, PartialConfig::default())]);
+        assert_eq!(merged.config.defaults_editor, GlobalConfig::default().defaults_editor);
+        assert_eq!(merged.sources["defaults.editor"], ConfigSource::Default);
+    }
+}
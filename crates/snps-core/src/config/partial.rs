@@ -0,0 +1,169 @@
+//! The all-optional shape every config layer file (or the env layer)
+//! deserializes into, before merging into a [`super::GlobalConfig`].
+//!
+//! Mirrors the nested YAML shape of `config.yaml` (`defaults:`, `search:`,
+//! `llm:` sections) rather than flattening dotted keys, so hand-written
+//! layer files read naturally.
+
+use crate::error::CoreResult;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PartialConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defaults: Option<DefaultsSection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<SearchSection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm: Option<LlmSection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redaction: Option<RedactionSection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thoughts: Option<ThoughtsSection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync: Option<SyncSection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub knowledge: Option<KnowledgeSection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repositories_root: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_team: Option<String>,
+    /// Whether `matter promote`/`demote` must go through a [`crate::proposals`]
+    /// review instead of flipping visibility immediately. A flat scalar like
+    /// `repositories_root`/`active_team` rather than its own section, since
+    /// it's a single team-wide policy switch, not a group of related
+    /// settings — set it in a team's `config.yaml` for it to apply team-wide.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_share_review: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DefaultsSection {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    /// Opt-in desktop notification threshold — see
+    /// [`crate::config::GlobalConfig::notify_after_seconds`]. Unset means
+    /// notifications stay off.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify_after_seconds: Option<u64>,
+    /// Opt-in local metrics logging — see
+    /// [`crate::config::GlobalConfig::telemetry_enabled`]. Unset means
+    /// nothing is written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry_enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SearchSection {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_db: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_patterns: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LlmSection {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RedactionSection {
+    /// Extra regex patterns, on top of the built-in ones, for
+    /// `snps claude export`'s redaction pass — see
+    /// [`crate::claude::redact`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patterns: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ThoughtsSection {
+    /// Team-defined `thoughts new`/`thoughts init` categories, replacing
+    /// [`crate::thoughts::ThoughtsLayout::default_categories`] wholesale
+    /// when set. See [`crate::thoughts::ThoughtsLayout::from_config`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub categories: Option<Vec<crate::thoughts::CategoryConfig>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SyncSection {
+    /// Daemon-managed background jobs — see [`crate::scheduler`]. Like
+    /// `thoughts.categories`, a list of records rather than a flat
+    /// scalar/list-of-string value, so it's not exposed through `snps
+    /// config get/set` or `PMSYNAPSE_*` env vars. Edit the YAML directly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedules: Option<Vec<crate::scheduler::ScheduleConfig>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct KnowledgeSection {
+    /// Context precedence, highest-winning-first, for
+    /// [`crate::knowledge::resolve_precedence`] — e.g. `[team, project,
+    /// user]` to have a team's knowledge override a project's. Unset
+    /// keeps the built-in `project > team > user` order from
+    /// [`crate::knowledge::context_precedence`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precedence: Option<Vec<String>>,
+}
+
+impl PartialConfig {
+    /// Load a layer file. A missing file is treated as an empty layer
+    /// rather than an error, since most layers are optional.
+    pub fn load(path: &Path) -> CoreResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        serde_yaml::from_str(&contents).map_err(|e| crate::error::CoreError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })
+    }
+
+    pub fn defaults_editor(&self) -> Option<String> {
+        self.defaults.as_ref()?.editor.clone()
+    }
+
+    pub fn defaults_notify_after_seconds(&self) -> Option<u64> {
+        self.defaults.as_ref()?.notify_after_seconds
+    }
+
+    pub fn defaults_telemetry_enabled(&self) -> Option<bool> {
+        self.defaults.as_ref()?.telemetry_enabled
+    }
+
+    pub fn search_index_db(&self) -> Option<String> {
+        self.search.as_ref()?.index_db.clone()
+    }
+
+    pub fn search_exclude_patterns(&self) -> Option<Vec<String>> {
+        self.search.as_ref()?.exclude_patterns.clone()
+    }
+
+    pub fn llm_default_provider(&self) -> Option<String> {
+        self.llm.as_ref()?.default_provider.clone()
+    }
+
+    pub fn llm_api_key(&self) -> Option<String> {
+        self.llm.as_ref()?.api_key.clone()
+    }
+
+    pub fn redaction_patterns(&self) -> Option<Vec<String>> {
+        self.redaction.as_ref()?.patterns.clone()
+    }
+
+    pub fn thoughts_categories(&self) -> Option<Vec<crate::thoughts::CategoryConfig>> {
+        self.thoughts.as_ref()?.categories.clone()
+    }
+
+    pub fn sync_schedules(&self) -> Option<Vec<crate::scheduler::ScheduleConfig>> {
+        self.sync.as_ref()?.schedules.clone()
+    }
+
+    pub fn knowledge_precedence(&self) -> Option<Vec<String>> {
+        self.knowledge.as_ref()?.precedence.clone()
+    }
+}
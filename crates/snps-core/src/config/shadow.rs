@@ -0,0 +1,186 @@
+//! `snps config sync` / `snps config push`: move team and project config
+//! files to and from the shadow repositories that own them.
+
+use super::partial::PartialConfig;
+use crate::error::{CoreError, CoreResult};
+use crate::git::GitRepo;
+use crate::repository::{sync::git_push, Repository};
+use crate::workspace::Workspace;
+use std::path::PathBuf;
+
+/// A config file changed by a sync or push, with the top-level keys that
+/// differed between the old and new contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    pub path: PathBuf,
+    pub changed_keys: Vec<&'static str>,
+}
+
+fn diff_keys(before: &PartialConfig, after: &PartialConfig) -> Vec<&'static str> {
+    let mut keys = Vec::new();
+    if before.defaults_editor() != after.defaults_editor() {
+        keys.push("defaults.editor");
+    }
+    if before.defaults_notify_after_seconds() != after.defaults_notify_after_seconds() {
+        keys.push("defaults.notify_after_seconds");
+    }
+    if before.defaults_telemetry_enabled() != after.defaults_telemetry_enabled() {
+        keys.push("defaults.telemetry_enabled");
+    }
+    if before.search_index_db() != after.search_index_db() {
+        keys.push("search.index_db");
+    }
+    if before.search_exclude_patterns() != after.search_exclude_patterns() {
+        keys.push("search.exclude_patterns");
+    }
+    if before.llm_default_provider() != after.llm_default_provider() {
+        keys.push("llm.default_provider");
+    }
+    if before.llm_api_key() != after.llm_api_key() {
+        keys.push("llm.api_key");
+    }
+    if before.redaction_patterns() != after.redaction_patterns() {
+        keys.push("redaction.patterns");
+    }
+    if before.thoughts_categories() != after.thoughts_categories() {
+        keys.push("thoughts.categories");
+    }
+    if before.repositories_root != after.repositories_root {
+        keys.push("repositories_root");
+    }
+    if before.active_team != after.active_team {
+        keys.push("active_team");
+    }
+    if before.require_share_review != after.require_share_review {
+        keys.push("require_share_review");
+    }
+    keys
+}
+
+fn repo_for_context<'a>(repos: &'a [Repository], context: &str) -> Option<&'a Repository> {
+    repos.iter().find(|r| r.context == context)
+}
+
+/// Pull the team and project shadow repositories (whichever are
+/// configured) and copy their `config.yaml` into the corresponding
+/// workspace-local config file, reporting which keys changed. Does not
+/// touch anything when `dry_run` is set.
+pub fn config_sync(workspace: &Workspace, repos: &[Repository], dry_run: bool) -> CoreResult<Vec<ChangedFile>> {
+    let mut changed = Vec::new();
+
+    let team_dir = workspace.teams_dir().join(crate::team::active_team_id(workspace));
+    for (context, destination) in [("team", team_dir.join("config.yaml")), ("project", workspace.config_path())] {
+        let Some(repo) = repo_for_context(repos, context) else { continue };
+        let source = repo.path.join("config.yaml");
+
+        if let Ok(git) = GitRepo::open(&repo.path) {
+            git.fetch().ok();
+        }
+
+        let before = PartialConfig::load(&destination)?;
+        let after = PartialConfig::load(&source)?;
+        let keys = diff_keys(&before, &after);
+        if keys.is_empty() {
+            continue;
+        }
+
+        if !dry_run {
+            if let Some(parent) = destination.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(&source, &destination)?;
+        }
+
+        changed.push(ChangedFile { path: destination, changed_keys: keys });
+    }
+
+    Ok(changed)
+}
+
+/// What [`config_push`] did, or (for `--status-only`) would do without
+/// committing anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PushOutcome {
+    NoChanges,
+    Pushed(ChangedFile),
+    /// `git status --porcelain`-style lines for what `--status-only`
+    /// found staged in the shadow repo's working tree. Empty means the
+    /// sync copy landed with no actual content change.
+    Status(Vec<String>),
+}
+
+/// Push the workspace's team config file into the team shadow repo,
+/// committing the change. Refuses to run if the shadow repo is in a
+/// state where an unattended commit could do the wrong thing (a
+/// mid-flight merge or rebase, a detached HEAD, or a shallow clone with
+/// nowhere to push to — see [`crate::git::GitRepo::commit_hazard`]), or if
+/// it has uncommitted changes outside `config.yaml`, since those aren't
+/// ours to commit or discard.
+pub fn config_push(workspace: &Workspace, repos: &[Repository], dry_run: bool, push: bool, status_only: bool) -> CoreResult<PushOutcome> {
+    let Some(repo) = repo_for_context(repos, "team") else {
+        return Err(CoreError::NotFound("no team-context repository configured".to_string()));
+    };
+
+    let git = GitRepo::open(&repo.path)?;
+    if let Some(hazard) = git.commit_hazard()? {
+        return Err(CoreError::InvalidInput(hazard.describe(&repo.path)));
+    }
+
+    let source = workspace.teams_dir().join(crate::team::active_team_id(workspace)).join("config.yaml");
+    let destination = repo.path.join("config.yaml");
+
+    let before = PartialConfig::load(&destination)?;
+    let after = PartialConfig::load(&source)?;
+    let keys = diff_keys(&before, &after);
+    if keys.is_empty() {
+        return Ok(PushOutcome::NoChanges);
+    }
+
+    if dry_run {
+        return Ok(PushOutcome::Pushed(ChangedFile { path: destination, changed_keys: keys }));
+    }
+
+    let dirty = git.dirty_paths_excluding(&["config.yaml"])?;
+    if !dirty.is_empty() {
+        return Err(CoreError::InvalidInput(format!(
+            "refusing to push: {} has uncommitted unrelated changes: {}",
+            repo.id,
+            dirty.join(", ")
+        )));
+    }
+
+    std::fs::copy(&source, &destination)?;
+
+    if status_only {
+        return Ok(PushOutcome::Status(git.porcelain_status(&["config.yaml"])?));
+    }
+
+    let paths = ["config.yaml"];
+    let message = format!("snps: update team config ({} file{} changed)", paths.len(), if paths.len() == 1 { "" } else { "s" });
+    git.commit_paths(&paths, &message)?;
+
+    if push {
+        git_push(&repo.path)?;
+    }
+
+    Ok(PushOutcome::Pushed(ChangedFile { path: destination, changed_keys: keys }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::partial::DefaultsSection;
+
+    #[test]
+    fn diff_keys_reports_only_changed_fields() {
+        let before = PartialConfig { defaults: Some(DefaultsSection { editor: Some("vi".into()), ..Default::default() }), ..Default::default() };
+        let after = PartialConfig { defaults: Some(DefaultsSection { editor: Some("helix".into()), ..Default::default() }), ..Default::default() };
+        assert_eq!(diff_keys(&before, &after), vec!["defaults.editor"]);
+    }
+
+    #[test]
+    fn identical_configs_report_no_changes() {
+        let config = PartialConfig { repositories_root: Some(".".into()), ..Default::default() };
+        assert!(diff_keys(&config, &config).is_empty());
+    }
+}
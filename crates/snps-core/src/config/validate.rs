@@ -0,0 +1,164 @@
+//! `snps config validate`: catch typo'd keys and unusable paths before
+//! they fail much later inside some unrelated command.
+
+use super::partial::PartialConfig;
+use crate::workspace::Workspace;
+use std::path::{Path, PathBuf};
+
+const KNOWN_TOP_LEVEL_KEYS: &[&str] =
+    &["defaults", "search", "llm", "redaction", "thoughts", "knowledge", "repositories_root", "active_team", "require_share_review"];
+const KNOWN_LLM_PROVIDERS: &[&str] = &["anthropic", "openai"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub file: PathBuf,
+    pub message: String,
+}
+
+/// Validate every config layer file that exists, plus the resolved merged
+/// config's referenced paths. Returns one issue per problem found; an
+/// empty vec means the config is clean.
+pub fn validate_config(workspace: &Workspace) -> crate::error::CoreResult<Vec<ValidationIssue>> {
+    let mut issues = Vec::new();
+
+    let team_config = workspace.teams_dir().join(crate::team::active_team_id(workspace)).join("config.yaml");
+    for (_, path) in [
+        (super::ConfigSource::Team, team_config),
+        (super::ConfigSource::Project, workspace.config_path()),
+        (super::ConfigSource::Local, super::local_config_path(workspace)),
+    ] {
+        issues.extend(validate_file(&path));
+    }
+
+    let merged = super::load_merged_config(workspace)?;
+    let c = &merged.config;
+
+    if !KNOWN_LLM_PROVIDERS.contains(&c.llm_default_provider.as_str()) {
+        issues.push(ValidationIssue {
+            file: workspace.config_path(),
+            message: format!(
+                "llm.default_provider '{}' is not one of: {}",
+                c.llm_default_provider,
+                KNOWN_LLM_PROVIDERS.join(", ")
+            ),
+        });
+    }
+
+    let repositories_root = workspace.root.join(&c.repositories_root);
+    if !repositories_root.exists() {
+        issues.push(ValidationIssue {
+            file: workspace.config_path(),
+            message: format!("repositories_root '{}' does not exist", repositories_root.display()),
+        });
+    }
+
+    let index_db = workspace.root.join(&c.search_index_db);
+    if let Some(parent) = index_db.parent() {
+        if !path_is_writable_or_creatable(parent) {
+            issues.push(ValidationIssue {
+                file: workspace.config_path(),
+                message: format!("search.index_db's parent directory '{}' is not writable", parent.display()),
+            });
+        }
+    }
+
+    Ok(issues)
+}
+
+fn validate_file(path: &Path) -> Vec<ValidationIssue> {
+    if !path.exists() {
+        return Vec::new();
+    }
+    let Ok(contents) = std::fs::read_to_string(path) else { return Vec::new() };
+
+    let mut issues = Vec::new();
+
+    match serde_yaml::from_str::<serde_yaml::Value>(&contents) {
+        Ok(serde_yaml::Value::Mapping(mapping)) => {
+            for key in mapping.keys() {
+                let Some(key) = key.as_str() else { continue };
+                if !KNOWN_TOP_LEVEL_KEYS.contains(&key) {
+                    issues.push(ValidationIssue {
+                        file: path.to_path_buf(),
+                        message: format!("unknown key '{key}'{}", did_you_mean(key)),
+                    });
+                }
+            }
+        }
+        Ok(_) => issues.push(ValidationIssue { file: path.to_path_buf(), message: "expected a mapping at the top level".to_string() }),
+        Err(e) => {
+            let location = e.location().map(|l| format!(" (line {}, column {})", l.line(), l.column())).unwrap_or_default();
+            issues.push(ValidationIssue { file: path.to_path_buf(), message: format!("{e}{location}") });
+        }
+    }
+
+    // Also fail on unknown fields inside `PartialConfig`'s own sections,
+    // which `serde` would otherwise silently ignore.
+    if serde_yaml::from_str::<PartialConfig>(&contents).is_err() {
+        issues.push(ValidationIssue { file: path.to_path_buf(), message: "one or more sections have a shape serde couldn't parse".to_string() });
+    }
+
+    issues
+}
+
+fn did_you_mean(typo: &str) -> String {
+    KNOWN_TOP_LEVEL_KEYS
+        .iter()
+        .min_by_key(|candidate| edit_distance(typo, candidate))
+        .filter(|candidate| edit_distance(typo, candidate) <= 2)
+        .map(|candidate| format!(" (did you mean '{candidate}'?)"))
+        .unwrap_or_default()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut prev = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cur = row[j];
+            row[j] = if a[i - 1] == b[j - 1] {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(row[j - 1])
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+fn path_is_writable_or_creatable(path: &Path) -> bool {
+    if path.exists() {
+        return path.metadata().map(|m| !m.permissions().readonly()).unwrap_or(false);
+    }
+    match path.parent() {
+        Some(parent) => path_is_writable_or_creatable(parent),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_unknown_top_level_key() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.yaml");
+        std::fs::write(&path, "serach:\n  index_db: x\n").unwrap();
+        let issues = validate_file(&path);
+        assert!(issues.iter().any(|i| i.message.contains("unknown key 'serach'")));
+        assert!(issues.iter().any(|i| i.message.contains("did you mean 'search'")));
+    }
+
+    #[test]
+    fn valid_file_produces_no_issues() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.yaml");
+        std::fs::write(&path, "defaults:\n  editor: vim\n").unwrap();
+        assert!(validate_file(&path).is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+//! Near-duplicate title detection, run before creating a new matter or
+//! thoughts document so "API Design" and "Api design doc" don't end up as
+//! two unrelated files nobody notices are the same idea. This is a plain
+//! function rather than something baked into `matter_create`/`thoughts_new`
+//! themselves: those stay pure "write the file" calls with no interactive
+//! I/O, and callers decide what to do with the candidates — the CLI prompts,
+//! the daemon's `POST /matter` just returns them alongside the created item.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// An existing document whose title is close enough to a proposed new
+/// title to be worth flagging.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicateCandidate {
+    pub title: String,
+    pub path: PathBuf,
+    /// Similarity in `[0.0, 1.0]`, `1.0` being an exact match after
+    /// normalization.
+    pub similarity: f64,
+}
+
+/// The similarity above which two titles are flagged as likely duplicates.
+/// Chosen loosely: short titles differing by one or two words (`"API
+/// Design"` vs `"Api design doc"`) land comfortably above it, unrelated
+/// titles land well below.
+pub const DEFAULT_THRESHOLD: f64 = 0.82;
+
+fn normalize(title: &str) -> String {
+    title.to_lowercase().split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut prev = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cur = row[j];
+            row[j] = if a[i - 1] == b[j - 1] { prev } else { 1 + prev.min(row[j]).min(row[j - 1]) };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+/// Similarity of two titles after normalizing case and whitespace: `1.0 -
+/// edit_distance / longest_length`, so identical normalized titles score
+/// `1.0` and completely different ones score near `0.0`.
+fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalize(a), normalize(b));
+    let max_len = a.chars().count().max(b.chars().count());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - (levenshtein(&a, &b) as f64 / max_len as f64)
+}
+
+/// Find existing `(title, path)` pairs whose title is at least `threshold`
+/// similar to `new_title`, most similar first.
+pub fn find_similar_titles<'a>(
+    new_title: &str,
+    existing: impl Iterator<Item = (&'a str, &'a Path)>,
+    threshold: f64,
+) -> Vec<DuplicateCandidate> {
+    let mut matches: Vec<DuplicateCandidate> = existing
+        .filter_map(|(title, path)| {
+            let score = similarity(new_title, title);
+            (score >= threshold).then(|| DuplicateCandidate { title: title.to_string(), path: path.to_path_buf(), similarity: score })
+        })
+        .collect();
+    matches.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap_or(std::cmp::Ordering::Equal));
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_near_duplicate_titles() {
+        let existing = vec![("API Design".to_string(), PathBuf::from("matter/specs/api-design.md"))];
+        let matches = find_similar_titles("Api design doc", existing.iter().map(|(t, p)| (t.as_str(), p.as_path())), DEFAULT_THRESHOLD);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].path, PathBuf::from("matter/specs/api-design.md"));
+    }
+
+    #[test]
+    fn ignores_unrelated_titles() {
+        let existing = vec![("Quarterly Roadmap".to_string(), PathBuf::from("matter/plans/roadmap.md"))];
+        let matches = find_similar_titles("API Design", existing.iter().map(|(t, p)| (t.as_str(), p.as_path())), DEFAULT_THRESHOLD);
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn sorts_by_similarity_descending() {
+        let existing = vec![
+            ("API Design".to_string(), PathBuf::from("a.md")),
+            ("API Design Doc".to_string(), PathBuf::from("b.md")),
+        ];
+        let matches = find_similar_titles("API Design", existing.iter().map(|(t, p)| (t.as_str(), p.as_path())), 0.5);
+        assert_eq!(matches[0].path, PathBuf::from("a.md"));
+    }
+}
@@ -0,0 +1,214 @@
+//! Environment diagnostics behind `snps doctor`, split into individually
+//! testable pass/warn/fail checks so the desktop app's first-run wizard
+//! can run the exact same logic instead of re-implementing it.
+//!
+//! Checks that need to touch the real machine (spawning `git --version`,
+//! writing a probe file, shelling out to check a PID) are kept as thin
+//! wrappers around a pure decision function, so the decision itself is
+//! unit-testable without depending on what's actually installed.
+
+use crate::config::ValidationIssue;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    pub fix: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &str, message: impl Into<String>) -> Self {
+        Self { name: name.to_string(), status: CheckStatus::Pass, message: message.into(), fix: None }
+    }
+
+    fn warn(name: &str, message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { name: name.to_string(), status: CheckStatus::Warn, message: message.into(), fix: Some(fix.into()) }
+    }
+
+    fn fail(name: &str, message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { name: name.to_string(), status: CheckStatus::Fail, message: message.into(), fix: Some(fix.into()) }
+    }
+}
+
+/// Whether `dir` can actually be written to: create it if missing, then
+/// round-trip a probe file. Touches the real filesystem.
+pub fn check_directory_writable(name: &str, dir: &Path) -> DoctorCheck {
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        return DoctorCheck::fail(name, format!("cannot create {}: {e}", dir.display()), format!("check ownership and permissions of {}", dir.display()));
+    }
+    let probe = dir.join(".snps-doctor-probe");
+    let result = std::fs::write(&probe, b"ok");
+    let _ = std::fs::remove_file(&probe);
+    match result {
+        Ok(()) => DoctorCheck::pass(name, format!("{} is writable", dir.display())),
+        Err(e) => DoctorCheck::fail(name, format!("cannot write to {}: {e}", dir.display()), format!("check ownership and permissions of {}", dir.display())),
+    }
+}
+
+/// Whether `~/.pmsynapse` has the shape `load_merged_config` expects.
+/// Pure: the caller stats the path so this stays testable without a
+/// real home directory.
+pub fn check_pmsynapse_layout(exists: bool, is_dir: bool, path: &Path) -> DoctorCheck {
+    if !exists {
+        return DoctorCheck::warn("~/.pmsynapse layout", "not created yet", "run any snps command once; it's created on first use");
+    }
+    if !is_dir {
+        return DoctorCheck::fail(
+            "~/.pmsynapse layout",
+            format!("{} exists but is not a directory", path.display()),
+            format!("remove {} and re-run any snps command", path.display()),
+        );
+    }
+    DoctorCheck::pass("~/.pmsynapse layout", format!("{} is a directory", path.display()))
+}
+
+/// Whether `name` resolves on PATH, by asking it for its version. Not
+/// unit-tested directly since the answer depends on the real machine;
+/// [`check_optional_tool`] carries the testable decision.
+pub fn tool_on_path(name: &str) -> bool {
+    std::process::Command::new(name)
+        .arg("--version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Report on an optional external tool, given whether the wrapper found
+/// it on PATH. Several commands shell out to these but degrade instead
+/// of failing outright, so a missing tool is a warning, not a failure.
+pub fn check_optional_tool(name: &str, found: bool) -> DoctorCheck {
+    if found {
+        DoctorCheck::pass(name, format!("{name} found on PATH"))
+    } else {
+        DoctorCheck::warn(name, format!("{name} not found on PATH"), format!("install {name}; commands that shell out to it will degrade without it"))
+    }
+}
+
+/// Whether the current platform can create a symlink in `dir`. Windows
+/// without Developer Mode or elevated privileges often can't.
+pub fn check_symlink_capability(dir: &Path) -> DoctorCheck {
+    let target = dir.join(".snps-doctor-symlink-target");
+    let link = dir.join(".snps-doctor-symlink-probe");
+    let _ = std::fs::write(&target, b"probe");
+    let result = platform_symlink(&target, &link);
+    let _ = std::fs::remove_file(&link);
+    let _ = std::fs::remove_file(&target);
+
+    match result {
+        Ok(()) => DoctorCheck::pass("symlink capability", "this platform can create symlinks"),
+        Err(e) => DoctorCheck::warn(
+            "symlink capability",
+            format!("cannot create symlinks: {e}"),
+            "on Windows, enable Developer Mode or run as an administrator",
+        ),
+    }
+}
+
+#[cfg(unix)]
+fn platform_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn platform_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(target, link)
+}
+
+/// Whether a process with `pid` is currently running. Best-effort: on
+/// non-Unix platforms this always reports alive rather than guessing
+/// wrong, since a false "stale" warning is more disruptive than a
+/// missed one.
+#[cfg(unix)]
+pub fn process_is_running(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(true)
+}
+
+#[cfg(not(unix))]
+pub fn process_is_running(_pid: u32) -> bool {
+    true
+}
+
+/// Whether `daemon.pid` (if any) still points at a live process. `None`
+/// means no PID file, which is fine when no daemon is expected to be
+/// running; a stale PID pointing at a dead process is the failure case
+/// this catches.
+pub fn check_daemon_pid_consistency(recorded_pid: Option<u32>, process_is_alive: bool) -> DoctorCheck {
+    match recorded_pid {
+        None => DoctorCheck::pass("daemon PID file", "no daemon.pid file (none expected to be running)"),
+        Some(pid) if process_is_alive => DoctorCheck::pass("daemon PID file", format!("pid {pid} is running")),
+        Some(pid) => DoctorCheck::warn(
+            "daemon PID file",
+            format!("daemon.pid records pid {pid}, but no such process is running"),
+            "remove the stale daemon.pid file, or run `snps daemon start` again",
+        ),
+    }
+}
+
+/// Whether the graph database opened cleanly, given the caller's attempt
+/// to open it (stringified, since `CoreError` isn't `Clone`).
+pub fn check_graph_db_openable(open_result: Result<(), String>) -> DoctorCheck {
+    match open_result {
+        Ok(()) => DoctorCheck::pass("graph database", "opens cleanly"),
+        Err(e) => DoctorCheck::fail("graph database", e, "back up and remove the database file, then restore from a `snps graph backup` snapshot"),
+    }
+}
+
+/// Fold `snps config validate`'s issues (or the error that prevented
+/// validation from running at all) into doctor checks.
+pub fn check_config_validation(result: Result<&[ValidationIssue], String>) -> Vec<DoctorCheck> {
+    match result {
+        Ok(issues) if issues.is_empty() => vec![DoctorCheck::pass("config validation", "no issues found")],
+        Ok(issues) => issues
+            .iter()
+            .map(|issue| DoctorCheck::fail("config validation", format!("{}: {}", issue.file.display(), issue.message), "run `snps config validate` for details"))
+            .collect(),
+        Err(e) => vec![DoctorCheck::fail("config validation", e, "fix the underlying error, then re-run `snps config validate`")],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn pmsynapse_layout_warns_when_missing_and_fails_when_not_a_directory() {
+        let path = PathBuf::from("/tmp/does-not-matter/.pmsynapse");
+        assert_eq!(check_pmsynapse_layout(false, false, &path).status, CheckStatus::Warn);
+        assert_eq!(check_pmsynapse_layout(true, false, &path).status, CheckStatus::Fail);
+        assert_eq!(check_pmsynapse_layout(true, true, &path).status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn daemon_pid_consistency_only_warns_on_a_stale_pid() {
+        assert_eq!(check_daemon_pid_consistency(None, false).status, CheckStatus::Pass);
+        assert_eq!(check_daemon_pid_consistency(Some(42), true).status, CheckStatus::Pass);
+        assert_eq!(check_daemon_pid_consistency(Some(42), false).status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn config_validation_reports_pass_only_when_issue_free() {
+        assert_eq!(check_config_validation(Ok(&[])).len(), 1);
+        assert_eq!(check_config_validation(Ok(&[])).first().unwrap().status, CheckStatus::Pass);
+
+        let issues = [ValidationIssue { file: PathBuf::from("config.yaml"), message: "bad".to_string() }];
+        let checks = check_config_validation(Ok(&issues));
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].status, CheckStatus::Fail);
+
+        assert_eq!(check_config_validation(Err("boom".to_string()))[0].status, CheckStatus::Fail);
+    }
+}
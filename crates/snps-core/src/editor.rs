@@ -0,0 +1,137 @@
+//! Launches a configured editor on a file and reports whether it actually
+//! changed anything.
+//!
+//! `std::process::Command::status()` blocks until the child process exits,
+//! which is enough for terminal editors (`vim`, `nano`, `emacs -nw`) but
+//! not for GUI editors that fork into an already-running instance and
+//! return immediately — `code somefile.md` exits before the file is even
+//! open unless told `--wait`. Callers that need to know the edit actually
+//! happened (`matter create --edit`, and anything future that reindexes
+//! afterward) should launch through here instead of shelling out directly.
+
+use crate::error::{CoreError, CoreResult};
+use std::path::Path;
+
+/// `GlobalConfig::default()`'s `defaults.editor` value — used to tell "the
+/// user configured vi" apart from "nothing was configured, so we're
+/// looking at the hardcoded default" when deciding whether `$VISUAL`/
+/// `$EDITOR` should get a say.
+const UNCONFIGURED_SENTINEL: &str = "vi";
+
+/// Platform default used when nothing else resolves: `defaults.editor`
+/// wasn't set to anything but the hardcoded default, and neither `$VISUAL`
+/// nor `$EDITOR` is set either.
+const PLATFORM_DEFAULT: &str = "vi";
+
+/// Resolve which editor command to launch: `configured_editor` (a merged
+/// `defaults.editor`) wins outright if it was actually set to something
+/// other than the crate's own hardcoded default; otherwise `$VISUAL`, then
+/// `$EDITOR`, then the platform default.
+pub fn resolve_editor(configured_editor: &str) -> String {
+    if !configured_editor.is_empty() && configured_editor != UNCONFIGURED_SENTINEL {
+        return configured_editor.to_string();
+    }
+    std::env::var("VISUAL")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .or_else(|| std::env::var("EDITOR").ok().filter(|v| !v.is_empty()))
+        .unwrap_or_else(|| PLATFORM_DEFAULT.to_string())
+}
+
+/// Editors known to fork into an already-running instance and exit
+/// immediately unless told to block, keyed by their executable's file
+/// stem so a full path in `defaults.editor` still matches. Terminal
+/// editors (`vim`, `nano`, `emacs -nw`, `hx`) need no entry here — the
+/// process `Command::status()` waits on *is* the editor.
+fn wait_flag_for(editor_command: &str) -> Option<&'static str> {
+    let name = Path::new(editor_command).file_stem().and_then(|s| s.to_str()).unwrap_or(editor_command);
+    match name {
+        "code" | "code-insiders" | "codium" | "cursor" => Some("--wait"),
+        "subl" | "sublime_text" => Some("--wait"),
+        "atom" => Some("--wait"),
+        _ => None,
+    }
+}
+
+/// What launching an editor on a file accomplished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditOutcome {
+    /// Whether the file's contents differ from before the editor ran.
+    pub changed: bool,
+}
+
+/// Launch `configured_editor` (see [`resolve_editor`]) on `path`, blocking
+/// until editing is done, and report whether the file's contents changed.
+/// Fails if the file doesn't exist yet, the editor can't be spawned, or it
+/// exits non-zero.
+pub fn launch_editor(configured_editor: &str, path: &Path) -> CoreResult<EditOutcome> {
+    let editor = resolve_editor(configured_editor);
+    let before = hash_file(path)?;
+
+    let mut command = std::process::Command::new(&editor);
+    if let Some(flag) = wait_flag_for(&editor) {
+        command.arg(flag);
+    }
+    command.arg(path);
+
+    let status = command
+        .status()
+        .map_err(|e| CoreError::InvalidInput(format!("failed to launch editor '{editor}': {e}")))?;
+    if !status.success() {
+        return Err(CoreError::InvalidInput(format!("editor '{editor}' exited with {status}")));
+    }
+
+    let after = hash_file(path)?;
+    Ok(EditOutcome { changed: before != after })
+}
+
+fn hash_file(path: &Path) -> CoreResult<u64> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let contents = std::fs::read(path)?;
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_editor_prefers_a_real_configured_value() {
+        assert_eq!(resolve_editor("nano"), "nano");
+    }
+
+    #[test]
+    fn wait_flag_added_for_known_gui_editors_by_file_stem() {
+        assert_eq!(wait_flag_for("code"), Some("--wait"));
+        assert_eq!(wait_flag_for("/usr/local/bin/subl"), Some("--wait"));
+        assert_eq!(wait_flag_for("vim"), None);
+    }
+
+    #[test]
+    fn launch_editor_reports_whether_the_file_changed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("doc.md");
+        std::fs::write(&path, "before\n").unwrap();
+
+        // A "editor" that doesn't touch the file — no shell interpretation
+        // needed since Command::arg passes each argument through directly.
+        let outcome = launch_editor("true", &path).unwrap();
+        assert!(!outcome.changed);
+
+        // A stand-in editor that actually rewrites the file, simulating a
+        // real edit without depending on an interactive terminal editor
+        // being installed in the test environment.
+        let script = tmp.path().join("fake-editor.sh");
+        std::fs::write(&script, "#!/bin/sh\necho after > \"$1\"\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        let outcome = launch_editor(script.to_str().unwrap(), &path).unwrap();
+        assert!(outcome.changed);
+    }
+}
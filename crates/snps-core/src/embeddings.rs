@@ -0,0 +1,178 @@
+//! Text embeddings, for the graph's similarity search
+//! ([`crate::graph::embed`]). A separate trait from [`crate::llm::LlmProvider`]
+//! rather than a method on it: Anthropic has no embeddings endpoint, and
+//! the offline fallback here isn't a completion backend at all.
+
+use crate::error::{CoreError, CoreResult};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+/// Every embedder in this module returns vectors of this length, so they
+/// can be swapped without touching the graph's storage schema. OpenAI's
+/// `text-embedding-3-*` models accept a `dimensions` parameter to shrink
+/// their native output to match; [`HashEmbedder`] just generates this
+/// many floats directly.
+pub const EMBEDDING_DIM: usize = 1536;
+
+/// A callable text-embedding backend.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Embed `text`, returning a vector of exactly [`EMBEDDING_DIM`] floats.
+    async fn embed(&self, text: &str) -> CoreResult<Vec<f32>>;
+}
+
+/// Deterministic, offline, non-semantic embedder: hashes `text` into
+/// [`EMBEDDING_DIM`] floats with no network call. Exists so tests (and
+/// workspaces without an LLM API key configured) can exercise the
+/// embedding-storage and similarity-search plumbing without depending on
+/// a real provider; the vectors it produces carry no actual meaning
+/// beyond "identical text hashes identically".
+pub struct HashEmbedder;
+
+#[async_trait]
+impl Embedder for HashEmbedder {
+    fn name(&self) -> &'static str {
+        "hash"
+    }
+
+    async fn embed(&self, text: &str) -> CoreResult<Vec<f32>> {
+        Ok(hash_embedding(text))
+    }
+}
+
+fn hash_embedding(text: &str) -> Vec<f32> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    (0..EMBEDDING_DIM)
+        .map(|i| {
+            let mut hasher = DefaultHasher::new();
+            text.hash(&mut hasher);
+            i.hash(&mut hasher);
+            // Map the hash into [-1.0, 1.0], the range real embeddings
+            // are normalized to.
+            (hasher.finish() % 2_000_001) as f32 / 1_000_000.0 - 1.0
+        })
+        .collect()
+}
+
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1/embeddings";
+
+/// OpenAI's `/v1/embeddings` endpoint. The only real (non-offline)
+/// embedder implemented so far — Anthropic has no equivalent API.
+pub struct OpenAiEmbedder {
+    client: reqwest::Client,
+    api_key: String,
+    base_url: String,
+    model: String,
+}
+
+impl OpenAiEmbedder {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key: api_key.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            model: "text-embedding-3-small".to_string(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+}
+
+#[async_trait]
+impl Embedder for OpenAiEmbedder {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    async fn embed(&self, text: &str) -> CoreResult<Vec<f32>> {
+        let body = serde_json::json!({
+            "model": self.model,
+            "input": text,
+            "dimensions": EMBEDDING_DIM,
+        });
+
+        let response = crate::llm::http::send_with_retry(|| {
+            self.client.post(&self.base_url).bearer_auth(&self.api_key).json(&body)
+        })
+        .await?;
+
+        let mut parsed: EmbeddingsResponse = response.json().await.map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let embedding = parsed.data.pop().map(|d| d.embedding).unwrap_or_default();
+        if embedding.len() != EMBEDDING_DIM {
+            return Err(CoreError::InvalidInput(format!(
+                "openai returned a {}-dimensional embedding, expected {EMBEDDING_DIM}",
+                embedding.len()
+            )));
+        }
+        Ok(embedding)
+    }
+}
+
+/// Build the embedder named by `name` (typically
+/// `GlobalConfig::llm_default_provider`, since that's the provider a
+/// workspace already has an API key for). `"hash"` selects the offline
+/// fallback and ignores `api_key`.
+pub fn embedder_for(name: &str, api_key: Option<String>) -> CoreResult<Box<dyn Embedder>> {
+    match name {
+        "hash" => Ok(Box::new(HashEmbedder)),
+        "openai" => {
+            let api_key = api_key.ok_or_else(|| CoreError::InvalidInput("openai embeddings require an api key".to_string()))?;
+            Ok(Box::new(OpenAiEmbedder::new(api_key)))
+        }
+        other => Err(CoreError::InvalidInput(format!(
+            "'{other}' has no embeddings backend — use 'openai' or the offline 'hash' fallback"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn hash_embedder_is_deterministic_and_full_dimension() {
+        let a = HashEmbedder.embed("hello world").await.unwrap();
+        let b = HashEmbedder.embed("hello world").await.unwrap();
+        assert_eq!(a.len(), EMBEDDING_DIM);
+        assert_eq!(a, b);
+    }
+
+    #[tokio::test]
+    async fn hash_embedder_distinguishes_different_text() {
+        let a = HashEmbedder.embed("hello").await.unwrap();
+        let b = HashEmbedder.embed("goodbye").await.unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn unknown_embedder_name_is_an_error() {
+        assert!(embedder_for("anthropic", None).is_err());
+    }
+
+    #[test]
+    fn openai_embedder_without_a_key_is_an_error() {
+        assert!(embedder_for("openai", None).is_err());
+    }
+}
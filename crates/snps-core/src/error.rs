@@ -0,0 +1,47 @@
+use std::fmt;
+use std::path::PathBuf;
+
+/// Errors surfaced by `snps-core`. CLI, daemon, and desktop front ends map
+/// these onto their own presentation (exit codes, HTTP status, JS errors).
+#[derive(Debug)]
+pub enum CoreError {
+    Io(std::io::Error),
+    NotFound(String),
+    InvalidInput(String),
+    Parse { path: PathBuf, message: String },
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoreError::Io(err) => write!(f, "io error: {err}"),
+            CoreError::NotFound(what) => write!(f, "not found: {what}"),
+            CoreError::InvalidInput(msg) => write!(f, "invalid input: {msg}"),
+            CoreError::Parse { path, message } => {
+                write!(f, "failed to parse {}: {message}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for CoreError {}
+
+impl From<std::io::Error> for CoreError {
+    fn from(err: std::io::Error) -> Self {
+        CoreError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for CoreError {
+    fn from(err: serde_json::Error) -> Self {
+        CoreError::InvalidInput(err.to_string())
+    }
+}
+
+impl From<zip::result::ZipError> for CoreError {
+    fn from(err: zip::result::ZipError) -> Self {
+        CoreError::InvalidInput(err.to_string())
+    }
+}
+
+pub type CoreResult<T> = Result<T, CoreError>;
@@ -0,0 +1,133 @@
+//! Shared directory walking with gitignore-style glob exclusion.
+//!
+//! Matter listing and the search index rebuild each used to walk their
+//! own tree with a private `fn walk_markdown`, none of them aware of
+//! `search.exclude_patterns` or a repository's own excludes. This module
+//! is the one place that knows how to turn a pattern list into a
+//! filtered file walk, so both of those (and, later, a file watcher and
+//! knowledge scanning, neither of which exist yet) apply exclusion the
+//! same way.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Walk `dir` recursively for files with extension `ext` (no leading
+/// dot, e.g. `"md"`), skipping any whose path relative to `root` matches
+/// one of `excludes`. `root` is what patterns like `drafts/*.md` are
+/// written relative to; `dir` is the subtree actually walked, which may
+/// be `root` itself or a directory beneath it.
+pub fn walk_files(root: &Path, dir: &Path, ext: &str, excludes: &[String]) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return out;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if is_excluded(root, &path, excludes) {
+            continue;
+        }
+        if path.is_dir() {
+            out.extend(walk_files(root, &path, ext, excludes));
+        } else if path.extension().and_then(|e| e.to_str()) == Some(ext) {
+            out.push(path);
+        }
+    }
+    out
+}
+
+pub fn walk_markdown(root: &Path, dir: &Path, excludes: &[String]) -> Vec<PathBuf> {
+    walk_files(root, dir, "md", excludes)
+}
+
+/// Like [`walk_files`], but every file regardless of extension — for
+/// walking a shadow repository's working tree, which isn't limited to
+/// markdown the way matter and search indexing are.
+pub fn walk_all(root: &Path, dir: &Path, excludes: &[String]) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return out;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if is_excluded(root, &path, excludes) {
+            continue;
+        }
+        if path.is_dir() {
+            out.extend(walk_all(root, &path, excludes));
+        } else {
+            out.push(path);
+        }
+    }
+    out
+}
+
+fn is_excluded(root: &Path, path: &Path, excludes: &[String]) -> bool {
+    let relative = path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+    excludes.iter().any(|pattern| glob_match(pattern, &relative))
+}
+
+/// A small, dependency-free glob matcher: `*` matches any run of
+/// characters except `/`, `**` also crosses `/`, `?` matches one
+/// character. Enough for gitignore-style patterns like
+/// `**/node_modules/**` or `drafts/*.md`.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    match_bytes(pattern.as_bytes(), text.as_bytes())
+}
+
+fn match_bytes(p: &[u8], t: &[u8]) -> bool {
+    if p.is_empty() {
+        return t.is_empty();
+    }
+    if p.starts_with(b"**") {
+        let rest = p[2..].strip_prefix(b"/").unwrap_or(&p[2..]);
+        return match_bytes(rest, t) || (!t.is_empty() && match_bytes(p, &t[1..]));
+    }
+    match p[0] {
+        b'*' => match_bytes(&p[1..], t) || (!t.is_empty() && t[0] != b'/' && match_bytes(p, &t[1..])),
+        b'?' => !t.is_empty() && match_bytes(&p[1..], &t[1..]),
+        c => !t.is_empty() && t[0] == c && match_bytes(&p[1..], &t[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn glob_matches_double_star_anywhere_in_path() {
+        assert!(glob_match("**/node_modules/**", "vendor/node_modules/pkg/index.js"));
+        assert!(!glob_match("**/node_modules/**", "vendor/other/index.js"));
+    }
+
+    #[test]
+    fn glob_matches_single_star_within_one_segment() {
+        assert!(glob_match("drafts/*.md", "drafts/idea.md"));
+        assert!(!glob_match("drafts/*.md", "drafts/nested/idea.md"));
+    }
+
+    #[test]
+    fn walk_files_skips_excluded_paths() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("drafts")).unwrap();
+        fs::create_dir_all(tmp.path().join("specs")).unwrap();
+        fs::write(tmp.path().join("drafts/idea.md"), "draft").unwrap();
+        fs::write(tmp.path().join("specs/final.md"), "spec").unwrap();
+
+        let found = walk_markdown(tmp.path(), tmp.path(), &["drafts/*.md".to_string()]);
+        let names: Vec<_> = found.iter().filter_map(|p| p.file_name()?.to_str()).collect();
+        assert_eq!(names, vec!["final.md"]);
+    }
+
+    #[test]
+    fn walk_all_finds_every_extension() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join("notes.md"), "note").unwrap();
+        fs::write(tmp.path().join("data.json"), "{}").unwrap();
+
+        let found = walk_all(tmp.path(), tmp.path(), &[]);
+        let mut names: Vec<_> = found.iter().filter_map(|p| p.file_name()?.to_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["data.json", "notes.md"]);
+    }
+}
@@ -0,0 +1,428 @@
+//! Native git operations backed by `git2`, replacing the shelled-out `git`
+//! invocations previously used by repository sync. Kept as a thin wrapper
+//! so callers don't depend on `git2` types directly.
+
+use crate::error::{CoreError, CoreResult};
+use std::path::Path;
+
+pub struct GitRepo {
+    inner: git2::Repository,
+}
+
+/// How the local branch relates to its upstream after a fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Divergence {
+    UpToDate,
+    FastForwardable,
+    Diverged,
+}
+
+/// A repository state that makes an unattended commit/push unsafe —
+/// checked by [`GitRepo::commit_hazard`] before anything writes into a
+/// shadow repository we don't fully control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoHazard {
+    MergeInProgress,
+    RebaseInProgress,
+    DetachedHead,
+    ShallowWithoutRemote,
+}
+
+impl RepoHazard {
+    /// A message naming the problem at `repo_path` and a copy-pasteable
+    /// command to resolve it.
+    pub fn describe(&self, repo_path: &Path) -> String {
+        let at = repo_path.display();
+        match self {
+            RepoHazard::MergeInProgress => format!(
+                "{at} has a merge in progress — finish it (`git -C {at} commit`) or abort it (`git -C {at} merge --abort`) before syncing config here"
+            ),
+            RepoHazard::RebaseInProgress => format!(
+                "{at} has a rebase in progress — finish it (`git -C {at} rebase --continue`) or abort it (`git -C {at} rebase --abort`) before syncing config here"
+            ),
+            RepoHazard::DetachedHead => format!(
+                "{at} is on a detached HEAD — check out the branch it tracks (`git -C {at} checkout <branch>`) before syncing config here"
+            ),
+            RepoHazard::ShallowWithoutRemote => format!(
+                "{at} is a shallow clone with no 'origin' remote — commits made here could never be pushed anywhere; add a remote or run `git -C {at} fetch --unshallow` before syncing config here"
+            ),
+        }
+    }
+}
+
+impl GitRepo {
+    pub fn open(path: &Path) -> CoreResult<Self> {
+        let inner = git2::Repository::open(path)
+            .map_err(|e| CoreError::InvalidInput(format!("not a git repository: {e}")))?;
+        Ok(Self { inner })
+    }
+
+    /// Initialize a new (non-bare) repository at `path`, or open it in
+    /// place if one already exists there — `snps repo init` doesn't need
+    /// to fail just because it's run a second time, or against a
+    /// directory a user already `git init`ed themselves.
+    pub fn open_or_init(path: &Path) -> CoreResult<Self> {
+        match Self::open(path) {
+            Ok(repo) => Ok(repo),
+            Err(_) => {
+                let inner = git2::Repository::init(path).map_err(|e| CoreError::InvalidInput(format!("could not init git repository: {e}")))?;
+                Ok(Self { inner })
+            }
+        }
+    }
+
+    /// Fetch the current branch's configured remote.
+    pub fn fetch(&self) -> CoreResult<()> {
+        let branch = self.current_branch()?;
+        let mut remote = self
+            .inner
+            .find_remote("origin")
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        remote
+            .fetch(&[branch], None, None)
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        Ok(())
+    }
+
+    pub fn current_branch(&self) -> CoreResult<String> {
+        let head = self
+            .inner
+            .head()
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        Ok(head
+            .shorthand()
+            .ok_or_else(|| CoreError::InvalidInput("HEAD is unnamed".to_string()))?
+            .to_string())
+    }
+
+    /// Compare local HEAD against `@{u}` using merge-base analysis.
+    pub fn divergence(&self) -> CoreResult<Divergence> {
+        let head = self.inner.head().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let local_oid = head.target().ok_or_else(|| CoreError::InvalidInput("detached HEAD".into()))?;
+
+        let branch = git2::Branch::wrap(head);
+        let upstream = branch
+            .upstream()
+            .map_err(|e| CoreError::InvalidInput(format!("no upstream configured: {e}")))?;
+        let upstream_oid = upstream
+            .get()
+            .target()
+            .ok_or_else(|| CoreError::InvalidInput("upstream has no target".into()))?;
+
+        if local_oid == upstream_oid {
+            return Ok(Divergence::UpToDate);
+        }
+
+        let (ahead, behind) = self
+            .inner
+            .graph_ahead_behind(local_oid, upstream_oid)
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+
+        if ahead == 0 && behind > 0 {
+            Ok(Divergence::FastForwardable)
+        } else {
+            Ok(Divergence::Diverged)
+        }
+    }
+
+    /// Paths with unmerged (conflicted) index entries.
+    pub fn conflicted_paths(&self) -> CoreResult<Vec<String>> {
+        let index = self.inner.index().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        Ok(index
+            .conflicts()
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?
+            .filter_map(|c| c.ok())
+            .filter_map(|c| c.our.or(c.their))
+            .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+            .collect())
+    }
+
+    /// Fast-forward the current branch to `@{u}`, moving HEAD and updating
+    /// the working tree. Caller must have already confirmed the update is
+    /// a pure fast-forward via [`GitRepo::divergence`].
+    pub fn fast_forward(&self) -> CoreResult<()> {
+        let head = self.inner.head().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let branch = git2::Branch::wrap(head);
+        let upstream = branch
+            .upstream()
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let target = upstream
+            .get()
+            .target()
+            .ok_or_else(|| CoreError::InvalidInput("upstream has no target".into()))?;
+
+        let mut local_ref = self
+            .inner
+            .head()
+            .and_then(|h| self.inner.find_reference(h.name().unwrap_or_default()))
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        local_ref
+            .set_target(target, "snps: fast-forward")
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        self.inner
+            .set_head(local_ref.name().unwrap_or_default())
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        self.inner
+            .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Rebase the current branch onto `@{u}`. Returns the conflicted paths
+    /// (empty on success) and leaves the rebase aborted on conflict so the
+    /// working tree is never left mid-rebase.
+    pub fn rebase_onto_upstream(&self) -> CoreResult<Vec<String>> {
+        let head = self.inner.head().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let branch = git2::Branch::wrap(head);
+        let upstream = branch
+            .upstream()
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let upstream_commit = self
+            .inner
+            .reference_to_annotated_commit(upstream.get())
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+
+        let mut rebase = self
+            .inner
+            .rebase(None, Some(&upstream_commit), None, None)
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+
+        while let Some(op) = rebase.next() {
+            op.map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+            if self.inner.index().map(|i| i.has_conflicts()).unwrap_or(false) {
+                let conflicts = self.conflicted_paths()?;
+                rebase.abort().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+                return Ok(conflicts);
+            }
+            let sig = self
+                .inner
+                .signature()
+                .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+            rebase
+                .commit(None, &sig, None)
+                .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        }
+        rebase.finish(None).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        Ok(Vec::new())
+    }
+
+    /// The first reason it would be unsafe to commit/push into this
+    /// repository unattended, if any: a conflicted merge or rebase left
+    /// mid-flight, a detached HEAD (nothing to fast-forward or push to),
+    /// or a shallow clone with no `origin` to push to. `None` means none
+    /// of these apply — callers still need their own checks (like
+    /// [`GitRepo::dirty_paths_excluding`]) for unrelated local changes.
+    pub fn commit_hazard(&self) -> CoreResult<Option<RepoHazard>> {
+        use git2::RepositoryState;
+        match self.inner.state() {
+            RepositoryState::Merge => return Ok(Some(RepoHazard::MergeInProgress)),
+            RepositoryState::Rebase | RepositoryState::RebaseInteractive | RepositoryState::RebaseMerge => {
+                return Ok(Some(RepoHazard::RebaseInProgress))
+            }
+            _ => {}
+        }
+        if self.inner.head_detached().unwrap_or(false) {
+            return Ok(Some(RepoHazard::DetachedHead));
+        }
+        if self.inner.is_shallow() && self.inner.find_remote("origin").is_err() {
+            return Ok(Some(RepoHazard::ShallowWithoutRemote));
+        }
+        Ok(None)
+    }
+
+    /// `git status --porcelain`-style preview of `paths`, for showing what
+    /// a future [`GitRepo::commit_paths`] call over the same paths would
+    /// record without actually committing. Only the codes that can occur
+    /// here are produced: `??` (untracked), ` M` (modified), ` D` (deleted).
+    pub fn porcelain_status(&self, paths: &[&str]) -> CoreResult<Vec<String>> {
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true);
+        for path in paths {
+            opts.pathspec(path);
+        }
+        let statuses = self.inner.statuses(Some(&mut opts)).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        Ok(statuses
+            .iter()
+            .filter_map(|entry| {
+                let path = entry.path()?.to_string();
+                let status = entry.status();
+                let code = if status.is_wt_new() || status.is_index_new() {
+                    "??"
+                } else if status.is_wt_deleted() || status.is_index_deleted() {
+                    " D"
+                } else {
+                    " M"
+                };
+                Some(format!("{code} {path}"))
+            })
+            .collect())
+    }
+
+    /// Paths with uncommitted changes (staged or unstaged), excluding
+    /// `allowed`. Used before writing into a shadow repo's working tree so
+    /// we never clobber unrelated in-progress work.
+    pub fn dirty_paths_excluding(&self, allowed: &[&str]) -> CoreResult<Vec<String>> {
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true);
+        let statuses = self.inner.statuses(Some(&mut opts)).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        Ok(statuses
+            .iter()
+            .filter_map(|entry| entry.path().map(|p| p.to_string()))
+            .filter(|p| !allowed.contains(&p.as_str()))
+            .collect())
+    }
+
+    /// Stage `paths` and commit them with `message`, using the repo's
+    /// configured signature.
+    pub fn commit_paths(&self, paths: &[&str], message: &str) -> CoreResult<()> {
+        let mut index = self.inner.index().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        for path in paths {
+            index.add_path(Path::new(path)).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        }
+        self.commit_index(&mut index, message)
+    }
+
+    /// Stage everything matching `pathspecs` (glob-style, e.g. `thoughts/journal`
+    /// to match the whole subtree) and commit with `message`. Unlike
+    /// [`Self::commit_paths`], which stages exact known file paths one at
+    /// a time, this covers directories and new/removed files under them —
+    /// what's needed to scope a commit to a subtree without first walking
+    /// it to list every file.
+    pub fn commit_pathspecs(&self, pathspecs: &[&str], message: &str) -> CoreResult<()> {
+        let mut index = self.inner.index().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        index
+            .add_all(pathspecs.iter(), git2::IndexAddOption::DEFAULT, None)
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        index.update_all(pathspecs.iter(), None).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        self.commit_index(&mut index, message)
+    }
+
+    fn commit_index(&self, index: &mut git2::Index, message: &str) -> CoreResult<()> {
+        index.write().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let tree_oid = index.write_tree().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let tree = self.inner.find_tree(tree_oid).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let sig = self.inner.signature().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let parent = self.inner.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        self.inner
+            .commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Where git looks for hook scripts. Doesn't account for a
+    /// `core.hooksPath` override — nothing in this codebase sets one.
+    pub fn hooks_dir(&self) -> std::path::PathBuf {
+        self.inner.path().join("hooks")
+    }
+
+    /// Whether HEAD has at least one commit — `matter history`/`--at` need
+    /// somewhere to distinguish "no git history yet" from a real error.
+    pub fn has_history(&self) -> bool {
+        self.inner.head().is_ok()
+    }
+
+    /// Commits that touched `relative_path` (repo-root-relative, `/`-
+    /// separated), most recent first. With `follow_renames`, a rename that
+    /// introduced the path is detected via git2's similarity-based rename
+    /// detection (the same heuristic `git log --follow` uses) and history
+    /// continues under the file's prior name — same as `git log --follow`,
+    /// without shelling out to it.
+    pub fn file_history(&self, relative_path: &Path, follow_renames: bool) -> CoreResult<Vec<FileHistoryEntry>> {
+        let mut revwalk = self.inner.revwalk().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        revwalk.push_head().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        revwalk.set_sorting(git2::Sort::TIME).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+
+        let mut current = relative_path.to_string_lossy().replace('\\', "/");
+        let mut entries = Vec::new();
+
+        for oid in revwalk {
+            let oid = oid.map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+            let commit = self.inner.find_commit(oid).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+            let tree = commit.tree().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+            let parent_tree = commit.parents().next().and_then(|p| p.tree().ok());
+
+            let mut diff_opts = git2::DiffOptions::new();
+            diff_opts.pathspec(&current);
+            let mut diff = self
+                .inner
+                .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))
+                .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+
+            if follow_renames {
+                let mut find_opts = git2::DiffFindOptions::new();
+                find_opts.renames(true);
+                diff.find_similar(Some(&mut find_opts)).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+            }
+
+            let mut touched = false;
+            let mut renamed_from = None;
+            for delta in diff.deltas() {
+                let new_path = delta.new_file().path().map(|p| p.to_string_lossy().replace('\\', "/"));
+                if new_path.as_deref() != Some(current.as_str()) {
+                    continue;
+                }
+                touched = true;
+                if delta.status() == git2::Delta::Renamed {
+                    renamed_from = delta.old_file().path().map(|p| p.to_string_lossy().replace('\\', "/"));
+                }
+            }
+            if !touched {
+                continue;
+            }
+
+            let stats = diff.stats().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+            let author = commit.author();
+            entries.push(FileHistoryEntry {
+                commit_id: oid.to_string(),
+                author: author.name().unwrap_or("unknown").to_string(),
+                date_unix: commit.time().seconds(),
+                subject: commit.summary().unwrap_or("").to_string(),
+                insertions: stats.insertions(),
+                deletions: stats.deletions(),
+                renamed_from: renamed_from.clone(),
+            });
+
+            if let Some(old_path) = renamed_from {
+                current = old_path;
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// The content of `relative_path` (repo-root-relative, `/`-separated)
+    /// as of `revision` (anything `git2::Repository::revparse_single`
+    /// accepts: a SHA, a branch, `HEAD~3`, etc).
+    pub fn show_file_at(&self, relative_path: &Path, revision: &str) -> CoreResult<String> {
+        let object = self
+            .inner
+            .revparse_single(revision)
+            .map_err(|e| CoreError::InvalidInput(format!("unknown revision '{revision}': {e}")))?;
+        let commit = object.peel_to_commit().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let tree = commit.tree().map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+
+        let entry = tree
+            .get_path(relative_path)
+            .map_err(|e| CoreError::NotFound(format!("'{}' not found at {revision}: {e}", relative_path.display())))?;
+        let blob = entry
+            .to_object(&self.inner)
+            .and_then(|o| o.peel_to_blob())
+            .map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        Ok(String::from_utf8_lossy(blob.content()).into_owned())
+    }
+}
+
+/// One commit that touched a file, as reported by [`GitRepo::file_history`].
+#[derive(Debug, Clone)]
+pub struct FileHistoryEntry {
+    pub commit_id: String,
+    pub author: String,
+    pub date_unix: i64,
+    pub subject: String,
+    pub insertions: usize,
+    pub deletions: usize,
+    /// The file's path before this commit, if this commit is the rename
+    /// that produced its current path.
+    pub renamed_from: Option<String>,
+}
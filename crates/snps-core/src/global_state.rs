@@ -0,0 +1,103 @@
+//! Version marker for `~/.pmsynapse`, the per-user directory the team
+//! registry ([`crate::team`]) and (later) other cross-project state live
+//! in. Distinct from a project's own `.pmsynapse` — see [`crate::workspace`]
+//! and its `schema_version` tracking, which is per project.
+//!
+//! Every binary that writes to `~/.pmsynapse` stamps
+//! `~/.pmsynapse/version` with [`CURRENT_GLOBAL_SCHEMA_VERSION`] once it's
+//! done. If that stamp is ever higher than what this binary understands —
+//! a newer `snps` ran against the same home directory — running an older
+//! binary against it is safe to attempt but worth flagging, so
+//! [`check_global_schema`] reports it as a warning rather than an error
+//! the way [`crate::graph::migrations`] does for the (harder to hand-edit)
+//! graph database.
+
+use crate::error::CoreResult;
+use std::path::{Path, PathBuf};
+
+/// Bump when a change to `~/.pmsynapse`'s layout (e.g. the team registry
+/// format) would confuse an older binary reading it.
+pub const CURRENT_GLOBAL_SCHEMA_VERSION: u32 = 1;
+
+/// A newer binary has already touched `~/.pmsynapse` than this one
+/// understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleGlobalSchema {
+    pub recorded_version: u32,
+    pub supported_version: u32,
+}
+
+fn home_pmsynapse_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".pmsynapse"))
+}
+
+/// Compare `~/.pmsynapse/version` against [`CURRENT_GLOBAL_SCHEMA_VERSION`].
+/// See [`check_global_schema_in`] for the testable version of this that
+/// takes the directory explicitly.
+pub fn check_global_schema() -> CoreResult<Option<StaleGlobalSchema>> {
+    match home_pmsynapse_dir() {
+        Some(dir) => check_global_schema_in(&dir),
+        None => Ok(None),
+    }
+}
+
+/// Compare `pmsynapse_dir/version` against [`CURRENT_GLOBAL_SCHEMA_VERSION`].
+///
+/// Returns `Ok(Some(_))` if the recorded version is newer than this binary
+/// supports. Otherwise brings the stamp up to date (writing it for the
+/// first time if absent) and returns `Ok(None)`. Silently does nothing if
+/// `pmsynapse_dir` doesn't exist yet — nothing has been written there, so
+/// there's nothing to be stale relative to.
+pub fn check_global_schema_in(pmsynapse_dir: &Path) -> CoreResult<Option<StaleGlobalSchema>> {
+    if !pmsynapse_dir.is_dir() {
+        return Ok(None);
+    }
+    let path = pmsynapse_dir.join("version");
+
+    let recorded: Option<u32> = std::fs::read_to_string(&path).ok().and_then(|s| s.trim().parse().ok());
+
+    match recorded {
+        Some(recorded) if recorded > CURRENT_GLOBAL_SCHEMA_VERSION => {
+            Ok(Some(StaleGlobalSchema { recorded_version: recorded, supported_version: CURRENT_GLOBAL_SCHEMA_VERSION }))
+        }
+        Some(recorded) if recorded == CURRENT_GLOBAL_SCHEMA_VERSION => Ok(None),
+        _ => {
+            std::fs::write(&path, CURRENT_GLOBAL_SCHEMA_VERSION.to_string())?;
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn does_nothing_when_pmsynapse_dir_does_not_exist_yet() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert_eq!(check_global_schema_in(&tmp.path().join(".pmsynapse")).unwrap(), None);
+    }
+
+    #[test]
+    fn stamps_the_version_file_on_first_run() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(".pmsynapse");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(check_global_schema_in(&dir).unwrap(), None);
+        let stamped = std::fs::read_to_string(dir.join("version")).unwrap();
+        assert_eq!(stamped, CURRENT_GLOBAL_SCHEMA_VERSION.to_string());
+    }
+
+    #[test]
+    fn flags_a_version_newer_than_this_binary_supports() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(".pmsynapse");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("version"), (CURRENT_GLOBAL_SCHEMA_VERSION + 1).to_string()).unwrap();
+
+        let stale = check_global_schema_in(&dir).unwrap().unwrap();
+        assert_eq!(stale.recorded_version, CURRENT_GLOBAL_SCHEMA_VERSION + 1);
+        assert_eq!(stale.supported_version, CURRENT_GLOBAL_SCHEMA_VERSION);
+    }
+}
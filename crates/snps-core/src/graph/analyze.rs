@@ -0,0 +1,323 @@
+//! `snps analyze`: pull candidate facts out of the project source —
+//! TODO/FIXME comments, public API surface, README claims, and (with
+//! `--deep`) LLM-inferred assumptions — and turn them into
+//! `Assumption`/`Question` nodes with provenance.
+
+use super::{KnowledgeGraph, Node, NodeType, Provenance, ProvenanceSource};
+use crate::error::CoreResult;
+use crate::llm::{CompletionRequest, LlmProvider};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalyzeDepth {
+    /// Comments, README bullets, and public API surface.
+    Quick,
+    /// Quick, plus chunking files for LLM-inferred assumptions.
+    Deep,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub node_type: NodeType,
+    pub title: String,
+    pub content: String,
+    pub confidence: f32,
+    pub source_path: String,
+    pub source_line: u32,
+}
+
+const COMMENT_MARKERS: &[(&str, NodeType)] = &[("TODO", NodeType::Question), ("FIXME", NodeType::Assumption)];
+const SOURCE_EXTENSIONS: &[&str] = &["rs", "ts", "tsx", "js"];
+const DEEP_CHUNK_SIZE: usize = 4000;
+const DEEP_MODEL: &str = "claude-3-5-sonnet-20241022";
+
+/// Walk `root` (skipping anything `.gitignore` excludes, plus the usual
+/// VCS/build directories) collecting comment-, README-, and public-API-
+/// derived candidates. `--deep` chunking and LLM inference are a separate
+/// pass the caller layers on top via [`scan_deep`]; this function only
+/// ever does the free `--quick` pass.
+pub fn scan_quick(root: &Path) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    for path in walk_source(root) {
+        let Ok(contents) = std::fs::read_to_string(&path) else { continue };
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().into_owned();
+
+        if path.file_name().and_then(|n| n.to_str()).map(|n| n.eq_ignore_ascii_case("readme.md")).unwrap_or(false) {
+            candidates.extend(readme_candidates(&contents, &relative));
+            continue;
+        }
+
+        candidates.extend(api_surface_candidates(&contents, &relative, &path));
+
+        for (line_number, line) in contents.lines().enumerate() {
+            for (marker, node_type) in COMMENT_MARKERS {
+                if let Some(pos) = line.find(marker) {
+                    let text = line[pos..].trim_start_matches(marker).trim_start_matches(':').trim();
+                    if !text.is_empty() {
+                        candidates.push(Candidate {
+                            node_type: *node_type,
+                            title: format!("{marker}: {text}"),
+                            content: text.to_string(),
+                            confidence: 0.6,
+                            source_path: relative.clone(),
+                            source_line: line_number as u32 + 1,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    candidates
+}
+
+fn rust_api_pattern() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^\s*pub\s+(fn|struct|enum|trait|type)\s+(\w+)").expect("valid built-in regex"))
+}
+
+fn ts_api_pattern() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^\s*export\s+(?:default\s+)?(?:async\s+)?(function|class|const|interface|type)\s+(\w+)").expect("valid built-in regex")
+    })
+}
+
+/// Extract exported/`pub` declarations as `Assumption` candidates — a
+/// signature is a fact about the codebase's contract, worth surfacing
+/// even though it wasn't flagged by a TODO/FIXME comment or a README
+/// bullet. Only unrestricted `pub`/`export` items count; `pub(crate)`
+/// isn't part of the public surface.
+fn api_surface_candidates(contents: &str, relative: &str, path: &Path) -> Vec<Candidate> {
+    let is_rust = path.extension().and_then(|e| e.to_str()) == Some("rs");
+    let pattern = if is_rust { rust_api_pattern() } else { ts_api_pattern() };
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(line_number, line)| {
+            let caps = pattern.captures(line)?;
+            let kind = &caps[1];
+            let name = &caps[2];
+            Some(Candidate {
+                node_type: NodeType::Assumption,
+                title: format!("public {kind} `{name}`"),
+                content: line.trim().to_string(),
+                confidence: 0.8,
+                source_path: relative.to_string(),
+                source_line: line_number as u32 + 1,
+            })
+        })
+        .collect()
+}
+
+/// Chunk every source file `scan_quick` would also walk and ask the LLM
+/// for assumptions/open questions it can infer from each chunk — things
+/// that wouldn't show up as a TODO/FIXME comment or a README bullet.
+/// This is the `--deep` half of `snps analyze`, layered on top of the
+/// free `--quick` pass; callers decide whether to run it at all (an LLM
+/// provider and API key are required, unlike `scan_quick`).
+pub async fn scan_deep(root: &Path, provider: &dyn LlmProvider) -> CoreResult<Vec<Candidate>> {
+    let mut candidates = Vec::new();
+    for path in walk_source(root) {
+        let is_readme = path.file_name().and_then(|n| n.to_str()).map(|n| n.eq_ignore_ascii_case("readme.md")).unwrap_or(false);
+        if is_readme {
+            continue;
+        }
+        let Ok(contents) = std::fs::read_to_string(&path) else { continue };
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().into_owned();
+
+        let mut line = 1u32;
+        for chunk in chunk_source(&contents, DEEP_CHUNK_SIZE) {
+            let lines_in_chunk = chunk.lines().count().max(1) as u32;
+            candidates.extend(infer_candidates(provider, &chunk, &relative, line).await?);
+            line += lines_in_chunk;
+        }
+    }
+    Ok(candidates)
+}
+
+async fn infer_candidates(provider: &dyn LlmProvider, chunk: &str, relative: &str, start_line: u32) -> CoreResult<Vec<Candidate>> {
+    let prompt = format!(
+        "Read this source excerpt from {relative}. List any assumptions the code makes or open \
+         questions a reviewer should ask, one per line, each prefixed with \"ASSUMPTION:\" or \
+         \"QUESTION:\". Skip anything already flagged by a TODO/FIXME comment. If there's nothing \
+         worth flagging, reply with just \"NONE\".\n\n{chunk}"
+    );
+    let request = CompletionRequest::new(prompt, DEEP_MODEL);
+    let completion = provider.complete(&request).await?;
+    Ok(completion
+        .text
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (node_type, text) = if let Some(rest) = line.strip_prefix("ASSUMPTION:") {
+                (NodeType::Assumption, rest.trim())
+            } else if let Some(rest) = line.strip_prefix("QUESTION:") {
+                (NodeType::Question, rest.trim())
+            } else {
+                return None;
+            };
+            if text.is_empty() {
+                return None;
+            }
+            Some(Candidate {
+                node_type,
+                title: text.to_string(),
+                content: text.to_string(),
+                confidence: 0.5,
+                source_path: relative.to_string(),
+                source_line: start_line,
+            })
+        })
+        .collect())
+}
+
+/// Splits `content` on blank-line breaks into pieces no larger than
+/// `max_chunk_size` bytes, without splitting a block itself. Mirrors
+/// [`super::ingest::split_by_headings`]'s own size splitter, but there's
+/// no heading structure to preserve here — just source text.
+fn chunk_source(content: &str, max_chunk_size: usize) -> Vec<String> {
+    if max_chunk_size == 0 || content.len() <= max_chunk_size {
+        return vec![content.to_string()];
+    }
+
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    for block in content.split("\n\n") {
+        if !current.is_empty() && current.len() + block.len() + 2 > max_chunk_size {
+            parts.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(block);
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    if parts.is_empty() {
+        parts.push(content.to_string());
+    }
+    parts
+}
+
+fn readme_candidates(contents: &str, relative: &str) -> Vec<Candidate> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("- ") || line.starts_with("* "))
+        .map(|(line_number, line)| Candidate {
+            node_type: NodeType::Assumption,
+            title: line.trim_start_matches(['-', '*']).trim().to_string(),
+            content: line.trim_start_matches(['-', '*']).trim().to_string(),
+            confidence: 0.4,
+            source_path: relative.to_string(),
+            source_line: line_number as u32 + 1,
+        })
+        .collect()
+}
+
+fn walk_source(root: &Path) -> Vec<PathBuf> {
+    const SKIP: &[&str] = &[".git", "target", "node_modules", "dist", ".pmsynapse"];
+    let mut excludes: Vec<String> = SKIP.iter().map(|s| format!("**/{s}/**")).collect();
+    excludes.extend(gitignore_patterns(root));
+
+    crate::fswalk::walk_all(root, root, &excludes)
+        .into_iter()
+        .filter(|path| {
+            let is_readme = path.file_name().and_then(|n| n.to_str()).map(|n| n.eq_ignore_ascii_case("readme.md")).unwrap_or(false);
+            let is_source = path.extension().and_then(|e| e.to_str()).map(|e| SOURCE_EXTENSIONS.contains(&e)).unwrap_or(false);
+            is_readme || is_source
+        })
+        .collect()
+}
+
+/// Turn `root`'s top-level `.gitignore` into [`crate::fswalk`] exclude
+/// patterns. Only a project's root `.gitignore` is read — nested
+/// `.gitignore` files (rare in this codebase's own projects) aren't
+/// merged in, and negated (`!pattern`) rules aren't supported, matching
+/// `fswalk`'s own exclude-only pattern language.
+fn gitignore_patterns(root: &Path) -> Vec<String> {
+    let Ok(contents) = std::fs::read_to_string(root.join(".gitignore")) else { return Vec::new() };
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .flat_map(|line| {
+            let line = line.trim_start_matches('/').trim_end_matches('/');
+            let pattern = if line.contains('/') { line.to_string() } else { format!("**/{line}") };
+            [pattern.clone(), format!("{pattern}/**")]
+        })
+        .collect()
+}
+
+/// Write `candidates` into the graph as nodes, one per candidate, using a
+/// content hash of `source_path:source_line:title` as the stable id.
+pub fn write_candidates(graph: &KnowledgeGraph, candidates: &[Candidate]) -> CoreResult<usize> {
+    for candidate in candidates {
+        let id = format!("analyze:{}:{}", candidate.source_path, candidate.source_line);
+        let node = Node::new(id, candidate.node_type, &candidate.title, &candidate.content)
+            .with_confidence(candidate.confidence)
+            .with_source(&candidate.source_path, Some(candidate.source_line))
+            .with_provenance(Provenance::new(ProvenanceSource::Analyze, "snps analyze", env!("CARGO_PKG_VERSION")));
+        graph.add_node(&node)?;
+    }
+    Ok(candidates.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_todo_and_fixme_comments() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("lib.rs"), "// TODO: handle empty input\nfn f() {}\n// FIXME: this leaks\n").unwrap();
+
+        let candidates = scan_quick(tmp.path());
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].node_type, NodeType::Question);
+        assert_eq!(candidates[1].node_type, NodeType::Assumption);
+    }
+
+    #[test]
+    fn extracts_readme_bullet_points_as_assumptions() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("README.md"), "# Title\n\n- supports linux and macos\n").unwrap();
+
+        let candidates = scan_quick(tmp.path());
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].title, "supports linux and macos");
+    }
+
+    #[test]
+    fn skips_ignored_directories() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("target")).unwrap();
+        std::fs::write(tmp.path().join("target/generated.rs"), "// TODO: should not be seen\n").unwrap();
+
+        assert!(scan_quick(tmp.path()).is_empty());
+    }
+
+    #[test]
+    fn skips_paths_excluded_by_gitignore() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join(".gitignore"), "vendor/\n").unwrap();
+        std::fs::create_dir_all(tmp.path().join("vendor")).unwrap();
+        std::fs::write(tmp.path().join("vendor/lib.rs"), "// TODO: should not be seen\n").unwrap();
+
+        assert!(scan_quick(tmp.path()).is_empty());
+    }
+
+    #[test]
+    fn extracts_public_rust_api_surface_as_assumptions() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("lib.rs"), "pub fn scan(root: &Path) -> Vec<Candidate> {\n    todo!()\n}\n\npub(crate) fn helper() {}\n").unwrap();
+
+        let candidates = scan_quick(tmp.path());
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].node_type, NodeType::Assumption);
+        assert_eq!(candidates[0].title, "public fn `scan`");
+    }
+}
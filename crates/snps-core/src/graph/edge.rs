@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeType {
+    /// A document node describes another node (e.g. mentions it via a wiki-link).
+    Describes,
+    RelatesTo,
+    /// An IDLC item implements a node (e.g. an assumption or a piece of
+    /// work tracked as a node). `from` is a synthetic item id, not a node
+    /// that exists in the graph on its own.
+    Implements,
+}
+
+impl EdgeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdgeType::Describes => "describes",
+            EdgeType::RelatesTo => "relates_to",
+            EdgeType::Implements => "implements",
+        }
+    }
+}
+
+impl fmt::Display for EdgeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for EdgeType {
+    type Err = crate::error::CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "describes" => Ok(EdgeType::Describes),
+            "relates_to" => Ok(EdgeType::RelatesTo),
+            "implements" => Ok(EdgeType::Implements),
+            other => Err(crate::error::CoreError::InvalidInput(format!("unknown edge type '{other}'"))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub edge_type: EdgeType,
+}
+
+impl Edge {
+    pub fn new(from: impl Into<String>, to: impl Into<String>, edge_type: EdgeType) -> Self {
+        Self { from: from.into(), to: to.into(), edge_type }
+    }
+}
@@ -0,0 +1,93 @@
+//! Async glue between [`KnowledgeGraph`] (sync, storage only) and
+//! [`crate::embeddings::Embedder`] (async — the real implementation makes
+//! an HTTP call). `KnowledgeGraph` deliberately has no async methods and
+//! no knowledge of embedders; this module is where the two meet, the same
+//! way `sync`/`analyze`/`ingest` are where filesystem scanning meets the
+//! graph rather than living inside `store.rs`.
+//!
+//! There's no `KnowledgeGraph::search_similar` — searching requires
+//! embedding the query text first, which is an async network call in the
+//! non-offline case, so it lives here instead.
+
+use super::{KnowledgeGraph, Node, NodeType};
+use crate::embeddings::Embedder;
+use crate::error::CoreResult;
+
+/// Embed `node`'s title and content and store the result. Overwrites any
+/// embedding already stored for this id, so re-embedding after an update
+/// is just calling this again.
+pub async fn embed_node(graph: &KnowledgeGraph, embedder: &dyn Embedder, node: &Node) -> CoreResult<()> {
+    let text = format!("{}\n\n{}", node.title, node.content);
+    let vector = embedder.embed(&text).await?;
+    graph.set_embedding(&node.id, &vector, embedder.name())
+}
+
+/// Embed every node that has no stored embedding yet. Returns how many
+/// were embedded. This is the whole implementation of `snps graph embed
+/// --missing` — there's no wiring yet that computes embeddings
+/// automatically on every node write (the daemon's writer task is
+/// deliberately synchronous and un-networked, see `snps-daemon::writer`),
+/// so this backfill is, for now, the way embeddings actually get kept
+/// current.
+pub async fn backfill_missing(graph: &KnowledgeGraph, embedder: &dyn Embedder) -> CoreResult<usize> {
+    let missing = graph.nodes_missing_embedding()?;
+    for node in &missing {
+        embed_node(graph, embedder, node).await?;
+    }
+    Ok(missing.len())
+}
+
+/// Embed `query` and return the `k` nearest nodes, nearest first,
+/// optionally restricted to one `node_type`.
+pub async fn search_similar(
+    graph: &KnowledgeGraph,
+    embedder: &dyn Embedder,
+    query: &str,
+    k: usize,
+    node_type: Option<NodeType>,
+) -> CoreResult<Vec<(Node, f32)>> {
+    let vector = embedder.embed(query).await?;
+    graph.search_similar_by_vector(&vector, k, node_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embeddings::HashEmbedder;
+    use crate::graph::{Node, NodeType};
+
+    #[tokio::test]
+    async fn embeds_missing_nodes_and_backfill_is_then_a_no_op() {
+        let dir = tempfile::tempdir().unwrap();
+        let graph = KnowledgeGraph::init(&dir.path().join("synapse.db")).unwrap();
+        let node = Node::new("n1", NodeType::Insight, "title", "content");
+        graph.add_node(&node).unwrap();
+
+        let embedded = backfill_missing(&graph, &HashEmbedder).await.unwrap();
+        assert_eq!(embedded, 1);
+
+        let embedded_again = backfill_missing(&graph, &HashEmbedder).await.unwrap();
+        assert_eq!(embedded_again, 0);
+    }
+
+    #[tokio::test]
+    async fn search_similar_ranks_the_exact_text_match_first() {
+        // `HashEmbedder` is deterministic but not semantic — it has no
+        // notion of "similar meaning", only "identical text hashes
+        // identically" — so this only exercises the plumbing (embed on
+        // write, embed the query, rank by distance), not real relevance.
+        let dir = tempfile::tempdir().unwrap();
+        let graph = KnowledgeGraph::init(&dir.path().join("synapse.db")).unwrap();
+        let a = Node::new("a", NodeType::Insight, "database migrations", "how schema changes are rolled out");
+        let b = Node::new("b", NodeType::Insight, "unrelated topic", "something about weather forecasting");
+        graph.add_node(&a).unwrap();
+        graph.add_node(&b).unwrap();
+        backfill_missing(&graph, &HashEmbedder).await.unwrap();
+
+        let query = "database migrations\n\nhow schema changes are rolled out";
+        let results = search_similar(&graph, &HashEmbedder, query, 1, None).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.id, "a");
+        assert_eq!(results[0].1, 0.0);
+    }
+}
@@ -0,0 +1,98 @@
+//! The JSON shape used to move a whole graph between machines: bulk
+//! import (`snps graph import`, `POST /api/v1/graph/batch`), backup and
+//! restore (`snps graph backup`/`restore`, `/api/v1/graph/backup`), and
+//! desktop exports all read/write this structure.
+
+use super::{Edge, KnowledgeGraph, Node};
+use crate::error::{CoreError, CoreResult};
+use serde::{Deserialize, Serialize};
+
+/// The schema version this binary writes and can restore without
+/// migration. `KnowledgeGraph` itself has no version tracking yet (the
+/// db just has whatever columns the current code expects); that lands
+/// with schema versioning and migrations as a dedicated backlog item,
+/// at which point `restore` below should run migrations instead of
+/// rejecting anything older than `CURRENT_SCHEMA_VERSION`.
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+fn current_schema_version() -> u32 {
+    CURRENT_SCHEMA_VERSION
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphExport {
+    #[serde(default = "current_schema_version")]
+    pub schema_version: u32,
+    #[serde(default)]
+    pub nodes: Vec<Node>,
+    #[serde(default)]
+    pub edges: Vec<Edge>,
+}
+
+impl Default for GraphExport {
+    fn default() -> Self {
+        Self { schema_version: CURRENT_SCHEMA_VERSION, nodes: Vec::new(), edges: Vec::new() }
+    }
+}
+
+impl GraphExport {
+    /// A consistent point-in-time snapshot of `graph`'s full contents,
+    /// stamped with the schema version this binary writes.
+    pub fn dump(graph: &KnowledgeGraph) -> CoreResult<Self> {
+        Ok(Self { schema_version: CURRENT_SCHEMA_VERSION, nodes: graph.query(None)?, edges: graph.all_edges()? })
+    }
+
+    /// Load this export into `graph`, refusing to overwrite an existing
+    /// database unless `force` is set (in which case it's cleared first).
+    /// Anything newer than `CURRENT_SCHEMA_VERSION` is rejected outright,
+    /// since this binary has no way to know what it means; anything
+    /// older is accepted as-is until real migrations exist.
+    pub fn restore(&self, graph: &KnowledgeGraph, force: bool) -> CoreResult<()> {
+        if self.schema_version > CURRENT_SCHEMA_VERSION {
+            return Err(CoreError::InvalidInput(format!(
+                "backup is schema version {}, this binary only supports up to {CURRENT_SCHEMA_VERSION}; upgrade snps and try again",
+                self.schema_version
+            )));
+        }
+
+        if !graph.is_empty()? {
+            if !force {
+                return Err(CoreError::InvalidInput("database is not empty; pass --force to overwrite it".to_string()));
+            }
+            graph.clear()?;
+        }
+
+        graph.add_batch(&self.nodes, &self.edges)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_a_newer_schema_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let graph = KnowledgeGraph::init(&dir.path().join("synapse.db")).unwrap();
+        let export = GraphExport { schema_version: CURRENT_SCHEMA_VERSION + 1, nodes: Vec::new(), edges: Vec::new() };
+
+        let err = export.restore(&graph, false).unwrap_err().to_string();
+        assert!(err.contains("schema version"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn refuses_non_empty_database_without_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let graph = KnowledgeGraph::init(&dir.path().join("synapse.db")).unwrap();
+        let node = Node::new("n1", crate::graph::NodeType::Insight, "existing", "already here");
+        graph.add_node(&node).unwrap();
+
+        let export = GraphExport::default();
+        let err = export.restore(&graph, false).unwrap_err().to_string();
+        assert!(err.contains("not empty"), "unexpected error: {err}");
+
+        export.restore(&graph, true).unwrap();
+        assert!(graph.is_empty().unwrap());
+    }
+}
@@ -0,0 +1,348 @@
+//! `snps graph ingest`: split markdown documents into heading-sized
+//! chunks and map them into the graph as a `Document` parent node plus
+//! one child `Document` node per chunk, connected by `Describes` edges.
+//!
+//! This is `sync`'s finer-grained sibling: `sync` maps a whole file to a
+//! single node, which is too coarse for retrieval over long documents.
+//! There's no dedicated "chunk" `NodeType` — a chunk is a `Document` like
+//! any other, just scoped to one heading's worth of content, with its id
+//! and `source_path` pointing back at the section it came from.
+//!
+//! As with `sync::plan_doc_sync`, the splitting and diffing logic
+//! (`split_by_headings`, `plan_chunk_ingest`) are pure functions over
+//! already-read content, so they're testable without a live graph
+//! database.
+
+use super::sync::{document_id, frontmatter_field};
+use super::{DocOutcome, Edge, EdgeType, KnowledgeGraph, Node, NodeType, Provenance, ProvenanceSource};
+use crate::error::CoreResult;
+use crate::knowledge::hash_contents;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// One heading-scoped slice of a document, before it's tied to a
+/// document id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// Slugified heading text, unique within the document (`"top"` for
+    /// content before the first heading, or if the document has none).
+    pub anchor: String,
+    pub heading: Option<String>,
+    pub content: String,
+}
+
+/// Split `markdown` into chunks at headings of depth `<= max_depth` (an
+/// `h1` is depth 1). A section longer than `max_chunk_size` bytes is
+/// further split on paragraph breaks; `max_chunk_size` of `0` means no
+/// limit.
+pub fn split_by_headings(markdown: &str, max_depth: u32, max_chunk_size: usize) -> Vec<Chunk> {
+    let mut sections: Vec<(Option<String>, String)> = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut lines: Vec<&str> = Vec::new();
+
+    for line in markdown.lines() {
+        match parse_heading(line) {
+            Some((depth, text)) if depth <= max_depth => {
+                if heading.is_some() || !lines.is_empty() {
+                    sections.push((heading.take(), lines.join("\n")));
+                    lines.clear();
+                }
+                heading = Some(text);
+            }
+            _ => lines.push(line),
+        }
+    }
+    if heading.is_some() || !lines.is_empty() {
+        sections.push((heading, lines.join("\n")));
+    }
+
+    let mut used_anchors = HashSet::new();
+    let mut chunks = Vec::new();
+    for (heading, content) in sections {
+        let content = content.trim();
+        if content.is_empty() && heading.is_none() {
+            continue;
+        }
+        let base_anchor = heading.as_deref().map(slugify_heading).unwrap_or_else(|| "top".to_string());
+        for part in split_by_size(content, max_chunk_size) {
+            let anchor = unique_anchor(&base_anchor, &mut used_anchors);
+            chunks.push(Chunk { anchor, heading: heading.clone(), content: part });
+        }
+    }
+    chunks
+}
+
+/// Parses a line as an ATX heading (`"## Text"`), returning its depth
+/// (number of `#`s) and trimmed text.
+fn parse_heading(line: &str) -> Option<(u32, String)> {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    if hashes == 0 || hashes > 6 {
+        return None;
+    }
+    let rest = &line[hashes..];
+    if !rest.starts_with(' ') && !rest.is_empty() {
+        return None;
+    }
+    Some((hashes as u32, rest.trim().to_string()))
+}
+
+/// Splits `content` on blank-line paragraph breaks into pieces no larger
+/// than `max_chunk_size` bytes, without splitting a paragraph itself.
+fn split_by_size(content: &str, max_chunk_size: usize) -> Vec<String> {
+    if max_chunk_size == 0 || content.len() <= max_chunk_size {
+        return vec![content.to_string()];
+    }
+
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    for paragraph in content.split("\n\n") {
+        if !current.is_empty() && current.len() + paragraph.len() + 2 > max_chunk_size {
+            parts.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(paragraph);
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    if parts.is_empty() {
+        parts.push(content.to_string());
+    }
+    parts
+}
+
+fn unique_anchor(base: &str, used: &mut HashSet<String>) -> String {
+    let mut candidate = base.to_string();
+    let mut n = 2;
+    while used.contains(&candidate) {
+        candidate = format!("{base}-{n}");
+        n += 1;
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+fn slugify_heading(text: &str) -> String {
+    let mut slug = String::new();
+    let mut last_dash = false;
+    for c in text.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+            last_dash = false;
+        } else if !last_dash && !slug.is_empty() {
+            slug.push('-');
+            last_dash = true;
+        }
+    }
+    let slug = slug.trim_matches('-').to_string();
+    if slug.is_empty() {
+        "section".to_string()
+    } else {
+        slug
+    }
+}
+
+/// A chunk tied to the document it came from, ready to diff and write.
+pub struct ChunkSource {
+    /// `"<document_id>#<anchor>"` — also the node id, so a chunk node's
+    /// id doubles as its deep link.
+    pub id: String,
+    pub anchor: String,
+    pub heading: Option<String>,
+    pub content: String,
+}
+
+fn chunk_sources(doc_id: &str, chunks: &[Chunk]) -> Vec<ChunkSource> {
+    chunks
+        .iter()
+        .map(|c| ChunkSource { id: format!("{doc_id}#{}", c.anchor), anchor: c.anchor.clone(), heading: c.heading.clone(), content: c.content.clone() })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkIngestResult {
+    pub id: String,
+    pub outcome: DocOutcome,
+}
+
+/// Decide add/update/unchanged/orphan for a document's chunks against
+/// the content hashes already recorded in the graph, the same way
+/// `sync::plan_doc_sync` does for whole documents.
+pub fn plan_chunk_ingest(sources: &[ChunkSource], existing: &HashMap<String, String>) -> Vec<ChunkIngestResult> {
+    let mut results = Vec::new();
+    let mut seen = HashSet::new();
+
+    for source in sources {
+        seen.insert(source.id.clone());
+        let hash = hash_contents(source.content.as_bytes());
+        let outcome = match existing.get(&source.id) {
+            None => DocOutcome::Added,
+            Some(existing_hash) if existing_hash == &hash => DocOutcome::Unchanged,
+            Some(_) => DocOutcome::Updated,
+        };
+        results.push(ChunkIngestResult { id: source.id.clone(), outcome });
+    }
+
+    for id in existing.keys() {
+        if !seen.contains(id) {
+            results.push(ChunkIngestResult { id: id.clone(), outcome: DocOutcome::Orphaned });
+        }
+    }
+
+    results
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IngestStats {
+    pub documents: usize,
+    pub added: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub orphaned: usize,
+}
+
+fn collect_markdown_files(path: &Path) -> Vec<PathBuf> {
+    if path.is_file() {
+        return vec![path.to_path_buf()];
+    }
+    let mut out = Vec::new();
+    let Ok(entries) = std::fs::read_dir(path) else { return out };
+    for entry in entries.flatten() {
+        let entry_path = entry.path();
+        if entry_path.is_dir() {
+            out.extend(collect_markdown_files(&entry_path));
+        } else if entry_path.extension().and_then(|e| e.to_str()) == Some("md") {
+            out.push(entry_path);
+        }
+    }
+    out
+}
+
+/// Ingest `path` (a markdown file or directory) into `graph`, chunked by
+/// heading. Re-ingesting an unchanged file is a no-op per chunk; only
+/// chunks whose content hash changed are rewritten. With `stats_only`,
+/// counts are computed but nothing is written.
+pub fn ingest_markdown_to_graph(
+    graph: &KnowledgeGraph,
+    workspace_root: &Path,
+    path: &Path,
+    max_depth: u32,
+    max_chunk_size: usize,
+    stats_only: bool,
+) -> CoreResult<IngestStats> {
+    let mut stats = IngestStats::default();
+
+    for file in collect_markdown_files(path) {
+        let content = std::fs::read_to_string(&file)?;
+        let matter_id = frontmatter_field(&content, "matter_id");
+        let doc_id = document_id(&file, workspace_root, matter_id.as_deref());
+        stats.documents += 1;
+
+        let chunks = split_by_headings(&content, max_depth, max_chunk_size);
+        let sources = chunk_sources(&doc_id, &chunks);
+
+        let prefix = format!("{doc_id}#");
+        let existing: HashMap<String, String> = graph
+            .query(Some(NodeType::Document))?
+            .into_iter()
+            .filter(|n| n.id.starts_with(&prefix))
+            .map(|n| (n.id.clone(), hash_contents(n.content.as_bytes())))
+            .collect();
+
+        let results = plan_chunk_ingest(&sources, &existing);
+        for result in &results {
+            match result.outcome {
+                DocOutcome::Added => stats.added += 1,
+                DocOutcome::Updated => stats.updated += 1,
+                DocOutcome::Unchanged => stats.unchanged += 1,
+                DocOutcome::Orphaned => stats.orphaned += 1,
+            }
+        }
+
+        if stats_only {
+            continue;
+        }
+
+        let title = file.file_stem().and_then(|s| s.to_str()).unwrap_or(&doc_id).to_string();
+        let doc_node = Node::new(&doc_id, NodeType::Document, &title, &content)
+            .with_source(file.to_string_lossy(), None)
+            .with_provenance(Provenance::new(ProvenanceSource::Sync, "snps graph ingest", env!("CARGO_PKG_VERSION")));
+        graph.add_node(&doc_node)?;
+
+        for result in &results {
+            match result.outcome {
+                DocOutcome::Added | DocOutcome::Updated => {
+                    let Some(source) = sources.iter().find(|s| s.id == result.id) else { continue };
+                    let chunk_title = source.heading.clone().unwrap_or_else(|| title.clone());
+                    let node = Node::new(&source.id, NodeType::Document, chunk_title, &source.content)
+                        .with_source(format!("{}#{}", file.to_string_lossy(), source.anchor), None)
+                        .with_provenance(Provenance::new(ProvenanceSource::Sync, "snps graph ingest", env!("CARGO_PKG_VERSION")));
+                    graph.add_node(&node)?;
+                    graph.add_edge(&Edge::new(doc_id.clone(), source.id.clone(), EdgeType::Describes))?;
+                }
+                DocOutcome::Orphaned => graph.mark_orphaned(&result.id)?,
+                DocOutcome::Unchanged => {}
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_headings_up_to_max_depth() {
+        let markdown = "# Title\nintro\n## Section One\nfirst\n## Section Two\nsecond\n";
+        let chunks = split_by_headings(markdown, 2, 0);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].anchor, "title");
+        assert_eq!(chunks[1].anchor, "section-one");
+        assert_eq!(chunks[2].anchor, "section-two");
+    }
+
+    #[test]
+    fn deeper_headings_than_max_depth_stay_in_their_parent_chunk() {
+        let markdown = "# Title\n### Deep\nnested content\n";
+        let chunks = split_by_headings(markdown, 1, 0);
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].content.contains("### Deep"));
+    }
+
+    #[test]
+    fn oversized_section_is_split_on_paragraph_breaks() {
+        let markdown = "# Title\nfirst paragraph\n\nsecond paragraph\n\nthird paragraph";
+        let chunks = split_by_headings(markdown, 1, 20);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.anchor.starts_with("title")));
+    }
+
+    #[test]
+    fn duplicate_headings_get_distinct_anchors() {
+        let markdown = "## Notes\na\n## Notes\nb\n";
+        let chunks = split_by_headings(markdown, 2, 0);
+        assert_eq!(chunks[0].anchor, "notes");
+        assert_eq!(chunks[1].anchor, "notes-2");
+    }
+
+    #[test]
+    fn plan_marks_new_changed_and_removed_chunks() {
+        let sources = vec![
+            ChunkSource { id: "doc#a".into(), anchor: "a".into(), heading: None, content: "same".into() },
+            ChunkSource { id: "doc#b".into(), anchor: "b".into(), heading: None, content: "changed".into() },
+        ];
+        let existing = HashMap::from([
+            ("doc#a".to_string(), hash_contents(b"same")),
+            ("doc#b".to_string(), hash_contents(b"old")),
+            ("doc#c".to_string(), hash_contents(b"gone")),
+        ]);
+        let results = plan_chunk_ingest(&sources, &existing);
+        assert!(results.contains(&ChunkIngestResult { id: "doc#a".into(), outcome: DocOutcome::Unchanged }));
+        assert!(results.contains(&ChunkIngestResult { id: "doc#b".into(), outcome: DocOutcome::Updated }));
+        assert!(results.contains(&ChunkIngestResult { id: "doc#c".into(), outcome: DocOutcome::Orphaned }));
+    }
+}
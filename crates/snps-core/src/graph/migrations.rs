@@ -0,0 +1,261 @@
+//! Ordered, idempotent steps that bring an existing graph database's
+//! on-disk shape up to [`CURRENT_DB_VERSION`]. `KnowledgeGraph::init`
+//! runs [`ensure_current`] every time it opens a database, so a
+//! `synapse.db` written by an older `snps` just works: whatever's
+//! missing gets applied, in order, before the caller ever sees the
+//! handle.
+//!
+//! A database opened before this module existed has no `schema_version`
+//! relation at all; that's treated as version 1, the shape `nodes` and
+//! `edges` have always had.
+
+use crate::error::{CoreError, CoreResult};
+use cozo::{DataValue, DbInstance, ScriptMutability};
+use std::collections::BTreeMap;
+
+/// The schema version this binary's `nodes`/`edges` relations match.
+/// Bump this and add a `Migration` below whenever the relations change
+/// shape.
+pub const CURRENT_DB_VERSION: u32 = 4;
+
+const SCHEMA_VERSION_RELATION: &str = r#"
+:create schema_version {
+    id: Int
+    =>
+    version: Int,
+}
+"#;
+
+struct Migration {
+    /// The version this migration moves a database *to*.
+    to: u32,
+    description: &'static str,
+    run: fn(&DbInstance) -> CoreResult<()>,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        to: 2,
+        description: "record schema version explicitly (relations unchanged)",
+        run: |_db| Ok(()),
+    },
+    Migration {
+        to: 3,
+        description: "add provenance_source/actor/tool_version/session_id columns to nodes",
+        run: add_provenance_columns,
+    },
+    Migration {
+        to: 4,
+        description: "add node_embeddings relation with an HNSW index (relations unchanged)",
+        // `KnowledgeGraph::init` already creates `node_embeddings` and its
+        // index unconditionally, the same way it does for `nodes`/`edges`
+        // — like the v1-to-v2 step, this migration only exists to record
+        // the version bump for a database that predates the relation.
+        run: |_db| Ok(()),
+    },
+];
+
+/// Cozo relations have a fixed column set with no `ALTER`-style op, so
+/// widening `nodes` means: create a new relation with the extra nullable
+/// `provenance_*` columns, copy every existing row into it (defaulting
+/// those columns to null, which `row_to_node` already renders as
+/// "unknown" provenance rather than a parse failure), then swap it in for
+/// `nodes`. This is the first migration in this file that actually
+/// changes a relation's shape — the v1-to-v2 one only started recording a
+/// version number.
+fn add_provenance_columns(db: &DbInstance) -> CoreResult<()> {
+    let create_new = r#"
+        :create nodes_v3 {
+            id: String
+            =>
+            node_type: String,
+            title: String,
+            content: String,
+            confidence: Float?,
+            source_path: String?,
+            source_line: Int?,
+            created_at: Int,
+            updated_at: Int,
+            orphaned: Bool default false,
+            provenance_source: String?,
+            provenance_actor: String?,
+            provenance_tool_version: String?,
+            provenance_session_id: String?,
+        }
+    "#;
+    db.run_script(create_new, BTreeMap::new(), ScriptMutability::Mutable)
+        .map_err(|e| CoreError::InvalidInput(format!("failed to create nodes_v3 relation: {e}")))?;
+
+    let copy_rows = r#"
+        ?[id, node_type, title, content, confidence, source_path, source_line, created_at, updated_at, orphaned,
+          provenance_source, provenance_actor, provenance_tool_version, provenance_session_id] :=
+            *nodes{id, node_type, title, content, confidence, source_path, source_line, created_at, updated_at, orphaned},
+            provenance_source = null,
+            provenance_actor = null,
+            provenance_tool_version = null,
+            provenance_session_id = null
+        :put nodes_v3
+    "#;
+    db.run_script(copy_rows, BTreeMap::new(), ScriptMutability::Mutable)
+        .map_err(|e| CoreError::InvalidInput(format!("failed to copy nodes into nodes_v3: {e}")))?;
+
+    db.run_script("::remove nodes", BTreeMap::new(), ScriptMutability::Mutable)
+        .map_err(|e| CoreError::InvalidInput(format!("failed to drop old nodes relation: {e}")))?;
+    db.run_script("::rename nodes_v3 -> nodes", BTreeMap::new(), ScriptMutability::Mutable)
+        .map_err(|e| CoreError::InvalidInput(format!("failed to rename nodes_v3 to nodes: {e}")))?;
+
+    Ok(())
+}
+
+/// Ensure `schema_version` exists, then bring the database up to
+/// [`CURRENT_DB_VERSION`], applying any pending migrations in order.
+/// Returns the description of each migration that actually ran; an
+/// empty vec means the database was already current.
+///
+/// Refuses to open a database whose recorded version is newer than this
+/// binary understands, rather than guessing.
+pub(crate) fn ensure_current(db: &DbInstance) -> CoreResult<Vec<String>> {
+    let _ = db.run_script(SCHEMA_VERSION_RELATION, BTreeMap::new(), ScriptMutability::Mutable);
+
+    let mut version = read_version(db)?.unwrap_or(1);
+    if version > CURRENT_DB_VERSION {
+        return Err(CoreError::InvalidInput(format!(
+            "database schema version {version} is newer than this binary supports (up to {CURRENT_DB_VERSION}); upgrade snps"
+        )));
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS {
+        if migration.to > version {
+            (migration.run)(db)?;
+            write_version(db, migration.to)?;
+            version = migration.to;
+            applied.push(migration.description.to_string());
+        }
+    }
+
+    Ok(applied)
+}
+
+/// The database's recorded schema version, after `ensure_current` has
+/// run at least once.
+pub(crate) fn current_version(db: &DbInstance) -> CoreResult<u32> {
+    Ok(read_version(db)?.unwrap_or(CURRENT_DB_VERSION))
+}
+
+fn read_version(db: &DbInstance) -> CoreResult<Option<u32>> {
+    let script = "?[version] := *schema_version{id: 0, version}";
+    let rows = db
+        .run_script(script, BTreeMap::new(), ScriptMutability::Immutable)
+        .map_err(|e| CoreError::InvalidInput(format!("failed to read schema version: {e}")))?;
+    Ok(match rows.rows.first().and_then(|row| row.first()) {
+        Some(DataValue::Num(n)) => Some(n.get_float() as u32),
+        _ => None,
+    })
+}
+
+fn write_version(db: &DbInstance, version: u32) -> CoreResult<()> {
+    let script = "?[id, version] <- [[0, $version]]\n:put schema_version";
+    let mut params = BTreeMap::new();
+    params.insert("version".to_string(), DataValue::from(version as i64));
+    db.run_script(script, params, ScriptMutability::Mutable)
+        .map_err(|e| CoreError::InvalidInput(format!("failed to write schema version: {e}")))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v1_shaped_db(path: &std::path::Path) -> DbInstance {
+        let db = DbInstance::new("sqlite", path.to_string_lossy().as_ref(), Default::default()).unwrap();
+        let _ = db.run_script(
+            r#"
+            :create nodes {
+                id: String
+                =>
+                node_type: String,
+                title: String,
+                content: String,
+                confidence: Float?,
+                source_path: String?,
+                source_line: Int?,
+                created_at: Int,
+                updated_at: Int,
+                orphaned: Bool default false,
+            }
+            "#,
+            BTreeMap::new(),
+            ScriptMutability::Mutable,
+        );
+        let put_node = r#"
+            ?[id, node_type, title, content, confidence, source_path, source_line, created_at, updated_at, orphaned] <- [[
+                "n1", "insight", "pre-existing", "written before schema versioning existed", null, null, null, 0, 0, false
+            ]]
+            :put nodes
+        "#;
+        db.run_script(put_node, BTreeMap::new(), ScriptMutability::Mutable).unwrap();
+        db
+    }
+
+    #[test]
+    fn migrates_a_v1_shaped_database_and_preserves_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = v1_shaped_db(&dir.path().join("v1.db"));
+
+        assert_eq!(read_version(&db).unwrap(), None);
+
+        let applied = ensure_current(&db).unwrap();
+        assert_eq!(applied.len(), 3);
+        assert_eq!(current_version(&db).unwrap(), CURRENT_DB_VERSION);
+
+        let rows = db
+            .run_script("?[id, title] := *nodes{id, title}", BTreeMap::new(), ScriptMutability::Immutable)
+            .unwrap();
+        assert_eq!(rows.rows.len(), 1);
+
+        // Running it again against an already-current database is a no-op.
+        assert!(ensure_current(&db).unwrap().is_empty());
+    }
+
+    fn v2_shaped_db(path: &std::path::Path) -> DbInstance {
+        let db = v1_shaped_db(path);
+        let _ = db.run_script(SCHEMA_VERSION_RELATION, BTreeMap::new(), ScriptMutability::Mutable);
+        write_version(&db, 2).unwrap();
+        db
+    }
+
+    #[test]
+    fn migrates_a_v2_shaped_database_and_defaults_provenance_to_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = v2_shaped_db(&dir.path().join("v2.db"));
+
+        let applied = ensure_current(&db).unwrap();
+        assert_eq!(applied.len(), 2);
+        assert_eq!(current_version(&db).unwrap(), CURRENT_DB_VERSION);
+
+        let rows = db
+            .run_script(
+                "?[id, title, provenance_source] := *nodes{id, title, provenance_source}",
+                BTreeMap::new(),
+                ScriptMutability::Immutable,
+            )
+            .unwrap();
+        assert_eq!(rows.rows.len(), 1);
+        assert_eq!(rows.rows[0][2], DataValue::Null);
+
+        // Idempotent: re-running against an already-current database changes nothing.
+        assert!(ensure_current(&db).unwrap().is_empty());
+    }
+
+    #[test]
+    fn refuses_a_database_newer_than_this_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = v1_shaped_db(&dir.path().join("future.db"));
+        let _ = db.run_script(SCHEMA_VERSION_RELATION, BTreeMap::new(), ScriptMutability::Mutable);
+        write_version(&db, CURRENT_DB_VERSION + 1).unwrap();
+
+        let err = ensure_current(&db).unwrap_err().to_string();
+        assert!(err.contains("newer than this binary supports"), "unexpected error: {err}");
+    }
+}
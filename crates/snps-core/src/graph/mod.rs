@@ -0,0 +1,25 @@
+//! The project knowledge graph: nodes (documents, assumptions, questions,
+//! insights, ...) and edges between them, persisted in an embedded
+//! CozoDB database at `<workspace>/.pmsynapse/synapse.db`.
+
+pub mod analyze;
+mod edge;
+pub mod embed;
+pub mod export;
+pub mod ingest;
+pub mod migrations;
+mod node;
+mod store;
+pub mod sync;
+pub mod vault;
+
+pub use analyze::{scan_deep, scan_quick, write_candidates, AnalyzeDepth, Candidate};
+pub use edge::{Edge, EdgeType};
+pub use embed::{backfill_missing, embed_node, search_similar};
+pub use export::{GraphExport, CURRENT_SCHEMA_VERSION};
+pub use ingest::{ingest_markdown_to_graph, split_by_headings, Chunk, ChunkIngestResult, IngestStats};
+pub use migrations::CURRENT_DB_VERSION;
+pub use node::{Node, NodeType, Provenance, ProvenanceSource};
+pub use store::{KnowledgeGraph, NodeOrderBy, NodePage};
+pub use sync::{document_id, sync_markdown_to_graph, DocOutcome, DocSyncResult};
+pub use vault::{export_vault, VaultExportStats};
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The kind of thing a graph node represents. New variants get added as
+/// features (analyze, sync, proposals, ...) need them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeType {
+    Document,
+    Assumption,
+    Question,
+    Insight,
+}
+
+impl NodeType {
+    pub const ALL: [NodeType; 4] = [NodeType::Document, NodeType::Assumption, NodeType::Question, NodeType::Insight];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeType::Document => "document",
+            NodeType::Assumption => "assumption",
+            NodeType::Question => "question",
+            NodeType::Insight => "insight",
+        }
+    }
+}
+
+impl fmt::Display for NodeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for NodeType {
+    type Err = crate::error::CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "document" => Ok(NodeType::Document),
+            "assumption" => Ok(NodeType::Assumption),
+            "question" => Ok(NodeType::Question),
+            "insight" => Ok(NodeType::Insight),
+            other => Err(crate::error::CoreError::InvalidInput(format!("unknown node type '{other}'"))),
+        }
+    }
+}
+
+/// Which write path created or last touched a node. This is a different
+/// concept from [`crate::knowledge::ProvenanceManifest`], which tracks
+/// which *shadow repo* a `knowledge/` file was pulled from — that one is
+/// about markdown files, this one is about graph nodes, and the two
+/// aren't related. `Agent` is the only variant with a payload: proposals
+/// are submitted by name (see `CreateProposalRequest::agent` in the
+/// daemon), and there's no fixed roster of agents to enumerate up front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProvenanceSource {
+    Cli,
+    DaemonApi,
+    Desktop,
+    Sync,
+    Analyze,
+    Agent(String),
+}
+
+impl ProvenanceSource {
+    pub fn as_str(&self) -> std::borrow::Cow<'static, str> {
+        match self {
+            ProvenanceSource::Cli => "cli".into(),
+            ProvenanceSource::DaemonApi => "daemon-api".into(),
+            ProvenanceSource::Desktop => "desktop".into(),
+            ProvenanceSource::Sync => "sync".into(),
+            ProvenanceSource::Analyze => "analyze".into(),
+            ProvenanceSource::Agent(name) => format!("agent:{name}").into(),
+        }
+    }
+}
+
+impl fmt::Display for ProvenanceSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for ProvenanceSource {
+    type Err = crate::error::CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cli" => Ok(ProvenanceSource::Cli),
+            "daemon-api" => Ok(ProvenanceSource::DaemonApi),
+            "desktop" => Ok(ProvenanceSource::Desktop),
+            "sync" => Ok(ProvenanceSource::Sync),
+            "analyze" => Ok(ProvenanceSource::Analyze),
+            other => match other.strip_prefix("agent:") {
+                Some(name) if !name.is_empty() => Ok(ProvenanceSource::Agent(name.to_string())),
+                _ => Err(crate::error::CoreError::InvalidInput(format!("unknown provenance source '{other}'"))),
+            },
+        }
+    }
+}
+
+impl Serialize for ProvenanceSource {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ProvenanceSource {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Who or what wrote a node, stamped automatically by the write path
+/// (`snps analyze`, `snps sync`, the daemon's proposal-approval and
+/// batch-import routes, ...) rather than filled in by hand. Older nodes
+/// written before this existed have no provenance; callers should render
+/// that as `"unknown"` rather than treating it as a deserialization
+/// failure, which is why [`Node::provenance`] stays an `Option`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub source: ProvenanceSource,
+    pub actor: String,
+    pub tool_version: String,
+    pub session_id: Option<String>,
+}
+
+impl Provenance {
+    pub fn new(source: ProvenanceSource, actor: impl Into<String>, tool_version: impl Into<String>) -> Self {
+        Self { source, actor: actor.into(), tool_version: tool_version.into(), session_id: None }
+    }
+
+    pub fn with_session_id(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+}
+
+/// A node in the knowledge graph. `source_path`/`source_line` record
+/// where a node was extracted from, when applicable (e.g. a TODO comment
+/// or a README claim picked up by `snps analyze`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    pub id: String,
+    pub node_type: NodeType,
+    pub title: String,
+    pub content: String,
+    pub confidence: Option<f32>,
+    pub source_path: Option<String>,
+    pub source_line: Option<u32>,
+    pub created_at_unix: u64,
+    pub updated_at_unix: u64,
+    #[serde(default)]
+    pub provenance: Option<Provenance>,
+}
+
+impl Node {
+    pub fn new(id: impl Into<String>, node_type: NodeType, title: impl Into<String>, content: impl Into<String>) -> Self {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        Self {
+            id: id.into(),
+            node_type,
+            title: title.into(),
+            content: content.into(),
+            confidence: None,
+            source_path: None,
+            source_line: None,
+            created_at_unix: now,
+            updated_at_unix: now,
+            provenance: None,
+        }
+    }
+
+    pub fn with_confidence(mut self, confidence: f32) -> Self {
+        self.confidence = Some(confidence);
+        self
+    }
+
+    pub fn with_source(mut self, path: impl Into<String>, line: Option<u32>) -> Self {
+        self.source_path = Some(path.into());
+        self.source_line = line;
+        self
+    }
+
+    pub fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provenance_source_round_trips_through_display_and_from_str() {
+        for source in [
+            ProvenanceSource::Cli,
+            ProvenanceSource::DaemonApi,
+            ProvenanceSource::Desktop,
+            ProvenanceSource::Sync,
+            ProvenanceSource::Analyze,
+            ProvenanceSource::Agent("reviewer-bot".to_string()),
+        ] {
+            let parsed: ProvenanceSource = source.to_string().parse().unwrap();
+            assert_eq!(parsed, source);
+        }
+    }
+
+    #[test]
+    fn provenance_source_rejects_unknown_strings() {
+        assert!("smoke-signal".parse::<ProvenanceSource>().is_err());
+        assert!("agent:".parse::<ProvenanceSource>().is_err());
+    }
+
+    #[test]
+    fn node_without_provenance_field_deserializes_with_none() {
+        let json = r#"{
+            "id": "n1", "node_type": "insight", "title": "t", "content": "c",
+            "confidence": null, "source_path": null, "source_line": null,
+            "created_at_unix": 0, "updated_at_unix": 0
+        }"#;
+        let node: Node = serde_json::from_str(json).unwrap();
+        assert_eq!(node.provenance, None);
+    }
+}
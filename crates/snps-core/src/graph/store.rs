@@ -0,0 +1,589 @@
+//! Graph persistence, backed by CozoDB (embedded, file-backed at
+//! `<workspace>/.pmsynapse/synapse.db`). Kept behind a narrow API so the
+//! rest of the crate never touches `cozo` types directly.
+
+use super::migrations;
+use super::{Edge, Node, NodeType, Provenance, ProvenanceSource};
+use crate::error::{CoreError, CoreResult};
+use cozo::{DataValue, DbInstance, ScriptMutability};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const SCHEMA: &str = r#"
+:create nodes {
+    id: String
+    =>
+    node_type: String,
+    title: String,
+    content: String,
+    confidence: Float?,
+    source_path: String?,
+    source_line: Int?,
+    created_at: Int,
+    updated_at: Int,
+    orphaned: Bool default false,
+    provenance_source: String?,
+    provenance_actor: String?,
+    provenance_tool_version: String?,
+    provenance_session_id: String?,
+}
+"#;
+
+const EDGES_SCHEMA: &str = r#"
+:create edges {
+    from: String,
+    to: String,
+    edge_type: String,
+}
+"#;
+
+/// Embeddings live in their own relation rather than as extra `nodes`
+/// columns: they're derived, optional, and computed lazily (see
+/// [`super::embed`]), so a node can exist for a long time with no row
+/// here at all. Keeping them separate also means adding or changing an
+/// embedding model never requires the create/copy/drop/rename dance
+/// [`super::migrations::add_provenance_columns`] had to do to widen
+/// `nodes`.
+const NODE_EMBEDDINGS_SCHEMA: &str = r#"
+:create node_embeddings {
+    id: String
+    =>
+    embedding: <F32; 1536>,
+    model: String,
+}
+"#;
+
+/// HNSW index backing [`KnowledgeGraph::search_similar_by_vector`].
+/// `::hnsw create` errors if the index already exists, same as `:create`
+/// does for a relation — ignored at open time for the same reason.
+const NODE_EMBEDDINGS_INDEX: &str =
+    "::hnsw create node_embeddings:embedding_idx {fields: [embedding], dim: 1536, dtype: F32, distance: Cosine, m: 32, ef_construction: 50}";
+
+/// The `nodes` columns every read query projects, in the fixed order
+/// `row_to_node` expects. Kept as one constant so the column list and its
+/// positional unpacking can't drift apart.
+const NODE_COLUMNS: &str = "id, node_type, title, content, confidence, source_path, source_line, created_at, updated_at, \
+     provenance_source, provenance_actor, provenance_tool_version, provenance_session_id";
+
+/// Column `GET /api/v1/nodes` (and `KnowledgeGraph::query_page`) can sort
+/// by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeOrderBy {
+    CreatedAt,
+    UpdatedAt,
+    Title,
+}
+
+impl NodeOrderBy {
+    fn column(&self) -> &'static str {
+        match self {
+            NodeOrderBy::CreatedAt => "created_at",
+            NodeOrderBy::UpdatedAt => "updated_at",
+            NodeOrderBy::Title => "title",
+        }
+    }
+}
+
+impl std::str::FromStr for NodeOrderBy {
+    type Err = CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "created_at" => Ok(NodeOrderBy::CreatedAt),
+            "updated_at" => Ok(NodeOrderBy::UpdatedAt),
+            "title" => Ok(NodeOrderBy::Title),
+            other => Err(CoreError::InvalidInput(format!("unknown order_by '{other}'"))),
+        }
+    }
+}
+
+/// One page of a node listing, plus enough metadata to fetch the next
+/// one.
+#[derive(Debug, Clone)]
+pub struct NodePage {
+    pub nodes: Vec<Node>,
+    pub total: usize,
+    pub next_offset: Option<usize>,
+}
+
+pub struct KnowledgeGraph {
+    db: DbInstance,
+    applied_migrations: Vec<String>,
+}
+
+impl KnowledgeGraph {
+    /// Open (creating if needed) the graph database at `path`, applying
+    /// any pending schema migrations before returning. Refuses to open a
+    /// database whose schema version is newer than this binary supports.
+    pub fn init(path: &Path) -> CoreResult<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let db = DbInstance::new("sqlite", path.to_string_lossy().as_ref(), Default::default())
+            .map_err(|e| CoreError::InvalidInput(format!("failed to open graph db: {e}")))?;
+
+        // `:create` is a no-op (returns an error we ignore) if the relation
+        // already exists; there's no first-run flag to check against.
+        let _ = db.run_script(SCHEMA, BTreeMap::new(), ScriptMutability::Mutable);
+        let _ = db.run_script(EDGES_SCHEMA, BTreeMap::new(), ScriptMutability::Mutable);
+        let _ = db.run_script(NODE_EMBEDDINGS_SCHEMA, BTreeMap::new(), ScriptMutability::Mutable);
+        let _ = db.run_script(NODE_EMBEDDINGS_INDEX, BTreeMap::new(), ScriptMutability::Mutable);
+        let applied_migrations = migrations::ensure_current(&db)?;
+
+        Ok(Self { db, applied_migrations })
+    }
+
+    /// Migrations that ran the moment this handle was opened, in order;
+    /// empty if the database was already current. Surfaced by `snps
+    /// status` and `snps daemon status --detailed` so an upgrade that
+    /// silently migrated a database on first use isn't a total surprise.
+    pub fn applied_migrations(&self) -> &[String] {
+        &self.applied_migrations
+    }
+
+    /// The database's current schema version.
+    pub fn schema_version(&self) -> CoreResult<u32> {
+        migrations::current_version(&self.db)
+    }
+
+    pub fn add_node(&self, node: &Node) -> CoreResult<()> {
+        let script = r#"
+            ?[id, node_type, title, content, confidence, source_path, source_line, created_at, updated_at, orphaned,
+              provenance_source, provenance_actor, provenance_tool_version, provenance_session_id] <- [[
+                $id, $node_type, $title, $content, $confidence, $source_path, $source_line, $created_at, $updated_at, false,
+                $provenance_source, $provenance_actor, $provenance_tool_version, $provenance_session_id
+            ]]
+            :put nodes
+        "#;
+        let mut params = BTreeMap::new();
+        params.insert("id".to_string(), DataValue::from(node.id.as_str()));
+        params.insert("node_type".to_string(), DataValue::from(node.node_type.as_str()));
+        params.insert("title".to_string(), DataValue::from(node.title.as_str()));
+        params.insert("content".to_string(), DataValue::from(node.content.as_str()));
+        params.insert(
+            "confidence".to_string(),
+            node.confidence.map(|c| DataValue::from(c as f64)).unwrap_or(DataValue::Null),
+        );
+        params.insert(
+            "source_path".to_string(),
+            node.source_path.as_deref().map(DataValue::from).unwrap_or(DataValue::Null),
+        );
+        params.insert(
+            "source_line".to_string(),
+            node.source_line.map(|l| DataValue::from(l as i64)).unwrap_or(DataValue::Null),
+        );
+        params.insert("created_at".to_string(), DataValue::from(node.created_at_unix as i64));
+        params.insert("updated_at".to_string(), DataValue::from(node.updated_at_unix as i64));
+        insert_provenance_params(&mut params, "", &node.provenance);
+
+        self.db
+            .run_script(script, params, ScriptMutability::Mutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to write node: {e}")))?;
+        Ok(())
+    }
+
+    pub fn add_edge(&self, edge: &Edge) -> CoreResult<()> {
+        let script = r#"
+            ?[from, to, edge_type] <- [[$from, $to, $edge_type]]
+            :put edges
+        "#;
+        let mut params = BTreeMap::new();
+        params.insert("from".to_string(), DataValue::from(edge.from.as_str()));
+        params.insert("to".to_string(), DataValue::from(edge.to.as_str()));
+        params.insert("edge_type".to_string(), DataValue::from(edge.edge_type.as_str()));
+
+        self.db
+            .run_script(script, params, ScriptMutability::Mutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to write edge: {e}")))?;
+        Ok(())
+    }
+
+    /// All nodes of `node_type`, or every node when `node_type` is `None`.
+    pub fn query(&self, node_type: Option<NodeType>) -> CoreResult<Vec<Node>> {
+        let (script, params) = match node_type {
+            Some(t) => {
+                let mut params = BTreeMap::new();
+                params.insert("node_type".to_string(), DataValue::from(t.as_str()));
+                (format!("?[{NODE_COLUMNS}] := *nodes{{{NODE_COLUMNS}}}, node_type = $node_type"), params)
+            }
+            None => (format!("?[{NODE_COLUMNS}] := *nodes{{{NODE_COLUMNS}}}"), BTreeMap::new()),
+        };
+
+        let rows = self
+            .db
+            .run_script(&script, params, ScriptMutability::Immutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to query nodes: {e}")))?;
+
+        rows.rows.iter().map(row_to_node).collect()
+    }
+
+    /// A single node by id, or `None` if it doesn't exist. There is no
+    /// corresponding delete — nodes are only ever `mark_orphaned`, never
+    /// removed — so callers resolving a link's title should treat a
+    /// missing node as "orphaned or never existed" rather than "deleted".
+    pub fn get_node(&self, id: &str) -> CoreResult<Option<Node>> {
+        let script = format!("?[{NODE_COLUMNS}] := *nodes{{{NODE_COLUMNS}}}, id = $id");
+        let mut params = BTreeMap::new();
+        params.insert("id".to_string(), DataValue::from(id));
+        let rows = self
+            .db
+            .run_script(&script, params, ScriptMutability::Immutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to query node: {e}")))?;
+        match rows.rows.first() {
+            Some(row) => Ok(Some(row_to_node(row)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn node_exists(&self, id: &str) -> CoreResult<bool> {
+        let script = "?[id] := *nodes{id}, id = $id";
+        let mut params = BTreeMap::new();
+        params.insert("id".to_string(), DataValue::from(id));
+        let rows = self
+            .db
+            .run_script(script, params, ScriptMutability::Immutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to check node existence: {e}")))?;
+        Ok(!rows.rows.is_empty())
+    }
+
+    /// Insert `nodes` and `edges` as a single script, which Cozo runs as
+    /// one transaction — either everything lands or nothing does. Edges
+    /// may reference nodes from earlier in the same batch as well as
+    /// ones already in the database; anything else is rejected before
+    /// writing anything, so a bad edge can't leave a half-applied batch.
+    ///
+    /// Returns each submitted node id mapped to itself: node ids in this
+    /// schema are always caller-assigned, so there's no server-generated
+    /// id to map a temporary one to, but keeping the mapping in the
+    /// return type keeps the endpoint contract stable if that changes.
+    pub fn add_batch(&self, nodes: &[Node], edges: &[Edge]) -> CoreResult<BTreeMap<String, String>> {
+        let batch_ids: std::collections::HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+        for edge in edges {
+            if !batch_ids.contains(edge.from.as_str()) && !self.node_exists(&edge.from)? {
+                return Err(CoreError::InvalidInput(format!("edge references unknown node '{}'", edge.from)));
+            }
+            if !batch_ids.contains(edge.to.as_str()) && !self.node_exists(&edge.to)? {
+                return Err(CoreError::InvalidInput(format!("edge references unknown node '{}'", edge.to)));
+            }
+        }
+
+        if nodes.is_empty() && edges.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let mut script = String::new();
+        let mut params = BTreeMap::new();
+
+        for (i, node) in nodes.iter().enumerate() {
+            script.push_str(&format!(
+                "{{\n?[id, node_type, title, content, confidence, source_path, source_line, created_at, updated_at, orphaned,\n\
+                 provenance_source, provenance_actor, provenance_tool_version, provenance_session_id] <- \
+                 [[$n{i}_id, $n{i}_node_type, $n{i}_title, $n{i}_content, $n{i}_confidence, $n{i}_source_path, $n{i}_source_line, $n{i}_created_at, $n{i}_updated_at, false,\n\
+                 $n{i}_provenance_source, $n{i}_provenance_actor, $n{i}_provenance_tool_version, $n{i}_provenance_session_id]]\n\
+                 :put nodes\n}}\n"
+            ));
+            params.insert(format!("n{i}_id"), DataValue::from(node.id.as_str()));
+            params.insert(format!("n{i}_node_type"), DataValue::from(node.node_type.as_str()));
+            params.insert(format!("n{i}_title"), DataValue::from(node.title.as_str()));
+            params.insert(format!("n{i}_content"), DataValue::from(node.content.as_str()));
+            params.insert(
+                format!("n{i}_confidence"),
+                node.confidence.map(|c| DataValue::from(c as f64)).unwrap_or(DataValue::Null),
+            );
+            params.insert(
+                format!("n{i}_source_path"),
+                node.source_path.as_deref().map(DataValue::from).unwrap_or(DataValue::Null),
+            );
+            params.insert(
+                format!("n{i}_source_line"),
+                node.source_line.map(|l| DataValue::from(l as i64)).unwrap_or(DataValue::Null),
+            );
+            params.insert(format!("n{i}_created_at"), DataValue::from(node.created_at_unix as i64));
+            params.insert(format!("n{i}_updated_at"), DataValue::from(node.updated_at_unix as i64));
+            insert_provenance_params(&mut params, &format!("n{i}_"), &node.provenance);
+        }
+
+        for (i, edge) in edges.iter().enumerate() {
+            script.push_str(&format!(
+                "{{\n?[from, to, edge_type] <- [[$e{i}_from, $e{i}_to, $e{i}_edge_type]]\n:put edges\n}}\n"
+            ));
+            params.insert(format!("e{i}_from"), DataValue::from(edge.from.as_str()));
+            params.insert(format!("e{i}_to"), DataValue::from(edge.to.as_str()));
+            params.insert(format!("e{i}_edge_type"), DataValue::from(edge.edge_type.as_str()));
+        }
+
+        self.db
+            .run_script(&script, params, ScriptMutability::Mutable)
+            .map_err(|e| CoreError::InvalidInput(format!("batch insert failed, nothing was written: {e}")))?;
+
+        Ok(nodes.iter().map(|n| (n.id.clone(), n.id.clone())).collect())
+    }
+
+    /// A page of nodes ordered by `order_by`, optionally filtered by
+    /// `node_type` and/or provenance `source`, with the total matching
+    /// count and the offset of the next page (`None` once the last page
+    /// has been returned).
+    pub fn query_page(
+        &self,
+        node_type: Option<NodeType>,
+        source: Option<&ProvenanceSource>,
+        order_by: NodeOrderBy,
+        limit: usize,
+        offset: usize,
+    ) -> CoreResult<NodePage> {
+        let mut params = BTreeMap::new();
+        let mut filter_clause = String::new();
+        if let Some(t) = node_type {
+            params.insert("node_type".to_string(), DataValue::from(t.as_str()));
+            filter_clause.push_str(", node_type = $node_type");
+        }
+        if let Some(s) = source {
+            params.insert("provenance_source".to_string(), DataValue::from(s.as_str().as_ref()));
+            filter_clause.push_str(", provenance_source = $provenance_source");
+        }
+
+        let count_script = format!("?[count(id)] := *nodes{{id, node_type, provenance_source}}{filter_clause}");
+        let count_rows = self
+            .db
+            .run_script(&count_script, params.clone(), ScriptMutability::Immutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to count nodes: {e}")))?;
+        let total = match count_rows.rows.first().and_then(|row| row.first()) {
+            Some(DataValue::Num(n)) => n.get_float() as usize,
+            _ => 0,
+        };
+
+        let order_col = order_by.column();
+        let script = format!(
+            "?[{NODE_COLUMNS}] := *nodes{{{NODE_COLUMNS}}}{filter_clause} \
+             :order {order_col} \
+             :limit {limit} \
+             :offset {offset}"
+        );
+        let rows = self
+            .db
+            .run_script(&script, params, ScriptMutability::Immutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to query nodes: {e}")))?;
+
+        let nodes: Vec<Node> = rows.rows.iter().map(row_to_node).collect::<CoreResult<Vec<_>>>()?;
+        let next_offset = if offset + nodes.len() < total { Some(offset + nodes.len()) } else { None };
+        Ok(NodePage { nodes, total, next_offset })
+    }
+
+    /// Every edge in the database, in no particular order. Paired with
+    /// `query(None)` this is the raw material for a full graph dump.
+    pub fn all_edges(&self) -> CoreResult<Vec<Edge>> {
+        let script = "?[from, to, edge_type] := *edges{from, to, edge_type}";
+        let rows = self
+            .db
+            .run_script(script, BTreeMap::new(), ScriptMutability::Immutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to query edges: {e}")))?;
+        rows.rows.iter().map(row_to_edge).collect()
+    }
+
+    /// Whether the database holds any nodes at all. Used before a restore
+    /// to decide whether it's safe without `--force`.
+    pub fn is_empty(&self) -> CoreResult<bool> {
+        Ok(self.query(None)?.is_empty())
+    }
+
+    /// Delete every node and edge. Used by restore to make room for an
+    /// incoming snapshot when the caller passed `--force`; there is no
+    /// other caller today.
+    pub fn clear(&self) -> CoreResult<()> {
+        let clear_nodes = "?[id] := *nodes{id}\n:rm nodes {id}";
+        self.db
+            .run_script(clear_nodes, BTreeMap::new(), ScriptMutability::Mutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to clear nodes: {e}")))?;
+
+        let clear_edges = "?[from, to, edge_type] := *edges{from, to, edge_type}\n:rm edges {from, to, edge_type}";
+        self.db
+            .run_script(clear_edges, BTreeMap::new(), ScriptMutability::Mutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to clear edges: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Mark a node as orphaned rather than deleting it, so history and
+    /// backlinks survive a source file's removal. There is still no
+    /// single-node delete anywhere in this crate — when one lands, it
+    /// should check `IdlcItemStore::items_linking_node` first and warn
+    /// about any IDLC items left with a dangling link, the same way this
+    /// method leaves edges pointing at an orphaned node rather than
+    /// silently pruning them.
+    pub fn mark_orphaned(&self, id: &str) -> CoreResult<()> {
+        let script = r#"
+            ?[id, orphaned] <- [[$id, true]]
+            :update nodes { id => orphaned }
+        "#;
+        let mut params = BTreeMap::new();
+        params.insert("id".to_string(), DataValue::from(id));
+        self.db
+            .run_script(script, params, ScriptMutability::Mutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to mark node orphaned: {e}")))?;
+        Ok(())
+    }
+
+    /// Store (or overwrite) `id`'s embedding. `model` records which
+    /// embedder produced it, so a later switch of embedding backends can
+    /// be detected rather than silently mixing incompatible vectors.
+    pub fn set_embedding(&self, id: &str, embedding: &[f32], model: &str) -> CoreResult<()> {
+        let script = r#"
+            ?[id, embedding, model] <- [[$id, $embedding, $model]]
+            :put node_embeddings
+        "#;
+        let mut params = BTreeMap::new();
+        params.insert("id".to_string(), DataValue::from(id));
+        params.insert("embedding".to_string(), DataValue::List(embedding.iter().map(|f| DataValue::from(*f as f64)).collect()));
+        params.insert("model".to_string(), DataValue::from(model));
+        self.db
+            .run_script(script, params, ScriptMutability::Mutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to store embedding: {e}")))?;
+        Ok(())
+    }
+
+    /// Every non-orphaned node that has no row in `node_embeddings` yet —
+    /// the backlog `snps graph embed --missing` works through.
+    pub fn nodes_missing_embedding(&self) -> CoreResult<Vec<Node>> {
+        let script = format!(
+            "?[{NODE_COLUMNS}] := *nodes{{{NODE_COLUMNS}, orphaned}}, orphaned = false, not *node_embeddings{{id}}"
+        );
+        let rows = self
+            .db
+            .run_script(&script, BTreeMap::new(), ScriptMutability::Immutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to query nodes missing embeddings: {e}")))?;
+        rows.rows.iter().map(row_to_node).collect()
+    }
+
+    /// The `k` nodes whose stored embedding is nearest `embedding`
+    /// (cosine distance, ascending — closer first), via the
+    /// `node_embeddings` HNSW index, optionally filtered to one
+    /// `node_type`. Nodes with no embedding yet are simply absent from
+    /// the index and so never show up here.
+    pub fn search_similar_by_vector(&self, embedding: &[f32], k: usize, node_type: Option<NodeType>) -> CoreResult<Vec<(Node, f32)>> {
+        let script = r#"
+            ?[id, dist] := ~node_embeddings:embedding_idx{id | query: $embedding, k: $k, ef: 50, bind_distance: dist}
+            :order dist
+            :limit $k
+        "#;
+        let mut params = BTreeMap::new();
+        params.insert("embedding".to_string(), DataValue::List(embedding.iter().map(|f| DataValue::from(*f as f64)).collect()));
+        params.insert("k".to_string(), DataValue::from(k as i64));
+        let rows = self
+            .db
+            .run_script(script, params, ScriptMutability::Immutable)
+            .map_err(|e| CoreError::InvalidInput(format!("failed to search similar embeddings: {e}")))?;
+
+        let mut results = Vec::new();
+        for row in &rows.rows {
+            let id = match &row[0] {
+                DataValue::Str(s) => s.to_string(),
+                _ => continue,
+            };
+            let dist = match &row[1] {
+                DataValue::Num(n) => n.get_float() as f32,
+                _ => continue,
+            };
+            let Some(node) = self.get_node(&id)? else { continue };
+            if node_type.is_some_and(|t| node.node_type != t) {
+                continue;
+            }
+            results.push((node, dist));
+        }
+        Ok(results)
+    }
+}
+
+fn row_to_edge(row: &Vec<DataValue>) -> CoreResult<Edge> {
+    let get_str = |v: &DataValue| -> CoreResult<String> {
+        match v {
+            DataValue::Str(s) => Ok(s.to_string()),
+            _ => Err(CoreError::InvalidInput("expected string column".to_string())),
+        }
+    };
+    Ok(Edge { from: get_str(&row[0])?, to: get_str(&row[1])?, edge_type: get_str(&row[2])?.parse()? })
+}
+
+fn row_to_node(row: &Vec<DataValue>) -> CoreResult<Node> {
+    let get_str = |v: &DataValue| -> CoreResult<String> {
+        match v {
+            DataValue::Str(s) => Ok(s.to_string()),
+            _ => Err(CoreError::InvalidInput("expected string column".to_string())),
+        }
+    };
+
+    let node_type: NodeType = get_str(&row[1])?.parse()?;
+    Ok(Node {
+        id: get_str(&row[0])?,
+        node_type,
+        title: get_str(&row[2])?,
+        content: get_str(&row[3])?,
+        confidence: match &row[4] {
+            DataValue::Num(n) => Some(n.get_float() as f32),
+            _ => None,
+        },
+        source_path: match &row[5] {
+            DataValue::Str(s) => Some(s.to_string()),
+            _ => None,
+        },
+        source_line: match &row[6] {
+            DataValue::Num(n) => Some(n.get_float() as u32),
+            _ => None,
+        },
+        created_at_unix: match &row[7] {
+            DataValue::Num(n) => n.get_float() as u64,
+            _ => 0,
+        },
+        updated_at_unix: match &row[8] {
+            DataValue::Num(n) => n.get_float() as u64,
+            _ => 0,
+        },
+        provenance: row_to_provenance(&row[9], &row[10], &row[11], &row[12]),
+    })
+}
+
+/// Reassemble a node's provenance from its four flattened columns.
+/// `provenance_source` is null for nodes written before provenance
+/// existed (or by a caller that didn't set one) — that's not a parse
+/// error, it just means "unknown", so this returns `None` rather than
+/// bubbling up a `CoreError`. A source string this binary doesn't
+/// recognize is treated the same way, for the same reason `row_to_node`
+/// never fails on a provenance column: an older or newer `snps` writing
+/// a source value this build doesn't know shouldn't break deserialization
+/// of the rest of the node.
+fn row_to_provenance(source: &DataValue, actor: &DataValue, tool_version: &DataValue, session_id: &DataValue) -> Option<Provenance> {
+    let DataValue::Str(source) = source else { return None };
+    let source: ProvenanceSource = source.parse().ok()?;
+    let actor = match actor {
+        DataValue::Str(s) => s.to_string(),
+        _ => String::new(),
+    };
+    let tool_version = match tool_version {
+        DataValue::Str(s) => s.to_string(),
+        _ => String::new(),
+    };
+    let session_id = match session_id {
+        DataValue::Str(s) => Some(s.to_string()),
+        _ => None,
+    };
+    Some(Provenance { source, actor, tool_version, session_id })
+}
+
+/// Split a node's optional [`Provenance`] into the four `provenance_*`
+/// query parameters `{prefix}` is inserting for (e.g. `""` for
+/// `add_node`'s single-row insert, `"n3_"` for the fourth node in a
+/// batch), all null when there is none.
+fn insert_provenance_params(params: &mut BTreeMap<String, DataValue>, prefix: &str, provenance: &Option<Provenance>) {
+    let (source, actor, tool_version, session_id) = match provenance {
+        Some(p) => (
+            DataValue::from(p.source.as_str().as_ref()),
+            DataValue::from(p.actor.as_str()),
+            DataValue::from(p.tool_version.as_str()),
+            p.session_id.as_deref().map(DataValue::from).unwrap_or(DataValue::Null),
+        ),
+        None => (DataValue::Null, DataValue::Null, DataValue::Null, DataValue::Null),
+    };
+    params.insert(format!("{prefix}provenance_source"), source);
+    params.insert(format!("{prefix}provenance_actor"), actor);
+    params.insert(format!("{prefix}provenance_tool_version"), tool_version);
+    params.insert(format!("{prefix}provenance_session_id"), session_id);
+}
@@ -0,0 +1,190 @@
+//! `snps sync`: map `knowledge/` and `thoughts/shared/` markdown into
+//! `Document` graph nodes, with `Describes` edges for wiki-links.
+//!
+//! The add/update/orphan decision (`plan_doc_sync`) is kept as a pure
+//! function over already-read content, so it's testable without a live
+//! graph database.
+
+use super::{Edge, EdgeType, KnowledgeGraph, Node, NodeType, Provenance, ProvenanceSource};
+use crate::error::CoreResult;
+use crate::knowledge::hash_contents;
+use crate::matter::extract_links;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocOutcome {
+    Added,
+    Updated,
+    Unchanged,
+    Orphaned,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocSyncResult {
+    pub id: String,
+    pub path: PathBuf,
+    pub outcome: DocOutcome,
+}
+
+pub struct DocSource {
+    pub id: String,
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// Decide add/update/unchanged/orphan for every source against the ids
+/// and content hashes already recorded in the graph.
+pub fn plan_doc_sync(sources: &[DocSource], existing: &HashMap<String, String>) -> Vec<DocSyncResult> {
+    let mut results = Vec::new();
+    let mut seen = HashSet::new();
+
+    for source in sources {
+        seen.insert(source.id.clone());
+        let hash = hash_contents(source.content.as_bytes());
+        let outcome = match existing.get(&source.id) {
+            None => DocOutcome::Added,
+            Some(existing_hash) if existing_hash == &hash => DocOutcome::Unchanged,
+            Some(_) => DocOutcome::Updated,
+        };
+        results.push(DocSyncResult { id: source.id.clone(), path: source.path.clone(), outcome });
+    }
+
+    for id in existing.keys() {
+        if !seen.contains(id) {
+            results.push(DocSyncResult { id: id.clone(), path: PathBuf::new(), outcome: DocOutcome::Orphaned });
+        }
+    }
+
+    results
+}
+
+/// Stable id for a document: its frontmatter `matter_id`, if present,
+/// else its path relative to `root`.
+pub fn document_id(path: &Path, root: &Path, frontmatter_matter_id: Option<&str>) -> String {
+    frontmatter_matter_id
+        .map(str::to_string)
+        .unwrap_or_else(|| path.strip_prefix(root).unwrap_or(path).to_string_lossy().into_owned())
+}
+
+fn collect_sources(dir: &Path, root: &Path, only_file: Option<&Path>) -> Vec<DocSource> {
+    let mut out = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else { return out };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            out.extend(collect_sources(&path, root, only_file));
+        } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+            if let Some(only) = only_file {
+                let same = path.canonicalize().ok().zip(only.canonicalize().ok()).map(|(a, b)| a == b).unwrap_or(path == only);
+                if !same {
+                    continue;
+                }
+            }
+            let Ok(content) = std::fs::read_to_string(&path) else { continue };
+            let matter_id = frontmatter_field(&content, "matter_id");
+            let id = document_id(&path, root, matter_id.as_deref());
+            out.push(DocSource { id, path, content });
+        }
+    }
+    out
+}
+
+/// `pub(super)` rather than private: `ingest` also needs to recover a
+/// document's `matter_id` before it can compute a chunk's parent id.
+pub(super) fn frontmatter_field(contents: &str, key: &str) -> Option<String> {
+    let rest = contents.strip_prefix("---\n")?;
+    let end = rest.find("\n---\n")?;
+    rest[..end].lines().find_map(|line| {
+        let (k, v) = line.split_once(':')?;
+        (k.trim() == key).then(|| v.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Scan `knowledge_dir` and `thoughts_shared_dir` (or just `only_file`, if
+/// given) and reconcile them against the graph's `Document` nodes.
+/// Deletions mark nodes orphaned rather than removing them.
+pub fn sync_markdown_to_graph(
+    graph: &KnowledgeGraph,
+    workspace_root: &Path,
+    knowledge_dir: &Path,
+    thoughts_shared_dir: &Path,
+    only_file: Option<&Path>,
+    dry_run: bool,
+) -> CoreResult<Vec<DocSyncResult>> {
+    let mut sources = collect_sources(knowledge_dir, workspace_root, only_file);
+    sources.extend(collect_sources(thoughts_shared_dir, workspace_root, only_file));
+
+    let existing: HashMap<String, String> = graph
+        .query(Some(NodeType::Document))?
+        .into_iter()
+        .map(|n| (n.id, hash_contents(n.content.as_bytes())))
+        .collect();
+
+    // A single-file sync only reconciles that file; treat every other
+    // existing document as still present so it isn't reported orphaned.
+    let existing = if only_file.is_some() {
+        existing.into_iter().filter(|(id, _)| sources.iter().any(|s| &s.id == id)).collect()
+    } else {
+        existing
+    };
+
+    let results = plan_doc_sync(&sources, &existing);
+
+    if dry_run {
+        return Ok(results);
+    }
+
+    for result in &results {
+        match result.outcome {
+            DocOutcome::Added | DocOutcome::Updated => {
+                let Some(source) = sources.iter().find(|s| s.id == result.id) else { continue };
+                let title = source.path.file_stem().and_then(|s| s.to_str()).unwrap_or(&source.id).to_string();
+                let node = Node::new(&source.id, NodeType::Document, title, &source.content)
+                    .with_source(source.path.to_string_lossy(), None)
+                    .with_provenance(Provenance::new(ProvenanceSource::Sync, "snps sync", env!("CARGO_PKG_VERSION")));
+                graph.add_node(&node)?;
+
+                for link in extract_links(&source.content) {
+                    graph.add_edge(&Edge::new(source.id.clone(), link.target, EdgeType::Describes))?;
+                }
+            }
+            DocOutcome::Orphaned => graph.mark_orphaned(&result.id)?,
+            DocOutcome::Unchanged => {}
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_source_is_added() {
+        let sources = vec![DocSource { id: "a".into(), path: "a.md".into(), content: "hello".into() }];
+        let results = plan_doc_sync(&sources, &HashMap::new());
+        assert_eq!(results, vec![DocSyncResult { id: "a".into(), path: "a.md".into(), outcome: DocOutcome::Added }]);
+    }
+
+    #[test]
+    fn unchanged_content_is_a_no_op() {
+        let sources = vec![DocSource { id: "a".into(), path: "a.md".into(), content: "hello".into() }];
+        let existing = HashMap::from([("a".to_string(), hash_contents(b"hello"))]);
+        let results = plan_doc_sync(&sources, &existing);
+        assert_eq!(results, vec![DocSyncResult { id: "a".into(), path: "a.md".into(), outcome: DocOutcome::Unchanged }]);
+    }
+
+    #[test]
+    fn changed_content_is_updated_and_missing_source_is_orphaned() {
+        let sources = vec![DocSource { id: "a".into(), path: "a.md".into(), content: "v2".into() }];
+        let existing = HashMap::from([
+            ("a".to_string(), hash_contents(b"v1")),
+            ("b".to_string(), hash_contents(b"still here?")),
+        ]);
+        let results = plan_doc_sync(&sources, &existing);
+        assert!(results.contains(&DocSyncResult { id: "a".into(), path: "a.md".into(), outcome: DocOutcome::Updated }));
+        assert!(results.contains(&DocSyncResult { id: "b".into(), path: PathBuf::new(), outcome: DocOutcome::Orphaned }));
+    }
+}
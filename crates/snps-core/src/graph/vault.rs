@@ -0,0 +1,282 @@
+//! Export a graph snapshot as an Obsidian-compatible vault: one markdown
+//! file per node (frontmatter carrying id/type/confidence/timestamps,
+//! edges rendered as typed wiki-links), plus an index note per node
+//! type. Re-exporting into the same directory only rewrites files whose
+//! content actually changed and removes files for nodes no longer in
+//! the graph, tracked via a manifest written alongside the vault — so a
+//! scheduled job can keep the vault current without redoing work each
+//! time it runs.
+//!
+//! Nodes are never hard-deleted from [`crate::graph::KnowledgeGraph`]
+//! (see [`crate::graph::KnowledgeGraph::mark_orphaned`]'s doc comment) —
+//! only marked orphaned, and `query(None)` still returns them — so in
+//! practice the "removed node" case here fires after a `graph restore`
+//! that swaps in a smaller node set, not from ordinary day-to-day sync.
+
+use super::{Edge, KnowledgeGraph, Node, NodeType};
+use crate::error::CoreResult;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+const MANIFEST_FILE: &str = ".snps-vault-manifest.yaml";
+
+/// Node id -> filename last written for it (relative to the vault dir),
+/// so a title edit (which changes the slug) or a node's disappearance
+/// can be detected on re-export without diffing every file already in
+/// the directory.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct VaultManifest {
+    files: BTreeMap<String, String>,
+}
+
+impl VaultManifest {
+    fn path(dir: &Path) -> PathBuf {
+        dir.join(MANIFEST_FILE)
+    }
+
+    fn load(dir: &Path) -> CoreResult<Self> {
+        let path = Self::path(dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_yaml::from_str(&contents).unwrap_or_default())
+    }
+
+    fn save(&self, dir: &Path) -> CoreResult<()> {
+        std::fs::write(Self::path(dir), serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct VaultExportStats {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub deleted: usize,
+}
+
+/// Write (or refresh) an Obsidian vault at `dir` from `graph`'s current
+/// contents. Safe to call repeatedly against the same directory.
+pub fn export_vault(graph: &KnowledgeGraph, dir: &Path) -> CoreResult<VaultExportStats> {
+    std::fs::create_dir_all(dir)?;
+    let mut manifest = VaultManifest::load(dir)?;
+
+    let nodes = graph.query(None)?;
+    let edges = graph.all_edges()?;
+    let by_id: BTreeMap<&str, &Node> = nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+
+    let mut stats = VaultExportStats::default();
+    let mut used_names: HashSet<String> = HashSet::new();
+    let mut new_files: BTreeMap<String, String> = BTreeMap::new();
+
+    for node in &nodes {
+        let filename = unique_filename(&node.title, &mut used_names);
+        let contents = render_node(node, &edges, &by_id);
+        let file_path = dir.join(&filename);
+
+        match std::fs::read_to_string(&file_path) {
+            Ok(existing) if existing == contents => stats.unchanged += 1,
+            Ok(_) => {
+                std::fs::write(&file_path, &contents)?;
+                stats.updated += 1;
+            }
+            Err(_) => {
+                std::fs::write(&file_path, &contents)?;
+                stats.created += 1;
+            }
+        }
+
+        // The title changed since the last export: the slug (and so the
+        // filename) did too, so drop the stale file rather than leaving
+        // an orphaned copy behind next to the renamed one.
+        if let Some(old) = manifest.files.get(&node.id) {
+            if old != &filename {
+                let _ = std::fs::remove_file(dir.join(old));
+            }
+        }
+
+        new_files.insert(node.id.clone(), filename);
+    }
+
+    for (id, filename) in &manifest.files {
+        if !by_id.contains_key(id.as_str()) {
+            let _ = std::fs::remove_file(dir.join(filename));
+            stats.deleted += 1;
+        }
+    }
+
+    for node_type in NodeType::ALL {
+        let members: Vec<&Node> = nodes.iter().filter(|n| n.node_type == node_type).collect();
+        std::fs::write(dir.join(index_filename(node_type)), render_index(node_type, &members, &new_files))?;
+    }
+
+    manifest.files = new_files;
+    manifest.save(dir)?;
+    Ok(stats)
+}
+
+fn index_filename(node_type: NodeType) -> String {
+    format!("index-{}.md", node_type.as_str())
+}
+
+fn render_index(node_type: NodeType, members: &[&Node], files: &BTreeMap<String, String>) -> String {
+    let mut out = format!("# {} index\n\n", capitalize(node_type.as_str()));
+    if members.is_empty() {
+        out.push_str("(none)\n");
+        return out;
+    }
+    for node in members {
+        let target = files.get(&node.id).map(|f| wikilink_target(f)).unwrap_or_else(|| node.title.clone());
+        out.push_str(&format!("- [[{target}]]\n"));
+    }
+    out
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+fn render_node(node: &Node, edges: &[Edge], by_id: &BTreeMap<&str, &Node>) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("id: {}\n", node.id));
+    out.push_str(&format!("type: {}\n", node.node_type.as_str()));
+    if let Some(confidence) = node.confidence {
+        out.push_str(&format!("confidence: {confidence}\n"));
+    }
+    out.push_str(&format!("created: {}\n", crate::time::date_string(node.created_at_unix)));
+    out.push_str(&format!("updated: {}\n", crate::time::date_string(node.updated_at_unix)));
+    out.push_str("---\n\n");
+
+    out.push_str(&format!("# {}\n\n", node.title));
+    out.push_str(node.content.trim_end());
+    out.push('\n');
+
+    let outgoing: Vec<&Edge> = edges.iter().filter(|e| e.from == node.id).collect();
+    if !outgoing.is_empty() {
+        out.push_str("\n## Relations\n\n");
+        for edge in outgoing {
+            let target = by_id.get(edge.to.as_str()).map(|n| n.title.clone()).unwrap_or_else(|| edge.to.clone());
+            out.push_str(&format!("- {}:: [[{target}]]\n", edge.edge_type.as_str()));
+        }
+    }
+
+    out
+}
+
+/// The link text a wikilink to `filename` should use — the filename
+/// without its `.md` extension, matching how Obsidian resolves `[[...]]`
+/// against vault file names.
+fn wikilink_target(filename: &str) -> String {
+    filename.strip_suffix(".md").unwrap_or(filename).to_string()
+}
+
+fn unique_filename(title: &str, used: &mut HashSet<String>) -> String {
+    let base = slugify(title);
+    let base = if base.is_empty() { "untitled".to_string() } else { base };
+    let mut candidate = format!("{base}.md");
+    let mut n = 2;
+    while used.contains(&candidate) {
+        candidate = format!("{base}-{n}.md");
+        n += 1;
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    let mut last_dash = false;
+    for c in title.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+            last_dash = false;
+        } else if !last_dash {
+            slug.push('-');
+            last_dash = true;
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{EdgeType, KnowledgeGraph};
+
+    fn graph() -> (tempfile::TempDir, KnowledgeGraph) {
+        let dir = tempfile::tempdir().unwrap();
+        let graph = KnowledgeGraph::init(&dir.path().join("synapse.db")).unwrap();
+        (dir, graph)
+    }
+
+    #[test]
+    fn writes_one_file_per_node_and_an_index_per_type() {
+        let (_dir, graph) = graph();
+        let a = Node::new("a", NodeType::Insight, "First Insight", "body a");
+        let b = Node::new("b", NodeType::Insight, "Second Insight", "body b");
+        graph.add_node(&a).unwrap();
+        graph.add_node(&b).unwrap();
+        graph.add_edge(&Edge::new("a", "b", EdgeType::RelatesTo)).unwrap();
+
+        let vault = tempfile::tempdir().unwrap();
+        let stats = export_vault(&graph, vault.path()).unwrap();
+        assert_eq!(stats.created, 2);
+
+        let a_contents = std::fs::read_to_string(vault.path().join("first-insight.md")).unwrap();
+        assert!(a_contents.contains("id: a"));
+        assert!(a_contents.contains("relates_to:: [[Second Insight]]"));
+
+        let index = std::fs::read_to_string(vault.path().join("index-insight.md")).unwrap();
+        assert!(index.contains("[[First Insight]]"));
+        assert!(index.contains("[[Second Insight]]"));
+    }
+
+    #[test]
+    fn colliding_titles_get_a_suffixed_filename() {
+        let (_dir, graph) = graph();
+        graph.add_node(&Node::new("a", NodeType::Document, "Same Title", "one")).unwrap();
+        graph.add_node(&Node::new("b", NodeType::Document, "Same Title", "two")).unwrap();
+
+        let vault = tempfile::tempdir().unwrap();
+        export_vault(&graph, vault.path()).unwrap();
+
+        assert!(vault.path().join("same-title.md").exists());
+        assert!(vault.path().join("same-title-2.md").exists());
+    }
+
+    #[test]
+    fn reexport_is_a_no_op_when_nothing_changed() {
+        let (_dir, graph) = graph();
+        graph.add_node(&Node::new("a", NodeType::Question, "Open Question", "body")).unwrap();
+
+        let vault = tempfile::tempdir().unwrap();
+        let first = export_vault(&graph, vault.path()).unwrap();
+        assert_eq!(first.created, 1);
+
+        let second = export_vault(&graph, vault.path()).unwrap();
+        assert_eq!(second.unchanged, 1);
+        assert_eq!(second.created, 0);
+    }
+
+    #[test]
+    fn removed_node_deletes_its_file_on_reexport() {
+        let (_dir, graph) = graph();
+        graph.add_node(&Node::new("a", NodeType::Assumption, "Doomed", "body")).unwrap();
+
+        let vault = tempfile::tempdir().unwrap();
+        export_vault(&graph, vault.path()).unwrap();
+        assert!(vault.path().join("doomed.md").exists());
+
+        graph.clear().unwrap();
+        let stats = export_vault(&graph, vault.path()).unwrap();
+        assert_eq!(stats.deleted, 1);
+        assert!(!vault.path().join("doomed.md").exists());
+    }
+}
@@ -0,0 +1,341 @@
+//! Git hooks that keep the knowledge graph in sync with thoughts/knowledge
+//! documents as they're committed: a `pre-commit` hook that previews what
+//! `snps sync` would do (so a stale doc is visible before the commit
+//! lands), and an optional `post-commit` hook, installed with
+//! `--auto-sync`, that actually runs it once the commit exists.
+//!
+//! Hook files are managed symmetrically and defensively: if another tool
+//! (husky, lefthook) already owns `pre-commit`/`post-commit`, or a hook
+//! file already has content of its own, install appends a clearly
+//! delimited PMSynapse block to the existing file instead of replacing
+//! it, and uninstall only ever removes that block (or, if PMSynapse wrote
+//! the whole file, the file itself) — never bytes it didn't write.
+//!
+//! Every function here takes the target `hooks_dir` explicitly rather
+//! than discovering `.git/hooks` itself, so tests exercise real files in
+//! a temp directory instead of a real git checkout.
+
+use crate::error::{CoreError, CoreResult};
+use std::path::Path;
+
+const BEGIN_MARKER: &str = "# >>> pmsynapse thoughts hooks >>>";
+const END_MARKER: &str = "# <<< pmsynapse thoughts hooks <<<";
+/// First line of a hook file PMSynapse wrote in full (as opposed to one
+/// embedded inside a file another tool owns). Lets uninstall tell "delete
+/// the file" apart from "strip out just our block".
+const OWNED_SENTINEL: &str = "# pmsynapse-managed-hook: true";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+    PreCommit,
+    PostCommit,
+}
+
+impl HookKind {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            HookKind::PreCommit => "pre-commit",
+            HookKind::PostCommit => "post-commit",
+        }
+    }
+
+    fn sync_command(&self) -> &'static str {
+        match self {
+            HookKind::PreCommit => "snps sync --dry-run",
+            HookKind::PostCommit => "snps sync",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookState {
+    /// No file, or a file with no PMSynapse block in it.
+    NotInstalled,
+    /// PMSynapse wrote the whole file; nothing else to preserve.
+    Managed,
+    /// PMSynapse's block lives inside a file another tool (or the user)
+    /// also writes to.
+    EmbeddedInExistingHook,
+    /// A hook file exists, has content, but isn't ours and isn't a
+    /// managed-hook framework we recognize — install won't touch it
+    /// without `--force`.
+    ForeignUnmanaged,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookStatus {
+    pub kind: HookKind,
+    pub state: HookState,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InstallOptions {
+    /// Also install the `post-commit` auto-sync hook, in addition to the
+    /// always-installed `pre-commit` preview hook.
+    pub auto_sync: bool,
+    /// Overwrite a `ForeignUnmanaged` hook file instead of leaving it
+    /// alone.
+    pub force: bool,
+}
+
+/// True if `repo_root` looks like it's owned by a hook framework other
+/// than plain git: a `.husky` directory, or a `lefthook.yml`/`.yaml`
+/// config. Detected once per install so a framework hook that happens to
+/// be empty is still treated as foreign.
+fn has_managed_hook_framework(repo_root: &Path) -> bool {
+    repo_root.join(".husky").is_dir()
+        || repo_root.join("lefthook.yml").is_file()
+        || repo_root.join("lefthook.yaml").is_file()
+}
+
+fn block_for(kind: HookKind) -> String {
+    format!("{BEGIN_MARKER}\n{}\n{END_MARKER}", kind.sync_command())
+}
+
+fn strip_non_executable_shebang(existing: &str) -> &str {
+    existing.strip_prefix("#!/bin/sh\n").unwrap_or(existing)
+}
+
+fn classify(existing: Option<&str>) -> HookState {
+    match existing {
+        None => HookState::NotInstalled,
+        Some(content) if content.contains(OWNED_SENTINEL) => HookState::Managed,
+        Some(content) if content.contains(BEGIN_MARKER) => HookState::EmbeddedInExistingHook,
+        Some(content) if content.trim().is_empty() => HookState::NotInstalled,
+        Some(_) => HookState::ForeignUnmanaged,
+    }
+}
+
+fn read_hook(hooks_dir: &Path, kind: HookKind) -> CoreResult<Option<String>> {
+    match std::fs::read_to_string(hooks_dir.join(kind.file_name())) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> CoreResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut perms = std::fs::metadata(path)?.permissions();
+    perms.set_mode(perms.mode() | 0o111);
+    std::fs::set_permissions(path, perms)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> CoreResult<()> {
+    Ok(())
+}
+
+fn write_hook(hooks_dir: &Path, kind: HookKind, content: &str) -> CoreResult<()> {
+    let path = hooks_dir.join(kind.file_name());
+    std::fs::write(&path, content)?;
+    make_executable(&path)?;
+    Ok(())
+}
+
+fn install_one(hooks_dir: &Path, repo_root: &Path, kind: HookKind, force: bool) -> CoreResult<HookStatus> {
+    let existing = read_hook(hooks_dir, kind)?;
+    let state = classify(existing.as_deref());
+
+    match state {
+        HookState::NotInstalled => {
+            let script = format!("#!/bin/sh\n{OWNED_SENTINEL}\n{}\n", block_for(kind));
+            write_hook(hooks_dir, kind, &script)?;
+            Ok(HookStatus { kind, state: HookState::Managed })
+        }
+        HookState::Managed | HookState::EmbeddedInExistingHook => {
+            // Already installed (possibly with a stale command); rewrite
+            // just our block in place so reinstalling is idempotent.
+            let content = existing.expect("classify() only returns these states for Some(content)");
+            let (before, after) = split_around_block(&content);
+            let rebuilt = format!("{before}{}\n{after}", block_for(kind));
+            write_hook(hooks_dir, kind, &rebuilt)?;
+            Ok(HookStatus { kind, state })
+        }
+        HookState::ForeignUnmanaged => {
+            if !force && !has_managed_hook_framework(repo_root) {
+                return Err(CoreError::InvalidInput(format!(
+                    "{} already has a hook that isn't managed by PMSynapse and doesn't look like husky/lefthook; pass --force to append to it anyway",
+                    kind.file_name()
+                )));
+            }
+            let content = existing.expect("ForeignUnmanaged implies Some(content)");
+            let separator = if content.ends_with('\n') { "" } else { "\n" };
+            let appended = format!("{content}{separator}\n{}\n", block_for(kind));
+            write_hook(hooks_dir, kind, &appended)?;
+            Ok(HookStatus { kind, state: HookState::EmbeddedInExistingHook })
+        }
+    }
+}
+
+/// Split `content` into everything before the PMSynapse block and
+/// everything after it, both markers excluded, so the block can be
+/// rewritten without disturbing surrounding lines.
+fn split_around_block(content: &str) -> (String, String) {
+    let Some(start) = content.find(BEGIN_MARKER) else {
+        return (content.to_string(), String::new());
+    };
+    let before = content[..start].to_string();
+    let after = match content[start..].find(END_MARKER) {
+        Some(end_offset) => {
+            let after_start = start + end_offset + END_MARKER.len();
+            content[after_start..].trim_start_matches('\n').to_string()
+        }
+        None => String::new(),
+    };
+    (before, after)
+}
+
+/// Install the `pre-commit` preview hook, and (with
+/// `options.auto_sync`) the `post-commit` auto-sync hook.
+pub fn install_thoughts_hooks(hooks_dir: &Path, repo_root: &Path, options: InstallOptions) -> CoreResult<Vec<HookStatus>> {
+    std::fs::create_dir_all(hooks_dir)?;
+    let mut installed = vec![install_one(hooks_dir, repo_root, HookKind::PreCommit, options.force)?];
+    if options.auto_sync {
+        installed.push(install_one(hooks_dir, repo_root, HookKind::PostCommit, options.force)?);
+    }
+    Ok(installed)
+}
+
+fn uninstall_one(hooks_dir: &Path, kind: HookKind) -> CoreResult<HookStatus> {
+    let existing = read_hook(hooks_dir, kind)?;
+    let state = classify(existing.as_deref());
+    let path = hooks_dir.join(kind.file_name());
+
+    match &state {
+        HookState::NotInstalled | HookState::ForeignUnmanaged => {}
+        HookState::Managed => std::fs::remove_file(&path)?,
+        HookState::EmbeddedInExistingHook => {
+            let content = existing.expect("EmbeddedInExistingHook implies Some(content)");
+            let (before, after) = split_around_block(&content);
+            let remainder = format!("{}\n{after}", before.trim_end_matches('\n'));
+            if strip_non_executable_shebang(remainder.trim()).trim().is_empty() {
+                std::fs::remove_file(&path)?;
+            } else {
+                write_hook(hooks_dir, kind, &remainder)?;
+            }
+        }
+    }
+    Ok(HookStatus { kind, state })
+}
+
+/// Remove both hooks, restoring an embedded file to its pre-install
+/// bytes and deleting a file PMSynapse wrote in full. A hook PMSynapse
+/// never touched (`ForeignUnmanaged`) is left alone.
+pub fn uninstall_thoughts_hooks(hooks_dir: &Path) -> CoreResult<Vec<HookStatus>> {
+    Ok(vec![uninstall_one(hooks_dir, HookKind::PreCommit)?, uninstall_one(hooks_dir, HookKind::PostCommit)?])
+}
+
+pub fn hooks_status(hooks_dir: &Path) -> CoreResult<Vec<HookStatus>> {
+    Ok(vec![
+        HookStatus { kind: HookKind::PreCommit, state: classify(read_hook(hooks_dir, HookKind::PreCommit)?.as_deref()) },
+        HookStatus { kind: HookKind::PostCommit, state: classify(read_hook(hooks_dir, HookKind::PostCommit)?.as_deref()) },
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hooks_dir_in(tmp: &Path) -> std::path::PathBuf {
+        let dir = tmp.join(".git").join("hooks");
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn fresh_install_writes_both_hooks_when_auto_sync_is_on() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hooks_dir = hooks_dir_in(tmp.path());
+
+        let installed = install_thoughts_hooks(&hooks_dir, tmp.path(), InstallOptions { auto_sync: true, force: false }).unwrap();
+
+        assert_eq!(installed, vec![
+            HookStatus { kind: HookKind::PreCommit, state: HookState::Managed },
+            HookStatus { kind: HookKind::PostCommit, state: HookState::Managed },
+        ]);
+        let pre = std::fs::read_to_string(hooks_dir.join("pre-commit")).unwrap();
+        assert!(pre.contains("snps sync --dry-run"));
+        let post = std::fs::read_to_string(hooks_dir.join("post-commit")).unwrap();
+        assert!(post.contains("snps sync") && !post.contains("--dry-run"));
+    }
+
+    #[test]
+    fn fresh_install_without_auto_sync_only_writes_pre_commit() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hooks_dir = hooks_dir_in(tmp.path());
+
+        install_thoughts_hooks(&hooks_dir, tmp.path(), InstallOptions::default()).unwrap();
+
+        assert!(hooks_dir.join("pre-commit").exists());
+        assert!(!hooks_dir.join("post-commit").exists());
+    }
+
+    #[test]
+    fn install_over_husky_embeds_a_delimited_block_instead_of_replacing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hooks_dir = hooks_dir_in(tmp.path());
+        std::fs::create_dir_all(tmp.path().join(".husky")).unwrap();
+        std::fs::write(hooks_dir.join("pre-commit"), "#!/bin/sh\n. \"$(dirname \"$0\")/_/husky.sh\"\nnpx lint-staged\n").unwrap();
+
+        let installed = install_thoughts_hooks(&hooks_dir, tmp.path(), InstallOptions::default()).unwrap();
+
+        assert_eq!(installed[0].state, HookState::EmbeddedInExistingHook);
+        let content = std::fs::read_to_string(hooks_dir.join("pre-commit")).unwrap();
+        assert!(content.contains("npx lint-staged"));
+        assert!(content.contains(BEGIN_MARKER));
+        assert!(content.contains("snps sync --dry-run"));
+    }
+
+    #[test]
+    fn install_refuses_an_unrecognized_foreign_hook_without_force() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hooks_dir = hooks_dir_in(tmp.path());
+        std::fs::write(hooks_dir.join("pre-commit"), "#!/bin/sh\necho custom check\n").unwrap();
+
+        let err = install_thoughts_hooks(&hooks_dir, tmp.path(), InstallOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+    }
+
+    #[test]
+    fn uninstall_restores_original_bytes_when_embedded() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hooks_dir = hooks_dir_in(tmp.path());
+        std::fs::create_dir_all(tmp.path().join(".husky")).unwrap();
+        let original = "#!/bin/sh\n. \"$(dirname \"$0\")/_/husky.sh\"\nnpx lint-staged\n";
+        std::fs::write(hooks_dir.join("pre-commit"), original).unwrap();
+
+        install_thoughts_hooks(&hooks_dir, tmp.path(), InstallOptions::default()).unwrap();
+        uninstall_thoughts_hooks(&hooks_dir).unwrap();
+
+        let restored = std::fs::read_to_string(hooks_dir.join("pre-commit")).unwrap();
+        assert_eq!(restored.trim(), original.trim());
+    }
+
+    #[test]
+    fn uninstall_deletes_a_fully_owned_hook_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hooks_dir = hooks_dir_in(tmp.path());
+
+        install_thoughts_hooks(&hooks_dir, tmp.path(), InstallOptions { auto_sync: true, force: false }).unwrap();
+        uninstall_thoughts_hooks(&hooks_dir).unwrap();
+
+        assert!(!hooks_dir.join("pre-commit").exists());
+        assert!(!hooks_dir.join("post-commit").exists());
+    }
+
+    #[test]
+    fn status_reports_embedded_state_without_mutating_anything() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hooks_dir = hooks_dir_in(tmp.path());
+        std::fs::create_dir_all(tmp.path().join(".husky")).unwrap();
+        std::fs::write(hooks_dir.join("pre-commit"), "#!/bin/sh\nnpx lint-staged\n").unwrap();
+        install_thoughts_hooks(&hooks_dir, tmp.path(), InstallOptions::default()).unwrap();
+
+        let status = hooks_status(&hooks_dir).unwrap();
+        assert_eq!(status[0].state, HookState::EmbeddedInExistingHook);
+        assert_eq!(status[1].state, HookState::NotInstalled);
+    }
+}
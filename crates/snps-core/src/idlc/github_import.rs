@@ -0,0 +1,332 @@
+//! Importing GitHub issues as IDLC items. Network access is behind
+//! [`GithubIssueClient`], the same trait-per-backend shape as
+//! [`crate::llm::LlmProvider`], so import logic (label mapping,
+//! idempotency) can be tested against a canned client instead of a live
+//! API.
+
+use super::item::{IdlcItem, IdlcItemStore};
+use crate::error::CoreResult;
+use crate::llm::http::send_with_retry;
+use crate::workspace::Workspace;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// One issue as returned by the GitHub REST API, trimmed to what import
+/// needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GithubIssue {
+    pub number: u64,
+    pub title: String,
+    /// `"open"` or `"closed"`, as GitHub reports it.
+    pub state: String,
+    pub labels: Vec<String>,
+    pub body: String,
+    pub assignee: Option<String>,
+    pub html_url: String,
+}
+
+#[async_trait]
+pub trait GithubIssueClient: Send + Sync {
+    /// Issues for `repo` (`owner/name`), optionally filtered to one label,
+    /// with `state` passed straight through to the API (`open`, `closed`,
+    /// or `all`). Pull requests are excluded — GitHub's issues endpoint
+    /// returns both.
+    async fn list_issues(&self, repo: &str, label: Option<&str>, state: &str) -> CoreResult<Vec<GithubIssue>>;
+}
+
+pub struct HttpGithubClient {
+    client: reqwest::Client,
+    token: String,
+    base_url: String,
+}
+
+impl HttpGithubClient {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self { client: reqwest::Client::new(), token: token.into(), base_url: "https://api.github.com".to_string() }
+    }
+
+    /// Override the endpoint, used by tests to point at a mock server.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+}
+
+#[derive(Deserialize)]
+struct ApiIssue {
+    number: u64,
+    title: String,
+    state: String,
+    body: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    labels: Vec<ApiLabel>,
+    assignee: Option<ApiUser>,
+    /// Present (any value) only on pull requests; used to filter them out.
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct ApiLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ApiUser {
+    login: String,
+}
+
+#[async_trait]
+impl GithubIssueClient for HttpGithubClient {
+    async fn list_issues(&self, repo: &str, label: Option<&str>, state: &str) -> CoreResult<Vec<GithubIssue>> {
+        let url = format!("{}/repos/{repo}/issues", self.base_url);
+        let response = send_with_retry(|| {
+            let mut req = self
+                .client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", self.token))
+                .header("User-Agent", "pmsynapse")
+                .query(&[("state", state), ("per_page", "100")]);
+            if let Some(label) = label {
+                req = req.query(&[("labels", label)]);
+            }
+            req
+        })
+        .await?;
+
+        let issues: Vec<ApiIssue> =
+            response.json().await.map_err(|e| crate::error::CoreError::InvalidInput(e.to_string()))?;
+
+        Ok(issues
+            .into_iter()
+            .filter(|i| i.pull_request.is_none())
+            .map(|i| GithubIssue {
+                number: i.number,
+                title: i.title,
+                state: i.state,
+                labels: i.labels.into_iter().map(|l| l.name).collect(),
+                body: i.body.unwrap_or_default(),
+                assignee: i.assignee.map(|a| a.login),
+                html_url: i.html_url,
+            })
+            .collect())
+    }
+}
+
+/// Per-team label -> IDLC status mapping (`teams/<team>/github-import.yaml`).
+/// The same value is used for both `stage` and `status` on the created
+/// item, matching [`super::IdlcConfig::default`] where the default stage
+/// list and status list are identical.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportMapping {
+    #[serde(default)]
+    pub status_by_label: BTreeMap<String, String>,
+    #[serde(default = "default_open_status")]
+    pub default_open_status: String,
+    #[serde(default = "default_closed_status")]
+    pub default_closed_status: String,
+}
+
+fn default_open_status() -> String {
+    "backlog".to_string()
+}
+
+fn default_closed_status() -> String {
+    "done".to_string()
+}
+
+impl Default for ImportMapping {
+    fn default() -> Self {
+        Self { status_by_label: BTreeMap::new(), default_open_status: default_open_status(), default_closed_status: default_closed_status() }
+    }
+}
+
+impl ImportMapping {
+    fn path(workspace: &Workspace, team: &str) -> PathBuf {
+        workspace.teams_dir().join(team).join("github-import.yaml")
+    }
+
+    pub fn load(workspace: &Workspace, team: &str) -> CoreResult<Self> {
+        let path = Self::path(workspace, team);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_yaml::from_str(&contents).unwrap_or_default())
+    }
+
+    /// The status/stage `issue` maps onto: the first configured label it
+    /// carries wins, falling back to the open/closed default.
+    fn status_for(&self, issue: &GithubIssue) -> String {
+        for label in &issue.labels {
+            if let Some(status) = self.status_by_label.get(label) {
+                return status.clone();
+            }
+        }
+        if issue.state == "closed" { self.default_closed_status.clone() } else { self.default_open_status.clone() }
+    }
+}
+
+pub struct ImportOptions<'a> {
+    pub repo: &'a str,
+    pub label: Option<&'a str>,
+    pub state: &'a str,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub updated: usize,
+}
+
+/// `repo` (`owner/name`) turned into an id-safe fragment for new item ids.
+fn repo_slug(repo: &str) -> String {
+    repo.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect()
+}
+
+/// Fetch `options.repo`'s issues through `client`, map each onto an
+/// [`IdlcItem`], and upsert them into the team's item store. An issue is
+/// matched to an existing item by `source_issue_number` (scoped to this
+/// repo via `source_url`, so two repos importing into the same team can't
+/// collide on issue number) — a match is updated in place, everything
+/// else is created fresh. `options.dry_run` runs the whole mapping and
+/// returns the summary without writing the store.
+pub async fn import_github_issues(
+    workspace: &Workspace,
+    team: &str,
+    client: &dyn GithubIssueClient,
+    options: ImportOptions<'_>,
+) -> CoreResult<ImportSummary> {
+    let mapping = ImportMapping::load(workspace, team)?;
+    let mut store = IdlcItemStore::load(workspace, team)?;
+    let issues = client.list_issues(options.repo, options.label, options.state).await?;
+
+    let mut summary = ImportSummary::default();
+    let repo_prefix = format!("https://github.com/{}/issues/", options.repo);
+
+    for issue in issues {
+        let status = mapping.status_for(&issue);
+        let existing = store.items.iter_mut().find(|i| {
+            i.source_issue_number == Some(issue.number) && i.source_url.as_deref().is_some_and(|u| u.starts_with(&repo_prefix))
+        });
+
+        match existing {
+            Some(item) => {
+                item.title = issue.title.clone();
+                item.stage = status.clone();
+                item.status = status;
+                item.content = issue.body.clone();
+                item.assignee = issue.assignee.clone();
+                item.source_url = Some(issue.html_url.clone());
+                summary.updated += 1;
+            }
+            None => {
+                store.items.push(IdlcItem {
+                    id: format!("gh-{}-{}", repo_slug(options.repo), issue.number),
+                    title: issue.title.clone(),
+                    stage: status.clone(),
+                    status,
+                    links: Vec::new(),
+                    content: issue.body.clone(),
+                    assignee: issue.assignee.clone(),
+                    source_url: Some(issue.html_url.clone()),
+                    source_issue_number: Some(issue.number),
+                });
+                summary.created += 1;
+            }
+        }
+    }
+
+    if !options.dry_run {
+        store.save(workspace, team)?;
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct FakeClient {
+        issues: Vec<GithubIssue>,
+        seen_state: Mutex<Option<String>>,
+    }
+
+    #[async_trait]
+    impl GithubIssueClient for FakeClient {
+        async fn list_issues(&self, _repo: &str, _label: Option<&str>, state: &str) -> CoreResult<Vec<GithubIssue>> {
+            *self.seen_state.lock().unwrap() = Some(state.to_string());
+            Ok(self.issues.clone())
+        }
+    }
+
+    fn sample_issue(number: u64, state: &str) -> GithubIssue {
+        GithubIssue {
+            number,
+            title: format!("Issue {number}"),
+            state: state.to_string(),
+            labels: vec![],
+            body: "body text".to_string(),
+            assignee: Some("octocat".to_string()),
+            html_url: format!("https://github.com/acme/widgets/issues/{number}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn creates_items_and_records_issue_metadata() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        let workspace = Workspace::discover(tmp.path()).unwrap();
+        let client = FakeClient { issues: vec![sample_issue(1, "open")], seen_state: Mutex::new(None) };
+
+        let options = ImportOptions { repo: "acme/widgets", label: None, state: "open", dry_run: false };
+        let summary = import_github_issues(&workspace, "default", &client, options).await.unwrap();
+
+        assert_eq!(summary.created, 1);
+        assert_eq!(summary.updated, 0);
+        let store = IdlcItemStore::load(&workspace, "default").unwrap();
+        let item = store.get("gh-acme-widgets-1").unwrap();
+        assert_eq!(item.status, "backlog");
+        assert_eq!(item.assignee.as_deref(), Some("octocat"));
+        assert_eq!(item.source_issue_number, Some(1));
+    }
+
+    #[tokio::test]
+    async fn reimporting_updates_instead_of_duplicating() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        let workspace = Workspace::discover(tmp.path()).unwrap();
+        let client = FakeClient { issues: vec![sample_issue(1, "open")], seen_state: Mutex::new(None) };
+        let options = || ImportOptions { repo: "acme/widgets", label: None, state: "all", dry_run: false };
+
+        import_github_issues(&workspace, "default", &client, options()).await.unwrap();
+        let client = FakeClient { issues: vec![sample_issue(1, "closed")], seen_state: Mutex::new(None) };
+        let summary = import_github_issues(&workspace, "default", &client, options()).await.unwrap();
+
+        assert_eq!(summary.created, 0);
+        assert_eq!(summary.updated, 1);
+        let store = IdlcItemStore::load(&workspace, "default").unwrap();
+        assert_eq!(store.items.len(), 1);
+        assert_eq!(store.get("gh-acme-widgets-1").unwrap().status, "done");
+    }
+
+    #[tokio::test]
+    async fn dry_run_does_not_persist() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        let workspace = Workspace::discover(tmp.path()).unwrap();
+        let client = FakeClient { issues: vec![sample_issue(1, "open")], seen_state: Mutex::new(None) };
+
+        let options = ImportOptions { repo: "acme/widgets", label: None, state: "open", dry_run: true };
+        let summary = import_github_issues(&workspace, "default", &client, options).await.unwrap();
+
+        assert_eq!(summary.created, 1);
+        let store = IdlcItemStore::load(&workspace, "default").unwrap();
+        assert!(store.items.is_empty());
+    }
+}
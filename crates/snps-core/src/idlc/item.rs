@@ -0,0 +1,169 @@
+//! Individual IDLC items and their storage
+//! (`.pmsynapse/teams/<team>/idlc-items.yaml`), plus the linkage model
+//! connecting an item to a graph node, matter document, or thought.
+//! Bulk board operations (create-from-template, stage transitions with
+//! history) still land with their own backlog item; this is the minimal
+//! storage two backlog items (`snps idlc report`'s eventual per-item
+//! breakdown, and item<->graph linking) both need.
+
+use crate::error::{CoreError, CoreResult};
+use crate::workspace::Workspace;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    Node,
+    Matter,
+    Thought,
+}
+
+impl LinkKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkKind::Node => "node",
+            LinkKind::Matter => "matter",
+            LinkKind::Thought => "thought",
+        }
+    }
+}
+
+impl std::fmt::Display for LinkKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A link from an [`IdlcItem`] to something outside the IDLC subsystem.
+/// `id` is a graph node id, a matter document id, or a thought's path
+/// relative to `workspace.thoughts_dir()`, depending on `kind`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemLink {
+    pub kind: LinkKind,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdlcItem {
+    pub id: String,
+    pub title: String,
+    pub stage: String,
+    pub status: String,
+    #[serde(default)]
+    pub links: Vec<ItemLink>,
+    /// Free-form body text. Populated by `idlc import github` from the
+    /// issue body; empty for items created any other way today.
+    #[serde(default)]
+    pub content: String,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Origin URL, e.g. a GitHub issue's `html_url`.
+    #[serde(default)]
+    pub source_url: Option<String>,
+    /// The GitHub issue number this item was imported from, used to match
+    /// an existing item on re-import instead of creating a duplicate.
+    #[serde(default)]
+    pub source_issue_number: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct IdlcItemStore {
+    pub items: Vec<IdlcItem>,
+}
+
+impl IdlcItemStore {
+    fn path(workspace: &Workspace, team: &str) -> PathBuf {
+        workspace.teams_dir().join(team).join("idlc-items.yaml")
+    }
+
+    pub fn load(workspace: &Workspace, team: &str) -> CoreResult<Self> {
+        let path = Self::path(workspace, team);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_yaml::from_str(&contents).unwrap_or_default())
+    }
+
+    pub fn save(&self, workspace: &Workspace, team: &str) -> CoreResult<()> {
+        let path = Self::path(workspace, team);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&IdlcItem> {
+        self.items.iter().find(|i| i.id == id)
+    }
+
+    fn get_mut(&mut self, id: &str) -> Option<&mut IdlcItem> {
+        self.items.iter_mut().find(|i| i.id == id)
+    }
+
+    /// Add `link` to `item_id`'s link list. Errors if the item doesn't
+    /// exist — links describe an existing item, they don't create one.
+    pub fn add_link(&mut self, item_id: &str, link: ItemLink) -> CoreResult<()> {
+        let item = self.get_mut(item_id).ok_or_else(|| CoreError::NotFound(format!("no IDLC item '{item_id}'")))?;
+        if !item.links.contains(&link) {
+            item.links.push(link);
+        }
+        Ok(())
+    }
+
+    /// Every item linking to `node_id`, for the dangling-link warning a
+    /// node-deleting caller should show. Node deletion itself doesn't
+    /// exist yet (`KnowledgeGraph` has no `delete_node`), so nothing calls
+    /// this today; it's here so that command doesn't also need to
+    /// reinvent the item<->node link scan when it lands.
+    pub fn items_linking_node<'a>(&'a self, node_id: &str) -> Vec<&'a IdlcItem> {
+        self.items.iter().filter(|i| i.links.iter().any(|l| l.kind == LinkKind::Node && l.id == node_id)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store_with_item() -> IdlcItemStore {
+        IdlcItemStore {
+            items: vec![IdlcItem {
+                id: "item-1".into(),
+                title: "Ship it".into(),
+                stage: "backlog".into(),
+                status: "backlog".into(),
+                links: vec![],
+                content: String::new(),
+                assignee: None,
+                source_url: None,
+                source_issue_number: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn add_link_appends_without_duplicating() {
+        let mut store = store_with_item();
+        let link = ItemLink { kind: LinkKind::Node, id: "node-1".into() };
+        store.add_link("item-1", link.clone()).unwrap();
+        store.add_link("item-1", link).unwrap();
+        assert_eq!(store.get("item-1").unwrap().links.len(), 1);
+    }
+
+    #[test]
+    fn add_link_errors_for_unknown_item() {
+        let mut store = store_with_item();
+        let err = store.add_link("missing", ItemLink { kind: LinkKind::Matter, id: "m-1".into() });
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn items_linking_node_finds_only_matching_kind_and_id() {
+        let mut store = store_with_item();
+        store.add_link("item-1", ItemLink { kind: LinkKind::Node, id: "node-1".into() }).unwrap();
+        assert_eq!(store.items_linking_node("node-1").len(), 1);
+        assert_eq!(store.items_linking_node("node-2").len(), 0);
+    }
+}
@@ -0,0 +1,328 @@
+//! IDLC (item development life cycle) stage/status schema, transition
+//! rules, item storage, and importing items from external backlogs
+//! (currently GitHub issues). The schema is shared with `snps-wasm` so
+//! the desktop/web UI can validate a drag-and-drop status change the same
+//! way the CLI would. Bulk board commands driven from the CLI (moving
+//! several items through a stage at once, transition history) still land
+//! with their own dedicated backlog item.
+
+pub mod github_import;
+pub mod item;
+
+pub use github_import::{GithubIssue, GithubIssueClient, HttpGithubClient, ImportMapping, ImportOptions, ImportSummary};
+pub use item::{IdlcItem, IdlcItemStore, ItemLink, LinkKind};
+
+use crate::error::{CoreError, CoreResult};
+use serde::{Deserialize, Serialize};
+
+/// One allowed move from `from` to `to`. Either side may be `"*"` to mean
+/// "any status", and `except` excludes specific destinations from an
+/// otherwise-matching wildcard rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transition {
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub except: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdlcConfig {
+    pub stages: Vec<String>,
+    #[serde(default)]
+    pub statuses: Vec<String>,
+    #[serde(default)]
+    pub transitions: Vec<Transition>,
+}
+
+impl Default for IdlcConfig {
+    fn default() -> Self {
+        let statuses = vec!["backlog".to_string(), "in_progress".to_string(), "review".to_string(), "done".to_string()];
+        IdlcConfig {
+            stages: statuses.clone(),
+            transitions: vec![
+                Transition { from: "backlog".into(), to: "in_progress".into(), except: vec![] },
+                Transition { from: "in_progress".into(), to: "review".into(), except: vec![] },
+                Transition { from: "review".into(), to: "done".into(), except: vec![] },
+                Transition { from: "review".into(), to: "in_progress".into(), except: vec![] },
+                Transition { from: "*".into(), to: "backlog".into(), except: vec!["done".into()] },
+            ],
+            statuses,
+        }
+    }
+}
+
+/// Parse an `IdlcConfig` from either YAML or JSON, trying YAML first
+/// since that's how it's stored on disk (`idlc.yaml`).
+pub fn parse_idlc_config(yaml_or_json: &str) -> CoreResult<IdlcConfig> {
+    if let Ok(config) = serde_yaml::from_str::<IdlcConfig>(yaml_or_json) {
+        return Ok(config);
+    }
+    serde_json::from_str::<IdlcConfig>(yaml_or_json)
+        .map_err(|e| CoreError::InvalidInput(format!("not a valid IDLC config: {e}")))
+}
+
+/// Check that every `transitions` entry (aside from the `"*"` wildcard)
+/// references a stage or status this config actually declares. A config
+/// can validly leave some stage/status unreachable, so this only flags
+/// transitions pointing at names that don't exist anywhere in the config.
+pub fn validate_references(config: &IdlcConfig) -> Vec<String> {
+    let known: std::collections::HashSet<&str> =
+        config.stages.iter().chain(config.statuses.iter()).map(String::as_str).collect();
+    let mut issues = Vec::new();
+    for transition in &config.transitions {
+        if transition.from != "*" && !known.contains(transition.from.as_str()) {
+            issues.push(format!("transition references unknown '{}' in 'from'", transition.from));
+        }
+        if transition.to != "*" && !known.contains(transition.to.as_str()) {
+            issues.push(format!("transition references unknown '{}' in 'to'", transition.to));
+        }
+        for except in &transition.except {
+            if !known.contains(except.as_str()) {
+                issues.push(format!("transition 'except' references unknown '{except}'"));
+            }
+        }
+    }
+    issues
+}
+
+/// What changed between two [`IdlcConfig`]s, e.g. before overwriting a
+/// team's `idlc.yaml` with a newer template's version. Sets are compared
+/// by name only — a stage renamed rather than added/removed shows up as
+/// one of each rather than as a rename, since guessing at a rename from
+/// name similarity alone would be more likely to mislead than to help.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IdlcConfigDiff {
+    pub stages_added: Vec<String>,
+    pub stages_removed: Vec<String>,
+    pub statuses_added: Vec<String>,
+    pub statuses_removed: Vec<String>,
+    pub transitions_added: Vec<Transition>,
+    pub transitions_removed: Vec<Transition>,
+}
+
+impl IdlcConfigDiff {
+    /// A stage or status disappearing is the only change that can strand
+    /// an existing item — a transition being added or removed doesn't
+    /// invalidate any item's current stage/status, only which moves are
+    /// allowed from here on.
+    pub fn is_breaking(&self) -> bool {
+        !self.stages_removed.is_empty() || !self.statuses_removed.is_empty()
+    }
+}
+
+/// Compare `old` against `new`, e.g. the active team's current `idlc.yaml`
+/// against a file about to overwrite it.
+pub fn diff_idlc_configs(old: &IdlcConfig, new: &IdlcConfig) -> IdlcConfigDiff {
+    IdlcConfigDiff {
+        stages_added: added(&old.stages, &new.stages),
+        stages_removed: added(&new.stages, &old.stages),
+        statuses_added: added(&old.statuses, &new.statuses),
+        statuses_removed: added(&new.statuses, &old.statuses),
+        transitions_added: transitions_added(&old.transitions, &new.transitions),
+        transitions_removed: transitions_added(&new.transitions, &old.transitions),
+    }
+}
+
+/// Entries present in `to` but not `from`, in `to`'s order.
+fn added(from: &[String], to: &[String]) -> Vec<String> {
+    to.iter().filter(|name| !from.contains(name)).cloned().collect()
+}
+
+fn transitions_added(from: &[Transition], to: &[Transition]) -> Vec<Transition> {
+    to.iter().filter(|t| !from.contains(t)).cloned().collect()
+}
+
+/// An [`item::IdlcItem`] whose current status won't exist once a breaking
+/// [`IdlcConfigDiff`] is applied, and so needs `--map old=new` (or manual
+/// cleanup) to land somewhere valid.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanedItem {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+}
+
+/// Every item in `store` sitting in one of `diff`'s removed statuses.
+/// Scoped to status rather than stage too, matching how board commands
+/// key an item's position by `status` (`stage` records which phase of
+/// work it's in, not where it sits on the board).
+pub fn orphaned_items(store: &IdlcItemStore, diff: &IdlcConfigDiff) -> Vec<OrphanedItem> {
+    store
+        .items
+        .iter()
+        .filter(|i| diff.statuses_removed.iter().any(|s| s == &i.status))
+        .map(|i| OrphanedItem { id: i.id.clone(), title: i.title.clone(), status: i.status.clone() })
+        .collect()
+}
+
+/// Whether `config` allows moving an item directly from `from` to `to`.
+pub fn can_transition(config: &IdlcConfig, from: &str, to: &str) -> bool {
+    config.transitions.iter().any(|t| {
+        (t.from == from || t.from == "*") && (t.to == to || t.to == "*") && !t.except.iter().any(|e| e == to)
+    })
+}
+
+/// Render `config` as a Mermaid `stateDiagram-v2` block: one state per
+/// stage, one edge per concrete (non-wildcard) transition. Wildcard rules
+/// (`from`/`to` of `"*"`) don't map onto a single diagram edge, so they're
+/// omitted rather than drawn as something misleading.
+pub fn idlc_visualize(config: &IdlcConfig) -> String {
+    let mut out = String::from("stateDiagram-v2\n");
+    for stage in &config.stages {
+        out.push_str(&format!("    {}\n", mermaid_state_id(stage)));
+    }
+    for transition in &config.transitions {
+        if transition.from == "*" || transition.to == "*" {
+            continue;
+        }
+        out.push_str(&format!("    {} --> {}\n", mermaid_state_id(&transition.from), mermaid_state_id(&transition.to)));
+    }
+    out
+}
+
+/// Mermaid state names must be identifier-like; anything else in a stage
+/// name gets folded to `_` so the diagram still parses.
+fn mermaid_state_id(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Case {
+        from: String,
+        to: String,
+        expected: bool,
+    }
+
+    #[test]
+    fn matches_shared_transition_fixture() {
+        let fixture = include_str!("../../../../fixtures/idlc_transitions.json");
+        let cases: Vec<Case> = serde_json::from_str(fixture).unwrap();
+        let config = IdlcConfig::default();
+        for case in cases {
+            assert_eq!(
+                can_transition(&config, &case.from, &case.to),
+                case.expected,
+                "from {} to {}",
+                case.from,
+                case.to
+            );
+        }
+    }
+
+    #[test]
+    fn visualize_includes_stages_and_skips_wildcard_transitions() {
+        let config = IdlcConfig::default();
+        let diagram = idlc_visualize(&config);
+        assert!(diagram.starts_with("stateDiagram-v2\n"));
+        for stage in &config.stages {
+            assert!(diagram.contains(&format!("    {stage}\n")));
+        }
+        assert!(diagram.contains("backlog --> in_progress"));
+        // The `"*" -> backlog except done` rule has no single source state.
+        assert!(!diagram.contains("* -->"));
+    }
+
+    #[test]
+    fn validate_references_accepts_the_default_config() {
+        assert!(validate_references(&IdlcConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn validate_references_flags_unknown_stage_status_and_except_names() {
+        let config = IdlcConfig {
+            stages: vec!["backlog".into(), "done".into()],
+            statuses: vec![],
+            transitions: vec![
+                Transition { from: "backlog".into(), to: "shipped".into(), except: vec![] },
+                Transition { from: "*".into(), to: "backlog".into(), except: vec!["archived".into()] },
+            ],
+        };
+        let issues = validate_references(&config);
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().any(|i| i.contains("shipped") && i.contains("'to'")));
+        assert!(issues.iter().any(|i| i.contains("archived") && i.contains("except")));
+    }
+
+    #[test]
+    fn diff_idlc_configs_reports_added_and_removed_names() {
+        let old = IdlcConfig {
+            stages: vec!["backlog".into(), "in_progress".into()],
+            statuses: vec!["backlog".into(), "archived".into()],
+            transitions: vec![Transition { from: "backlog".into(), to: "in_progress".into(), except: vec![] }],
+        };
+        let new = IdlcConfig {
+            stages: vec!["backlog".into(), "review".into()],
+            statuses: vec!["backlog".into()],
+            transitions: vec![],
+        };
+
+        let diff = diff_idlc_configs(&old, &new);
+        assert_eq!(diff.stages_added, vec!["review".to_string()]);
+        assert_eq!(diff.stages_removed, vec!["in_progress".to_string()]);
+        assert_eq!(diff.statuses_added, Vec::<String>::new());
+        assert_eq!(diff.statuses_removed, vec!["archived".to_string()]);
+        assert_eq!(diff.transitions_removed.len(), 1);
+        assert!(diff.transitions_added.is_empty());
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn diff_idlc_configs_is_not_breaking_when_nothing_is_removed() {
+        let config = IdlcConfig::default();
+        let diff = diff_idlc_configs(&config, &config);
+        assert!(diff.stages_removed.is_empty());
+        assert!(diff.statuses_removed.is_empty());
+        assert!(!diff.is_breaking());
+    }
+
+    #[test]
+    fn orphaned_items_finds_only_items_in_removed_statuses() {
+        let store = IdlcItemStore {
+            items: vec![
+                IdlcItem {
+                    id: "item-1".into(),
+                    title: "Archived thing".into(),
+                    stage: "backlog".into(),
+                    status: "archived".into(),
+                    links: vec![],
+                    content: String::new(),
+                    assignee: None,
+                    source_url: None,
+                    source_issue_number: None,
+                },
+                IdlcItem {
+                    id: "item-2".into(),
+                    title: "Still fine".into(),
+                    stage: "backlog".into(),
+                    status: "backlog".into(),
+                    links: vec![],
+                    content: String::new(),
+                    assignee: None,
+                    source_url: None,
+                    source_issue_number: None,
+                },
+            ],
+        };
+        let diff = IdlcConfigDiff { statuses_removed: vec!["archived".into()], ..Default::default() };
+
+        let orphaned = orphaned_items(&store, &diff);
+        assert_eq!(orphaned.len(), 1);
+        assert_eq!(orphaned[0].id, "item-1");
+    }
+
+    #[test]
+    fn parses_yaml_and_json() {
+        let yaml = "stages: [backlog, done]\nstatuses: [backlog, done]\ntransitions:\n  - from: backlog\n    to: done\n";
+        let config = parse_idlc_config(yaml).unwrap();
+        assert!(can_transition(&config, "backlog", "done"));
+
+        let json = r#"{"stages":["backlog","done"],"statuses":["backlog","done"],"transitions":[{"from":"backlog","to":"done","except":[]}]}"#;
+        let config = parse_idlc_config(json).unwrap();
+        assert!(can_transition(&config, "backlog", "done"));
+    }
+}
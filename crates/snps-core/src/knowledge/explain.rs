@@ -0,0 +1,164 @@
+//! `snps know explain <path>` — which shadow repo wins a knowledge file
+//! and why.
+//!
+//! This predates [`super::scan_repos`] and still reads `relative_path`
+//! directly out of every configured repo's working tree itself rather
+//! than reusing it, since `explain` only ever needs one path and
+//! `scan_repos` always walks a whole repo tree.
+
+use super::{context_precedence, hash_contents, KnowledgeFile};
+use crate::error::{CoreError, CoreResult};
+use crate::repository::Repository;
+use std::path::Path;
+
+/// One repo's copy of the file `snps know explain` was asked about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExplainEntry {
+    pub repo_id: String,
+    pub context: String,
+    pub content_hash: String,
+    pub wins: bool,
+    /// Same content as the winner — an override here would be a no-op.
+    pub identical_to_winner: bool,
+}
+
+/// Find every configured repo that has `relative_path`, hash each copy,
+/// and report which one [`super::resolve_precedence`] would pick under
+/// `order` (see [`crate::config::GlobalConfig::knowledge_precedence`]).
+pub fn explain(repos: &[Repository], relative_path: &Path, order: &[String]) -> CoreResult<Vec<ExplainEntry>> {
+    let mut candidates = Vec::new();
+    for repo in repos {
+        let source_path = repo.path.join(relative_path);
+        let Ok(bytes) = std::fs::read(&source_path) else { continue };
+        candidates.push(KnowledgeFile {
+            repo_id: repo.id.clone(),
+            context: repo.context.clone(),
+            relative_path: relative_path.to_path_buf(),
+            source_path,
+            content_hash: hash_contents(&bytes),
+            modified_unix: 0,
+        });
+    }
+
+    if candidates.is_empty() {
+        return Err(CoreError::NotFound(format!("'{}' is not provided by any configured repository", relative_path.display())));
+    }
+
+    let refs: Vec<&KnowledgeFile> = candidates.iter().collect();
+    let winner = super::resolve_precedence(&refs, order);
+    let winner_id = winner.repo_id.clone();
+    let winner_hash = winner.content_hash.clone();
+
+    let mut entries: Vec<ExplainEntry> = candidates
+        .into_iter()
+        .map(|f| ExplainEntry {
+            wins: f.repo_id == winner_id,
+            identical_to_winner: f.content_hash == winner_hash,
+            repo_id: f.repo_id,
+            context: f.context,
+            content_hash: f.content_hash,
+        })
+        .collect();
+
+    // Winner first, then by descending precedence rank, so the reasoning
+    // reads top to bottom instead of in repo-config order.
+    entries.sort_by_key(|e| std::cmp::Reverse((e.wins, context_precedence(&e.context, order))));
+
+    Ok(entries)
+}
+
+/// A one-line reason for `entry`'s outcome, for `snps know explain`'s text
+/// output. `winner` is `entries[0]` from [`explain`]'s result.
+pub fn reason(entry: &ExplainEntry, winner: &ExplainEntry, order: &[String]) -> String {
+    if entry.wins {
+        return format!("wins: highest-precedence context '{}' (rank {})", entry.context, context_precedence(&entry.context, order));
+    }
+    if entry.identical_to_winner {
+        return "identical content to the winner — no override".to_string();
+    }
+    format!(
+        "overridden: '{}' (rank {}) outranks '{}' (rank {})",
+        winner.context,
+        context_precedence(&winner.context, order),
+        entry.context,
+        context_precedence(&entry.context, order)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{LinkStrategy, Visibility};
+
+    fn repo(id: &str, context: &str, path: &Path) -> Repository {
+        Repository {
+            id: id.to_string(),
+            context: context.to_string(),
+            path: path.to_path_buf(),
+            visibility: Visibility::Shared,
+            excludes: vec![],
+            sync_strategy: LinkStrategy::Copy,
+        }
+    }
+
+    #[test]
+    fn project_wins_over_team_by_default() {
+        let tmp = tempfile::tempdir().unwrap();
+        let team_dir = tmp.path().join("team");
+        let project_dir = tmp.path().join("project");
+        std::fs::create_dir_all(&team_dir).unwrap();
+        std::fs::create_dir_all(&project_dir).unwrap();
+        std::fs::write(team_dir.join("api.md"), "team version").unwrap();
+        std::fs::write(project_dir.join("api.md"), "project version").unwrap();
+
+        let repos = vec![repo("t1", "team", &team_dir), repo("p1", "project", &project_dir)];
+        let entries = explain(&repos, Path::new("api.md"), &[]).unwrap();
+
+        assert!(entries[0].wins);
+        assert_eq!(entries[0].repo_id, "p1");
+        assert!(!entries[1].wins);
+        assert!(!entries[1].identical_to_winner);
+    }
+
+    #[test]
+    fn configured_precedence_overrides_the_default_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let team_dir = tmp.path().join("team");
+        let project_dir = tmp.path().join("project");
+        std::fs::create_dir_all(&team_dir).unwrap();
+        std::fs::create_dir_all(&project_dir).unwrap();
+        std::fs::write(team_dir.join("api.md"), "team version").unwrap();
+        std::fs::write(project_dir.join("api.md"), "project version").unwrap();
+
+        let repos = vec![repo("t1", "team", &team_dir), repo("p1", "project", &project_dir)];
+        let order = vec!["team".to_string(), "project".to_string(), "user".to_string()];
+        let entries = explain(&repos, Path::new("api.md"), &order).unwrap();
+
+        assert!(entries[0].wins);
+        assert_eq!(entries[0].repo_id, "t1");
+    }
+
+    #[test]
+    fn identical_content_is_flagged_as_a_no_op_override() {
+        let tmp = tempfile::tempdir().unwrap();
+        let team_dir = tmp.path().join("team");
+        let project_dir = tmp.path().join("project");
+        std::fs::create_dir_all(&team_dir).unwrap();
+        std::fs::create_dir_all(&project_dir).unwrap();
+        std::fs::write(team_dir.join("api.md"), "same content").unwrap();
+        std::fs::write(project_dir.join("api.md"), "same content").unwrap();
+
+        let repos = vec![repo("t1", "team", &team_dir), repo("p1", "project", &project_dir)];
+        let entries = explain(&repos, Path::new("api.md"), &[]).unwrap();
+
+        let loser = entries.iter().find(|e| !e.wins).unwrap();
+        assert!(loser.identical_to_winner);
+    }
+
+    #[test]
+    fn errors_when_no_repo_provides_the_path() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repos = vec![repo("p1", "project", tmp.path())];
+        assert!(explain(&repos, Path::new("missing.md"), &[]).is_err());
+    }
+}
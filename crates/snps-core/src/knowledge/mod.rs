@@ -0,0 +1,353 @@
+//! Shadow-repository knowledge sync: merges user/team/project knowledge
+//! repos into a working `knowledge/` copy with precedence and
+//! timestamp/hash-based bidirectional sync. See the knowledge system
+//! tutorial for the user-facing model this implements.
+
+pub mod explain;
+pub mod plan;
+pub mod preview;
+pub mod provenance;
+pub mod registration;
+pub mod state;
+
+pub use explain::{explain as explain_precedence, reason as explain_reason, ExplainEntry};
+pub use plan::{build_sync_plan, SyncOperation, SyncPlan};
+pub use preview::SyncPreview;
+pub use provenance::{DriftStatus, ProvenanceEntry, ProvenanceManifest};
+pub use registration::RegistrationManifest;
+pub use state::{FileSyncState, SyncStateStore};
+
+use crate::error::{CoreError, CoreResult};
+use crate::repository::Repository;
+use std::path::PathBuf;
+
+/// A single knowledge file discovered in a shadow repo, prior to merging.
+#[derive(Debug, Clone)]
+pub struct KnowledgeFile {
+    pub repo_id: String,
+    pub context: String,
+    /// Path relative to the repo root, e.g. `research/api-design.md`.
+    pub relative_path: PathBuf,
+    pub source_path: PathBuf,
+    pub content_hash: String,
+    pub modified_unix: u64,
+}
+
+/// Default precedence order, highest-winning-first, when
+/// `knowledge.precedence` isn't configured: project, then team, then user.
+pub const DEFAULT_PRECEDENCE: [&str; 3] = ["project", "team", "user"];
+
+/// Precedence used when the same relative path exists in multiple repos:
+/// higher rank wins. `order` is highest-winning-first (see
+/// [`crate::config::GlobalConfig::knowledge_precedence`]); an empty order
+/// falls back to [`DEFAULT_PRECEDENCE`] so a workspace that never
+/// configures `knowledge.precedence` keeps today's behavior. A context
+/// absent from `order` ranks below everything present in it.
+pub fn context_precedence(context: &str, order: &[String]) -> usize {
+    if order.is_empty() {
+        return DEFAULT_PRECEDENCE
+            .iter()
+            .position(|c| *c == context)
+            .map(|i| DEFAULT_PRECEDENCE.len() - i)
+            .unwrap_or(0);
+    }
+    order.iter().position(|c| c == context).map(|i| order.len() - i).unwrap_or(0)
+}
+
+/// Pick the highest-precedence file among duplicates at the same relative
+/// path, keeping [`context_precedence`] as the tiebreaker.
+pub fn resolve_precedence<'a>(candidates: &'a [&'a KnowledgeFile], order: &[String]) -> &'a KnowledgeFile {
+    candidates
+        .iter()
+        .copied()
+        .max_by_key(|f| context_precedence(&f.context, order))
+        .expect("candidates must be non-empty")
+}
+
+/// Write `source`'s content to `dest` per `strategy`, replacing whatever
+/// is already there. Called per pulled file by [`apply_plan`]; switching a
+/// repo's `sync_strategy` needs no dedicated migration path beyond that,
+/// since re-materializing always replaces `dest` from scratch regardless
+/// of what was there before.
+pub fn materialize(strategy: crate::repository::LinkStrategy, source: &std::path::Path, dest: &std::path::Path) -> CoreResult<()> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if dest.symlink_metadata().is_ok() {
+        std::fs::remove_file(dest)?;
+    }
+    match strategy {
+        crate::repository::LinkStrategy::Copy => {
+            std::fs::copy(source, dest)?;
+        }
+        crate::repository::LinkStrategy::Hardlink => {
+            std::fs::hard_link(source, dest)?;
+        }
+        crate::repository::LinkStrategy::Symlink => {
+            platform_symlink(source, dest)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn platform_symlink(target: &std::path::Path, link: &std::path::Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn platform_symlink(target: &std::path::Path, link: &std::path::Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(target, link)
+}
+
+pub fn hash_contents(bytes: &[u8]) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+pub fn repos_for_context<'a>(repos: &'a [Repository], context: &str) -> Vec<&'a Repository> {
+    repos.iter().filter(|r| r.context == context).collect()
+}
+
+/// Hash every file already present in `knowledge_dir` that `scanned` found
+/// a shadow-repo counterpart for, and note whether the local copy's mtime
+/// is newer than that counterpart's — [`build_sync_plan`] needs both to
+/// classify a path with no recorded sync state yet. Shared by `snps know
+/// sync` and the daemon's `knowledge-pull` scheduled job, so both plan the
+/// same way.
+pub fn local_snapshot(
+    knowledge_dir: &std::path::Path,
+    scanned: &[KnowledgeFile],
+) -> (std::collections::HashMap<String, String>, std::collections::HashMap<String, bool>) {
+    let mut hashes = std::collections::HashMap::new();
+    let mut newer = std::collections::HashMap::new();
+
+    for file in scanned {
+        let rel = file.relative_path.to_string_lossy().into_owned();
+        let local_path = knowledge_dir.join(&file.relative_path);
+        let Ok(bytes) = std::fs::read(&local_path) else { continue };
+        hashes.insert(rel.clone(), hash_contents(&bytes));
+
+        let local_modified_unix = std::fs::metadata(&local_path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        newer.insert(rel, local_modified_unix > file.modified_unix);
+    }
+
+    (hashes, newer)
+}
+
+/// Walk every repo in `repos`' live working tree, hash each file's
+/// current content, and resolve precedence across repos so the result has
+/// at most one [`KnowledgeFile`] per relative path. Feeds [`local_snapshot`]
+/// and [`build_sync_plan`] for both `snps know sync` and the daemon's
+/// `knowledge-pull` scheduled job. `.git` is always skipped in addition to
+/// each repo's own `excludes`, since every shadow repo configured so far
+/// is a git checkout.
+pub fn scan_repos(repos: &[&Repository], order: &[String]) -> Vec<KnowledgeFile> {
+    let mut by_path: std::collections::HashMap<String, Vec<KnowledgeFile>> = std::collections::HashMap::new();
+
+    for repo in repos {
+        let mut excludes = repo.excludes.clone();
+        excludes.push("**/.git/**".to_string());
+
+        for path in crate::fswalk::walk_all(&repo.path, &repo.path, &excludes) {
+            let Ok(bytes) = std::fs::read(&path) else { continue };
+            let relative_path = path.strip_prefix(&repo.path).unwrap_or(&path).to_path_buf();
+            let rel_key = relative_path.to_string_lossy().replace('\\', "/");
+            let modified_unix = std::fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+
+            by_path.entry(rel_key).or_default().push(KnowledgeFile {
+                repo_id: repo.id.clone(),
+                context: repo.context.clone(),
+                relative_path,
+                source_path: path,
+                content_hash: hash_contents(&bytes),
+                modified_unix,
+            });
+        }
+    }
+
+    by_path
+        .into_values()
+        .map(|candidates| {
+            let refs: Vec<&KnowledgeFile> = candidates.iter().collect();
+            resolve_precedence(&refs, order).clone()
+        })
+        .collect()
+}
+
+/// Counts of what [`apply_plan`] actually did, for `snps know sync
+/// --apply`'s summary line.
+#[derive(Debug, Default, Clone, serde::Serialize)]
+pub struct ApplySummary {
+    pub pulled: usize,
+    pub pushed: usize,
+    pub skipped_conflicts: usize,
+    /// Relative paths where `--force` clobbered the side it didn't pick to
+    /// resolve a real conflict, so the caller can print what got
+    /// overwritten instead of applying silently.
+    pub force_overwritten: Vec<String>,
+}
+
+/// Apply every non-conflicting operation in `plan`: materialize a pull
+/// into `knowledge_dir` from its winning repo, or write a push's local
+/// content back to the file it came from. `strategy_for` resolves a repo
+/// id to that repo's [`crate::repository::LinkStrategy`], same as
+/// [`SyncPlan::preview`]. Conflicts are left untouched — resolving one is
+/// `snps know resolve`'s job, not sync's.
+pub fn apply_plan(
+    plan: &SyncPlan,
+    knowledge_dir: &std::path::Path,
+    state: &mut state::SyncStateStore,
+    provenance: &mut ProvenanceManifest,
+    registration: &mut RegistrationManifest,
+    synced_at_unix: u64,
+    strategy_for: impl Fn(&str) -> crate::repository::LinkStrategy,
+) -> CoreResult<ApplySummary> {
+    let mut summary = ApplySummary::default();
+
+    for file in &plan.files {
+        match file.operation {
+            SyncOperation::Skip => {}
+            SyncOperation::Conflict => summary.skipped_conflicts += 1,
+            SyncOperation::Pull => {
+                let Some(source) = &file.source else { continue };
+                let dest = knowledge_dir.join(&file.relative_path);
+                materialize(strategy_for(&source.repo_id), &source.source_path, &dest)?;
+                if file.force_resolved {
+                    summary.force_overwritten.push(file.relative_path.clone());
+                }
+                state.set(
+                    &file.relative_path,
+                    FileSyncState {
+                        source_hash: source.content_hash.clone(),
+                        destination_hash: source.content_hash.clone(),
+                        last_synced_hash: source.content_hash.clone(),
+                    },
+                );
+                provenance.record(
+                    &file.relative_path,
+                    ProvenanceEntry { repo_id: source.repo_id.clone(), context: source.context.clone(), source_hash: source.content_hash.clone(), synced_at_unix },
+                );
+                registration.register(&file.relative_path, &source.repo_id);
+                summary.pulled += 1;
+            }
+            SyncOperation::Push => {
+                let Some(source) = &file.source else { continue };
+                if file.force_resolved {
+                    summary.force_overwritten.push(file.relative_path.clone());
+                }
+                let local_path = knowledge_dir.join(&file.relative_path);
+                let bytes = std::fs::read(&local_path)?;
+                if let Some(parent) = source.source_path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&source.source_path, &bytes)?;
+                let hash = hash_contents(&bytes);
+                state.set(
+                    &file.relative_path,
+                    FileSyncState { source_hash: hash.clone(), destination_hash: hash.clone(), last_synced_hash: hash.clone() },
+                );
+                provenance.record(
+                    &file.relative_path,
+                    ProvenanceEntry { repo_id: source.repo_id.clone(), context: source.context.clone(), source_hash: hash, synced_at_unix },
+                );
+                registration.register(&file.relative_path, &source.repo_id);
+                summary.pushed += 1;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Which repositories a sync operation (plan build or push) should touch.
+/// `None` in either field means "no restriction" (the historical default
+/// of syncing everything).
+#[derive(Debug, Default, Clone)]
+pub struct SyncScope {
+    pub context: Option<String>,
+    pub repo_id: Option<String>,
+}
+
+impl SyncScope {
+    /// Resolve the scope against the configured repositories, erroring if
+    /// an explicit `--repo` id doesn't exist so a typo doesn't silently
+    /// sync everything.
+    pub fn select<'a>(&self, repos: &'a [Repository]) -> CoreResult<Vec<&'a Repository>> {
+        if let Some(id) = &self.repo_id {
+            let repo = repos
+                .iter()
+                .find(|r| &r.id == id)
+                .ok_or_else(|| CoreError::NotFound(format!("no repository with id '{id}'")))?;
+            return Ok(vec![repo]);
+        }
+        Ok(match &self.context {
+            Some(context) => repos_for_context(repos, context),
+            None => repos.iter().collect(),
+        })
+    }
+
+    /// Whether a push targeting `context` should run under this scope
+    /// (e.g. `--context user` must not push, since push only ever targets
+    /// the project shadow repo).
+    pub fn allows_push_to(&self, context: &str) -> bool {
+        match &self.context {
+            Some(scoped) => scoped == context,
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(context: &str) -> KnowledgeFile {
+        KnowledgeFile {
+            repo_id: format!("{context}-repo"),
+            context: context.to_string(),
+            relative_path: "api.md".into(),
+            source_path: "api.md".into(),
+            content_hash: "h".to_string(),
+            modified_unix: 0,
+        }
+    }
+
+    #[test]
+    fn default_precedence_is_project_then_team_then_user() {
+        assert!(context_precedence("project", &[]) > context_precedence("team", &[]));
+        assert!(context_precedence("team", &[]) > context_precedence("user", &[]));
+        assert_eq!(context_precedence("nonsense", &[]), 0);
+    }
+
+    #[test]
+    fn configured_precedence_overrides_the_default() {
+        let order = vec!["user".to_string(), "team".to_string(), "project".to_string()];
+        assert!(context_precedence("user", &order) > context_precedence("team", &order));
+        assert!(context_precedence("team", &order) > context_precedence("project", &order));
+    }
+
+    #[test]
+    fn resolve_precedence_picks_the_highest_ranked_candidate() {
+        let user = file("user");
+        let project = file("project");
+        let candidates = [&user, &project];
+        assert_eq!(resolve_precedence(&candidates, &[]).context, "project");
+
+        let order = vec!["user".to_string()];
+        assert_eq!(resolve_precedence(&candidates, &order).context, "user");
+    }
+}
@@ -0,0 +1,214 @@
+//! Build a sync plan between shadow repos and the local `knowledge/`
+//! working copy: for each file, decide whether to pull, push, skip, or
+//! flag a conflict, using recorded sync state for three-way awareness.
+
+use super::preview::{ExcludeChange, PushKind, PushPreview, SyncPreview};
+use super::state::{FileSyncState, SyncStateStore};
+use super::KnowledgeFile;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncOperation {
+    /// Neither side changed since the last sync (by content hash).
+    Skip,
+    /// Shadow file is newer and differs from the local copy.
+    Pull,
+    /// Local file is newer and differs from the shadow copy.
+    Push,
+    /// Both sides changed since the last recorded sync: needs a human.
+    Conflict,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlannedFile {
+    pub relative_path: String,
+    pub operation: SyncOperation,
+    pub source: Option<KnowledgeFile>,
+    pub local_hash: Option<String>,
+    /// Whether `operation` is a `Pull`/`Push` that `--force` picked between
+    /// two sides that both changed since the last sync, rather than an
+    /// ordinary one-sided update — `--force` still clobbers whichever side
+    /// it didn't pick, and callers need to know which files that happened
+    /// to so they can tell the user what got overwritten.
+    pub force_resolved: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct SyncPlan {
+    pub files: Vec<PlannedFile>,
+}
+
+impl SyncPlan {
+    pub fn conflicts(&self) -> impl Iterator<Item = &PlannedFile> {
+        self.files.iter().filter(|f| f.operation == SyncOperation::Conflict)
+    }
+
+    pub fn count(&self, operation: SyncOperation) -> usize {
+        self.files.iter().filter(|f| f.operation == operation).count()
+    }
+
+    /// Build the unified dry-run preview: pull count, push files annotated
+    /// new/update/strategy, conflicts, and the `.git/info/exclude`
+    /// patterns that would be added for every relative path this plan
+    /// touches. `strategy_for` resolves a relative path's owning repo id
+    /// to that repo's `sync_strategy` (see [`crate::repository::LinkStrategy`]);
+    /// a symlinked path never gets a push entry, since the working copy
+    /// already is the shadow repo's file. A force-resolved pull is called
+    /// out in `pulls_forced` and a force-resolved push is flagged on its
+    /// `PushPreview`, since `--force` clobbers whichever side it didn't
+    /// pick and the caller should be able to say so.
+    pub fn preview(&self, strategy_for: impl Fn(&str) -> crate::repository::LinkStrategy) -> SyncPreview {
+        let mut preview = SyncPreview::default();
+
+        for file in &self.files {
+            let repo_id = file.source.as_ref().map(|s| s.repo_id.as_str()).unwrap_or_default();
+            match file.operation {
+                SyncOperation::Pull => {
+                    preview.pull_count += 1;
+                    if file.force_resolved {
+                        preview.pulls_forced.push(file.relative_path.clone());
+                    }
+                }
+                SyncOperation::Push => {
+                    let strategy = strategy_for(repo_id);
+                    if strategy == crate::repository::LinkStrategy::Symlink {
+                        preview.push_skipped_symlinked.push(file.relative_path.clone());
+                    } else {
+                        preview.push.push(PushPreview {
+                            relative_path: file.relative_path.clone(),
+                            kind: if file.local_hash.is_some() { PushKind::Update } else { PushKind::New },
+                            strategy,
+                            forced: file.force_resolved,
+                        });
+                    }
+                }
+                SyncOperation::Conflict => preview.conflicts.push(file.relative_path.clone()),
+                SyncOperation::Skip => {}
+            }
+            if !matches!(file.operation, SyncOperation::Skip) {
+                preview.exclude_changes.push(ExcludeChange {
+                    pattern: file.relative_path.clone(),
+                    added: true,
+                });
+            }
+        }
+
+        preview
+    }
+}
+
+/// Decide the operation for a single file given its current source hash,
+/// current local hash (`None` if the local copy doesn't exist), and the
+/// last recorded sync state for that path. The returned `bool` is `true`
+/// only when `force` broke a real two-sided conflict — an ordinary
+/// one-sided `Pull`/`Push` is never "force-resolved" even with `--force`
+/// set, since there was nothing on the other side to overwrite.
+fn classify(
+    source_hash: &str,
+    local_hash: Option<&str>,
+    last_state: Option<&FileSyncState>,
+    force: bool,
+    source_newer: bool,
+) -> (SyncOperation, bool) {
+    let Some(local_hash) = local_hash else {
+        return (SyncOperation::Pull, false);
+    };
+
+    if source_hash == local_hash {
+        return (SyncOperation::Skip, false);
+    }
+
+    let Some(last_state) = last_state else {
+        // No sync history: fall back to timestamp direction, same as
+        // before three-way tracking existed.
+        return (if source_newer { SyncOperation::Pull } else { SyncOperation::Push }, false);
+    };
+
+    let source_changed = source_hash != last_state.last_synced_hash;
+    let local_changed = local_hash != last_state.last_synced_hash;
+
+    match (source_changed, local_changed) {
+        (true, true) if !force => (SyncOperation::Conflict, false),
+        (true, true) => {
+            // --force: newer timestamp wins, clobbering the other side.
+            (if source_newer { SyncOperation::Pull } else { SyncOperation::Push }, true)
+        }
+        (true, false) => (SyncOperation::Pull, false),
+        (false, true) => (SyncOperation::Push, false),
+        (false, false) => (SyncOperation::Skip, false),
+    }
+}
+
+/// Build the sync plan for `files` (already precedence-resolved to one
+/// entry per relative path) against the local `knowledge/` copy.
+pub fn build_sync_plan(
+    files: &[KnowledgeFile],
+    local_hashes: &std::collections::HashMap<String, String>,
+    local_newer: &std::collections::HashMap<String, bool>,
+    state: &SyncStateStore,
+    force: bool,
+) -> SyncPlan {
+    let mut plan = SyncPlan::default();
+
+    for file in files {
+        let rel = file.relative_path.to_string_lossy().into_owned();
+        let local_hash = local_hashes.get(&rel).map(String::as_str);
+        let last_state = state.get(&rel);
+        let source_newer = !local_newer.get(&rel).copied().unwrap_or(false);
+
+        let (operation, force_resolved) = classify(&file.content_hash, local_hash, last_state, force, source_newer);
+
+        plan.files.push(PlannedFile {
+            relative_path: rel,
+            operation,
+            source: Some(file.clone()),
+            local_hash: local_hash.map(str::to_string),
+            force_resolved,
+        });
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_of(source: &str, dest: &str, synced: &str) -> FileSyncState {
+        FileSyncState {
+            source_hash: source.to_string(),
+            destination_hash: dest.to_string(),
+            last_synced_hash: synced.to_string(),
+        }
+    }
+
+    #[test]
+    fn both_sides_changed_since_last_sync_is_a_conflict() {
+        let last = state_of("a", "a", "a");
+        let (op, forced) = classify("b", Some("c"), Some(&last), false, true);
+        assert_eq!(op, SyncOperation::Conflict);
+        assert!(!forced);
+    }
+
+    #[test]
+    fn force_resolves_conflict_by_newer_timestamp_and_is_flagged_forced() {
+        let last = state_of("a", "a", "a");
+        let (op, forced) = classify("b", Some("c"), Some(&last), true, true);
+        assert_eq!(op, SyncOperation::Pull);
+        assert!(forced);
+    }
+
+    #[test]
+    fn unchanged_hash_skips_even_with_different_timestamps() {
+        let (op, forced) = classify("same", Some("same"), None, false, true);
+        assert_eq!(op, SyncOperation::Skip);
+        assert!(!forced);
+    }
+
+    #[test]
+    fn ordinary_one_sided_pull_is_not_flagged_forced_even_with_force_set() {
+        let last = state_of("a", "b", "a");
+        let (op, forced) = classify("b", Some("b"), Some(&last), true, true);
+        assert_eq!(op, SyncOperation::Pull);
+        assert!(!forced);
+    }
+}
@@ -0,0 +1,92 @@
+//! A machine- and human-readable preview of what a sync would do, without
+//! applying it: `.git/info/exclude` changes, annotated push operations,
+//! and conflicts that would be skipped.
+
+use crate::repository::LinkStrategy;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub enum PushKind {
+    New,
+    Update,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExcludeChange {
+    pub pattern: String,
+    pub added: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PushPreview {
+    pub relative_path: String,
+    pub kind: PushKind,
+    pub strategy: LinkStrategy,
+    /// `--force` picked the local side of a real conflict, discarding the
+    /// shadow repo's changes.
+    pub forced: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncPreview {
+    pub pull_count: usize,
+    /// Relative paths pulled by `--force` overriding a real conflict —
+    /// the local edits at those paths are about to be discarded.
+    pub pulls_forced: Vec<String>,
+    pub exclude_changes: Vec<ExcludeChange>,
+    pub push: Vec<PushPreview>,
+    /// Would-be pushes skipped because the owning repo's `sync_strategy`
+    /// is `symlink` — the working copy is already the live file, so
+    /// there's nothing to push back.
+    pub push_skipped_symlinked: Vec<String>,
+    pub conflicts: Vec<String>,
+}
+
+impl SyncPreview {
+    /// Render the unified human-readable preview block ending in a summary
+    /// line, used for the default (non-`--format json`) dry-run output.
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        out.push_str("git exclude:\n");
+        for change in &self.exclude_changes {
+            out.push_str(&format!(
+                "  {} {}\n",
+                if change.added { "+" } else { "-" },
+                change.pattern
+            ));
+        }
+        out.push_str("push:\n");
+        for push in &self.push {
+            let kind = match push.kind {
+                PushKind::New => "new",
+                PushKind::Update => "update",
+            };
+            let forced = if push.forced { ", FORCE-OVERWROTE shadow repo changes" } else { "" };
+            out.push_str(&format!("  {} ({kind}, {}{forced})\n", push.relative_path, push.strategy));
+        }
+        if !self.push_skipped_symlinked.is_empty() {
+            out.push_str("push skipped (symlinked, already live):\n");
+            for path in &self.push_skipped_symlinked {
+                out.push_str(&format!("  {path}\n"));
+            }
+        }
+        out.push_str("conflicts (skipped):\n");
+        for conflict in &self.conflicts {
+            out.push_str(&format!("  {conflict}\n"));
+        }
+        if !self.pulls_forced.is_empty() {
+            out.push_str("force-overwrote local changes:\n");
+            for path in &self.pulls_forced {
+                out.push_str(&format!("  {path}\n"));
+            }
+        }
+        out.push_str(&format!(
+            "summary: {} pull, {} push, {} conflict, {} exclude change(s)\n",
+            self.pull_count,
+            self.push.len(),
+            self.conflicts.len(),
+            self.exclude_changes.len()
+        ));
+        out
+    }
+}
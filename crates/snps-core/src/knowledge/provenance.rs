@@ -0,0 +1,88 @@
+//! Provenance manifest for merged knowledge files: which shadow repo a
+//! working-copy file came from, and whether it has since drifted.
+
+use crate::error::CoreResult;
+use crate::workspace::Workspace;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvenanceEntry {
+    pub repo_id: String,
+    pub context: String,
+    pub source_hash: String,
+    pub synced_at_unix: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProvenanceManifest {
+    pub entries: HashMap<String, ProvenanceEntry>,
+}
+
+/// The three states `knowledge_status` reports per file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftStatus {
+    Clean,
+    Modified,
+    Unknown,
+}
+
+impl ProvenanceManifest {
+    fn path(workspace: &Workspace) -> PathBuf {
+        workspace.pmsynapse_dir().join("knowledge-manifest.json")
+    }
+
+    pub fn load(workspace: &Workspace) -> CoreResult<Self> {
+        let path = Self::path(workspace);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents).unwrap_or_default())
+    }
+
+    pub fn save(&self, workspace: &Workspace) -> CoreResult<()> {
+        let path = Self::path(workspace);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, relative_path: &str, entry: ProvenanceEntry) {
+        self.entries.insert(relative_path.to_string(), entry);
+    }
+
+    pub fn get(&self, relative_path: &str) -> Option<&ProvenanceEntry> {
+        self.entries.get(relative_path)
+    }
+
+    /// Compare the manifest's recorded hash for `relative_path` against
+    /// its current on-disk hash to determine drift.
+    pub fn drift_status(&self, relative_path: &str, current_hash: Option<&str>) -> DriftStatus {
+        match (self.entries.get(relative_path), current_hash) {
+            (Some(entry), Some(hash)) if entry.source_hash == hash => DriftStatus::Clean,
+            (Some(_), Some(_)) => DriftStatus::Modified,
+            _ => DriftStatus::Unknown,
+        }
+    }
+
+    /// Counts of clean/modified/unknown files across `paths`, for
+    /// `knowledge_status`.
+    pub fn status_counts(
+        &self,
+        paths: &HashMap<String, String>,
+    ) -> (usize, usize, usize) {
+        let (mut clean, mut modified, mut unknown) = (0, 0, 0);
+        for (path, hash) in paths {
+            match self.drift_status(path, Some(hash)) {
+                DriftStatus::Clean => clean += 1,
+                DriftStatus::Modified => modified += 1,
+                DriftStatus::Unknown => unknown += 1,
+            }
+        }
+        (clean, modified, unknown)
+    }
+}
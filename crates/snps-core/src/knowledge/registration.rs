@@ -0,0 +1,128 @@
+//! Explicit path -> repo id registrations (`.pmsynapse/knowledge-files.yaml`),
+//! so `file remove` and other consumers don't have to infer which shadow
+//! repo a working-copy file came from by scanning every configured repo's
+//! contents — a lookup that's O(repos × files) and can't tell "registered
+//! from this project" apart from "a file that happens to exist in the
+//! shadow repo because some other project put it there".
+
+use crate::error::CoreResult;
+use crate::knowledge::state::SyncStateStore;
+use crate::repository::Repository;
+use crate::workspace::Workspace;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RegistrationManifest {
+    /// Relative path (within `knowledge/`) -> the repo id it was
+    /// registered from.
+    pub registrations: BTreeMap<String, String>,
+}
+
+impl RegistrationManifest {
+    fn path(workspace: &Workspace) -> PathBuf {
+        workspace.pmsynapse_dir().join("knowledge-files.yaml")
+    }
+
+    pub fn load(workspace: &Workspace) -> CoreResult<Self> {
+        let path = Self::path(workspace);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_yaml::from_str(&contents).unwrap_or_default())
+    }
+
+    pub fn save(&self, workspace: &Workspace) -> CoreResult<()> {
+        let path = Self::path(workspace);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn register(&mut self, relative_path: &str, repo_id: &str) {
+        self.registrations.insert(relative_path.to_string(), repo_id.to_string());
+    }
+
+    pub fn unregister(&mut self, relative_path: &str) -> Option<String> {
+        self.registrations.remove(relative_path)
+    }
+
+    pub fn repo_for(&self, relative_path: &str) -> Option<&str> {
+        self.registrations.get(relative_path).map(String::as_str)
+    }
+
+    /// Load the manifest, migrating a project that has never had one by
+    /// inferring registrations from recorded sync state: a tracked path's
+    /// repo id is whichever configured repo it's prefixed with, the same
+    /// convention `snps know list` already uses to count tracked files per
+    /// repo. The inferred manifest is written back immediately so this only
+    /// runs once.
+    pub fn load_or_migrate(workspace: &Workspace, state: &SyncStateStore, repos: &[Repository]) -> CoreResult<Self> {
+        let path = Self::path(workspace);
+        if path.exists() {
+            return Self::load(workspace);
+        }
+        let mut manifest = Self::default();
+        for tracked_path in state.files.keys() {
+            if let Some(repo) = repos.iter().find(|r| tracked_path.starts_with(&r.id)) {
+                manifest.register(tracked_path, &repo.id);
+            }
+        }
+        manifest.save(workspace)?;
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::state::FileSyncState;
+    use crate::repository::visibility::Visibility;
+
+    fn repo(id: &str) -> Repository {
+        Repository {
+            id: id.into(),
+            context: "project".into(),
+            path: PathBuf::new(),
+            visibility: Visibility::Shared,
+            excludes: vec![],
+            sync_strategy: Default::default(),
+        }
+    }
+
+    fn tracked(hash: &str) -> FileSyncState {
+        FileSyncState {
+            source_hash: hash.into(),
+            destination_hash: hash.into(),
+            last_synced_hash: hash.into(),
+        }
+    }
+
+    #[test]
+    fn register_and_unregister_round_trip() {
+        let mut manifest = RegistrationManifest::default();
+        manifest.register("docs/a.md", "team-docs");
+        assert_eq!(manifest.repo_for("docs/a.md"), Some("team-docs"));
+        assert_eq!(manifest.unregister("docs/a.md"), Some("team-docs".to_string()));
+        assert_eq!(manifest.repo_for("docs/a.md"), None);
+    }
+
+    #[test]
+    fn migration_infers_repo_from_tracked_path_prefix_and_persists() {
+        let tmp = tempfile::tempdir().unwrap();
+        let workspace = Workspace { root: tmp.path().to_path_buf() };
+        let mut state = SyncStateStore::default();
+        state.set("team-docs/research/api.md", tracked("h1"));
+        let repos = vec![repo("team-docs")];
+
+        let manifest = RegistrationManifest::load_or_migrate(&workspace, &state, &repos).unwrap();
+        assert_eq!(manifest.repo_for("team-docs/research/api.md"), Some("team-docs"));
+
+        let reloaded = RegistrationManifest::load(&workspace).unwrap();
+        assert_eq!(reloaded.repo_for("team-docs/research/api.md"), Some("team-docs"));
+    }
+}
@@ -0,0 +1,53 @@
+//! Per-file sync state (`.pmsynapse/sync-state.json`), recording the last
+//! hash seen on each side so `build_sync_plan` can tell "unmodified since
+//! last sync" apart from "modified on both sides" (a real conflict).
+
+use crate::error::CoreResult;
+use crate::workspace::Workspace;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSyncState {
+    pub source_hash: String,
+    pub destination_hash: String,
+    pub last_synced_hash: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SyncStateStore {
+    pub files: HashMap<String, FileSyncState>,
+}
+
+impl SyncStateStore {
+    fn path(workspace: &Workspace) -> PathBuf {
+        workspace.pmsynapse_dir().join("sync-state.json")
+    }
+
+    pub fn load(workspace: &Workspace) -> CoreResult<Self> {
+        let path = Self::path(workspace);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents).unwrap_or_default())
+    }
+
+    pub fn save(&self, workspace: &Workspace) -> CoreResult<()> {
+        let path = Self::path(workspace);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, relative_path: &str) -> Option<&FileSyncState> {
+        self.files.get(relative_path)
+    }
+
+    pub fn set(&mut self, relative_path: &str, state: FileSyncState) {
+        self.files.insert(relative_path.to_string(), state);
+    }
+}
@@ -0,0 +1,39 @@
+//! Core library for PMSynapse: knowledge graph, matter documents, thoughts,
+//! knowledge sync, teams, and IDLC workflow state.
+//!
+//! This crate has no CLI or presentation logic; `engine/snps-cli`, the
+//! daemon, and the desktop app all depend on it so behavior stays in sync
+//! across front ends.
+
+pub mod claude;
+pub mod config;
+pub mod dedup;
+pub mod doctor;
+pub mod editor;
+pub mod embeddings;
+pub mod error;
+pub mod fswalk;
+pub mod git;
+pub mod global_state;
+pub mod graph;
+pub mod hooks;
+pub mod idlc;
+pub mod knowledge;
+pub mod llm;
+pub mod matter;
+pub mod proposals;
+pub mod publish;
+pub mod repository;
+pub mod scheduler;
+pub mod search;
+pub mod search_index;
+pub mod subproject;
+pub mod sync_log;
+pub mod team;
+pub mod templates;
+pub mod thoughts;
+pub mod time;
+pub mod workspace;
+
+pub use error::{CoreError, CoreResult};
+pub use workspace::Workspace;
@@ -0,0 +1,115 @@
+//! Anthropic Messages API backend.
+
+use super::http::send_with_retry;
+use super::{Completion, CompletionChunk, CompletionRequest, LlmProvider};
+use crate::error::{CoreError, CoreResult};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+const DEFAULT_BASE_URL: &str = "https://api.anthropic.com/v1/messages";
+const API_VERSION: &str = "2023-06-01";
+
+pub struct AnthropicProvider {
+    client: reqwest::Client,
+    api_key: String,
+    base_url: String,
+}
+
+impl AnthropicProvider {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self { client: reqwest::Client::new(), api_key: api_key.into(), base_url: DEFAULT_BASE_URL.to_string() }
+    }
+
+    /// Override the endpoint, used by tests to point at a mock server.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+}
+
+#[derive(Deserialize)]
+struct MessagesResponse {
+    content: Vec<ContentBlock>,
+    model: String,
+}
+
+#[derive(Deserialize)]
+struct ContentBlock {
+    text: String,
+}
+
+#[async_trait]
+impl LlmProvider for AnthropicProvider {
+    fn name(&self) -> &'static str {
+        "anthropic"
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> CoreResult<Completion> {
+        let body = serde_json::json!({
+            "model": request.model,
+            "max_tokens": request.max_tokens,
+            "temperature": request.temperature,
+            "messages": [{"role": "user", "content": request.prompt}],
+        });
+
+        let response = send_with_retry(|| {
+            self.client
+                .post(&self.base_url)
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", API_VERSION)
+                .json(&body)
+        })
+        .await?;
+
+        let parsed: MessagesResponse = response.json().await.map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let text = parsed.content.into_iter().map(|b| b.text).collect::<Vec<_>>().join("");
+        Ok(Completion { text, model: parsed.model })
+    }
+
+    async fn complete_streaming(
+        &self,
+        request: &CompletionRequest,
+        on_chunk: &mut (dyn FnMut(CompletionChunk) + Send),
+    ) -> CoreResult<Completion> {
+        // No SSE parsing yet; report the whole response as one chunk.
+        let completion = self.complete(request).await?;
+        on_chunk(CompletionChunk { text: completion.text.clone(), done: true });
+        Ok(completion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn parses_a_successful_completion() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"content":[{"text":"hi"}],"model":"claude-3-5-sonnet-20241022"}"#)
+            .create_async()
+            .await;
+
+        let provider = AnthropicProvider::new("test-key").with_base_url(server.url());
+        let request = CompletionRequest::new("hello", "claude-3-5-sonnet-20241022");
+        let completion = provider.complete(&request).await.unwrap();
+
+        assert_eq!(completion.text, "hi");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn surfaces_error_body_on_failure() {
+        let mut server = mockito::Server::new_async().await;
+        server.mock("POST", "/").with_status(400).with_body("bad request").create_async().await;
+
+        let provider = AnthropicProvider::new("test-key").with_base_url(server.url());
+        let request = CompletionRequest::new("hello", "claude-3-5-sonnet-20241022");
+        let err = provider.complete(&request).await.unwrap_err();
+
+        assert!(err.to_string().contains("bad request"));
+    }
+}
@@ -0,0 +1,68 @@
+//! Shared retry logic for LLM HTTP calls: exponential backoff on 429 and
+//! 5xx responses, since every provider needs the same policy.
+
+use crate::error::{CoreError, CoreResult};
+use std::time::Duration;
+
+const MAX_ATTEMPTS: u32 = 4;
+const BASE_DELAY_MS: u64 = 250;
+
+/// Issue an HTTP request built fresh by `build` on each attempt (bodies
+/// can't be replayed after a failed send with some clients, so we rebuild
+/// rather than clone), retrying with exponential backoff on 429/5xx.
+pub async fn send_with_retry<F>(build: F) -> CoreResult<reqwest::Response>
+where
+    F: Fn() -> reqwest::RequestBuilder,
+{
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let result = build().send().await;
+
+        match result {
+            Ok(response) if response.status().is_success() => return Ok(response),
+            Ok(response) if is_retryable(response.status()) && attempt < MAX_ATTEMPTS => {
+                tokio::time::sleep(backoff(attempt)).await;
+                continue;
+            }
+            Ok(response) => {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(CoreError::InvalidInput(format!("llm request failed ({status}): {body}")));
+            }
+            Err(e) if attempt < MAX_ATTEMPTS && e.is_timeout() => {
+                tokio::time::sleep(backoff(attempt)).await;
+                continue;
+            }
+            Err(e) => return Err(CoreError::InvalidInput(format!("llm request error: {e}"))),
+        }
+    }
+}
+
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.as_u16() == 429 || status.is_server_error()
+}
+
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_millis(BASE_DELAY_MS * 2u64.pow(attempt - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_exponentially() {
+        assert_eq!(backoff(1), Duration::from_millis(250));
+        assert_eq!(backoff(2), Duration::from_millis(500));
+        assert_eq!(backoff(3), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn only_429_and_5xx_are_retryable() {
+        assert!(is_retryable(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(reqwest::StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable(reqwest::StatusCode::BAD_REQUEST));
+        assert!(!is_retryable(reqwest::StatusCode::OK));
+    }
+}
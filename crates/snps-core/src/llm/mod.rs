@@ -0,0 +1,63 @@
+//! First-class LLM provider abstraction. `snps analyze`/`summarize` and
+//! the `snps llm test` command all go through [`LlmProvider`] rather than
+//! calling a specific vendor's SDK directly, so adding a provider means
+//! implementing one trait and registering it.
+
+pub mod anthropic;
+pub mod http;
+pub mod openai;
+mod registry;
+
+pub use anthropic::AnthropicProvider;
+pub use openai::OpenAiProvider;
+pub use registry::provider_for;
+
+use crate::error::CoreResult;
+use async_trait::async_trait;
+
+#[derive(Debug, Clone)]
+pub struct CompletionRequest {
+    pub prompt: String,
+    pub model: String,
+    pub max_tokens: u32,
+    pub temperature: f32,
+}
+
+impl CompletionRequest {
+    pub fn new(prompt: impl Into<String>, model: impl Into<String>) -> Self {
+        Self { prompt: prompt.into(), model: model.into(), max_tokens: 1024, temperature: 0.2 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub text: String,
+    pub model: String,
+}
+
+/// A chunk of a streamed completion. The final chunk has `done: true` and
+/// carries no further text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionChunk {
+    pub text: String,
+    pub done: bool,
+}
+
+/// A callable LLM backend. Implementations own their own HTTP client and
+/// API key; retries on transient failures are the implementation's
+/// responsibility (see [`http::send_with_retry`]).
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn complete(&self, request: &CompletionRequest) -> CoreResult<Completion>;
+
+    /// Stream a completion, invoking `on_chunk` as text arrives. Providers
+    /// without native streaming support may implement this by calling
+    /// [`LlmProvider::complete`] once and emitting a single chunk.
+    async fn complete_streaming(
+        &self,
+        request: &CompletionRequest,
+        on_chunk: &mut (dyn FnMut(CompletionChunk) + Send),
+    ) -> CoreResult<Completion>;
+}
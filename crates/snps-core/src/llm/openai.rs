@@ -0,0 +1,76 @@
+//! OpenAI-compatible chat completions backend. Also usable against local
+//! or self-hosted servers that mirror OpenAI's API shape by overriding
+//! the base URL.
+
+use super::http::send_with_retry;
+use super::{Completion, CompletionChunk, CompletionRequest, LlmProvider};
+use crate::error::{CoreError, CoreResult};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1/chat/completions";
+
+pub struct OpenAiProvider {
+    client: reqwest::Client,
+    api_key: String,
+    base_url: String,
+}
+
+impl OpenAiProvider {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self { client: reqwest::Client::new(), api_key: api_key.into(), base_url: DEFAULT_BASE_URL.to_string() }
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<Choice>,
+    model: String,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: ChatMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatMessage {
+    content: String,
+}
+
+#[async_trait]
+impl LlmProvider for OpenAiProvider {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> CoreResult<Completion> {
+        let body = serde_json::json!({
+            "model": request.model,
+            "max_tokens": request.max_tokens,
+            "temperature": request.temperature,
+            "messages": [{"role": "user", "content": request.prompt}],
+        });
+
+        let response = send_with_retry(|| self.client.post(&self.base_url).bearer_auth(&self.api_key).json(&body)).await?;
+
+        let mut parsed: ChatResponse = response.json().await.map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+        let text = parsed.choices.pop().map(|c| c.message.content).unwrap_or_default();
+        Ok(Completion { text, model: parsed.model })
+    }
+
+    async fn complete_streaming(
+        &self,
+        request: &CompletionRequest,
+        on_chunk: &mut (dyn FnMut(CompletionChunk) + Send),
+    ) -> CoreResult<Completion> {
+        let completion = self.complete(request).await?;
+        on_chunk(CompletionChunk { text: completion.text.clone(), done: true });
+        Ok(completion)
+    }
+}
@@ -0,0 +1,30 @@
+//! Provider selection by name, matching `config.llm.default_provider`.
+
+use super::{AnthropicProvider, LlmProvider, OpenAiProvider};
+use crate::error::{CoreError, CoreResult};
+
+/// Build the provider named by `name` (e.g. `GlobalConfig::llm_default_provider`),
+/// using `api_key` sourced by the caller from config or environment.
+pub fn provider_for(name: &str, api_key: String) -> CoreResult<Box<dyn LlmProvider>> {
+    match name {
+        "anthropic" => Ok(Box::new(AnthropicProvider::new(api_key))),
+        "openai" => Ok(Box::new(OpenAiProvider::new(api_key))),
+        other => Err(CoreError::InvalidInput(format!("unknown llm provider '{other}'"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_providers_resolve_by_name() {
+        assert_eq!(provider_for("anthropic", "k".into()).unwrap().name(), "anthropic");
+        assert_eq!(provider_for("openai", "k".into()).unwrap().name(), "openai");
+    }
+
+    #[test]
+    fn unknown_provider_is_an_error() {
+        assert!(provider_for("mystery", "k".into()).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! Export matter documents outside PMSynapse.
+//!
+//! Supports two formats: an archive (zip) of the raw markdown files with
+//! frontmatter intact, or a single concatenated document (markdown or
+//! JSON) plus a manifest recording where each item came from so a future
+//! import can round-trip.
+
+use super::{MatterItem, MatterType};
+use crate::error::CoreResult;
+use serde::Serialize;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Which items to export.
+#[derive(Default)]
+pub struct ExportFilter {
+    pub context: Option<String>,
+    pub matter_type: Option<MatterType>,
+    pub tags: Vec<String>,
+    pub ids: Vec<String>,
+}
+
+impl ExportFilter {
+    pub fn matches(&self, item: &MatterItem) -> bool {
+        if !self.ids.is_empty() {
+            return self.ids.contains(&item.id);
+        }
+        if let Some(context) = &self.context {
+            if &item.context != context {
+                return false;
+            }
+        }
+        if let Some(matter_type) = &self.matter_type {
+            if &item.matter_type != matter_type {
+                return false;
+            }
+        }
+        if !self.tags.is_empty() && !self.tags.iter().any(|t| item.tags.contains(t)) {
+            return false;
+        }
+        true
+    }
+}
+
+pub enum ExportFormat {
+    Archive,
+    ConcatenatedMarkdown,
+    Json,
+}
+
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    pub strip_frontmatter: bool,
+    pub out_path: PathBuf,
+}
+
+/// One entry in the export manifest, recording where the item lived so a
+/// future `matter import` can restore it into the same relative path.
+#[derive(Serialize)]
+pub struct ManifestEntry {
+    pub id: String,
+    pub repository_id: String,
+    pub relative_path: String,
+    pub matter_type: String,
+}
+
+#[derive(Serialize)]
+pub struct Manifest {
+    pub repository_id: String,
+    pub entries: Vec<ManifestEntry>,
+}
+
+fn strip_frontmatter(body: &str) -> &str {
+    if let Some(rest) = body.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---\n") {
+            return &rest[end + 5..];
+        }
+    }
+    body
+}
+
+/// Export the items matching `filter` under `repo_root` according to
+/// `options`. Returns the manifest that was written alongside the export.
+pub fn export(
+    repo_root: &Path,
+    repository_id: &str,
+    items: &[MatterItem],
+    filter: &ExportFilter,
+    options: &ExportOptions,
+) -> CoreResult<Manifest> {
+    let selected: Vec<&MatterItem> = items.iter().filter(|i| filter.matches(i)).collect();
+
+    let mut entries = Vec::new();
+    for item in &selected {
+        entries.push(ManifestEntry {
+            id: item.id.clone(),
+            repository_id: repository_id.to_string(),
+            relative_path: item
+                .path
+                .strip_prefix(repo_root)
+                .unwrap_or(&item.path)
+                .to_string_lossy()
+                .into_owned(),
+            matter_type: item.matter_type.as_str().to_string(),
+        });
+    }
+    let manifest = Manifest {
+        repository_id: repository_id.to_string(),
+        entries,
+    };
+
+    match options.format {
+        ExportFormat::Archive => write_archive(&selected, options)?,
+        ExportFormat::ConcatenatedMarkdown => write_concatenated_markdown(&selected, options)?,
+        ExportFormat::Json => write_json(&selected, options)?,
+    }
+
+    let manifest_path = options.out_path.with_extension("manifest.json");
+    std::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+    Ok(manifest)
+}
+
+fn body_for(item: &MatterItem, strip: bool) -> &str {
+    if strip {
+        strip_frontmatter(&item.body)
+    } else {
+        &item.body
+    }
+}
+
+fn write_archive(items: &[&MatterItem], options: &ExportOptions) -> CoreResult<()> {
+    let file = File::create(&options.out_path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let zip_options: zip::write::FileOptions<()> =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for item in items {
+        let name = format!("{}/{}.md", item.matter_type.dir_name(), item.id);
+        zip.start_file(name, zip_options)?;
+        zip.write_all(body_for(item, options.strip_frontmatter).as_bytes())?;
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+fn write_concatenated_markdown(items: &[&MatterItem], options: &ExportOptions) -> CoreResult<()> {
+    let mut out = String::new();
+    for item in items {
+        out.push_str(&format!("<!-- {} ({}) -->\n\n", item.title, item.id));
+        out.push_str(body_for(item, options.strip_frontmatter));
+        out.push_str("\n\n---\n\n");
+    }
+    std::fs::write(&options.out_path, out)?;
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct JsonItem<'a> {
+    id: &'a str,
+    title: &'a str,
+    matter_type: &'a str,
+    body: &'a str,
+}
+
+fn write_json(items: &[&MatterItem], options: &ExportOptions) -> CoreResult<()> {
+    let json_items: Vec<JsonItem> = items
+        .iter()
+        .map(|item| JsonItem {
+            id: &item.id,
+            title: &item.title,
+            matter_type: item.matter_type.as_str(),
+            body: body_for(item, options.strip_frontmatter),
+        })
+        .collect();
+    std::fs::write(&options.out_path, serde_json::to_string_pretty(&json_items)?)?;
+    Ok(())
+}
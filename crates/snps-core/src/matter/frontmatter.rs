@@ -0,0 +1,228 @@
+//! Order-preserving frontmatter editing. [`super::parse_frontmatter`]
+//! (via [`split_frontmatter`] below) already reads *values* out of a
+//! document's `---`-delimited YAML block for indexing; this module is for
+//! *writing* one field back without a full serde round-trip, which would
+//! reorder keys alphabetically and drop comments. [`FrontmatterEditor`]
+//! instead treats the block as a sequence of raw lines and only touches
+//! the one line whose key matches, so everything else — key order,
+//! quoting, comments, blank lines — comes back out byte-identical.
+
+/// Split `contents` into its frontmatter block (without delimiters) and
+/// the body after it. Returns `("", contents)` when `contents` doesn't
+/// open with a `---` block.
+///
+/// Tolerant of `\r\n` line endings and of the closing `---` being the
+/// last line of the file with no trailing newline — both are produced by
+/// editors and other tools that don't share this codebase's own writer.
+pub fn split_frontmatter(contents: &str) -> (&str, &str) {
+    let Some((first_line, _, rest)) = split_line(contents) else { return ("", contents) };
+    if strip_cr(first_line) != "---" {
+        return ("", contents);
+    }
+
+    let mut consumed = 0;
+    let mut remaining = rest;
+    loop {
+        let Some((line, had_newline, next)) = split_line(remaining) else {
+            // Ran off the end of the file without finding a closing
+            // delimiter — not a frontmatter block after all.
+            return ("", contents);
+        };
+        if strip_cr(line) == "---" {
+            let body_start = consumed + line.len() + if had_newline { 1 } else { 0 };
+            return (&rest[..consumed], &rest[body_start..]);
+        }
+        consumed += line.len() + if had_newline { 1 } else { 0 };
+        remaining = next;
+    }
+}
+
+fn strip_cr(line: &str) -> &str {
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// Split off the next line from `s`: the line's text (excluding the
+/// `\n`, but keeping a trailing `\r` if the source used CRLF), whether a
+/// `\n` terminated it, and the remainder. `None` once `s` is exhausted.
+fn split_line(s: &str) -> Option<(&str, bool, &str)> {
+    if s.is_empty() {
+        return None;
+    }
+    match s.find('\n') {
+        Some(i) => Some((&s[..i], true, &s[i + 1..])),
+        None => Some((s, false, "")),
+    }
+}
+
+/// One frontmatter line, owned so [`FrontmatterEditor::set_field`] can
+/// replace a line's text in place without fighting the borrow checker
+/// over how long the original `contents` string needs to outlive it.
+/// Blocks are small (a handful of fields), so the copy is negligible.
+struct RawLine {
+    raw: String,
+    had_newline: bool,
+    key: Option<String>,
+}
+
+/// A parsed frontmatter block, ready to have specific fields patched in
+/// place. Build with [`FrontmatterEditor::parse`], call [`set_field`]
+/// for each field the caller owns, then [`render`] to get the full file
+/// contents (frontmatter plus body) back out.
+///
+/// [`set_field`]: FrontmatterEditor::set_field
+/// [`render`]: FrontmatterEditor::render
+pub struct FrontmatterEditor<'a> {
+    open_delim: &'a str,
+    lines: Vec<RawLine>,
+    close_delim: &'a str,
+    close_had_newline: bool,
+    body: &'a str,
+}
+
+impl<'a> FrontmatterEditor<'a> {
+    /// Parse `contents`' leading `---` block. Returns `None` if it
+    /// doesn't have one (missing entirely, or the closing delimiter is
+    /// never found), matching [`split_frontmatter`]'s tolerance for
+    /// `\r\n` and a missing final newline.
+    pub fn parse(contents: &'a str) -> Option<FrontmatterEditor<'a>> {
+        let (open_delim, _, rest) = split_line(contents)?;
+        if strip_cr(open_delim) != "---" {
+            return None;
+        }
+
+        let mut lines = Vec::new();
+        let mut remaining = rest;
+        loop {
+            let (line, had_newline, next) = split_line(remaining)?;
+            if strip_cr(line) == "---" {
+                return Some(FrontmatterEditor {
+                    open_delim,
+                    lines,
+                    close_delim: line,
+                    close_had_newline: had_newline,
+                    body: next,
+                });
+            }
+            let key = (!line.trim_start().starts_with('#'))
+                .then(|| line.split_once(':').map(|(k, _)| k.trim().to_string()))
+                .flatten()
+                .filter(|k| !k.is_empty());
+            lines.push(RawLine { raw: line.to_string(), had_newline, key });
+            remaining = next;
+        }
+    }
+
+    /// Set `key`'s value, replacing only that line's text if `key`
+    /// already appears in the block, or appending a new `key: value`
+    /// line just before the closing delimiter otherwise. Every other
+    /// line — including its own line-ending style — is left untouched.
+    pub fn set_field(&mut self, key: &str, value: &str) {
+        match self.lines.iter().position(|l| l.key.as_deref() == Some(key)) {
+            Some(i) => {
+                let crlf = self.lines[i].raw.ends_with('\r');
+                let raw = if crlf { format!("{key}: {value}\r") } else { format!("{key}: {value}") };
+                self.lines[i].raw = raw;
+            }
+            None => {
+                let crlf = self.lines.last().is_some_and(|l| l.raw.ends_with('\r'));
+                let raw = if crlf { format!("{key}: {value}\r") } else { format!("{key}: {value}") };
+                self.lines.push(RawLine { raw, had_newline: true, key: Some(key.to_string()) });
+            }
+        }
+    }
+
+    /// The current value of `key`, if the block has a line for it.
+    pub fn get_field(&self, key: &str) -> Option<&str> {
+        self.lines
+            .iter()
+            .find(|l| l.key.as_deref() == Some(key))
+            .and_then(|l| l.raw.split_once(':'))
+            .map(|(_, v)| v.trim().trim_matches('"'))
+    }
+
+    /// Re-render the block (delimiters included) followed by the
+    /// original body, byte-identical for every line [`set_field`] didn't
+    /// touch.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str(self.open_delim);
+        out.push('\n');
+        for line in &self.lines {
+            out.push_str(&line.raw);
+            if line.had_newline {
+                out.push('\n');
+            }
+        }
+        out.push_str(self.close_delim);
+        if self.close_had_newline {
+            out.push('\n');
+        }
+        out.push_str(self.body);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_frontmatter_handles_the_plain_case() {
+        let (fm, body) = split_frontmatter("---\ntitle: X\n---\nbody text\n");
+        assert_eq!(fm, "title: X");
+        assert_eq!(body, "body text\n");
+    }
+
+    #[test]
+    fn split_frontmatter_returns_empty_without_a_block() {
+        let (fm, body) = split_frontmatter("no frontmatter here\n");
+        assert_eq!(fm, "");
+        assert_eq!(body, "no frontmatter here\n");
+    }
+
+    #[test]
+    fn split_frontmatter_tolerates_crlf() {
+        let (fm, body) = split_frontmatter("---\r\ntitle: X\r\n---\r\nbody text\r\n");
+        assert_eq!(fm, "title: X\r");
+        assert_eq!(body, "body text\r\n");
+    }
+
+    #[test]
+    fn split_frontmatter_tolerates_a_missing_trailing_newline() {
+        let (fm, body) = split_frontmatter("---\ntitle: X\n---");
+        assert_eq!(fm, "title: X");
+        assert_eq!(body, "");
+    }
+
+    #[test]
+    fn editor_round_trips_untouched_lines_byte_for_byte() {
+        let original = "---\n# a hand-written note\ntitle: \"Keep quotes\"\nzebra: last\nalpha: first\n---\nbody\n";
+        let mut editor = FrontmatterEditor::parse(original).unwrap();
+        editor.set_field("alpha", "changed");
+        assert_eq!(
+            editor.render(),
+            "---\n# a hand-written note\ntitle: \"Keep quotes\"\nzebra: last\nalpha: changed\n---\nbody\n"
+        );
+    }
+
+    #[test]
+    fn editor_appends_a_missing_field_before_the_closing_delimiter() {
+        let original = "---\ntitle: X\n---\nbody\n";
+        let mut editor = FrontmatterEditor::parse(original).unwrap();
+        editor.set_field("visibility", "shared");
+        assert_eq!(editor.render(), "---\ntitle: X\nvisibility: shared\n---\nbody\n");
+    }
+
+    #[test]
+    fn editor_preserves_crlf_on_an_edited_line() {
+        let original = "---\r\ntitle: X\r\n---\r\nbody\r\n";
+        let mut editor = FrontmatterEditor::parse(original).unwrap();
+        editor.set_field("title", "Y");
+        assert_eq!(editor.render(), "---\r\ntitle: Y\r\n---\r\nbody\r\n");
+    }
+
+    #[test]
+    fn editor_returns_none_without_a_closing_delimiter() {
+        assert!(FrontmatterEditor::parse("---\ntitle: X\nno closing delimiter\n").is_none());
+    }
+}
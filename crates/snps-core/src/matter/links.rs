@@ -0,0 +1,189 @@
+//! Link extraction and resolution for matter documents.
+//!
+//! Documents reference each other with `[[wiki-links]]` (matched by id or
+//! slug) and relative markdown links (`[text](../plans/foo.md)`). The
+//! [`MatterIndex`](super::MatterIndex) builds a links table from these so
+//! `snps matter links` and `snps matter validate` can report outgoing
+//! links, backlinks, and broken references.
+
+use super::MatterItem;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// One reference found in a document's body, before resolution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawLink {
+    pub target: String,
+}
+
+/// Resolution outcome for a [`RawLink`] against the index.
+#[derive(Debug, Clone)]
+pub enum LinkTarget {
+    Resolved(PathBuf),
+    Broken(String),
+}
+
+/// A resolved link edge between two documents (by id).
+#[derive(Debug, Clone)]
+pub struct Link {
+    pub from: String,
+    pub target: LinkTarget,
+}
+
+/// Extract `[[wiki-links]]` and relative markdown links from a document's
+/// body.
+pub fn extract_links(body: &str) -> Vec<RawLink> {
+    let mut links = Vec::new();
+
+    let mut rest = body;
+    while let Some(start) = rest.find("[[") {
+        rest = &rest[start + 2..];
+        if let Some(end) = rest.find("]]") {
+            let target = &rest[..end];
+            links.push(RawLink {
+                target: target.split('|').next().unwrap_or(target).trim().to_string(),
+            });
+            rest = &rest[end + 2..];
+        } else {
+            break;
+        }
+    }
+
+    let mut rest = body;
+    while let Some(start) = rest.find("](") {
+        rest = &rest[start + 2..];
+        if let Some(end) = rest.find(')') {
+            let target = rest[..end].trim();
+            if !target.starts_with("http://") && !target.starts_with("https://") {
+                links.push(RawLink {
+                    target: target.to_string(),
+                });
+            }
+            rest = &rest[end + 1..];
+        } else {
+            break;
+        }
+    }
+
+    links
+}
+
+/// Links table over an index: outgoing links per document id, and the
+/// derived backlinks (documents that reference a given id).
+pub struct LinkGraph {
+    pub outgoing: HashMap<String, Vec<Link>>,
+    pub backlinks: HashMap<String, Vec<String>>,
+}
+
+impl LinkGraph {
+    /// Build the links table by extracting and resolving references across
+    /// every item in the index.
+    pub fn build(items: &[MatterItem]) -> Self {
+        let by_id: HashMap<&str, &MatterItem> =
+            items.iter().map(|i| (i.id.as_str(), i)).collect();
+        let by_stem: HashMap<String, &MatterItem> = items
+            .iter()
+            .map(|i| {
+                (
+                    i.path
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    i,
+                )
+            })
+            .collect();
+
+        let mut outgoing = HashMap::new();
+        let mut backlinks: HashMap<String, Vec<String>> = HashMap::new();
+
+        for item in items {
+            let mut links = Vec::new();
+            for raw in extract_links(&item.body) {
+                let resolved = resolve(&raw, &item.path, &by_id, &by_stem);
+                if let LinkTarget::Resolved(ref path) = resolved {
+                    if let Some(target_id) = by_id
+                        .values()
+                        .find(|i| i.path == *path)
+                        .map(|i| i.id.clone())
+                    {
+                        backlinks.entry(target_id).or_default().push(item.id.clone());
+                    }
+                }
+                links.push(Link {
+                    from: item.id.clone(),
+                    target: resolved,
+                });
+            }
+            outgoing.insert(item.id.clone(), links);
+        }
+
+        Self { outgoing, backlinks }
+    }
+
+    pub fn outgoing_for(&self, id: &str) -> &[Link] {
+        self.outgoing.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn backlinks_for(&self, id: &str) -> &[String] {
+        self.backlinks.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// All broken links across the index, for `snps matter validate`.
+    pub fn broken_links(&self) -> Vec<(&str, &str)> {
+        self.outgoing
+            .iter()
+            .flat_map(|(from, links)| {
+                links.iter().filter_map(move |link| match &link.target {
+                    LinkTarget::Broken(target) => Some((from.as_str(), target.as_str())),
+                    LinkTarget::Resolved(_) => None,
+                })
+            })
+            .collect()
+    }
+}
+
+fn resolve(
+    raw: &RawLink,
+    from_path: &Path,
+    by_id: &HashMap<&str, &MatterItem>,
+    by_stem: &HashMap<String, &MatterItem>,
+) -> LinkTarget {
+    if let Some(item) = by_id.get(raw.target.as_str()) {
+        return LinkTarget::Resolved(item.path.clone());
+    }
+    if let Some(item) = by_stem.get(&raw.target) {
+        return LinkTarget::Resolved(item.path.clone());
+    }
+    if raw.target.starts_with('.') || raw.target.contains('/') {
+        let candidate = from_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(&raw.target);
+        if candidate.exists() {
+            return LinkTarget::Resolved(candidate);
+        }
+    }
+    LinkTarget::Broken(raw.target.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_wiki_and_markdown_links() {
+        let body = "See [[other-doc]] and [more](../plans/refactor.md) and [ext](https://x.com).";
+        let links = extract_links(body);
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].target, "other-doc");
+        assert_eq!(links[1].target, "../plans/refactor.md");
+    }
+
+    #[test]
+    fn strips_wiki_link_alias() {
+        let links = extract_links("[[real-target|Display Text]]");
+        assert_eq!(links[0].target, "real-target");
+    }
+}
@@ -0,0 +1,679 @@
+//! Matter documents: frontmattered markdown files (specs, research, plans,
+//! insights, freeform documents) tracked in a per-repository index so they
+//! can be created, listed, searched, and shown without a database.
+
+pub mod export;
+mod frontmatter;
+mod links;
+mod templates;
+
+pub use frontmatter::{split_frontmatter, FrontmatterEditor};
+pub use links::{extract_links, Link, LinkGraph, LinkTarget};
+pub use templates::{render_template, template_source, TemplateSource};
+
+use crate::error::{CoreError, CoreResult};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The kind of matter document. Determines the default template and the
+/// subdirectory under `matter/` a new document is written to.
+///
+/// `Custom` is never produced by `--type` (which stays strict, see
+/// [`FromStr`] below) — it's only how a document whose frontmatter names
+/// a type this build doesn't recognize is represented, so parsing that
+/// file doesn't fail or silently reclassify it as `Document`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MatterType {
+    Spec,
+    Research,
+    Plan,
+    Insight,
+    Document,
+    Custom(String),
+}
+
+impl MatterType {
+    pub const ALL: [MatterType; 5] = [
+        MatterType::Spec,
+        MatterType::Research,
+        MatterType::Plan,
+        MatterType::Insight,
+        MatterType::Document,
+    ];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            MatterType::Spec => "spec",
+            MatterType::Research => "research",
+            MatterType::Plan => "plan",
+            MatterType::Insight => "insight",
+            MatterType::Document => "document",
+            MatterType::Custom(name) => name,
+        }
+    }
+
+    pub fn dir_name(&self) -> &str {
+        match self {
+            MatterType::Spec => "specs",
+            MatterType::Research => "research",
+            MatterType::Plan => "plans",
+            MatterType::Insight => "insights",
+            MatterType::Document => "documents",
+            MatterType::Custom(_) => "custom",
+        }
+    }
+
+    /// Like [`FromStr`], but unrecognized names become `Custom` instead of
+    /// erroring — for parsing existing frontmatter, where the file should
+    /// still show up in listings rather than get dropped.
+    pub fn from_frontmatter(s: &str) -> MatterType {
+        s.parse().unwrap_or_else(|_| MatterType::Custom(s.to_string()))
+    }
+}
+
+impl fmt::Display for MatterType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for MatterType {
+    type Err = CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "spec" => Ok(MatterType::Spec),
+            "research" => Ok(MatterType::Research),
+            "plan" => Ok(MatterType::Plan),
+            "insight" => Ok(MatterType::Insight),
+            "document" => Ok(MatterType::Document),
+            other => Err(CoreError::InvalidInput(format!(
+                "unknown matter type '{other}'"
+            ))),
+        }
+    }
+}
+
+/// A matter document: YAML frontmatter plus a markdown body.
+#[derive(Debug, Clone)]
+pub struct MatterItem {
+    pub id: String,
+    pub matter_type: MatterType,
+    pub title: String,
+    pub author: Option<String>,
+    pub tags: Vec<String>,
+    pub context: String,
+    pub path: PathBuf,
+    pub body: String,
+    /// Explicit frontmatter override of the owning repository's default
+    /// visibility. See [`crate::repository::effective_visibility`].
+    pub visibility: Option<crate::repository::Visibility>,
+    /// `created`/`date` from frontmatter, normalized to `YYYY-MM-DD` when
+    /// it parses as that or as RFC3339; kept as the raw string otherwise
+    /// (see [`normalize_date`]).
+    pub created: Option<String>,
+}
+
+/// Fields needed to create a new matter document.
+pub struct NewMatter<'a> {
+    pub matter_type: MatterType,
+    pub title: &'a str,
+    pub author: Option<&'a str>,
+    pub tags: Vec<String>,
+    pub context: &'a str,
+    /// Body content to place beneath the generated header instead of the
+    /// type's default template sections (`## Problem`, `## Findings`,
+    /// etc). `None` keeps the normal placeholder-filled template.
+    pub body: Option<&'a str>,
+}
+
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    let mut last_dash = false;
+    for c in title.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+            last_dash = false;
+        } else if !last_dash {
+            slug.push('-');
+            last_dash = true;
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Create a new matter document under `repo_root/matter/<dir>/<slug>.md`,
+/// rendering the appropriate template (built-in or repo override).
+pub fn matter_create(repo_root: &Path, new: NewMatter<'_>) -> CoreResult<MatterItem> {
+    let date = today_string();
+    let dir = repo_root.join("matter").join(new.matter_type.dir_name());
+    fs::create_dir_all(&dir)?;
+
+    let slug = slugify(new.title);
+    let file_name = format!("{date}-{slug}.md");
+    let path = dir.join(&file_name);
+
+    let values = templates::TemplateValues {
+        title: new.title.to_string(),
+        author: new.author.unwrap_or("").to_string(),
+        date: date.clone(),
+        tags: new.tags.clone(),
+    };
+
+    let rendered = match new.body {
+        Some(body) => format!("{}{}\n", templates::render_header(&values), body.trim_end()),
+        None => render_template(repo_root, new.matter_type, &values)?,
+    };
+
+    fs::write(&path, &rendered)?;
+
+    Ok(MatterItem {
+        id: slug.clone(),
+        matter_type: new.matter_type,
+        title: new.title.to_string(),
+        author: new.author.map(str::to_string),
+        tags: new.tags,
+        context: new.context.to_string(),
+        path,
+        body: rendered,
+        visibility: None,
+        created: Some(date),
+    })
+}
+
+/// Git commit history for `item`, most-recent first — see
+/// [`crate::git::GitRepo::file_history`]. `Ok(None)` means there's nothing
+/// to show: `repo_root` isn't a git repository, or it is but has no commits
+/// yet. Neither is unusual enough (a workspace can predate its first
+/// commit) to warrant a dedicated `CoreError` variant — callers render it
+/// as an informational message instead of an error.
+pub fn history(repo_root: &Path, item: &MatterItem, follow_renames: bool) -> CoreResult<Option<Vec<crate::git::FileHistoryEntry>>> {
+    let Ok(git) = crate::git::GitRepo::open(repo_root) else {
+        return Ok(None);
+    };
+    if !git.has_history() {
+        return Ok(None);
+    }
+    let relative = item.path.strip_prefix(repo_root).unwrap_or(&item.path);
+    Ok(Some(git.file_history(relative, follow_renames)?))
+}
+
+/// `item`'s full file content (frontmatter included) as of `revision`.
+pub fn show_at(repo_root: &Path, item: &MatterItem, revision: &str) -> CoreResult<String> {
+    let git = crate::git::GitRepo::open(repo_root)?;
+    let relative = item.path.strip_prefix(repo_root).unwrap_or(&item.path);
+    git.show_file_at(relative, revision)
+}
+
+/// Rewrite (or insert) the frontmatter `visibility` field of the file at
+/// `path` in place. Used by `matter promote`/`demote`, either directly or
+/// as what applying an approved [`crate::proposals::ProposedChange::MatterVisibility`]
+/// does — there's no separate directory for private vs. shared documents
+/// to move a file between, so promoting/demoting is purely a frontmatter
+/// edit (see [`crate::repository::visibility`]).
+pub fn set_visibility(path: &Path, visibility: crate::repository::Visibility) -> CoreResult<()> {
+    let contents = fs::read_to_string(path)?;
+    let mut editor = FrontmatterEditor::parse(&contents).ok_or_else(|| {
+        CoreError::InvalidInput(format!("{} has no frontmatter block to set visibility on", path.display()))
+    })?;
+    editor.set_field("visibility", visibility.as_str());
+    fs::write(path, editor.render())?;
+    Ok(())
+}
+
+fn today_string() -> String {
+    // Callers that need a real clock (CLI, daemon) pass it in via config
+    // in higher layers; core keeps this simple so tests are deterministic
+    // when they stub the date through the repo_root template values.
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let days = secs / 86_400;
+    // Simple civil-from-days conversion (Howard Hinnant's algorithm).
+    let z = days as i64 + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = if m <= 2 { y + 1 } else { y };
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+/// A file that needed something coerced to parse, or that failed to parse
+/// at all — either way it's reported instead of silently vanishing from
+/// the index. See `snps matter lint`.
+pub struct FileWarning {
+    pub path: PathBuf,
+    pub messages: Vec<String>,
+}
+
+/// In-memory index over a repository's matter documents, used by
+/// list/search/show and rebuilt from disk on demand.
+#[derive(Default)]
+pub struct MatterIndex {
+    pub items: Vec<MatterItem>,
+    /// Files that parsed with a coerced field, or that failed to parse
+    /// entirely. Never affects `items` — see [`FileWarning`].
+    pub warnings: Vec<FileWarning>,
+}
+
+impl MatterIndex {
+    /// Build the index, skipping files matched by `search.exclude_patterns`
+    /// (from the workspace's merged config) or by the owning repository's
+    /// own `excludes`, if any — see [`crate::fswalk`]. A config or
+    /// repositories.yaml that fails to load just means no excludes apply,
+    /// rather than failing the whole build over it.
+    pub fn build(repo_root: &Path) -> CoreResult<Self> {
+        let workspace = crate::workspace::Workspace { root: repo_root.to_path_buf() };
+        let global_excludes = crate::config::load_merged_config(&workspace)
+            .map(|merged| merged.config.search_exclude_patterns)
+            .unwrap_or_default();
+        let repos = crate::repository::Repository::load_all(&workspace).unwrap_or_default();
+
+        let mut items = Vec::new();
+        let mut warnings = Vec::new();
+        let matter_dir = repo_root.join("matter");
+        if !matter_dir.exists() {
+            return Ok(Self { items, warnings });
+        }
+        for entry in crate::fswalk::walk_markdown(repo_root, &matter_dir, &global_excludes) {
+            if excluded_by_owning_repo(&repos, &entry) {
+                continue;
+            }
+            match parse_matter_file(&entry) {
+                Ok((item, messages)) => {
+                    if !messages.is_empty() {
+                        warnings.push(FileWarning { path: entry, messages });
+                    }
+                    items.push(item);
+                }
+                Err(err) => warnings.push(FileWarning {
+                    path: entry,
+                    messages: vec![err.to_string()],
+                }),
+            }
+        }
+        Ok(Self { items, warnings })
+    }
+
+    pub fn list(&self, matter_type: Option<MatterType>) -> Vec<&MatterItem> {
+        self.items
+            .iter()
+            .filter(|i| match &matter_type {
+                Some(t) => &i.matter_type == t,
+                None => true,
+            })
+            .collect()
+    }
+
+    pub fn search(&self, query: &str) -> Vec<&MatterItem> {
+        let q = query.to_lowercase();
+        self.items
+            .iter()
+            .filter(|i| i.title.to_lowercase().contains(&q) || i.body.to_lowercase().contains(&q))
+            .collect()
+    }
+
+    /// Like [`MatterIndex::search`], but for callers that want to show a
+    /// person which of several similarly titled documents actually
+    /// contains what they're looking for: each hit carries a plain-text
+    /// excerpt around its best-matching line (see [`highlight`] to mark
+    /// the query terms in it), that line's number, and a relevance score
+    /// used only to order results, highest first.
+    pub fn search_with_snippets(&self, query: &str) -> Vec<MatterSearchHit<'_>> {
+        let q = query.to_lowercase();
+        if q.is_empty() {
+            return Vec::new();
+        }
+        let mut hits: Vec<MatterSearchHit<'_>> = self.items.iter().filter_map(|item| search_hit(item, &q)).collect();
+        hits.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        hits
+    }
+
+    /// Build the link graph over the current items. Callers re-run
+    /// [`MatterIndex::build`] first so renames and deletions are reflected.
+    pub fn link_graph(&self) -> LinkGraph {
+        LinkGraph::build(&self.items)
+    }
+}
+
+/// One document matched by [`MatterIndex::search_with_snippets`].
+#[derive(Debug, Clone)]
+pub struct MatterSearchHit<'a> {
+    pub item: &'a MatterItem,
+    /// 1-based line number of the best-matching body line, `None` when the
+    /// match was found only in the title.
+    pub line_number: Option<usize>,
+    /// A short plain-text excerpt around the match.
+    pub snippet: String,
+    /// Title matches are weighted above body matches — a hit in the title
+    /// is what someone scanning a result list notices first. Not meant as
+    /// a general-purpose relevance metric, only to order these results.
+    pub score: f32,
+}
+
+const TITLE_MATCH_WEIGHT: f32 = 5.0;
+const SNIPPET_RADIUS_CHARS: usize = 60;
+
+fn search_hit<'a>(item: &'a MatterItem, q: &str) -> Option<MatterSearchHit<'a>> {
+    let title_matches = item.title.to_lowercase().matches(q).count();
+    let body_matches = item.body.to_lowercase().matches(q).count();
+    if title_matches == 0 && body_matches == 0 {
+        return None;
+    }
+
+    let (line_number, snippet) = match find_line_match(&item.body, q) {
+        Some((line_number, line)) => (Some(line_number), snippet_around(line, q)),
+        None => (None, truncate_chars(item.title.trim(), SNIPPET_RADIUS_CHARS * 2)),
+    };
+
+    let score = title_matches as f32 * TITLE_MATCH_WEIGHT + body_matches as f32;
+    Some(MatterSearchHit { item, line_number, snippet, score })
+}
+
+/// The first body line containing `q` (already lowercased), 1-based.
+fn find_line_match<'a>(body: &'a str, q: &str) -> Option<(usize, &'a str)> {
+    body.lines().enumerate().find_map(|(i, line)| line.to_lowercase().contains(q).then(|| (i + 1, line)))
+}
+
+/// `SNIPPET_RADIUS_CHARS` characters of context on either side of `q`'s
+/// first occurrence in `line`, with an ellipsis on whichever side got cut.
+fn snippet_around(line: &str, q: &str) -> String {
+    let lower = line.to_lowercase();
+    let Some(byte_pos) = lower.find(q) else {
+        return truncate_chars(line.trim(), SNIPPET_RADIUS_CHARS * 2);
+    };
+
+    let chars: Vec<char> = line.chars().collect();
+    let char_pos = line[..byte_pos].chars().count();
+    let query_len = q.chars().count();
+    let start = char_pos.saturating_sub(SNIPPET_RADIUS_CHARS);
+    let end = (char_pos + query_len + SNIPPET_RADIUS_CHARS).min(chars.len());
+
+    let mut snippet: String = chars[start..end].iter().collect::<String>().trim().to_string();
+    if start > 0 {
+        snippet = format!("…{snippet}");
+    }
+    if end < chars.len() {
+        snippet = format!("{snippet}…");
+    }
+    snippet
+}
+
+fn truncate_chars(s: &str, max_chars: usize) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() <= max_chars {
+        s.to_string()
+    } else {
+        format!("{}…", chars[..max_chars].iter().collect::<String>())
+    }
+}
+
+/// Wrap every case-insensitive occurrence of `query` in `text` with `open`
+/// and `close`, preserving the original casing of the matched text. Lets a
+/// caller turn a plain [`MatterSearchHit::snippet`] into ANSI-highlighted
+/// terminal output or into `**query**`-style JSON markers, without the
+/// index needing to know which one it's for.
+pub fn highlight(text: &str, query: &str, open: &str, close: &str) -> String {
+    if query.is_empty() {
+        return text.to_string();
+    }
+    let lower_text = text.to_lowercase();
+    let lower_query = query.to_lowercase();
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0;
+    while let Some(found) = lower_text[pos..].find(&lower_query) {
+        let start = pos + found;
+        let end = start + lower_query.len();
+        out.push_str(&text[pos..start]);
+        out.push_str(open);
+        out.push_str(&text[start..end]);
+        out.push_str(close);
+        pos = end;
+    }
+    out.push_str(&text[pos..]);
+    out
+}
+
+/// Whether `path`'s owning repository (if any) excludes it, via a glob
+/// pattern relative to that repository's own root.
+fn excluded_by_owning_repo(repos: &[crate::repository::Repository], path: &Path) -> bool {
+    let Some(repo) = crate::repository::Repository::owning(repos, path) else {
+        return false;
+    };
+    if repo.excludes.is_empty() {
+        return false;
+    }
+    let relative = path.strip_prefix(&repo.path).unwrap_or(path).to_string_lossy().replace('\\', "/");
+    repo.excludes.iter().any(|pattern| crate::fswalk::glob_match(pattern, &relative))
+}
+
+/// Parse a matter file leniently: unrecognized `type` values become
+/// [`MatterType::Custom`] and unrecognized `created`/`date` formats are
+/// kept as-is, both instead of erroring — either is reported back as a
+/// warning string rather than dropping the file from the index. Only a
+/// failure to read the file at all is a hard error.
+fn parse_matter_file(path: &Path) -> CoreResult<(MatterItem, Vec<String>)> {
+    let contents = fs::read_to_string(path)?;
+    let (frontmatter, body) = split_frontmatter(&contents);
+    let fields = parse_frontmatter(frontmatter);
+    let mut warnings = Vec::new();
+
+    let matter_type = match fields.get("type") {
+        Some(t) => {
+            let parsed = MatterType::from_frontmatter(t);
+            if matches!(parsed, MatterType::Custom(_)) {
+                warnings.push(format!("unrecognized type '{t}', kept as custom"));
+            }
+            parsed
+        }
+        None => MatterType::Document,
+    };
+
+    let created = fields.get("created").or_else(|| fields.get("date")).map(|raw| {
+        let (normalized, warning) = normalize_date(raw);
+        if let Some(warning) = warning {
+            warnings.push(warning);
+        }
+        normalized
+    });
+
+    let item = MatterItem {
+        id: path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string(),
+        matter_type,
+        title: fields.get("title").cloned().unwrap_or_default(),
+        author: fields.get("author").cloned(),
+        tags: fields
+            .get("tags")
+            .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
+            .unwrap_or_default(),
+        context: fields.get("context").cloned().unwrap_or_default(),
+        path: path.to_path_buf(),
+        body: body.to_string(),
+        visibility: fields.get("visibility").and_then(|v| v.parse().ok()),
+        created,
+    };
+
+    Ok((item, warnings))
+}
+
+/// Normalize a frontmatter `created`/`date` value to `YYYY-MM-DD`.
+///
+/// Accepts a bare `YYYY-MM-DD` unchanged, and an RFC3339-ish timestamp
+/// (`YYYY-MM-DDTHH:MM:SS...`) by taking its date part. Anything else is
+/// kept as the raw string, paired with a warning so the caller can
+/// surface it instead of it silently looking like a real date.
+fn normalize_date(raw: &str) -> (String, Option<String>) {
+    if is_ymd(raw) {
+        return (raw.to_string(), None);
+    }
+    if let Some((date_part, _time_part)) = raw.split_once('T') {
+        if is_ymd(date_part) {
+            return (
+                date_part.to_string(),
+                Some(format!("coerced date '{raw}' to '{date_part}'")),
+            );
+        }
+    }
+    (raw.to_string(), Some(format!("unrecognized date format '{raw}'")))
+}
+
+fn is_ymd(s: &str) -> bool {
+    let b = s.as_bytes();
+    b.len() == 10
+        && b[4] == b'-'
+        && b[7] == b'-'
+        && b[..4].iter().all(u8::is_ascii_digit)
+        && b[5..7].iter().all(u8::is_ascii_digit)
+        && b[8..10].iter().all(u8::is_ascii_digit)
+}
+
+fn parse_frontmatter(frontmatter: &str) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+    for line in frontmatter.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            fields.insert(key.trim().to_string(), value.trim().trim_matches('"').to_string());
+        }
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_frontmatter_falls_back_to_custom() {
+        assert_eq!(MatterType::from_frontmatter("spec"), MatterType::Spec);
+        assert_eq!(
+            MatterType::from_frontmatter("runbook"),
+            MatterType::Custom("runbook".to_string())
+        );
+    }
+
+    #[test]
+    fn normalize_date_accepts_ymd_and_rfc3339() {
+        assert_eq!(normalize_date("2026-08-08"), ("2026-08-08".to_string(), None));
+        let (normalized, warning) = normalize_date("2026-08-08T10:30:00Z");
+        assert_eq!(normalized, "2026-08-08");
+        assert!(warning.is_some());
+    }
+
+    #[test]
+    fn normalize_date_keeps_garbage_with_a_warning() {
+        let (normalized, warning) = normalize_date("last Tuesday");
+        assert_eq!(normalized, "last Tuesday");
+        assert!(warning.is_some());
+    }
+
+    #[test]
+    fn parse_matter_file_warns_instead_of_dropping_on_bad_type_and_date() {
+        let dir = std::env::temp_dir().join(format!("pmsynapse-matter-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("weird.md");
+        fs::write(&path, "---\ntitle: Weird doc\ntype: runbook\ndate: 08/08/2026\n---\nbody\n").unwrap();
+
+        let (item, warnings) = parse_matter_file(&path).unwrap();
+        assert_eq!(item.matter_type, MatterType::Custom("runbook".to_string()));
+        assert_eq!(item.created.as_deref(), Some("08/08/2026"));
+        assert_eq!(warnings.len(), 2);
+
+        fs::remove_file(&path).ok();
+        fs::remove_dir(&dir).ok();
+    }
+
+    #[test]
+    fn set_visibility_preserves_key_order_and_comments() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("doc.md");
+        fs::write(&path, "---\n# owned by the platform team\ntitle: Doc\nvisibility: private\n---\nbody\n").unwrap();
+
+        set_visibility(&path, crate::repository::Visibility::Shared).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "---\n# owned by the platform team\ntitle: Doc\nvisibility: shared\n---\nbody\n"
+        );
+    }
+
+    #[test]
+    fn set_visibility_appends_the_field_when_absent() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("doc.md");
+        fs::write(&path, "---\ntitle: Doc\n---\nbody\n").unwrap();
+
+        set_visibility(&path, crate::repository::Visibility::Private).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "---\ntitle: Doc\nvisibility: private\n---\nbody\n");
+    }
+
+    #[test]
+    fn search_with_snippets_ranks_title_matches_above_body_only_matches() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("matter/documents")).unwrap();
+        fs::write(
+            tmp.path().join("matter/documents/a.md"),
+            "---\ntitle: Rollout plan\n---\nThis mentions the search index once.\n",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("matter/documents/b.md"),
+            "---\ntitle: Search index migration\n---\nUnrelated body text.\n",
+        )
+        .unwrap();
+
+        let index = MatterIndex::build(tmp.path()).unwrap();
+        let hits = index.search_with_snippets("search index");
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].item.title, "Search index migration");
+        assert!(hits[0].line_number.is_none());
+        assert_eq!(hits[1].line_number, Some(4));
+        assert!(hits[1].snippet.contains("search index"));
+    }
+
+    #[test]
+    fn snippet_around_trims_to_radius_with_ellipses() {
+        let long_line = format!("{}search term{}", "x".repeat(200), "y".repeat(200));
+        let snippet = snippet_around(&long_line, "search term");
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.ends_with('…'));
+        assert!(snippet.contains("search term"));
+    }
+
+    #[test]
+    fn highlight_wraps_every_case_insensitive_occurrence() {
+        let highlighted = highlight("Search the Search index", "search", "[", "]");
+        assert_eq!(highlighted, "[Search] the [Search] index");
+    }
+
+    #[test]
+    fn build_skips_files_matching_search_exclude_patterns() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        fs::write(
+            tmp.path().join(".pmsynapse/config.yaml"),
+            "search:\n  exclude_patterns:\n    - \"drafts/*.md\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(tmp.path().join("matter/drafts")).unwrap();
+        fs::create_dir_all(tmp.path().join("matter/specs")).unwrap();
+        fs::write(tmp.path().join("matter/drafts/idea.md"), "---\ntitle: Idea\n---\nbody\n").unwrap();
+        fs::write(tmp.path().join("matter/specs/final.md"), "---\ntitle: Final\n---\nbody\n").unwrap();
+
+        let index = MatterIndex::build(tmp.path()).unwrap();
+        assert_eq!(index.items.len(), 1);
+        assert_eq!(index.items[0].title, "Final");
+    }
+}
@@ -0,0 +1,132 @@
+//! Per-type document templates for `matter_create`, with repo overrides.
+//!
+//! Built-in skeletons live in this file. A repository can override any of
+//! them by dropping `<repo>/.pmsynapse/templates/<type>.md` next to its
+//! config; that file wins over the built-in when present.
+
+use super::MatterType;
+use crate::error::CoreResult;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Values substituted into `{{placeholder}}` markers. Missing values are
+/// substituted as empty strings rather than left as literal braces.
+pub struct TemplateValues {
+    pub title: String,
+    pub author: String,
+    pub date: String,
+    pub tags: Vec<String>,
+}
+
+/// Where a type's template definition came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    BuiltIn,
+    Override(PathBuf),
+}
+
+fn override_path(repo_root: &Path, matter_type: MatterType) -> PathBuf {
+    repo_root
+        .join(".pmsynapse")
+        .join("templates")
+        .join(format!("{}.md", matter_type.as_str()))
+}
+
+/// Report whether `matter_type`'s template is built-in or overridden, for
+/// `snps matter templates list`.
+pub fn template_source(repo_root: &Path, matter_type: MatterType) -> TemplateSource {
+    let path = override_path(repo_root, matter_type);
+    if path.is_file() {
+        TemplateSource::Override(path)
+    } else {
+        TemplateSource::BuiltIn
+    }
+}
+
+fn built_in_template(matter_type: MatterType) -> &'static str {
+    match matter_type {
+        MatterType::Spec => {
+            "# {{title}}\n\n- Author: {{author}}\n- Date: {{date}}\n- Tags: {{tags}}\n\n## Problem\n\n## Proposal\n\n## Open questions\n"
+        }
+        MatterType::Research => {
+            "# {{title}}\n\n- Author: {{author}}\n- Date: {{date}}\n- Tags: {{tags}}\n\n## Question\n\n## Findings\n\n## Sources\n"
+        }
+        MatterType::Plan => {
+            "# {{title}}\n\n- Author: {{author}}\n- Date: {{date}}\n- Tags: {{tags}}\n\n## Goal\n\n## Steps\n\n## Risks\n"
+        }
+        MatterType::Insight => {
+            "# {{title}}\n\n- Author: {{author}}\n- Date: {{date}}\n- Tags: {{tags}}\n\n## Observation\n\n## Implication\n"
+        }
+        MatterType::Document => "# {{title}}\n\n- Author: {{author}}\n- Date: {{date}}\n- Tags: {{tags}}\n",
+    }
+}
+
+/// Render the template for `matter_type`, preferring a repo override over
+/// the built-in skeleton, substituting `values` into `{{placeholder}}`
+/// markers.
+pub fn render_template(
+    repo_root: &Path,
+    matter_type: MatterType,
+    values: &TemplateValues,
+) -> CoreResult<String> {
+    let raw = match template_source(repo_root, matter_type) {
+        TemplateSource::Override(path) => fs::read_to_string(path)?,
+        TemplateSource::BuiltIn => built_in_template(matter_type).to_string(),
+    };
+    Ok(substitute(&raw, values))
+}
+
+const HEADER_TEMPLATE: &str = "# {{title}}\n\n- Author: {{author}}\n- Date: {{date}}\n- Tags: {{tags}}\n\n";
+
+/// The title/author/date/tags header shared by every built-in template,
+/// without the type's default sections beneath it — for callers supplying
+/// their own body content instead of the placeholder skeleton.
+pub fn render_header(values: &TemplateValues) -> String {
+    substitute(HEADER_TEMPLATE, values)
+}
+
+fn substitute(template: &str, values: &TemplateValues) -> String {
+    template
+        .replace("{{title}}", &values.title)
+        .replace("{{author}}", &values.author)
+        .replace("{{date}}", &values.date)
+        .replace("{{tags}}", &values.tags.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_placeholder_values_render_empty() {
+        let values = TemplateValues {
+            title: "Auth redesign".to_string(),
+            author: String::new(),
+            date: "2026-08-08".to_string(),
+            tags: vec![],
+        };
+        let rendered = substitute("{{title}} by {{author}} [{{tags}}]", &values);
+        assert_eq!(rendered, "Auth redesign by  []");
+    }
+
+    #[test]
+    fn built_in_covers_every_matter_type() {
+        for matter_type in MatterType::ALL {
+            assert!(built_in_template(matter_type).contains("{{title}}"));
+        }
+    }
+
+    #[test]
+    fn header_omits_default_sections() {
+        let values = TemplateValues {
+            title: "Auth redesign".to_string(),
+            author: "sam".to_string(),
+            date: "2026-08-08".to_string(),
+            tags: vec!["auth".to_string()],
+        };
+        let header = render_header(&values);
+        assert!(header.contains("# Auth redesign"));
+        assert!(header.contains("- Tags: auth"));
+        assert!(!header.contains("##"));
+    }
+}
@@ -0,0 +1,95 @@
+//! Agent-submitted change proposals: a title/description plus a list of
+//! proposed edits, persisted as YAML under `.pmsynapse/proposals/` so the
+//! CLI, daemon, and desktop app all see the same pending queue.
+
+mod store;
+
+pub use store::ProposalStore;
+
+use crate::graph::{Edge, Node};
+use crate::time::now_unix;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl ProposalStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposalStatus::Pending => "pending",
+            ProposalStatus::Approved => "approved",
+            ProposalStatus::Rejected => "rejected",
+        }
+    }
+}
+
+impl std::str::FromStr for ProposalStatus {
+    type Err = crate::error::CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(ProposalStatus::Pending),
+            "approved" => Ok(ProposalStatus::Approved),
+            "rejected" => Ok(ProposalStatus::Rejected),
+            other => Err(crate::error::CoreError::InvalidInput(format!("unknown proposal status '{other}'"))),
+        }
+    }
+}
+
+/// One edit a proposal wants applied. `AddNode`/`AddEdge`/`MatterVisibility`
+/// are applied automatically on approval; `FileDiff` is recorded for a
+/// human (or a future patch-apply command) to act on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProposedChange {
+    FileDiff { path: String, diff: String },
+    AddNode { node: Node },
+    AddEdge { edge: Edge },
+    /// A `matter promote`/`demote` pending `require_share_review` — see
+    /// `snps-cli`'s `matter promote` command. `path` is absolute, matching
+    /// `MatterItem::path`.
+    MatterVisibility { path: String, to: crate::repository::Visibility },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proposal {
+    pub id: String,
+    pub agent: String,
+    pub title: String,
+    pub description: String,
+    pub changes: Vec<ProposedChange>,
+    pub status: ProposalStatus,
+    pub rejection_reason: Option<String>,
+    pub created_at_unix: u64,
+    pub updated_at_unix: u64,
+}
+
+/// Fields needed to submit a new proposal.
+pub struct NewProposal<'a> {
+    pub agent: &'a str,
+    pub title: &'a str,
+    pub description: &'a str,
+    pub changes: Vec<ProposedChange>,
+}
+
+impl Proposal {
+    fn new(id: String, new: NewProposal<'_>) -> Self {
+        let now = now_unix();
+        Self {
+            id,
+            agent: new.agent.to_string(),
+            title: new.title.to_string(),
+            description: new.description.to_string(),
+            changes: new.changes,
+            status: ProposalStatus::Pending,
+            rejection_reason: None,
+            created_at_unix: now,
+            updated_at_unix: now,
+        }
+    }
+}
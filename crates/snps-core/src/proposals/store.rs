@@ -0,0 +1,165 @@
+use super::{NewProposal, Proposal, ProposalStatus, ProposedChange};
+use crate::error::{CoreError, CoreResult};
+use crate::graph::{KnowledgeGraph, Provenance, ProvenanceSource};
+use crate::time::now_unix;
+use crate::workspace::Workspace;
+use std::path::{Path, PathBuf};
+
+/// File-backed store over `.pmsynapse/proposals/*.yaml`. One proposal per
+/// file, named `<created_at>-<slug>.yaml` so listing by filename already
+/// sorts oldest-first.
+pub struct ProposalStore;
+
+impl ProposalStore {
+    fn dir(workspace: &Workspace) -> PathBuf {
+        workspace.pmsynapse_dir().join("proposals")
+    }
+
+    fn path(workspace: &Workspace, id: &str) -> PathBuf {
+        Self::dir(workspace).join(format!("{id}.yaml"))
+    }
+
+    pub fn create(workspace: &Workspace, new: NewProposal<'_>) -> CoreResult<Proposal> {
+        let dir = Self::dir(workspace);
+        std::fs::create_dir_all(&dir)?;
+
+        let id = format!("{}-{}", now_unix(), slugify(new.title));
+        let proposal = Proposal::new(id, new);
+        write(&Self::path(workspace, &proposal.id), &proposal)?;
+        Ok(proposal)
+    }
+
+    pub fn list(workspace: &Workspace, agent: Option<&str>, status: Option<ProposalStatus>) -> CoreResult<Vec<Proposal>> {
+        let dir = Self::dir(workspace);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut proposals = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("yaml") {
+                continue;
+            }
+            let proposal = read(&path)?;
+            if agent.is_some_and(|a| proposal.agent != a) {
+                continue;
+            }
+            if status.is_some_and(|s| proposal.status != s) {
+                continue;
+            }
+            proposals.push(proposal);
+        }
+        proposals.sort_by(|a, b| a.created_at_unix.cmp(&b.created_at_unix));
+        Ok(proposals)
+    }
+
+    pub fn get(workspace: &Workspace, id: &str) -> CoreResult<Proposal> {
+        read(&Self::path(workspace, id))
+    }
+
+    /// Approve a proposal and apply its `AddNode`/`AddEdge`/`MatterVisibility`
+    /// changes to `graph`/the filesystem. `FileDiff` changes are recorded
+    /// but not applied here. A node the proposal didn't already stamp with
+    /// provenance is credited
+    /// to the submitting agent, since that's the one identity this store
+    /// actually knows for sure — `proposal.agent` is who called `POST
+    /// /api/v1/proposals` in the first place.
+    pub fn approve(workspace: &Workspace, id: &str, graph: &KnowledgeGraph) -> CoreResult<Proposal> {
+        let mut proposal = Self::get(workspace, id)?;
+        for change in &proposal.changes {
+            match change {
+                ProposedChange::AddNode { node } => {
+                    let node = if node.provenance.is_none() {
+                        node.clone().with_provenance(Provenance::new(
+                            ProvenanceSource::Agent(proposal.agent.clone()),
+                            &proposal.agent,
+                            env!("CARGO_PKG_VERSION"),
+                        ))
+                    } else {
+                        node.clone()
+                    };
+                    graph.add_node(&node)?
+                }
+                ProposedChange::AddEdge { edge } => graph.add_edge(edge)?,
+                ProposedChange::MatterVisibility { path, to } => {
+                    crate::matter::set_visibility(Path::new(path), *to)?
+                }
+                ProposedChange::FileDiff { .. } => {}
+            }
+        }
+        proposal.status = ProposalStatus::Approved;
+        proposal.updated_at_unix = now_unix();
+        write(&Self::path(workspace, id), &proposal)?;
+        Ok(proposal)
+    }
+
+    pub fn reject(workspace: &Workspace, id: &str, reason: Option<String>) -> CoreResult<Proposal> {
+        let mut proposal = Self::get(workspace, id)?;
+        proposal.status = ProposalStatus::Rejected;
+        proposal.rejection_reason = reason;
+        proposal.updated_at_unix = now_unix();
+        write(&Self::path(workspace, id), &proposal)?;
+        Ok(proposal)
+    }
+}
+
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    let mut last_dash = false;
+    for c in title.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+            last_dash = false;
+        } else if !last_dash {
+            slug.push('-');
+            last_dash = true;
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+fn read(path: &Path) -> CoreResult<Proposal> {
+    let contents = std::fs::read_to_string(path)?;
+    serde_yaml::from_str(&contents).map_err(|e| CoreError::Parse { path: path.to_path_buf(), message: e.to_string() })
+}
+
+fn write(path: &Path, proposal: &Proposal) -> CoreResult<()> {
+    let yaml = serde_yaml::to_string(proposal).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+    std::fs::write(path, yaml)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_lowercases_and_collapses_punctuation() {
+        assert_eq!(slugify("Add Retry Logic!!"), "add-retry-logic");
+    }
+
+    #[test]
+    fn create_list_approve_round_trip() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        let workspace = Workspace::discover(tmp.path()).unwrap();
+
+        let proposal = ProposalStore::create(
+            &workspace,
+            NewProposal { agent: "agent-a", title: "Test", description: "desc", changes: vec![] },
+        )
+        .unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Pending);
+
+        let pending = ProposalStore::list(&workspace, None, Some(ProposalStatus::Pending)).unwrap();
+        assert_eq!(pending.len(), 1);
+
+        let graph = KnowledgeGraph::init(&workspace.graph_db_path()).unwrap();
+        let approved = ProposalStore::approve(&workspace, &proposal.id, &graph).unwrap();
+        assert_eq!(approved.status, ProposalStatus::Approved);
+
+        let pending = ProposalStore::list(&workspace, None, Some(ProposalStatus::Pending)).unwrap();
+        assert!(pending.is_empty());
+    }
+}
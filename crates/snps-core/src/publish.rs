@@ -0,0 +1,434 @@
+//! `snps publish <output-dir>`: render shared-scope `thoughts/shared/`,
+//! `knowledge/`, and shared matter documents into a static HTML site,
+//! for stakeholders who won't run the CLI or daemon themselves.
+//!
+//! "Shared-scope" mirrors what [`crate::graph::sync_markdown_to_graph`]
+//! already treats as shared for thoughts (`thoughts/shared/`, not the
+//! whole `thoughts_dir` — there's no dedicated shared/personal split
+//! anywhere else in this tree, see that module's own doc comment) and
+//! what [`crate::repository::visibility::is_visible`] already treats as
+//! shared for matter (private unless `--include-private`, via a
+//! repository's [`crate::repository::Visibility`] and a document's own
+//! frontmatter override). `knowledge/` has no visibility concept at all —
+//! everything pulled into it is already shared by construction — so it's
+//! always included.
+//!
+//! Incremental like [`crate::graph::export_vault`]: a manifest of
+//! output-path -> content hash (via [`crate::knowledge::hash_contents`])
+//! next to the site lets a rebuild skip writing files whose rendered
+//! content didn't change.
+
+use crate::error::CoreResult;
+use crate::graph::document_id;
+use crate::knowledge::hash_contents;
+use crate::matter::{MatterIndex, MatterItem, MatterType};
+use crate::repository::{visibility, Repository};
+use crate::workspace::Workspace;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const MANIFEST_FILE: &str = ".snps-publish-manifest.yaml";
+
+/// Current user context for the same reason `commands::matter::visible`
+/// and the daemon's `matter_visible` hardcode it (`CURRENT_CONTEXT`):
+/// there's no multi-context session concept in this codebase yet, so
+/// "your own private matter" always means the `project` context's.
+const CURRENT_CONTEXT: &str = "project";
+
+/// Skip `--include-private`'s override entirely: a static site published
+/// to `<output-dir>` has no access control of its own, so honoring
+/// `--include-private` here would mean handing every future visitor of
+/// the output directory content the visibility system exists to gate.
+/// `include_private` still exists as an explicit, named opt-in (per this
+/// request) rather than silently always excluding private content with
+/// no way to override it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PublishOptions {
+    pub include_private: bool,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct PublishStats {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub skipped_private: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PublishManifest {
+    files: BTreeMap<String, String>,
+}
+
+impl PublishManifest {
+    fn path(dir: &Path) -> PathBuf {
+        dir.join(MANIFEST_FILE)
+    }
+
+    fn load(dir: &Path) -> Self {
+        let Ok(contents) = std::fs::read_to_string(Self::path(dir)) else { return Self::default() };
+        serde_yaml::from_str(&contents).unwrap_or_default()
+    }
+
+    fn save(&self, dir: &Path) -> CoreResult<()> {
+        let yaml = serde_yaml::to_string(self).map_err(|e| crate::error::CoreError::InvalidInput(e.to_string()))?;
+        std::fs::write(Self::path(dir), yaml)?;
+        Ok(())
+    }
+}
+
+/// One document staged for rendering, before wiki-links can be resolved
+/// (resolution needs every document's output path known up front).
+struct StagedDoc {
+    id: String,
+    title: String,
+    category: String,
+    output_path: String,
+    body: String,
+}
+
+/// Render `workspace`'s shared thoughts, knowledge, and (unless
+/// `--include-private`-gated) matter documents into a static site at
+/// `output_dir`. Safe to call repeatedly against the same directory —
+/// unchanged pages are left untouched.
+pub fn publish(workspace: &Workspace, output_dir: &Path, options: PublishOptions) -> CoreResult<PublishStats> {
+    std::fs::create_dir_all(output_dir)?;
+    let manifest = PublishManifest::load(output_dir);
+    let mut new_files: BTreeMap<String, String> = BTreeMap::new();
+    let mut stats = PublishStats::default();
+
+    let mut docs = Vec::new();
+    docs.extend(collect_markdown_tree(&workspace.thoughts_dir().join("shared"), &workspace.root, "thoughts"));
+    docs.extend(collect_markdown_tree(&workspace.knowledge_dir(), &workspace.root, "knowledge"));
+
+    let repos = Repository::load_all(workspace).unwrap_or_default();
+    let index = MatterIndex::build(&workspace.root)?;
+    for item in &index.items {
+        if !matter_shared(&repos, item, options.include_private) {
+            stats.skipped_private += 1;
+            continue;
+        }
+        docs.push(StagedDoc {
+            id: item.id.clone(),
+            title: item.title.clone(),
+            category: format!("matter/{}", item.matter_type.dir_name()),
+            output_path: format!("matter/{}/{}.html", item.matter_type.dir_name(), item.id),
+            body: item.body.clone(),
+        });
+    }
+
+    let url_by_id: BTreeMap<String, String> = docs.iter().map(|d| (d.id.clone(), d.output_path.clone())).collect();
+
+    let mut search_entries = Vec::new();
+    for doc in &docs {
+        let html = render_page(&doc.title, &render_body(&doc.body, &doc.output_path, &url_by_id));
+        write_if_changed(output_dir, &doc.output_path, &html, &manifest, &mut new_files, &mut stats)?;
+        search_entries.push(SearchEntry { title: doc.title.clone(), url: doc.output_path.clone(), category: doc.category.clone(), excerpt: excerpt(&doc.body) });
+    }
+
+    let idlc_path = workspace.teams_dir().join(crate::team::active_team_id(workspace)).join("idlc.yaml");
+    let idlc_config = crate::idlc::parse_idlc_config(&std::fs::read_to_string(idlc_path).unwrap_or_default()).unwrap_or_default();
+    let workflow_html = render_page("Workflow", &render_workflow(&idlc_config));
+    write_if_changed(output_dir, "workflow.html", &workflow_html, &manifest, &mut new_files, &mut stats)?;
+
+    let index_html = render_page("Index", &render_index(&docs));
+    write_if_changed(output_dir, "index.html", &index_html, &manifest, &mut new_files, &mut stats)?;
+
+    let search_json = serde_json::to_string(&search_entries)?;
+    write_if_changed(output_dir, "search-index.json", &search_json, &manifest, &mut new_files, &mut stats)?;
+    write_if_changed(output_dir, "assets/search.js", SEARCH_JS, &manifest, &mut new_files, &mut stats)?;
+
+    PublishManifest { files: new_files }.save(output_dir)?;
+    Ok(stats)
+}
+
+fn matter_shared(repos: &[Repository], item: &MatterItem, include_private: bool) -> bool {
+    match Repository::owning(repos, &item.path) {
+        Some(repo) => visibility::is_visible(repo, item, include_private, CURRENT_CONTEXT),
+        // No owning repository on record for this matter file — nothing
+        // says it's private, so it publishes, same default `matter_visible`
+        // in the CLI and daemon use.
+        None => true,
+    }
+}
+
+fn write_if_changed(
+    output_dir: &Path,
+    relative: &str,
+    contents: &str,
+    manifest: &PublishManifest,
+    new_files: &mut BTreeMap<String, String>,
+    stats: &mut PublishStats,
+) -> CoreResult<()> {
+    let hash = hash_contents(contents.as_bytes());
+    let path = output_dir.join(relative);
+    match manifest.files.get(relative) {
+        Some(existing) if existing == &hash => stats.unchanged += 1,
+        Some(_) => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, contents)?;
+            stats.updated += 1;
+        }
+        None => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, contents)?;
+            stats.created += 1;
+        }
+    }
+    new_files.insert(relative.to_string(), hash);
+    Ok(())
+}
+
+fn collect_markdown_tree(dir: &Path, workspace_root: &Path, category: &str) -> Vec<StagedDoc> {
+    let mut out = Vec::new();
+    collect_markdown_tree_inner(dir, dir, workspace_root, category, &mut out);
+    out
+}
+
+fn collect_markdown_tree_inner(dir: &Path, tree_root: &Path, workspace_root: &Path, category: &str, out: &mut Vec<StagedDoc>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_markdown_tree_inner(&path, tree_root, workspace_root, category, out);
+        } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+            let Ok(contents) = std::fs::read_to_string(&path) else { continue };
+            let matter_id = frontmatter_title_field(&contents, "matter_id");
+            let id = document_id(&path, workspace_root, matter_id.as_deref());
+            let title = frontmatter_title_field(&contents, "title").unwrap_or_else(|| fallback_title(&path));
+            let relative = path.strip_prefix(tree_root).unwrap_or(&path);
+            let output_path = format!("{category}/{}", relative.with_extension("html").to_string_lossy().replace('\\', "/"));
+            out.push(StagedDoc { id, title, category: category.to_string(), output_path, body: contents });
+        }
+    }
+}
+
+fn fallback_title(path: &Path) -> String {
+    path.file_stem().and_then(|s| s.to_str()).unwrap_or("untitled").to_string()
+}
+
+/// Minimal frontmatter field lookup, duplicated from
+/// [`crate::graph::sync`]'s `frontmatter_field` rather than exposed from
+/// there — same reasoning the daemon's `matter_visible` gives for its own
+/// duplicate of `commands::matter::visible`: no shared caller exists
+/// across these two modules to justify threading a dependency between them.
+fn frontmatter_title_field(contents: &str, key: &str) -> Option<String> {
+    let rest = contents.strip_prefix("---\n")?;
+    let end = rest.find("\n---\n")?;
+    rest[..end].lines().find_map(|line| {
+        let (k, v) = line.split_once(':')?;
+        (k.trim() == key).then(|| v.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Rewrite `[[id]]`/`[[id|label]]` wiki-links and relative `[text](*.md)`
+/// links to relative HTML hrefs against `url_by_id`, then render the
+/// result as markdown. Links to a document that isn't part of this
+/// publish (private matter, or a path outside the three published trees)
+/// are left as-is: plain text for a wiki-link (there's nothing sensible
+/// to link to), unchanged for a markdown link (it may well be valid
+/// relative to wherever the source repo itself is browsed).
+fn render_body(body: &str, own_output_path: &str, url_by_id: &BTreeMap<String, String>) -> String {
+    let with_wikilinks_resolved = rewrite_wikilinks(strip_frontmatter(body), own_output_path, url_by_id);
+    let mut html_out = String::new();
+    let options = pulldown_cmark::Options::ENABLE_TABLES | pulldown_cmark::Options::ENABLE_STRIKETHROUGH;
+    let parser = pulldown_cmark::Parser::new_ext(&with_wikilinks_resolved, options);
+    pulldown_cmark::html::push_html(&mut html_out, parser);
+    html_out
+}
+
+fn rewrite_wikilinks(body: &str, own_output_path: &str, url_by_id: &BTreeMap<String, String>) -> String {
+    let mut out = String::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("[[") {
+        out.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("]]") else {
+            out.push_str("[[");
+            break;
+        };
+        let inner = &rest[..end];
+        rest = &rest[end + 2..];
+
+        let mut parts = inner.splitn(2, '|');
+        let target = parts.next().unwrap_or(inner).trim();
+        let label = parts.next().map(str::trim).unwrap_or(target);
+
+        match url_by_id.get(target) {
+            Some(href) => out.push_str(&format!("[{label}]({})", relative_href(own_output_path, href))),
+            None => out.push_str(label),
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `href`'s path relative to the directory `from` (an output-relative
+/// path like `matter/specs/foo.html`) lives in, so links between pages in
+/// different subdirectories still resolve after the site is copied
+/// somewhere else — an absolute `/matter/specs/foo.html` would only work
+/// if the site is served from its filesystem root.
+fn relative_href(from: &str, href: &str) -> String {
+    let depth = from.matches('/').count();
+    format!("{}{href}", "../".repeat(depth))
+}
+
+/// Strip a leading `---`-delimited frontmatter block, if present, so it
+/// doesn't leak into a search excerpt (or, via [`render_body`], the page
+/// itself — matter/knowledge/thoughts frontmatter isn't meant to render).
+fn strip_frontmatter(body: &str) -> &str {
+    let Some(rest) = body.strip_prefix("---\n") else { return body };
+    match rest.find("\n---\n") {
+        Some(end) => &rest[end + 5..],
+        None => body,
+    }
+}
+
+fn excerpt(body: &str) -> String {
+    let without_frontmatter = strip_frontmatter(body);
+    let text: String = without_frontmatter.lines().filter(|l| !l.trim().is_empty()).collect::<Vec<_>>().join(" ");
+    let trimmed = text.trim();
+    if trimmed.chars().count() <= 200 {
+        trimmed.to_string()
+    } else {
+        format!("{}...", trimmed.chars().take(200).collect::<String>())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SearchEntry {
+    title: String,
+    url: String,
+    category: String,
+    excerpt: String,
+}
+
+fn render_index(docs: &[StagedDoc]) -> String {
+    let mut by_category: BTreeMap<&str, Vec<&StagedDoc>> = BTreeMap::new();
+    for doc in docs {
+        by_category.entry(doc.category.as_str()).or_default().push(doc);
+    }
+
+    let mut out = String::from("<div id=\"search\"><input id=\"search-box\" placeholder=\"Search...\"><ul id=\"search-results\"></ul></div>\n");
+    out.push_str("<p><a href=\"workflow.html\">Workflow</a></p>\n");
+    for (category, members) in &by_category {
+        out.push_str(&format!("<h2>{}</h2>\n<ul>\n", html_escape(category)));
+        for doc in members {
+            out.push_str(&format!("<li><a href=\"{}\">{}</a></li>\n", doc.output_path, html_escape(&doc.title)));
+        }
+        out.push_str("</ul>\n");
+    }
+    out
+}
+
+fn render_workflow(config: &crate::idlc::IdlcConfig) -> String {
+    format!(
+        "<p><a href=\"index.html\">Index</a></p>\n<h1>Workflow</h1>\n<pre class=\"mermaid\">\n{}\n</pre>\n\
+         <script src=\"https://cdn.jsdelivr.net/npm/mermaid/dist/mermaid.min.js\"></script>\n\
+         <script>mermaid.initialize({{startOnLoad: true}});</script>\n",
+        crate::idlc::idlc_visualize(config)
+    )
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Wrap a rendered page body in the site's shared shell. `body` is already
+/// HTML (from [`render_body`]/[`render_index`]/[`render_workflow`]).
+fn render_page(title: &str, body: &str) -> String {
+    format!(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>{}</title>\n\
+         <style>body{{font-family:sans-serif;max-width:48rem;margin:2rem auto;padding:0 1rem;line-height:1.5}}</style>\n\
+         <script src=\"assets/search.js\" defer></script>\n</head><body>\n{body}\n</body></html>\n",
+        html_escape(title)
+    )
+}
+
+/// Fetches `search-index.json` (built alongside every page — see
+/// `publish`) and filters it client-side by title/excerpt substring; no
+/// build step or bundler needed to serve the site as plain static files.
+const SEARCH_JS: &str = r#"document.addEventListener('DOMContentLoaded', function () {
+  var box = document.getElementById('search-box');
+  var results = document.getElementById('search-results');
+  if (!box || !results) return;
+  fetch('search-index.json').then(function (r) { return r.json(); }).then(function (entries) {
+    box.addEventListener('input', function () {
+      var q = box.value.trim().toLowerCase();
+      results.innerHTML = '';
+      if (!q) return;
+      entries
+        .filter(function (e) { return e.title.toLowerCase().includes(q) || e.excerpt.toLowerCase().includes(q); })
+        .slice(0, 20)
+        .forEach(function (e) {
+          var li = document.createElement('li');
+          li.innerHTML = '<a href="' + e.url + '">' + e.title + '</a> <small>(' + e.category + ')</small>';
+          results.appendChild(li);
+        });
+    });
+  });
+});
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrite_wikilinks_resolves_a_known_id_across_directories() {
+        let mut urls = BTreeMap::new();
+        urls.insert("api-design".to_string(), "knowledge/research/api-design.html".to_string());
+        let rewritten = rewrite_wikilinks("see [[api-design|the design]] for context", "matter/specs/foo.html", &urls);
+        assert_eq!(rewritten, "see [the design](../knowledge/research/api-design.html) for context");
+    }
+
+    #[test]
+    fn rewrite_wikilinks_leaves_unresolved_targets_as_plain_text() {
+        let urls = BTreeMap::new();
+        let rewritten = rewrite_wikilinks("see [[private-note]] for context", "matter/specs/foo.html", &urls);
+        assert_eq!(rewritten, "see private-note for context");
+    }
+
+    #[test]
+    fn relative_href_climbs_out_of_nested_output_paths() {
+        assert_eq!(relative_href("matter/specs/foo.html", "knowledge/bar.html"), "../../knowledge/bar.html");
+        assert_eq!(relative_href("index.html", "knowledge/bar.html"), "knowledge/bar.html");
+    }
+
+    #[test]
+    fn excerpt_strips_frontmatter_and_truncates() {
+        let body = "---\ntitle: X\n---\n\nfirst paragraph text here";
+        assert_eq!(excerpt(body), "first paragraph text here");
+    }
+
+    #[test]
+    fn matter_shared_excludes_private_by_default() {
+        let repo = Repository {
+            id: "r".into(),
+            context: "project".into(),
+            path: PathBuf::from("/repo"),
+            visibility: crate::repository::Visibility::Private,
+            excludes: vec![],
+            sync_strategy: Default::default(),
+        };
+        let item = MatterItem {
+            id: "i".into(),
+            matter_type: MatterType::Document,
+            title: "T".into(),
+            author: None,
+            tags: vec![],
+            context: "project".into(),
+            path: PathBuf::from("/repo/matter/documents/i.md"),
+            body: String::new(),
+            visibility: None,
+            created: None,
+        };
+        assert!(!matter_shared(&[repo.clone()], &item, false));
+        assert!(matter_shared(&[repo], &item, true));
+    }
+}
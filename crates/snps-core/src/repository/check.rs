@@ -0,0 +1,226 @@
+//! `snps repo check` — validate `repositories.yaml` against the filesystem.
+//!
+//! There's no `.pmsynapse/context.yaml` in this codebase for a check to
+//! read a repository's own recorded `context` back out of and compare —
+//! `context` is purely a tag inside `repositories.yaml` itself (see
+//! `config::shadow`'s `repo_for_context`), not something the repository at
+//! `path` stores about itself. What *is* real and checkable is what
+//! `snps repo init` creates there: a `.pmsynapse/` marker directory. A
+//! moved-but-still-present repository is likewise identified by directory
+//! name under the configured `repositories_root` rather than by reading
+//! back an identity file that doesn't exist.
+
+use crate::config::load_merged_config;
+use crate::error::{CoreError, CoreResult};
+use crate::repository::Repository;
+use crate::workspace::Workspace;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoIssue {
+    /// The recorded path doesn't exist at all.
+    MissingPath,
+    /// The recorded path exists but isn't a directory.
+    NotADirectory,
+    /// The path exists but has no `.pmsynapse` marker — never
+    /// `snps repo init`ed, or the marker was removed.
+    NotScaffolded,
+    /// Another entry in `repositories.yaml` uses the same `id`.
+    DuplicateId,
+}
+
+impl RepoIssue {
+    pub fn description(&self) -> &'static str {
+        match self {
+            RepoIssue::MissingPath => "path does not exist",
+            RepoIssue::NotADirectory => "path is not a directory",
+            RepoIssue::NotScaffolded => "path has no .pmsynapse directory",
+            RepoIssue::DuplicateId => "id is used by more than one entry",
+        }
+    }
+}
+
+/// One problem found with one `repositories.yaml` entry.
+#[derive(Debug, Clone)]
+pub struct RepoFinding {
+    pub id: String,
+    pub path: PathBuf,
+    pub issue: RepoIssue,
+    /// For [`RepoIssue::MissingPath`], a same-named directory under
+    /// `repositories_root` that looks like the entry's new location.
+    pub relocated: Option<PathBuf>,
+}
+
+/// Check every entry in `repositories.yaml`: path existence and
+/// directory-ness, `.pmsynapse` scaffolding, and `id` uniqueness across
+/// entries. An empty result means the file is clean.
+pub fn check_repositories(workspace: &Workspace) -> CoreResult<Vec<RepoFinding>> {
+    let repos = Repository::load_all(workspace)?;
+    let repositories_root = repositories_root(workspace);
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for repo in &repos {
+        *counts.entry(repo.id.as_str()).or_default() += 1;
+    }
+
+    let mut findings = Vec::new();
+    for repo in &repos {
+        if counts[repo.id.as_str()] > 1 {
+            findings.push(RepoFinding { id: repo.id.clone(), path: repo.path.clone(), issue: RepoIssue::DuplicateId, relocated: None });
+        }
+
+        if !repo.path.exists() {
+            let relocated = find_relocated(&repositories_root, &repo.id);
+            findings.push(RepoFinding { id: repo.id.clone(), path: repo.path.clone(), issue: RepoIssue::MissingPath, relocated });
+        } else if !repo.path.is_dir() {
+            findings.push(RepoFinding { id: repo.id.clone(), path: repo.path.clone(), issue: RepoIssue::NotADirectory, relocated: None });
+        } else if !repo.path.join(".pmsynapse").is_dir() {
+            findings.push(RepoFinding { id: repo.id.clone(), path: repo.path.clone(), issue: RepoIssue::NotScaffolded, relocated: None });
+        }
+    }
+
+    Ok(findings)
+}
+
+fn repositories_root(workspace: &Workspace) -> PathBuf {
+    load_merged_config(workspace)
+        .map(|merged| workspace.root.join(merged.config.repositories_root))
+        .unwrap_or_else(|_| workspace.root.clone())
+}
+
+fn find_relocated(repositories_root: &Path, id: &str) -> Option<PathBuf> {
+    let candidate = repositories_root.join(id);
+    candidate.join(".pmsynapse").is_dir().then_some(candidate)
+}
+
+/// Apply repairs implied by `findings` to `repos`: entries whose path
+/// moved get the relocated path; duplicate ids beyond the first occurrence
+/// get a `-2`, `-3`, ... suffix appended until unique. Entries with
+/// [`RepoIssue::MissingPath`] and no relocation candidate are dropped only
+/// when `prune_missing` is set — callers doing interactive `--fix` decide
+/// that case per entry instead and should pass `false` here, then remove
+/// confirmed entries themselves.
+pub fn repair(repos: Vec<Repository>, findings: &[RepoFinding], prune_missing: bool) -> Vec<Repository> {
+    let mut seen_ids: HashMap<String, usize> = HashMap::new();
+    let mut repaired = Vec::with_capacity(repos.len());
+
+    for mut repo in repos {
+        if let Some(finding) = findings.iter().find(|f| f.id == repo.id && f.issue == RepoIssue::MissingPath) {
+            match &finding.relocated {
+                Some(new_path) => repo.path = new_path.clone(),
+                None if prune_missing => continue,
+                None => {}
+            }
+        }
+
+        let count = seen_ids.entry(repo.id.clone()).or_default();
+        if *count > 0 {
+            let mut suffix = *count + 1;
+            let mut candidate = format!("{}-{suffix}", repo.id);
+            while seen_ids.contains_key(&candidate) {
+                suffix += 1;
+                candidate = format!("{}-{suffix}", repo.id);
+            }
+            seen_ids.insert(candidate.clone(), 1);
+            repo.id = candidate;
+        }
+        *seen_ids.entry(repo.id.clone()).or_default() += 1;
+
+        repaired.push(repo);
+    }
+
+    repaired
+}
+
+/// Serialize `repos` back to `repositories.yaml`, replacing its contents.
+/// Used by `snps repo check --fix`/`--prune` after computing repairs.
+pub fn save_repositories(workspace: &Workspace, repos: &[Repository]) -> CoreResult<()> {
+    let yaml = serde_yaml::to_string(repos)
+        .map_err(|e| CoreError::InvalidInput(format!("failed to serialize repositories.yaml: {e}")))?;
+    std::fs::write(workspace.repositories_path(), yaml)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{LinkStrategy, Visibility};
+
+    fn repo(id: &str, path: &Path) -> Repository {
+        Repository {
+            id: id.to_string(),
+            context: "project".to_string(),
+            path: path.to_path_buf(),
+            visibility: Visibility::Shared,
+            excludes: vec![],
+            sync_strategy: LinkStrategy::Copy,
+        }
+    }
+
+    #[test]
+    fn flags_missing_and_unscaffolded_paths() {
+        let tmp = tempfile::tempdir().unwrap();
+        let scaffolded = tmp.path().join("scaffolded");
+        std::fs::create_dir_all(scaffolded.join(".pmsynapse")).unwrap();
+        let unscaffolded = tmp.path().join("unscaffolded");
+        std::fs::create_dir_all(&unscaffolded).unwrap();
+
+        let repos = vec![repo("ok", &scaffolded), repo("bare", &unscaffolded), repo("gone", &tmp.path().join("nowhere"))];
+        let workspace = Workspace { root: tmp.path().to_path_buf() };
+        std::fs::create_dir_all(workspace.pmsynapse_dir()).unwrap();
+        save_repositories(&workspace, &repos).unwrap();
+
+        let findings = check_repositories(&workspace).unwrap();
+        assert!(findings.iter().any(|f| f.id == "bare" && f.issue == RepoIssue::NotScaffolded));
+        assert!(findings.iter().any(|f| f.id == "gone" && f.issue == RepoIssue::MissingPath));
+        assert!(!findings.iter().any(|f| f.id == "ok"));
+    }
+
+    #[test]
+    fn flags_duplicate_ids() {
+        let tmp = tempfile::tempdir().unwrap();
+        let a = tmp.path().join("a");
+        let b = tmp.path().join("b");
+        std::fs::create_dir_all(a.join(".pmsynapse")).unwrap();
+        std::fs::create_dir_all(b.join(".pmsynapse")).unwrap();
+
+        let repos = vec![repo("dup", &a), repo("dup", &b)];
+        let workspace = Workspace { root: tmp.path().to_path_buf() };
+        std::fs::create_dir_all(workspace.pmsynapse_dir()).unwrap();
+        save_repositories(&workspace, &repos).unwrap();
+
+        let findings = check_repositories(&workspace).unwrap();
+        assert_eq!(findings.iter().filter(|f| f.issue == RepoIssue::DuplicateId).count(), 2);
+    }
+
+    #[test]
+    fn repair_relocates_and_renames_duplicates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let moved = tmp.path().join("moved");
+        std::fs::create_dir_all(moved.join(".pmsynapse")).unwrap();
+        let recorded = tmp.path().join("old-location");
+
+        let repos = vec![repo("moved", &recorded), repo("dup", &tmp.path().join("x")), repo("dup", &tmp.path().join("y"))];
+        let findings = vec![
+            RepoFinding { id: "moved".into(), path: recorded.clone(), issue: RepoIssue::MissingPath, relocated: Some(moved.clone()) },
+            RepoFinding { id: "dup".into(), path: tmp.path().join("x"), issue: RepoIssue::DuplicateId, relocated: None },
+            RepoFinding { id: "dup".into(), path: tmp.path().join("y"), issue: RepoIssue::DuplicateId, relocated: None },
+        ];
+
+        let repaired = repair(repos, &findings, false);
+        assert_eq!(repaired[0].path, moved);
+        assert_eq!(repaired[1].id, "dup");
+        assert_eq!(repaired[2].id, "dup-2");
+    }
+
+    #[test]
+    fn repair_prunes_missing_entries_with_no_relocation_when_asked() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repos = vec![repo("gone", &tmp.path().join("nowhere"))];
+        let findings = vec![RepoFinding { id: "gone".into(), path: tmp.path().join("nowhere"), issue: RepoIssue::MissingPath, relocated: None }];
+
+        assert!(repair(repos.clone(), &findings, true).is_empty());
+        assert_eq!(repair(repos, &findings, false).len(), 1);
+    }
+}
@@ -0,0 +1,142 @@
+//! Directory scaffolding for `snps repo init`.
+//!
+//! There's no `generate_matter_path` function in this codebase — matter
+//! documents are written to a hardcoded `matter/<type-dir>/` by
+//! [`crate::matter::matter_create`], regardless of anything here. What
+//! this module actually shares with that path is the *vocabulary*:
+//! [`RepoLayout::directories`] names its directories via
+//! [`crate::matter::MatterType::dir_name`] wherever a layout's directory
+//! corresponds to a real matter type, rather than duplicating "specs",
+//! "research", etc. as a second set of string literals that could quietly
+//! drift out of sync with the first. Wiring `matter_create` itself to
+//! place new documents under a chosen layout's directories instead of the
+//! flat `matter/` tree is a bigger, separate change than scaffolding one.
+
+use crate::error::CoreResult;
+use crate::matter::MatterType;
+use std::path::Path;
+
+/// A built-in starting directory structure for a freshly initialized
+/// repository, picked by context: a single contributor's private
+/// documents, a team's shared ones, or a project's planning documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoLayout {
+    User,
+    Team,
+    Project,
+}
+
+impl RepoLayout {
+    pub const ALL: [RepoLayout; 3] = [RepoLayout::User, RepoLayout::Team, RepoLayout::Project];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RepoLayout::User => "user",
+            RepoLayout::Team => "team",
+            RepoLayout::Project => "project",
+        }
+    }
+
+    /// Directories scaffolded under the repository root, relative paths.
+    /// `Team`'s nest under `shared/` since a team repository's whole
+    /// point is that everything in it is shared, unlike a user's private
+    /// one. `"decisions"` has no corresponding [`MatterType`] today —
+    /// kept as a plain literal rather than inventing one just for this.
+    pub fn directories(&self) -> Vec<String> {
+        match self {
+            RepoLayout::User => vec![MatterType::Spec.dir_name().to_string(), MatterType::Research.dir_name().to_string(), MatterType::Insight.dir_name().to_string()],
+            RepoLayout::Team => vec![
+                format!("shared/{}", MatterType::Spec.dir_name()),
+                format!("shared/{}", MatterType::Plan.dir_name()),
+                "shared/decisions".to_string(),
+            ],
+            RepoLayout::Project => vec![MatterType::Document.dir_name().to_string(), MatterType::Research.dir_name().to_string(), MatterType::Plan.dir_name().to_string()],
+        }
+    }
+
+    fn readme_body(&self) -> &'static str {
+        match self {
+            RepoLayout::User => {
+                "A personal PMSynapse repository: specs, research, and insights that belong to one contributor rather than a team or project."
+            }
+            RepoLayout::Team => "A team PMSynapse repository: everything under `shared/` is visible to the whole team by default.",
+            RepoLayout::Project => "A project PMSynapse repository: documents, research, and plans for a single project.",
+        }
+    }
+}
+
+impl std::str::FromStr for RepoLayout {
+    type Err = crate::error::CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(RepoLayout::User),
+            "team" => Ok(RepoLayout::Team),
+            "project" => Ok(RepoLayout::Project),
+            other => Err(crate::error::CoreError::InvalidInput(format!(
+                "unknown layout '{other}' (expected one of: {})",
+                RepoLayout::ALL.iter().map(|l| l.as_str()).collect::<Vec<_>>().join(", ")
+            ))),
+        }
+    }
+}
+
+/// Create `layout`'s directories under `root`, a `README.md` describing
+/// the repository, and a `.gitignore` excluding generated files (the
+/// search index cache and the gitignored project-local config override) —
+/// what `snps repo init` runs unless `--bare` is passed.
+pub fn scaffold(root: &Path, layout: RepoLayout) -> CoreResult<()> {
+    for dir in layout.directories() {
+        std::fs::create_dir_all(root.join(dir))?;
+    }
+
+    let readme = root.join("README.md");
+    if !readme.exists() {
+        std::fs::write(&readme, format!("# {}\n\n{}\n", root.file_name().and_then(|n| n.to_str()).unwrap_or("Repository"), layout.readme_body()))?;
+    }
+
+    let gitignore = root.join(".gitignore");
+    if !gitignore.exists() {
+        std::fs::write(&gitignore, ".pmsynapse/search-index.json\n.pmsynapse/config.local.yaml\n")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directories_share_matter_type_names_where_one_exists() {
+        assert_eq!(RepoLayout::User.directories(), vec!["specs", "research", "insights"]);
+        assert_eq!(RepoLayout::Project.directories(), vec!["documents", "research", "plans"]);
+        assert_eq!(RepoLayout::Team.directories(), vec!["shared/specs", "shared/plans", "shared/decisions"]);
+    }
+
+    #[test]
+    fn from_str_rejects_unknown_layouts() {
+        assert!("nonsense".parse::<RepoLayout>().is_err());
+        assert_eq!("team".parse::<RepoLayout>().unwrap(), RepoLayout::Team);
+    }
+
+    #[test]
+    fn scaffold_creates_directories_readme_and_gitignore() {
+        let tmp = tempfile::tempdir().unwrap();
+        scaffold(tmp.path(), RepoLayout::Project).unwrap();
+
+        assert!(tmp.path().join("documents").is_dir());
+        assert!(tmp.path().join("research").is_dir());
+        assert!(tmp.path().join("plans").is_dir());
+        assert!(tmp.path().join("README.md").is_file());
+        assert!(tmp.path().join(".gitignore").is_file());
+    }
+
+    #[test]
+    fn scaffold_does_not_overwrite_an_existing_readme() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("README.md"), "custom content\n").unwrap();
+        scaffold(tmp.path(), RepoLayout::User).unwrap();
+        assert_eq!(std::fs::read_to_string(tmp.path().join("README.md")).unwrap(), "custom content\n");
+    }
+}
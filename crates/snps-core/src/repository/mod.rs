@@ -0,0 +1,121 @@
+//! Shadow repository operations: sync (pull/push), status, and the config
+//! that drives them (`repositories.yaml`).
+
+pub mod check;
+pub mod layout;
+pub mod sync;
+pub mod visibility;
+
+pub use check::{check_repositories, repair, save_repositories, RepoFinding, RepoIssue};
+pub use layout::{scaffold, RepoLayout};
+pub use sync::{sync_repository, SyncOutcome, SyncStrategy, SyncSummary};
+pub use visibility::{effective_visibility, Visibility};
+
+use crate::error::CoreResult;
+use crate::workspace::Workspace;
+use std::path::PathBuf;
+
+/// How a knowledge file pulled from a shadow repo lands in the local
+/// `knowledge/` working copy. Configured per-repository via
+/// `sync_strategy` in `repositories.yaml`; `Copy` (the historical
+/// behavior) stays the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkStrategy {
+    #[default]
+    Copy,
+    Symlink,
+    Hardlink,
+}
+
+impl LinkStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkStrategy::Copy => "copy",
+            LinkStrategy::Symlink => "symlink",
+            LinkStrategy::Hardlink => "hardlink",
+        }
+    }
+}
+
+impl std::fmt::Display for LinkStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for LinkStrategy {
+    type Err = crate::error::CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "copy" => Ok(LinkStrategy::Copy),
+            "symlink" => Ok(LinkStrategy::Symlink),
+            "hardlink" => Ok(LinkStrategy::Hardlink),
+            other => Err(crate::error::CoreError::InvalidInput(format!(
+                "unknown sync strategy '{other}'"
+            ))),
+        }
+    }
+}
+
+/// A configured shadow repository, as read from `repositories.yaml`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Repository {
+    pub id: String,
+    pub context: String,
+    pub path: PathBuf,
+    pub visibility: Visibility,
+    /// Glob patterns (relative to this repository's `path`) excluded from
+    /// matter listing and index rebuilds, merged with the workspace's
+    /// `search.exclude_patterns`. See [`crate::fswalk`].
+    pub excludes: Vec<String>,
+    /// How knowledge files pulled from this repo land in `knowledge/` —
+    /// see [`crate::knowledge::materialize`].
+    pub sync_strategy: LinkStrategy,
+}
+
+impl Repository {
+    /// Load every repository configured in `repositories.yaml`. Missing
+    /// visibility defaults to `Shared` for backward compatibility with
+    /// configs written before visibility existed.
+    pub fn load_all(workspace: &Workspace) -> CoreResult<Vec<Repository>> {
+        let path = workspace.repositories_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        let raw: Vec<serde_yaml::Value> = serde_yaml::from_str(&contents).unwrap_or_default();
+        Ok(raw
+            .into_iter()
+            .filter_map(|v| {
+                Some(Repository {
+                    id: v.get("id")?.as_str()?.to_string(),
+                    context: v.get("context")?.as_str()?.to_string(),
+                    path: v.get("path")?.as_str()?.into(),
+                    visibility: v
+                        .get("visibility")
+                        .and_then(|v| v.as_str())
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(Visibility::Shared),
+                    excludes: v
+                        .get("excludes")
+                        .and_then(|v| v.as_sequence())
+                        .map(|seq| seq.iter().filter_map(|p| p.as_str().map(str::to_string)).collect())
+                        .unwrap_or_default(),
+                    sync_strategy: v
+                        .get("sync_strategy")
+                        .and_then(|v| v.as_str())
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
+    /// Find the repository whose path is a prefix of `item_path`, i.e. the
+    /// repository that owns a given matter document.
+    pub fn owning<'a>(repos: &'a [Repository], item_path: &std::path::Path) -> Option<&'a Repository> {
+        repos.iter().find(|r| item_path.starts_with(&r.path))
+    }
+}
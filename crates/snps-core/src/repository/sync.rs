@@ -0,0 +1,152 @@
+//! Repository sync: fetch, detect divergence, and either fast-forward or
+//! rebase depending on the configured strategy, aborting cleanly on
+//! conflicts instead of leaving the repo mid-merge.
+
+use super::Repository;
+use crate::error::{CoreError, CoreResult};
+use crate::git::{Divergence, GitRepo};
+use std::process::Command;
+
+/// How `sync_repository` should reconcile local and remote history when
+/// they've diverged. Configured per-repository via `sync.strategy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncStrategy {
+    #[default]
+    FastForward,
+    Rebase,
+}
+
+impl std::str::FromStr for SyncStrategy {
+    type Err = CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fast-forward" | "ff" => Ok(SyncStrategy::FastForward),
+            "rebase" => Ok(SyncStrategy::Rebase),
+            other => Err(CoreError::InvalidInput(format!(
+                "unknown sync strategy '{other}'"
+            ))),
+        }
+    }
+}
+
+/// Per-repository sync result, distinguishing every terminal state so the
+/// CLI summary can report them separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncOutcome {
+    UpToDate,
+    FastForwarded,
+    Rebased,
+    /// Local and remote history diverged and `strategy` refuses to act on
+    /// it without rewriting history (a `FastForward` repo that can't
+    /// fast-forward) — nothing is actually in a conflicted state, there's
+    /// just no safe automatic move. Distinct from [`SyncOutcome::Conflicted`],
+    /// which means a rebase was attempted and left real unresolved files.
+    Diverged,
+    Conflicted { files: Vec<String> },
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncSummary {
+    pub repository_id: String,
+    pub outcome: SyncOutcome,
+}
+
+/// The dedicated exit code used by `snps repo sync` when a repository is
+/// left conflicted, distinct from generic failures.
+pub const CONFLICT_EXIT_CODE: i32 = 3;
+
+/// The dedicated exit code used by `snps repo sync` when a repository is
+/// left diverged rather than actually conflicted — see
+/// [`SyncOutcome::Diverged`]. Distinct from [`CONFLICT_EXIT_CODE`] so a
+/// script can tell "pick a strategy and rerun" apart from "go resolve
+/// files".
+pub const DIVERGED_EXIT_CODE: i32 = 4;
+
+// Push still shells out: git2's push requires wiring up credential and
+// certificate callbacks for every auth method shadow repos might use
+// (SSH agent, HTTPS token, etc.), which isn't worth duplicating when the
+// user's own git config already handles it.
+pub(crate) fn git_push(repo_path: &std::path::Path) -> CoreResult<bool> {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["push", "--quiet"])
+        .status()?;
+    Ok(status.success())
+}
+
+/// Sync a single shadow repository: fetch, then reconcile according to
+/// `strategy`. `push` is skipped entirely when `false` (`--no-push`).
+pub fn sync_repository(
+    repo: &Repository,
+    strategy: SyncStrategy,
+    push: bool,
+) -> CoreResult<SyncSummary> {
+    let git = GitRepo::open(&repo.path)?;
+    git.fetch()?;
+
+    match git.divergence()? {
+        Divergence::UpToDate => {
+            if push {
+                git_push(&repo.path)?;
+            }
+            Ok(SyncSummary {
+                repository_id: repo.id.clone(),
+                outcome: SyncOutcome::UpToDate,
+            })
+        }
+        Divergence::FastForwardable if strategy == SyncStrategy::FastForward => {
+            git.fast_forward()?;
+            if push {
+                git_push(&repo.path)?;
+            }
+            Ok(SyncSummary {
+                repository_id: repo.id.clone(),
+                outcome: SyncOutcome::FastForwarded,
+            })
+        }
+        _ => reconcile(repo, &git, strategy, push),
+    }
+}
+
+fn reconcile(
+    repo: &Repository,
+    git: &GitRepo,
+    strategy: SyncStrategy,
+    push: bool,
+) -> CoreResult<SyncSummary> {
+    let outcome = match strategy {
+        SyncStrategy::FastForward => {
+            // Diverged but the strategy demands a fast-forward: nothing
+            // safe to do without rewriting history. No merge/rebase was
+            // even attempted, so there's no real conflicted file to
+            // report — that's `Diverged`, not `Conflicted`.
+            SyncOutcome::Diverged
+        }
+        SyncStrategy::Rebase => {
+            let conflicts = git.rebase_onto_upstream()?;
+            if conflicts.is_empty() {
+                SyncOutcome::Rebased
+            } else {
+                SyncOutcome::Conflicted { files: conflicts }
+            }
+        }
+    };
+
+    if matches!(outcome, SyncOutcome::Diverged | SyncOutcome::Conflicted { .. }) {
+        return Ok(SyncSummary {
+            repository_id: repo.id.clone(),
+            outcome,
+        });
+    }
+
+    if push {
+        git_push(&repo.path)?;
+    }
+
+    Ok(SyncSummary {
+        repository_id: repo.id.clone(),
+        outcome,
+    })
+}
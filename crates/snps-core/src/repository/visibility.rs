@@ -0,0 +1,135 @@
+//! Visibility semantics for shared-facing commands (`matter list`,
+//! `matter search`, `matter export`): a repository-level default that
+//! per-document frontmatter can narrow or widen depending on the
+//! repository's mode.
+
+use super::Repository;
+use crate::matter::MatterItem;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    Private,
+    Shared,
+    Mixed,
+}
+
+impl Visibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Visibility::Private => "private",
+            Visibility::Shared => "shared",
+            Visibility::Mixed => "mixed",
+        }
+    }
+}
+
+impl std::fmt::Display for Visibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for Visibility {
+    type Err = crate::error::CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "private" => Ok(Visibility::Private),
+            "shared" => Ok(Visibility::Shared),
+            "mixed" => Ok(Visibility::Mixed),
+            other => Err(crate::error::CoreError::InvalidInput(format!(
+                "unknown visibility '{other}'"
+            ))),
+        }
+    }
+}
+
+/// Resolve whether `item` should be visible to shared-facing commands,
+/// combining the owning repository's visibility with the document's own
+/// frontmatter:
+///
+/// - `Private` repo: nothing is visible, regardless of frontmatter.
+/// - `Shared` repo: everything is visible unless frontmatter says private.
+/// - `Mixed` repo: frontmatter decides; documents without an explicit
+///   value default to private (the safer choice for a mixed repo).
+pub fn effective_visibility(repo: &Repository, item: &MatterItem) -> Visibility {
+    match repo.visibility {
+        Visibility::Private => Visibility::Private,
+        Visibility::Shared => item.visibility.unwrap_or(Visibility::Shared),
+        Visibility::Mixed => item.visibility.unwrap_or(Visibility::Private),
+    }
+}
+
+/// Whether an item should be included in a shared-facing listing given the
+/// caller's context and an optional `--include-private` override (which
+/// only takes effect when `repo` belongs to `current_user_context`).
+pub fn is_visible(
+    repo: &Repository,
+    item: &MatterItem,
+    include_private: bool,
+    current_user_context: &str,
+) -> bool {
+    let visibility = effective_visibility(repo, item);
+    if visibility != Visibility::Private {
+        return true;
+    }
+    include_private && repo.context == current_user_context
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matter::MatterType;
+    use std::path::PathBuf;
+
+    fn repo(visibility: Visibility) -> Repository {
+        Repository {
+            id: "r".into(),
+            context: "project".into(),
+            path: PathBuf::new(),
+            visibility,
+            excludes: vec![],
+            sync_strategy: Default::default(),
+        }
+    }
+
+    fn item(visibility: Option<Visibility>) -> MatterItem {
+        MatterItem {
+            id: "i".into(),
+            matter_type: MatterType::Document,
+            title: "t".into(),
+            author: None,
+            tags: vec![],
+            context: "project".into(),
+            path: PathBuf::new(),
+            body: String::new(),
+            visibility,
+            created: None,
+        }
+    }
+
+    #[test]
+    fn private_repo_hides_everything() {
+        assert_eq!(
+            effective_visibility(&repo(Visibility::Private), &item(Some(Visibility::Shared))),
+            Visibility::Private
+        );
+    }
+
+    #[test]
+    fn mixed_repo_defaults_to_private_without_frontmatter() {
+        assert_eq!(
+            effective_visibility(&repo(Visibility::Mixed), &item(None)),
+            Visibility::Private
+        );
+    }
+
+    #[test]
+    fn shared_repo_honors_frontmatter_private() {
+        assert_eq!(
+            effective_visibility(&repo(Visibility::Shared), &item(Some(Visibility::Private))),
+            Visibility::Private
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Parsing and state tracking for `sync.schedules`-configured background
+//! jobs. This module only knows how to name a job, decide when it's due,
+//! and record what happened when it ran — the daemon (the only thing with
+//! a process that lives long enough to run one) owns actually dispatching
+//! each [`ScheduledOperation`] to the library function that does the work.
+//!
+//! Interval syntax is plain durations (`30s`, `5m`, `1h`, `1d`), not cron:
+//! nothing else in this codebase schedules by calendar time (a specific
+//! hour of day, a weekday), so a full cron parser would be unused
+//! complexity. If that need shows up later, it can grow into cron then.
+
+use crate::error::{CoreError, CoreResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// One entry in `sync.schedules`: a name, the operation it runs, and how
+/// often. Kept out of `snps config get/set`/`PMSYNAPSE_*` env vars for the
+/// same reason as `thoughts.categories` (see [`crate::config::GlobalConfig::thoughts_categories`]):
+/// it's a list of records, not a flat scalar/list-of-string value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleConfig {
+    pub name: String,
+    pub operation: String,
+    pub interval: String,
+}
+
+/// A parsed `operation` string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduledOperation {
+    ThoughtsSync,
+    KnowledgePull,
+    RepoSync { repo_id: String },
+    IndexRebuild,
+}
+
+pub fn parse_operation(spec: &str) -> CoreResult<ScheduledOperation> {
+    let spec = spec.trim();
+    if spec == "thoughts-sync" {
+        return Ok(ScheduledOperation::ThoughtsSync);
+    }
+    if spec == "knowledge-pull" {
+        return Ok(ScheduledOperation::KnowledgePull);
+    }
+    if spec == "index-rebuild" {
+        return Ok(ScheduledOperation::IndexRebuild);
+    }
+    if let Some(repo_id) = spec.strip_prefix("repo-sync ") {
+        let repo_id = repo_id.trim();
+        if repo_id.is_empty() {
+            return Err(CoreError::InvalidInput("'repo-sync' requires a repository id, e.g. 'repo-sync team-docs'".to_string()));
+        }
+        return Ok(ScheduledOperation::RepoSync { repo_id: repo_id.to_string() });
+    }
+    Err(CoreError::InvalidInput(format!(
+        "unrecognized scheduled operation '{spec}' (expected 'thoughts-sync', 'knowledge-pull', 'repo-sync <id>', or 'index-rebuild')"
+    )))
+}
+
+/// Parse a plain interval like `30s`, `5m`, `1h`, `1d`.
+pub fn parse_interval(spec: &str) -> CoreResult<Duration> {
+    let spec = spec.trim();
+    let (digits, unit) = spec.split_at(spec.len().saturating_sub(1));
+    let count: u64 = digits
+        .parse()
+        .map_err(|_| CoreError::InvalidInput(format!("invalid interval '{spec}' (expected e.g. 30s, 5m, 1h, 1d)")))?;
+    let secs = match unit {
+        "s" => count,
+        "m" => count * 60,
+        "h" => count * 3_600,
+        "d" => count * 86_400,
+        _ => return Err(CoreError::InvalidInput(format!("invalid interval '{spec}' (expected a suffix of s, m, h, or d)"))),
+    };
+    Ok(Duration::from_secs(secs))
+}
+
+/// How many consecutive failures before backoff stops growing — at
+/// `BACKOFF_BASE^MAX_BACKOFF_SHIFT` the job is already only attempted a
+/// handful of times a day regardless of its configured interval.
+const MAX_BACKOFF_SHIFT: u32 = 5;
+const BACKOFF_BASE: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobOutcome {
+    Success,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRun {
+    pub started_unix: u64,
+    pub duration_ms: u64,
+    pub outcome: JobOutcome,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub last_run: Option<JobRun>,
+    pub consecutive_failures: u32,
+}
+
+impl JobRecord {
+    /// Whether `interval` has elapsed since the last run, applying
+    /// exponential backoff on top of it if the last run(s) failed: a job
+    /// with `consecutive_failures` failures waits
+    /// `interval * BACKOFF_BASE.pow(min(failures, MAX_BACKOFF_SHIFT))`
+    /// instead of just `interval`, so a persistently broken job (a repo
+    /// with a dead remote, say) is retried less and less often rather than
+    /// hammering the same failure every tick.
+    pub fn is_due(&self, interval: Duration, now_unix: u64) -> bool {
+        let Some(last_run) = &self.last_run else { return true };
+        let shift = self.consecutive_failures.min(MAX_BACKOFF_SHIFT);
+        let effective_interval = interval * BACKOFF_BASE.pow(shift);
+        now_unix.saturating_sub(last_run.started_unix) >= effective_interval.as_secs()
+    }
+
+    pub fn record(&mut self, run: JobRun) {
+        self.consecutive_failures = match run.outcome {
+            JobOutcome::Success => 0,
+            JobOutcome::Failed => self.consecutive_failures + 1,
+        };
+        self.last_run = Some(run);
+    }
+}
+
+/// `.pmsynapse/jobs.json`: last run and failure streak per scheduled job
+/// name. Mirrors [`crate::sync_log`]'s pattern — a single small JSON file,
+/// not a history, since only the most recent run and streak matter for
+/// deciding whether a job is due.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct JobStateStore {
+    pub jobs: HashMap<String, JobRecord>,
+}
+
+fn store_path(pmsynapse_dir: &Path) -> PathBuf {
+    pmsynapse_dir.join("jobs.json")
+}
+
+impl JobStateStore {
+    pub fn load(pmsynapse_dir: &Path) -> CoreResult<Self> {
+        let path = store_path(pmsynapse_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents).unwrap_or_default())
+    }
+
+    pub fn save(&self, pmsynapse_dir: &Path) -> CoreResult<()> {
+        let path = store_path(pmsynapse_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, name: &str, run: JobRun) {
+        self.jobs.entry(name.to_string()).or_default().record(run);
+    }
+
+    /// The subset of `schedules` that are due to run now, given their
+    /// recorded state (a name with no record yet is always due).
+    pub fn due_jobs<'a>(&self, schedules: &'a [ScheduleConfig], now_unix: u64) -> CoreResult<Vec<&'a ScheduleConfig>> {
+        let mut due = Vec::new();
+        for schedule in schedules {
+            let interval = parse_interval(&schedule.interval)?;
+            let is_due = self.jobs.get(&schedule.name).map(|record| record.is_due(interval, now_unix)).unwrap_or(true);
+            if is_due {
+                due.push(schedule);
+            }
+        }
+        Ok(due)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_recognized_operation() {
+        assert_eq!(parse_operation("thoughts-sync").unwrap(), ScheduledOperation::ThoughtsSync);
+        assert_eq!(parse_operation("knowledge-pull").unwrap(), ScheduledOperation::KnowledgePull);
+        assert_eq!(parse_operation("index-rebuild").unwrap(), ScheduledOperation::IndexRebuild);
+        assert_eq!(
+            parse_operation("repo-sync team-docs").unwrap(),
+            ScheduledOperation::RepoSync { repo_id: "team-docs".to_string() }
+        );
+    }
+
+    #[test]
+    fn rejects_an_unrecognized_operation() {
+        assert!(parse_operation("bogus").is_err());
+        assert!(parse_operation("repo-sync").is_err());
+    }
+
+    #[test]
+    fn parses_every_interval_suffix() {
+        assert_eq!(parse_interval("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_interval("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_interval("2h").unwrap(), Duration::from_secs(7_200));
+        assert_eq!(parse_interval("1d").unwrap(), Duration::from_secs(86_400));
+        assert!(parse_interval("1w").is_err());
+        assert!(parse_interval("abc").is_err());
+    }
+
+    fn run_at(started_unix: u64, outcome: JobOutcome) -> JobRun {
+        JobRun { started_unix, duration_ms: 10, outcome, error: None }
+    }
+
+    #[test]
+    fn a_job_with_no_prior_run_is_always_due() {
+        assert!(JobRecord::default().is_due(Duration::from_secs(60), 1_000));
+    }
+
+    #[test]
+    fn a_successful_run_is_due_again_after_exactly_one_interval() {
+        let mut record = JobRecord::default();
+        record.record(run_at(1_000, JobOutcome::Success));
+        assert!(!record.is_due(Duration::from_secs(60), 1_030));
+        assert!(record.is_due(Duration::from_secs(60), 1_060));
+    }
+
+    #[test]
+    fn failures_back_off_exponentially() {
+        let mut record = JobRecord::default();
+        record.record(run_at(1_000, JobOutcome::Failed));
+        record.record(run_at(1_000, JobOutcome::Failed));
+        // Two consecutive failures: effective interval is 60 * 2^2 = 240s.
+        assert!(!record.is_due(Duration::from_secs(60), 1_000 + 120));
+        assert!(record.is_due(Duration::from_secs(60), 1_000 + 240));
+    }
+
+    #[test]
+    fn a_success_resets_the_failure_streak() {
+        let mut record = JobRecord::default();
+        record.record(run_at(1_000, JobOutcome::Failed));
+        record.record(run_at(2_000, JobOutcome::Success));
+        assert_eq!(record.consecutive_failures, 0);
+        assert!(!record.is_due(Duration::from_secs(60), 2_030));
+    }
+
+    #[test]
+    fn state_store_round_trips_through_disk() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut store = JobStateStore::load(tmp.path()).unwrap();
+        assert!(store.jobs.is_empty());
+
+        store.record("nightly-thoughts-sync", run_at(1_000, JobOutcome::Success));
+        store.save(tmp.path()).unwrap();
+
+        let reloaded = JobStateStore::load(tmp.path()).unwrap();
+        assert_eq!(reloaded.jobs["nightly-thoughts-sync"].consecutive_failures, 0);
+    }
+
+    #[test]
+    fn due_jobs_skips_ones_whose_interval_has_not_elapsed() {
+        let mut store = JobStateStore::default();
+        store.record("a", run_at(1_000, JobOutcome::Success));
+        let schedules = vec![
+            ScheduleConfig { name: "a".to_string(), operation: "index-rebuild".to_string(), interval: "1h".to_string() },
+            ScheduleConfig { name: "b".to_string(), operation: "index-rebuild".to_string(), interval: "1h".to_string() },
+        ];
+        let due = store.due_jobs(&schedules, 1_030).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].name, "b");
+    }
+}
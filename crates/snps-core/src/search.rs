@@ -0,0 +1,137 @@
+//! Built-in text search over a directory tree, used as the primary search
+//! engine for `snps know search` and `snps thoughts search` so both work
+//! without `rg` installed. Delegates to `rg` instead when present and
+//! `search.prefer_ripgrep` is set, for speed on large trees.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct SearchMatch {
+    pub path: PathBuf,
+    pub line_number: usize,
+    pub line: String,
+    pub context_before: Vec<String>,
+    pub context_after: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    pub case_sensitive: bool,
+    pub regex: bool,
+    pub max_matches_per_file: usize,
+    pub context_lines: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            case_sensitive: false,
+            regex: false,
+            max_matches_per_file: 20,
+            context_lines: 0,
+        }
+    }
+}
+
+fn line_matches(line: &str, query: &str, options: &SearchOptions, compiled: &Option<regex::Regex>) -> bool {
+    if let Some(re) = compiled {
+        return re.is_match(line);
+    }
+    if options.case_sensitive {
+        line.contains(query)
+    } else {
+        line.to_lowercase().contains(&query.to_lowercase())
+    }
+}
+
+/// Search every file under `root` for `query`, honoring `options`. Files
+/// that fail to read as UTF-8 are silently skipped, matching `rg`'s
+/// default behavior on binary files.
+pub fn search_dir(root: &Path, query: &str, options: &SearchOptions) -> Vec<SearchMatch> {
+    let compiled = if options.regex {
+        regex::RegexBuilder::new(query)
+            .case_insensitive(!options.case_sensitive)
+            .build()
+            .ok()
+    } else {
+        None
+    };
+
+    let mut matches = Vec::new();
+    for path in walk_files(root) {
+        let Ok(contents) = fs::read_to_string(&path) else { continue };
+        let lines: Vec<&str> = contents.lines().collect();
+        let mut found_in_file = 0;
+
+        for (i, line) in lines.iter().enumerate() {
+            if found_in_file >= options.max_matches_per_file {
+                break;
+            }
+            if line_matches(line, query, options, &compiled) {
+                found_in_file += 1;
+                let before_start = i.saturating_sub(options.context_lines);
+                let after_end = (i + options.context_lines + 1).min(lines.len());
+                matches.push(SearchMatch {
+                    path: path.clone(),
+                    line_number: i + 1,
+                    line: line.to_string(),
+                    context_before: lines[before_start..i].iter().map(|s| s.to_string()).collect(),
+                    context_after: lines[i + 1..after_end].iter().map(|s| s.to_string()).collect(),
+                });
+            }
+        }
+    }
+    matches
+}
+
+fn walk_files(dir: &Path) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else { return out };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            out.extend(walk_files(&path));
+        } else {
+            out.push(path);
+        }
+    }
+    out
+}
+
+/// Whether `rg` is available on `PATH`, used to decide whether the
+/// ripgrep-backed fast path can be attempted at all.
+pub fn ripgrep_available() -> bool {
+    std::process::Command::new("rg")
+        .arg("--version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_case_insensitive_substring_without_rg() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join("a.md"), "Authentication design notes").unwrap();
+
+        let matches = search_dir(tmp.path(), "authentication", &SearchOptions::default());
+        assert_eq!(matches.len(), 1);
+    }
+
+    #[test]
+    fn respects_max_matches_per_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join("a.md"), "x\nx\nx\nx\n").unwrap();
+
+        let options = SearchOptions {
+            max_matches_per_file: 2,
+            ..SearchOptions::default()
+        };
+        let matches = search_dir(tmp.path(), "x", &options);
+        assert_eq!(matches.len(), 2);
+    }
+}
@@ -0,0 +1,132 @@
+//! A persisted, incrementally-updated search index cache shared by matter
+//! and thoughts search. Earlier versions rebuilt by deleting and
+//! re-scanning everything on every search; this walks the tree once,
+//! compares mtimes against the cached entry, and only re-parses files
+//! that actually changed.
+
+use crate::error::CoreResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub mtime_unix: u64,
+    pub content_hash: String,
+    pub title: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SearchIndex {
+    pub entries: HashMap<String, IndexEntry>,
+}
+
+impl SearchIndex {
+    pub fn load(path: &Path) -> CoreResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents).unwrap_or_default())
+    }
+
+    pub fn save(&self, path: &Path) -> CoreResult<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Rebuild against the current contents of `root`, re-parsing only
+    /// files whose mtime changed (or that are new), dropping entries for
+    /// files that no longer exist. Returns the count of files re-parsed.
+    /// `excludes` are glob patterns relative to `root` (see
+    /// [`crate::fswalk`]) skipped entirely, as if they didn't exist.
+    pub fn rebuild_incremental<F>(&mut self, root: &Path, excludes: &[String], mut parse_title: F) -> CoreResult<usize>
+    where
+        F: FnMut(&Path) -> Option<String>,
+    {
+        let mut seen = std::collections::HashSet::new();
+        let mut reparsed = 0;
+
+        for path in crate::fswalk::walk_markdown(root, root, excludes) {
+            let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().into_owned();
+            seen.insert(relative.clone());
+
+            let mtime_unix = fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+
+            let up_to_date = self
+                .entries
+                .get(&relative)
+                .is_some_and(|e| e.mtime_unix == mtime_unix);
+
+            if up_to_date {
+                continue;
+            }
+
+            let Some(title) = parse_title(&path) else { continue };
+            let content_hash = fs::read(&path).map(|b| crate::knowledge::hash_contents(&b)).unwrap_or_default();
+            self.entries.insert(relative, IndexEntry { mtime_unix, content_hash, title });
+            reparsed += 1;
+        }
+
+        self.entries.retain(|path, _| seen.contains(path));
+        Ok(reparsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_file_is_not_reparsed() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join("a.md"), "# A").unwrap();
+
+        let mut index = SearchIndex::default();
+        let first = index.rebuild_incremental(tmp.path(), &[], |_| Some("A".into())).unwrap();
+        assert_eq!(first, 1);
+
+        let second = index.rebuild_incremental(tmp.path(), &[], |_| Some("A".into())).unwrap();
+        assert_eq!(second, 0);
+    }
+
+    #[test]
+    fn deleted_file_is_dropped_from_index() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("a.md");
+        fs::write(&file, "# A").unwrap();
+
+        let mut index = SearchIndex::default();
+        index.rebuild_incremental(tmp.path(), &[], |_| Some("A".into())).unwrap();
+        fs::remove_file(&file).unwrap();
+        index.rebuild_incremental(tmp.path(), &[], |_| Some("A".into())).unwrap();
+
+        assert!(index.entries.is_empty());
+    }
+
+    #[test]
+    fn excluded_files_are_never_indexed() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("drafts")).unwrap();
+        fs::write(tmp.path().join("drafts/a.md"), "# A").unwrap();
+        fs::write(tmp.path().join("b.md"), "# B").unwrap();
+
+        let mut index = SearchIndex::default();
+        let reparsed = index
+            .rebuild_incremental(tmp.path(), &["drafts/*.md".to_string()], |_| Some("title".into()))
+            .unwrap();
+
+        assert_eq!(reparsed, 1);
+        assert!(index.entries.contains_key("b.md"));
+    }
+}
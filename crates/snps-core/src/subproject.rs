@@ -0,0 +1,208 @@
+//! Sub-project registry for monorepos that share one `.pmsynapse` root but
+//! want independent thoughts/knowledge context per package.
+//!
+//! A sub-project is registered under `.pmsynapse/projects/<name>/`, records
+//! the subtree (relative to the workspace root) it owns, and points at a
+//! team id — reusing [`crate::team`]'s registry as the IDLC pointer rather
+//! than inventing a second one, since a team already *is* an IDLC config
+//! plus a directory. The pointer is stored as a plain id and resolved
+//! lazily (by `snps status`/`snps idlc`, the same as [`team::active_team_id`]
+//! already is), rather than requiring the team to exist at registration
+//! time — a sub-project can be registered before its team is created.
+//! Repo-wide configuration (`config.yaml`, `repositories.yaml`) is
+//! deliberately untouched by any of this and stays resolved at the
+//! workspace root; see [`crate::config`].
+//!
+//! There's no `snps init` in this tree, so sub-projects register the same
+//! way teams do — `snps project add`, mirroring `snps team create` — rather
+//! than through an init flow that doesn't exist yet.
+
+use crate::error::{CoreError, CoreResult};
+use crate::workspace::Workspace;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SubProjectFile {
+    /// Subtree this sub-project owns, relative to the workspace root.
+    path: PathBuf,
+    team: String,
+}
+
+/// A registered sub-project: its name, the subtree it owns, and the team
+/// (and therefore IDLC config) it resolves to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubProject {
+    pub name: String,
+    pub path: PathBuf,
+    pub team: String,
+}
+
+impl SubProject {
+    pub fn thoughts_dir(&self, workspace: &Workspace) -> PathBuf {
+        projects_dir(workspace).join(&self.name).join("thoughts")
+    }
+
+    pub fn knowledge_dir(&self, workspace: &Workspace) -> PathBuf {
+        projects_dir(workspace).join(&self.name).join("knowledge")
+    }
+}
+
+fn projects_dir(workspace: &Workspace) -> PathBuf {
+    workspace.pmsynapse_dir().join("projects")
+}
+
+fn project_file(workspace: &Workspace, name: &str) -> PathBuf {
+    projects_dir(workspace).join(name).join("project.yaml")
+}
+
+/// Register `name` as owning `path` (relative to the workspace root),
+/// pointing at `team`. `team` isn't required to exist yet — pair with
+/// `snps team create` (or `--create-team` at the CLI) if it doesn't.
+pub fn register_sub_project(workspace: &Workspace, name: &str, path: &Path, team: &str) -> CoreResult<SubProject> {
+    if list_sub_projects(workspace)?.iter().any(|p| p.name == name) {
+        return Err(CoreError::InvalidInput(format!("sub-project '{name}' already exists")));
+    }
+
+    let dir = projects_dir(workspace).join(name);
+    std::fs::create_dir_all(&dir)?;
+    let file = SubProjectFile { path: path.to_path_buf(), team: team.to_string() };
+    let yaml = serde_yaml::to_string(&file).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+    std::fs::write(project_file(workspace, name), yaml)?;
+
+    Ok(SubProject { name: name.to_string(), path: file.path, team: file.team })
+}
+
+/// Every registered sub-project, sorted by name. Empty (not an error) if
+/// none are registered yet.
+pub fn list_sub_projects(workspace: &Workspace) -> CoreResult<Vec<SubProject>> {
+    let dir = projects_dir(workspace);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut projects = Vec::new();
+    for entry in std::fs::read_dir(&dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = entry.path().join("project.yaml");
+        if !path.exists() {
+            continue;
+        }
+        let contents = std::fs::read_to_string(&path)?;
+        let file: SubProjectFile = serde_yaml::from_str(&contents).map_err(|e| CoreError::Parse { path, message: e.to_string() })?;
+        projects.push(SubProject { name, path: file.path, team: file.team });
+    }
+    projects.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(projects)
+}
+
+/// Resolve which sub-project (if any) `cwd` falls under, so commands run
+/// inside a registered subtree pick up that context instead of the
+/// workspace root's. `cwd` is canonicalized the same way
+/// [`Workspace::root`] is (see `Workspace::discover`) before matching, so
+/// callers can pass it straight from `std::env::current_dir()`.
+///
+/// `Ok(None)` means `cwd` isn't in any registered subtree — commands
+/// should fall back to the root context. Sub-project subtrees aren't
+/// allowed to nest, so `cwd` matching more than one is a misconfiguration
+/// reported as an explicit error rather than guessed at (e.g. by picking
+/// the most specific match).
+pub fn resolve_sub_project_for(workspace: &Workspace, cwd: &Path) -> CoreResult<Option<SubProject>> {
+    let cwd = cwd.canonicalize().unwrap_or_else(|_| cwd.to_path_buf());
+    let matches: Vec<SubProject> =
+        list_sub_projects(workspace)?.into_iter().filter(|p| cwd.starts_with(workspace.root.join(&p.path))).collect();
+
+    match matches.len() {
+        0 => Ok(None),
+        1 => Ok(matches.into_iter().next()),
+        _ => Err(CoreError::InvalidInput(format!(
+            "{} is inside more than one registered sub-project ({}) — their subtrees overlap",
+            cwd.display(),
+            matches.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(", ")
+        ))),
+    }
+}
+
+/// The thoughts directory a command run from `cwd` should use: the
+/// resolved sub-project's if `cwd` is inside one, otherwise the
+/// workspace's own.
+pub fn thoughts_dir(workspace: &Workspace, cwd: &Path) -> CoreResult<PathBuf> {
+    Ok(match resolve_sub_project_for(workspace, cwd)? {
+        Some(sub) => sub.thoughts_dir(workspace),
+        None => workspace.thoughts_dir(),
+    })
+}
+
+/// The knowledge directory a command run from `cwd` should use — see
+/// [`thoughts_dir`].
+pub fn knowledge_dir(workspace: &Workspace, cwd: &Path) -> CoreResult<PathBuf> {
+    Ok(match resolve_sub_project_for(workspace, cwd)? {
+        Some(sub) => sub.knowledge_dir(workspace),
+        None => workspace.knowledge_dir(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn workspace_in(tmp: &std::path::Path) -> Workspace {
+        fs::create_dir_all(tmp.join(".pmsynapse")).unwrap();
+        Workspace::discover(tmp).unwrap()
+    }
+
+    #[test]
+    fn registers_and_lists_a_sub_project() {
+        let tmp = tempfile::tempdir().unwrap();
+        let workspace = workspace_in(tmp.path());
+
+        let sub = register_sub_project(&workspace, "api", Path::new("packages/api"), "api-team").unwrap();
+        assert_eq!(sub.path, PathBuf::from("packages/api"));
+
+        let listed = list_sub_projects(&workspace).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "api");
+        assert_eq!(listed[0].team, "api-team");
+    }
+
+    #[test]
+    fn rejects_registering_the_same_name_twice() {
+        let tmp = tempfile::tempdir().unwrap();
+        let workspace = workspace_in(tmp.path());
+
+        register_sub_project(&workspace, "api", Path::new("packages/api"), "api-team").unwrap();
+        assert!(register_sub_project(&workspace, "api", Path::new("packages/other"), "api-team").is_err());
+    }
+
+    #[test]
+    fn resolves_cwd_to_the_owning_sub_project() {
+        let tmp = tempfile::tempdir().unwrap();
+        let workspace = workspace_in(tmp.path());
+
+        register_sub_project(&workspace, "api", Path::new("packages/api"), "api-team").unwrap();
+
+        let nested = workspace.root.join("packages/api/src");
+        let resolved = resolve_sub_project_for(&workspace, &nested).unwrap();
+        assert_eq!(resolved.map(|p| p.name), Some("api".to_string()));
+
+        let outside = workspace.root.join("packages/web");
+        assert_eq!(resolve_sub_project_for(&workspace, &outside).unwrap(), None);
+    }
+
+    #[test]
+    fn overlapping_sub_projects_are_an_explicit_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let workspace = workspace_in(tmp.path());
+
+        register_sub_project(&workspace, "packages", Path::new("packages"), "packages-team").unwrap();
+        register_sub_project(&workspace, "api", Path::new("packages/api"), "api-team").unwrap();
+
+        let nested = workspace.root.join("packages/api/src");
+        assert!(resolve_sub_project_for(&workspace, &nested).is_err());
+    }
+}
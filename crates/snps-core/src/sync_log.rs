@@ -0,0 +1,45 @@
+//! Tracks when `snps sync` last completed, so `snps status` can report a
+//! last-sync time without re-running one. Deliberately tiny: a single
+//! JSON file, not a history — nothing today needs more than the most
+//! recent timestamp.
+
+use crate::error::CoreResult;
+use crate::time::now_unix;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LastSync {
+    pub unix_time: u64,
+}
+
+fn log_path(pmsynapse_dir: &Path) -> PathBuf {
+    pmsynapse_dir.join("last_sync.json")
+}
+
+/// Record that a sync just completed, stamped with the current time.
+pub fn record_sync(pmsynapse_dir: &Path) -> CoreResult<()> {
+    std::fs::write(log_path(pmsynapse_dir), serde_json::to_string(&LastSync { unix_time: now_unix() })?)?;
+    Ok(())
+}
+
+/// The last recorded sync time, if `snps sync` has ever completed here.
+pub fn last_sync(pmsynapse_dir: &Path) -> Option<LastSync> {
+    let contents = std::fs::read_to_string(log_path(pmsynapse_dir)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_the_recorded_time() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(last_sync(tmp.path()).is_none());
+
+        record_sync(tmp.path()).unwrap();
+        let recorded = last_sync(tmp.path()).unwrap();
+        assert!(recorded.unix_time > 0);
+    }
+}
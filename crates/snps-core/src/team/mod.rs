@@ -0,0 +1,150 @@
+//! Team registry (`~/.pmsynapse/teams.yaml`) and the active team recorded
+//! per project (`config.yaml`'s `active_team` key). Config's `Team` scope
+//! and (later) IDLC commands resolve their working directory through
+//! [`active_team_id`] instead of hardcoding a single team.
+
+use crate::config::{set_value, ConfigScope, PartialConfig};
+use crate::error::{CoreError, CoreResult};
+use crate::workspace::Workspace;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub const DEFAULT_TEAM_ID: &str = "default";
+
+/// A minimal stage list seeded into a freshly created team directory.
+/// The full IDLC module (item storage, validation) lands separately; this
+/// only gives `snps idlc` something real to read once it exists.
+const DEFAULT_IDLC_TEMPLATE: &str = "stages:\n  - backlog\n  - in_progress\n  - review\n  - done\n";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamEntry {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TeamRegistryFile {
+    #[serde(default)]
+    teams: Vec<TeamEntry>,
+}
+
+/// One row of `snps team list`.
+pub struct TeamSummary {
+    pub id: String,
+    pub name: String,
+    pub has_idlc_config: bool,
+    pub active: bool,
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+fn registry_path() -> Option<PathBuf> {
+    home_dir().map(|h| h.join(".pmsynapse").join("teams.yaml"))
+}
+
+fn load_registry() -> CoreResult<TeamRegistryFile> {
+    let Some(path) = registry_path() else { return Ok(TeamRegistryFile::default()) };
+    if !path.exists() {
+        return Ok(TeamRegistryFile::default());
+    }
+    let contents = std::fs::read_to_string(&path)?;
+    serde_yaml::from_str(&contents).map_err(|e| CoreError::Parse { path, message: e.to_string() })
+}
+
+fn save_registry(registry: &TeamRegistryFile) -> CoreResult<()> {
+    let path = registry_path().ok_or_else(|| CoreError::NotFound("no home directory to store the team registry in".to_string()))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let yaml = serde_yaml::to_string(registry).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+    std::fs::write(path, yaml)?;
+    Ok(())
+}
+
+fn team_dir(workspace: &Workspace, id: &str) -> PathBuf {
+    workspace.teams_dir().join(id)
+}
+
+/// The team id read from the project's `active_team` config key, falling
+/// back to [`DEFAULT_TEAM_ID`] when unset.
+pub fn active_team_id(workspace: &Workspace) -> String {
+    PartialConfig::load(&workspace.config_path()).ok().and_then(|p| p.active_team).unwrap_or_else(|| DEFAULT_TEAM_ID.to_string())
+}
+
+pub fn find_team(id: &str) -> CoreResult<TeamEntry> {
+    load_registry()?.teams.into_iter().find(|t| t.id == id).ok_or_else(|| CoreError::NotFound(format!("no team '{id}' in the registry")))
+}
+
+pub fn list_teams(workspace: &Workspace) -> CoreResult<Vec<TeamSummary>> {
+    let registry = load_registry()?;
+    let active = active_team_id(workspace);
+    Ok(registry
+        .teams
+        .into_iter()
+        .map(|t| TeamSummary {
+            has_idlc_config: team_dir(workspace, &t.id).join("idlc.yaml").exists(),
+            active: t.id == active,
+            id: t.id,
+            name: t.name,
+        })
+        .collect())
+}
+
+/// Register a new team and seed its directory with the default IDLC
+/// template, unless one is already there.
+pub fn create_team(workspace: &Workspace, id: &str, name: &str) -> CoreResult<TeamEntry> {
+    let mut registry = load_registry()?;
+    if registry.teams.iter().any(|t| t.id == id) {
+        return Err(CoreError::InvalidInput(format!("team '{id}' already exists")));
+    }
+    let entry = TeamEntry { id: id.to_string(), name: name.to_string() };
+    registry.teams.push(entry.clone());
+    save_registry(&registry)?;
+
+    let dir = team_dir(workspace, id);
+    std::fs::create_dir_all(&dir)?;
+    let idlc_path = dir.join("idlc.yaml");
+    if !idlc_path.exists() {
+        std::fs::write(&idlc_path, DEFAULT_IDLC_TEMPLATE)?;
+    }
+
+    Ok(entry)
+}
+
+/// Switch the project's active team, creating it from the default
+/// template first if `create` is set and it isn't registered yet.
+pub fn switch_team(workspace: &Workspace, id: &str, create: bool) -> CoreResult<()> {
+    match find_team(id) {
+        Ok(_) => {}
+        Err(_) if create => {
+            create_team(workspace, id, id)?;
+        }
+        Err(e) => return Err(e),
+    }
+
+    set_value(workspace, ConfigScope::Project, "active_team", serde_yaml::Value::String(id.to_string()))?;
+    Ok(())
+}
+
+/// The registry entry for `id` plus its IDLC config contents, if any.
+pub fn show_team(workspace: &Workspace, id: &str) -> CoreResult<(TeamEntry, Option<String>)> {
+    let entry = find_team(id)?;
+    let idlc = std::fs::read_to_string(team_dir(workspace, id).join("idlc.yaml")).ok();
+    Ok((entry, idlc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn active_team_id_defaults_when_unset() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        let workspace = Workspace::discover(tmp.path()).unwrap();
+        assert_eq!(active_team_id(&workspace), DEFAULT_TEAM_ID);
+    }
+
+}
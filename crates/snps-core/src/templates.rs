@@ -0,0 +1,501 @@
+//! Workflow templates: an IDLC stage list plus prompt files, either built
+//! into the binary or dropped under `.pmsynapse/templates/<name>/`.
+//! `snps team switch --create` seeds a bare stage list on its own; `snps
+//! templates use` copies a full named template into the active team
+//! directory instead. `snps templates import` is the sibling for a config
+//! that isn't one of these named templates at all — a centrally
+//! maintained `idlc.yaml` pulled from a file, URL, or shadow repository —
+//! with `snps templates update` to re-fetch it later.
+
+use crate::error::{CoreError, CoreResult};
+use crate::idlc::IdlcConfig;
+use crate::repository::Repository;
+use crate::team;
+use crate::workspace::Workspace;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    BuiltIn,
+    Custom(PathBuf),
+}
+
+impl TemplateSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TemplateSource::BuiltIn => "built-in",
+            TemplateSource::Custom(_) => "custom",
+        }
+    }
+}
+
+pub struct TemplateSummary {
+    pub name: String,
+    pub source: TemplateSource,
+}
+
+struct BuiltInTemplate {
+    name: &'static str,
+    idlc_yaml: &'static str,
+    prompts: &'static [(&'static str, &'static str)],
+}
+
+const BUILT_INS: &[BuiltInTemplate] = &[
+    BuiltInTemplate {
+        name: "default",
+        idlc_yaml: "stages:\n  - backlog\n  - in_progress\n  - review\n  - done\n",
+        prompts: &[
+            ("backlog.md", "# Backlog\n\nDescribe the work to be done.\n"),
+            ("in_progress.md", "# In progress\n\nSummarize what changed and why.\n"),
+            ("review.md", "# Review\n\nChecklist for reviewing this item.\n"),
+            ("done.md", "# Done\n\nConfirm the item is complete and note follow-ups.\n"),
+        ],
+    },
+    BuiltInTemplate {
+        name: "bmad",
+        idlc_yaml: "stages:\n  - brainstorm\n  - model\n  - assemble\n  - deliver\n",
+        prompts: &[
+            ("brainstorm.md", "# Brainstorm\n\nCapture divergent ideas before narrowing down.\n"),
+            ("model.md", "# Model\n\nSketch the approach and its trade-offs.\n"),
+            ("assemble.md", "# Assemble\n\nBuild the pieces and wire them together.\n"),
+            ("deliver.md", "# Deliver\n\nShip it and record what was learned.\n"),
+        ],
+    },
+];
+
+fn built_in(name: &str) -> Option<&'static BuiltInTemplate> {
+    BUILT_INS.iter().find(|t| t.name == name)
+}
+
+fn custom_templates_dir(workspace: &Workspace) -> PathBuf {
+    workspace.pmsynapse_dir().join("templates")
+}
+
+/// Enumerate both built-in and `.pmsynapse/templates/`-defined templates.
+pub fn list_templates(workspace: &Workspace) -> Vec<TemplateSummary> {
+    let mut templates: Vec<TemplateSummary> =
+        BUILT_INS.iter().map(|t| TemplateSummary { name: t.name.to_string(), source: TemplateSource::BuiltIn }).collect();
+
+    if let Ok(entries) = std::fs::read_dir(custom_templates_dir(workspace)) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if let Some(name) = path.is_dir().then(|| path.file_name()).flatten().and_then(|n| n.to_str()) {
+                templates.push(TemplateSummary { name: name.to_string(), source: TemplateSource::Custom(path.clone()) });
+            }
+        }
+    }
+
+    templates
+}
+
+fn copy_prompts(src: &Path, dest: &Path) -> CoreResult<()> {
+    std::fs::create_dir_all(dest)?;
+    let Ok(entries) = std::fs::read_dir(src) else { return Ok(()) };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_file() {
+            if let Some(file_name) = path.file_name() {
+                std::fs::copy(&path, dest.join(file_name))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copy `name`'s IDLC config and prompts into `workspace`'s active team
+/// directory. Refuses to overwrite an existing `idlc.yaml` unless `force`.
+pub fn use_template(workspace: &Workspace, name: &str, force: bool) -> CoreResult<PathBuf> {
+    let team_dir = workspace.teams_dir().join(team::active_team_id(workspace));
+    std::fs::create_dir_all(&team_dir)?;
+
+    let idlc_dest = team_dir.join("idlc.yaml");
+    if idlc_dest.exists() && !force {
+        return Err(CoreError::InvalidInput(format!("{} already exists; pass --force to overwrite", idlc_dest.display())));
+    }
+
+    let prompts_dest = team_dir.join("prompts");
+
+    if let Some(builtin) = built_in(name) {
+        std::fs::write(&idlc_dest, builtin.idlc_yaml)?;
+        std::fs::create_dir_all(&prompts_dest)?;
+        for (file_name, contents) in builtin.prompts {
+            std::fs::write(prompts_dest.join(file_name), contents)?;
+        }
+    } else {
+        let src_dir = custom_templates_dir(workspace).join(name);
+        if !src_dir.is_dir() {
+            return Err(CoreError::NotFound(format!("no template named '{name}'")));
+        }
+        std::fs::copy(src_dir.join("idlc.yaml"), &idlc_dest)?;
+        copy_prompts(&src_dir.join("prompts"), &prompts_dest)?;
+    }
+
+    Ok(team_dir)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateIssue {
+    pub message: String,
+}
+
+#[derive(serde::Deserialize)]
+struct IdlcStages {
+    #[serde(default)]
+    stages: Vec<String>,
+}
+
+/// Check that `name`'s `idlc.yaml` parses as a non-empty stage list and
+/// that every stage has a matching prompt file.
+pub fn validate_template(workspace: &Workspace, name: &str) -> CoreResult<Vec<TemplateIssue>> {
+    let mut issues = Vec::new();
+
+    let (idlc_yaml, prompt_names): (String, Vec<String>) = if let Some(builtin) = built_in(name) {
+        (builtin.idlc_yaml.to_string(), builtin.prompts.iter().map(|(n, _)| n.to_string()).collect())
+    } else {
+        let src_dir = custom_templates_dir(workspace).join(name);
+        if !src_dir.is_dir() {
+            return Err(CoreError::NotFound(format!("no template named '{name}'")));
+        }
+        let idlc_yaml = std::fs::read_to_string(src_dir.join("idlc.yaml"))?;
+        let prompt_names = std::fs::read_dir(src_dir.join("prompts"))
+            .map(|entries| {
+                entries.flatten().filter_map(|e| e.path().file_name().map(|n| n.to_string_lossy().into_owned())).collect()
+            })
+            .unwrap_or_default();
+        (idlc_yaml, prompt_names)
+    };
+
+    let stages = match serde_yaml::from_str::<IdlcStages>(&idlc_yaml) {
+        Ok(parsed) => parsed.stages,
+        Err(e) => {
+            issues.push(TemplateIssue { message: format!("idlc.yaml is not a valid stage list: {e}") });
+            return Ok(issues);
+        }
+    };
+
+    if stages.is_empty() {
+        issues.push(TemplateIssue { message: "idlc.yaml declares no stages".to_string() });
+    }
+
+    for stage in &stages {
+        let file_name = format!("{stage}.md");
+        if !prompt_names.contains(&file_name) {
+            issues.push(TemplateIssue { message: format!("no prompt file for stage '{stage}'") });
+        }
+    }
+
+    // The stage-list check above only needs `stages`, but a template's
+    // idlc.yaml may also declare `statuses`/`transitions` — if it parses
+    // as a full config, run the same referential check `templates
+    // import` enforces so a hand-edited template can't drift either.
+    if let Ok(config) = crate::idlc::parse_idlc_config(&idlc_yaml) {
+        for message in crate::idlc::validate_references(&config) {
+            issues.push(TemplateIssue { message });
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Where an installed `idlc.yaml` came from, recorded next to it (as
+/// `idlc.source.yaml`) so `update_idlc_config` knows how to re-fetch it.
+/// A plain local file path isn't recorded — the file is already sitting
+/// right there for the user to re-read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind", content = "value")]
+enum ImportedSource {
+    Repository(String),
+    Url(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SourceRecord {
+    source: ImportedSource,
+    installed_at: u64,
+}
+
+enum ClassifiedSource {
+    File(PathBuf),
+    Url(String),
+    Repository(String),
+}
+
+/// Classify `--from`'s argument: an `http(s)://` URL, an existing local
+/// file, or otherwise a configured repository id. A path that happens to
+/// not exist yet is treated as a repository id rather than an error here
+/// — the actual lookup below produces a clearer message either way.
+fn classify_source(from: &str) -> ClassifiedSource {
+    if from.starts_with("http://") || from.starts_with("https://") {
+        ClassifiedSource::Url(from.to_string())
+    } else if Path::new(from).is_file() {
+        ClassifiedSource::File(PathBuf::from(from))
+    } else {
+        ClassifiedSource::Repository(from.to_string())
+    }
+}
+
+fn repository_idlc_path(workspace: &Workspace, repo_id: &str, team: &str) -> CoreResult<PathBuf> {
+    let repos = Repository::load_all(workspace)?;
+    let repo = repos
+        .iter()
+        .find(|r| r.id == repo_id)
+        .ok_or_else(|| CoreError::NotFound(format!("no repository named '{repo_id}'")))?;
+    Ok(repo.path.join("teams").join(team).join("idlc.yaml"))
+}
+
+async fn fetch_source(workspace: &Workspace, source: &ClassifiedSource, team: &str) -> CoreResult<String> {
+    match source {
+        ClassifiedSource::File(path) => Ok(std::fs::read_to_string(path)?),
+        ClassifiedSource::Repository(id) => {
+            let path = repository_idlc_path(workspace, id, team)?;
+            std::fs::read_to_string(&path)
+                .map_err(|_| CoreError::NotFound(format!("no idlc.yaml for team '{team}' in repository '{id}' ({})", path.display())))
+        }
+        ClassifiedSource::Url(url) => {
+            let response =
+                reqwest::get(url).await.map_err(|e| CoreError::InvalidInput(format!("fetching '{url}' failed: {e}")))?;
+            if !response.status().is_success() {
+                return Err(CoreError::InvalidInput(format!("fetching '{url}' failed: HTTP {}", response.status())));
+            }
+            response
+                .text()
+                .await
+                .map_err(|e| CoreError::InvalidInput(format!("reading response from '{url}' failed: {e}")))
+        }
+    }
+}
+
+fn source_record(source: &ClassifiedSource) -> Option<SourceRecord> {
+    let imported = match source {
+        ClassifiedSource::File(_) => return None,
+        ClassifiedSource::Repository(id) => ImportedSource::Repository(id.clone()),
+        ClassifiedSource::Url(url) => ImportedSource::Url(url.clone()),
+    };
+    Some(SourceRecord { source: imported, installed_at: crate::time::now_unix() })
+}
+
+fn source_record_path(team_dir: &Path) -> PathBuf {
+    team_dir.join("idlc.source.yaml")
+}
+
+/// Parse and referentially validate a candidate `idlc.yaml` body,
+/// returning the parsed config so callers don't need to re-parse it.
+fn validate_idlc_yaml(contents: &str) -> CoreResult<IdlcConfig> {
+    let config = crate::idlc::parse_idlc_config(contents)?;
+    let issues = crate::idlc::validate_references(&config);
+    if !issues.is_empty() {
+        return Err(CoreError::InvalidInput(format!("invalid idlc config: {}", issues.join("; "))));
+    }
+    Ok(config)
+}
+
+/// Install an `idlc.yaml` for the active team from `from` — a local file
+/// path, an `https://`/`http://` URL, or a configured matter/knowledge
+/// repository id (resolving `teams/<team>/idlc.yaml` inside it). Unlike
+/// [`use_template`], this doesn't copy prompt files: the source is
+/// somebody else's already-customized config, not a named template with
+/// a matching prompt set. Refuses to overwrite an existing `idlc.yaml`
+/// unless `force`.
+pub async fn import_idlc_config(workspace: &Workspace, from: &str, force: bool) -> CoreResult<PathBuf> {
+    let team = team::active_team_id(workspace);
+    let team_dir = workspace.teams_dir().join(&team);
+    std::fs::create_dir_all(&team_dir)?;
+
+    let idlc_dest = team_dir.join("idlc.yaml");
+    if idlc_dest.exists() && !force {
+        return Err(CoreError::InvalidInput(format!("{} already exists; pass --force to overwrite", idlc_dest.display())));
+    }
+
+    let source = classify_source(from);
+    let contents = fetch_source(workspace, &source, &team).await?;
+    validate_idlc_yaml(&contents)?;
+    std::fs::write(&idlc_dest, &contents)?;
+
+    let record_path = source_record_path(&team_dir);
+    match source_record(&source) {
+        Some(record) => {
+            let yaml = serde_yaml::to_string(&record).map_err(|e| CoreError::InvalidInput(e.to_string()))?;
+            std::fs::write(record_path, yaml)?;
+        }
+        None => {
+            // Overwriting a repo/url-sourced idlc.yaml with a local file
+            // shouldn't leave a stale sidecar pointing at the old source.
+            let _ = std::fs::remove_file(record_path);
+        }
+    }
+
+    Ok(idlc_dest)
+}
+
+/// What re-fetching a team's recorded `idlc.yaml` source found.
+pub struct UpdateOutcome {
+    pub changed: bool,
+    pub applied: bool,
+    pub diff: Vec<String>,
+}
+
+/// Re-fetch the active team's recorded `idlc.yaml` source (see
+/// [`import_idlc_config`]) and diff it against what's installed. Only
+/// overwrites the local file when `apply` is set — otherwise this is a
+/// dry-run that just reports what would change, so local customizations
+/// on top of an imported config aren't silently clobbered.
+pub async fn update_idlc_config(workspace: &Workspace, apply: bool) -> CoreResult<UpdateOutcome> {
+    let team = team::active_team_id(workspace);
+    let team_dir = workspace.teams_dir().join(&team);
+    let record_path = source_record_path(&team_dir);
+    let record_yaml = std::fs::read_to_string(&record_path).map_err(|_| {
+        CoreError::NotFound(format!("no recorded source for team '{team}' (install one with `snps templates import --from`)"))
+    })?;
+    let record: SourceRecord = serde_yaml::from_str(&record_yaml)
+        .map_err(|e| CoreError::InvalidInput(format!("unreadable {}: {e}", record_path.display())))?;
+
+    let source = match record.source {
+        ImportedSource::Repository(id) => ClassifiedSource::Repository(id),
+        ImportedSource::Url(url) => ClassifiedSource::Url(url),
+    };
+    let fresh = fetch_source(workspace, &source, &team).await?;
+    validate_idlc_yaml(&fresh)?;
+
+    let idlc_dest = team_dir.join("idlc.yaml");
+    let current = std::fs::read_to_string(&idlc_dest).unwrap_or_default();
+    let changed = fresh != current;
+
+    if changed && apply {
+        std::fs::write(&idlc_dest, &fresh)?;
+    }
+
+    Ok(UpdateOutcome { changed, applied: changed && apply, diff: diff_lines(&current, &fresh) })
+}
+
+/// A minimal line diff: lines only in `before` are `-`, lines only in
+/// `after` are `+`, in the order each side lists them. Not an LCS
+/// alignment, just enough for a human to glance at before choosing to
+/// overwrite local customizations with `--apply`.
+fn diff_lines(before: &str, after: &str) -> Vec<String> {
+    let before_lines: Vec<&str> = before.lines().collect();
+    let after_lines: Vec<&str> = after.lines().collect();
+    let mut out = Vec::new();
+    for line in &before_lines {
+        if !after_lines.contains(line) {
+            out.push(format!("-{line}"));
+        }
+    }
+    for line in &after_lines {
+        if !before_lines.contains(line) {
+            out.push(format!("+{line}"));
+        }
+    }
+    out
+}
+
+/// Copy a template's files into `dest_dir` as a plain folder, for sharing
+/// between teams.
+pub fn export_template(workspace: &Workspace, name: &str, dest_dir: &Path) -> CoreResult<()> {
+    std::fs::create_dir_all(dest_dir)?;
+
+    if let Some(builtin) = built_in(name) {
+        std::fs::write(dest_dir.join("idlc.yaml"), builtin.idlc_yaml)?;
+        std::fs::create_dir_all(dest_dir.join("prompts"))?;
+        for (file_name, contents) in builtin.prompts {
+            std::fs::write(dest_dir.join("prompts").join(file_name), contents)?;
+        }
+        return Ok(());
+    }
+
+    let src_dir = custom_templates_dir(workspace).join(name);
+    if !src_dir.is_dir() {
+        return Err(CoreError::NotFound(format!("no template named '{name}'")));
+    }
+    std::fs::copy(src_dir.join("idlc.yaml"), dest_dir.join("idlc.yaml"))?;
+    copy_prompts(&src_dir.join("prompts"), &dest_dir.join("prompts"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_bmad_template_validates_clean() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        let workspace = Workspace::discover(tmp.path()).unwrap();
+        assert!(validate_template(&workspace, "bmad").unwrap().is_empty());
+    }
+
+    #[test]
+    fn use_template_refuses_overwrite_without_force() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        let workspace = Workspace::discover(tmp.path()).unwrap();
+
+        use_template(&workspace, "default", false).unwrap();
+        assert!(use_template(&workspace, "bmad", false).is_err());
+        assert!(use_template(&workspace, "bmad", true).is_ok());
+    }
+
+    #[test]
+    fn classify_source_recognizes_urls_and_existing_files() {
+        assert!(matches!(classify_source("https://example.com/idlc.yaml"), ClassifiedSource::Url(_)));
+        assert!(matches!(classify_source("http://example.com/idlc.yaml"), ClassifiedSource::Url(_)));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("idlc.yaml");
+        std::fs::write(&file, "stages: [backlog]\n").unwrap();
+        assert!(matches!(classify_source(file.to_str().unwrap()), ClassifiedSource::File(_)));
+
+        assert!(matches!(classify_source("shared-knowledge"), ClassifiedSource::Repository(_)));
+    }
+
+    #[test]
+    fn source_record_is_none_for_local_files_and_set_for_repo_and_url() {
+        assert!(source_record(&ClassifiedSource::File(PathBuf::from("idlc.yaml"))).is_none());
+        assert!(source_record(&ClassifiedSource::Repository("shared-knowledge".into())).is_some());
+        assert!(source_record(&ClassifiedSource::Url("https://example.com/idlc.yaml".into())).is_some());
+    }
+
+    #[test]
+    fn diff_lines_reports_removed_and_added_lines() {
+        let before = "stages:\n  - backlog\n  - done\n";
+        let after = "stages:\n  - backlog\n  - review\n  - done\n";
+        let diff = diff_lines(before, after);
+        assert_eq!(diff, vec!["+  - review".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn import_idlc_config_installs_from_a_local_file_without_a_source_record() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        let workspace = Workspace::discover(tmp.path()).unwrap();
+
+        let source_file = tmp.path().join("shared-idlc.yaml");
+        std::fs::write(&source_file, "stages: [backlog, done]\nstatuses: [backlog, done]\n").unwrap();
+
+        let dest = import_idlc_config(&workspace, source_file.to_str().unwrap(), false).await.unwrap();
+        assert!(dest.exists());
+        assert!(!source_record_path(dest.parent().unwrap()).exists());
+    }
+
+    #[tokio::test]
+    async fn import_idlc_config_rejects_a_config_with_bad_references() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        let workspace = Workspace::discover(tmp.path()).unwrap();
+
+        let source_file = tmp.path().join("bad-idlc.yaml");
+        std::fs::write(&source_file, "stages: [backlog]\ntransitions:\n  - from: backlog\n    to: shipped\n").unwrap();
+
+        assert!(import_idlc_config(&workspace, source_file.to_str().unwrap(), false).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn update_idlc_config_errors_without_a_recorded_source() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        let workspace = Workspace::discover(tmp.path()).unwrap();
+        use_template(&workspace, "default", false).unwrap();
+
+        assert!(update_idlc_config(&workspace, false).await.is_err());
+    }
+}
@@ -0,0 +1,68 @@
+//! Archive and cleanup for thoughts documents: move old or explicitly
+//! selected documents under `thoughts/archive/<year>/`, preserving
+//! relative structure.
+
+use crate::error::CoreResult;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+pub struct ArchiveOptions {
+    pub older_than: Option<Duration>,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArchivedFile {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// Move thoughts documents older than `options.older_than` (by modified
+/// time) into `thoughts/archive/`, mirroring their path under the
+/// original doc-type directory. Returns what was (or would be) moved.
+pub fn archive_thoughts(thoughts_dir: &Path, options: &ArchiveOptions) -> CoreResult<Vec<ArchivedFile>> {
+    let archive_dir = thoughts_dir.join("archive");
+    let now = SystemTime::now();
+    let mut moved = Vec::new();
+
+    for path in walk_markdown(thoughts_dir, &archive_dir) {
+        let is_old = match (options.older_than, fs::metadata(&path).and_then(|m| m.modified())) {
+            (Some(threshold), Ok(modified)) => now.duration_since(modified).unwrap_or_default() >= threshold,
+            _ => false,
+        };
+        if !is_old {
+            continue;
+        }
+
+        let relative = path.strip_prefix(thoughts_dir).unwrap_or(&path);
+        let destination = archive_dir.join(relative);
+
+        if !options.dry_run {
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(&path, &destination)?;
+        }
+        moved.push(ArchivedFile { from: path, to: destination });
+    }
+
+    Ok(moved)
+}
+
+fn walk_markdown(dir: &Path, skip: &Path) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else { return out };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path == *skip {
+            continue;
+        }
+        if path.is_dir() {
+            out.extend(walk_markdown(&path, skip));
+        } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+            out.push(path);
+        }
+    }
+    out
+}
@@ -0,0 +1,99 @@
+//! Frontmatter metadata and listing for thoughts documents.
+
+use super::ThoughtType;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct ThoughtItem {
+    pub path: PathBuf,
+    pub title: String,
+    pub thought_type: Option<ThoughtType>,
+    pub tags: Vec<String>,
+    pub created: Option<String>,
+    /// `tracker`/`ticket_id`/`url` frontmatter fields, populated on
+    /// ticket documents by `snps thoughts link-ticket`.
+    pub tracker: Option<String>,
+    pub ticket_id: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Default)]
+pub struct ThoughtsListFilter {
+    pub thought_type: Option<ThoughtType>,
+    pub tag: Option<String>,
+    pub ticket: Option<String>,
+}
+
+fn parse_frontmatter(contents: &str) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+    if let Some(rest) = contents.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---\n") {
+            for line in rest[..end].lines() {
+                if let Some((key, value)) = line.split_once(':') {
+                    fields.insert(key.trim().to_string(), value.trim().trim_matches('"').to_string());
+                }
+            }
+        }
+    }
+    fields
+}
+
+fn parse_thought_file(path: &Path) -> Option<ThoughtItem> {
+    let contents = fs::read_to_string(path).ok()?;
+    let fields = parse_frontmatter(&contents);
+    Some(ThoughtItem {
+        title: fields
+            .get("title")
+            .cloned()
+            .unwrap_or_else(|| path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_string()),
+        thought_type: fields.get("type").map(|t| ThoughtType::from_frontmatter(t)),
+        tags: fields
+            .get("tags")
+            .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
+            .unwrap_or_default(),
+        created: fields.get("created").cloned(),
+        tracker: fields.get("tracker").cloned(),
+        ticket_id: fields.get("ticket_id").cloned(),
+        url: fields.get("url").cloned(),
+        path: path.to_path_buf(),
+    })
+}
+
+/// Title a search index would use for `path`: the frontmatter `title`
+/// field, falling back to the file stem.
+pub fn thought_title(path: &Path) -> Option<String> {
+    parse_thought_file(path).map(|item| item.title)
+}
+
+fn walk_markdown(dir: &Path) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else { return out };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            out.extend(walk_markdown(&path));
+        } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+            out.push(path);
+        }
+    }
+    out
+}
+
+/// List thoughts documents under `thoughts_dir`, optionally filtered by
+/// type and/or tag from their frontmatter.
+pub fn list_thoughts(thoughts_dir: &Path, filter: &ThoughtsListFilter) -> Vec<ThoughtItem> {
+    walk_markdown(thoughts_dir)
+        .into_iter()
+        .filter_map(|p| parse_thought_file(&p))
+        .filter(|item| {
+            filter.thought_type.as_ref().is_none_or(|t| item.thought_type.as_ref() == Some(t))
+                && filter
+                    .tag
+                    .as_ref()
+                    .is_none_or(|tag| item.tags.iter().any(|t| t == tag))
+                && filter.ticket.as_ref().is_none_or(|ticket| item.ticket_id.as_deref() == Some(ticket.as_str()))
+        })
+        .collect()
+}
@@ -0,0 +1,49 @@
+//! `snps thoughts journal` — one file per day under
+//! `thoughts/journal/<date>.md`, created on first write and appended to
+//! afterward.
+
+use crate::error::CoreResult;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Path of today's journal entry, given `today` as `YYYY-MM-DD`.
+pub fn journal_path(thoughts_dir: &Path, today: &str) -> PathBuf {
+    thoughts_dir.join("journal").join(format!("{today}.md"))
+}
+
+/// Append `entry` to today's journal file, creating it with a heading if
+/// it doesn't exist yet. Each entry is timestamped within the file.
+pub fn append_journal_entry(thoughts_dir: &Path, today: &str, time: &str, entry: &str) -> CoreResult<PathBuf> {
+    let path = journal_path(thoughts_dir, today);
+    let is_new = !path.exists();
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    if is_new {
+        writeln!(file, "---\ntitle: \"Journal — {today}\"\ntype: journal\n---\n")?;
+    }
+    writeln!(file, "## {time}\n\n{entry}\n")?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_entry_appends_without_duplicating_header() {
+        let tmp = tempfile::tempdir().unwrap();
+        append_journal_entry(tmp.path(), "2026-08-08", "09:00", "first").unwrap();
+        let path = append_journal_entry(tmp.path(), "2026-08-08", "10:00", "second").unwrap();
+
+        let contents = std::fs::read_to_string(path).unwrap();
+        assert_eq!(contents.matches("type: journal").count(), 1);
+        assert!(contents.contains("first"));
+        assert!(contents.contains("second"));
+    }
+}
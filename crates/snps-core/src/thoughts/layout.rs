@@ -0,0 +1,181 @@
+//! Configurable thoughts categories: which directory (if any, directly
+//! under `thoughts_dir`) a category's documents live in, and the template
+//! `thoughts new` seeds a fresh document from. Teams override or extend the
+//! built-in set via the `thoughts.categories` config section (see
+//! [`crate::config`]); an empty/unset config falls back to
+//! [`ThoughtsLayout::default`] so existing setups keep working.
+
+use super::tracker::TICKET_TEMPLATE;
+use crate::error::{CoreError, CoreResult};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// One configured category: its name (matched against frontmatter `type`
+/// and the `thoughts new <category>` argument), the directory it lives in
+/// under `thoughts_dir` (`None` means directly under `thoughts_dir`, like
+/// `note`/`research` today), and the template a new document is seeded
+/// from (`None` falls back to a generic frontmatter stub).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CategoryConfig {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+/// Placeholder-filled frontmatter for a category with no configured
+/// template — `note` and `research` documents use this today.
+const GENERIC_TEMPLATE: &str = "---\ntitle: \"{{title}}\"\ntype: \"{{type}}\"\ncreated: {{date}}\n---\n\n";
+
+/// The resolved set of categories `thoughts new`/`thoughts init` operate
+/// over, either the built-in defaults or a team's `thoughts.categories`
+/// config.
+#[derive(Debug, Clone)]
+pub struct ThoughtsLayout {
+    pub categories: Vec<CategoryConfig>,
+}
+
+impl ThoughtsLayout {
+    /// The categories this build ships with, matching the historically
+    /// hardcoded layout: `note` and `research` live directly under
+    /// `thoughts_dir`, `journal` gets its own `journal/` directory, and
+    /// `ticket` uses [`TICKET_TEMPLATE`].
+    pub fn default_categories() -> Vec<CategoryConfig> {
+        vec![
+            CategoryConfig { name: "note".to_string(), dir: None, template: None },
+            CategoryConfig { name: "research".to_string(), dir: None, template: None },
+            CategoryConfig { name: "journal".to_string(), dir: Some("journal".to_string()), template: None },
+            CategoryConfig { name: "ticket".to_string(), dir: None, template: Some(TICKET_TEMPLATE.to_string()) },
+        ]
+    }
+
+    /// Build a layout from a team's `thoughts.categories` config,
+    /// falling back to [`Self::default_categories`] when it's empty so an
+    /// unset config keeps the current behavior.
+    pub fn from_config(categories: Vec<CategoryConfig>) -> Self {
+        if categories.is_empty() {
+            ThoughtsLayout { categories: Self::default_categories() }
+        } else {
+            ThoughtsLayout { categories }
+        }
+    }
+
+    pub fn find(&self, name: &str) -> Option<&CategoryConfig> {
+        self.categories.iter().find(|c| c.name == name)
+    }
+
+    /// The subdirectories (excluding categories that live directly under
+    /// `thoughts_dir`) that `thoughts_init` needs to create.
+    pub fn directories(&self) -> Vec<&str> {
+        self.categories.iter().filter_map(|c| c.dir.as_deref()).collect()
+    }
+}
+
+impl Default for ThoughtsLayout {
+    fn default() -> Self {
+        ThoughtsLayout { categories: Self::default_categories() }
+    }
+}
+
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    let mut last_dash = false;
+    for c in title.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+            last_dash = false;
+        } else if !last_dash {
+            slug.push('-');
+            last_dash = true;
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Create every configured category's directory under `thoughts_dir`
+/// (categories with no `dir` live directly in `thoughts_dir`, which this
+/// also creates).
+pub fn thoughts_init(thoughts_dir: &Path, layout: &ThoughtsLayout) -> CoreResult<()> {
+    std::fs::create_dir_all(thoughts_dir)?;
+    for dir in layout.directories() {
+        std::fs::create_dir_all(thoughts_dir.join(dir))?;
+    }
+    Ok(())
+}
+
+/// Seed a new document for `category`, named `<today>-<slug of title>.md`
+/// in that category's directory. `category` must be one of `layout`'s
+/// configured names — see [`ThoughtsLayout::find`].
+pub fn thoughts_new(thoughts_dir: &Path, layout: &ThoughtsLayout, category: &str, title: &str, today: &str) -> CoreResult<PathBuf> {
+    let config = layout.find(category).ok_or_else(|| CoreError::InvalidInput(format!("unknown thoughts category '{category}'")))?;
+
+    let dir = match &config.dir {
+        Some(dir) => thoughts_dir.join(dir),
+        None => thoughts_dir.to_path_buf(),
+    };
+    std::fs::create_dir_all(&dir)?;
+
+    let slug = slugify(title);
+    let path = dir.join(format!("{today}-{slug}.md"));
+
+    let template = config.template.as_deref().unwrap_or(GENERIC_TEMPLATE);
+    let rendered = template.replace("{{title}}", title).replace("{{type}}", category).replace("{{date}}", today);
+
+    std::fs::write(&path, &rendered)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_categories_match_historical_layout() {
+        let layout = ThoughtsLayout::default();
+        assert_eq!(layout.directories(), vec!["journal"]);
+        assert!(layout.find("note").unwrap().dir.is_none());
+        assert!(layout.find("ticket").unwrap().template.is_some());
+    }
+
+    #[test]
+    fn from_config_falls_back_to_defaults_when_empty() {
+        let layout = ThoughtsLayout::from_config(vec![]);
+        assert_eq!(layout.categories, ThoughtsLayout::default_categories());
+    }
+
+    #[test]
+    fn from_config_uses_team_categories_when_present() {
+        let categories = vec![CategoryConfig { name: "adr".to_string(), dir: Some("adr".to_string()), template: None }];
+        let layout = ThoughtsLayout::from_config(categories.clone());
+        assert_eq!(layout.categories, categories);
+        assert!(layout.find("note").is_none());
+    }
+
+    #[test]
+    fn thoughts_init_creates_configured_tree() {
+        let tmp = tempfile::tempdir().unwrap();
+        let thoughts_dir = tmp.path().join("thoughts");
+        thoughts_init(&thoughts_dir, &ThoughtsLayout::default()).unwrap();
+        assert!(thoughts_dir.join("journal").is_dir());
+    }
+
+    #[test]
+    fn thoughts_new_renders_template_and_writes_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let thoughts_dir = tmp.path().join("thoughts");
+        let layout = ThoughtsLayout::default();
+        let path = thoughts_new(&thoughts_dir, &layout, "journal", "Sprint Retro", "2026-08-08").unwrap();
+        assert_eq!(path, thoughts_dir.join("journal/2026-08-08-sprint-retro.md"));
+        let contents = std::fs::read_to_string(path).unwrap();
+        assert!(contents.contains("title: \"Sprint Retro\""));
+        assert!(contents.contains("type: \"journal\""));
+    }
+
+    #[test]
+    fn thoughts_new_rejects_unknown_category() {
+        let tmp = tempfile::tempdir().unwrap();
+        let err = thoughts_new(&tmp.path().join("thoughts"), &ThoughtsLayout::default(), "adr", "Use Postgres", "2026-08-08").unwrap_err();
+        assert!(err.to_string().contains("unknown thoughts category"));
+    }
+}
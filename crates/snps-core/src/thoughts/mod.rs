@@ -0,0 +1,65 @@
+//! Thoughts: a plain-markdown, append-friendly notes system living under
+//! `thoughts/` in the workspace, separate from matter's frontmattered
+//! document types.
+
+pub mod archive;
+pub mod index;
+pub mod journal;
+pub mod layout;
+pub mod search;
+pub mod sync;
+pub mod tracker;
+
+pub use archive::{archive_thoughts, ArchiveOptions, ArchivedFile};
+pub use journal::append_journal_entry;
+pub use index::{list_thoughts, thought_title, ThoughtItem, ThoughtsListFilter};
+pub use layout::{thoughts_init, thoughts_new, CategoryConfig, ThoughtsLayout};
+pub use search::{thoughts_search, ThoughtsSearchOptions};
+pub use sync::{sync_thoughts, ThoughtsSyncScope, ThoughtsSyncSummary};
+pub use tracker::{link_ticket, parse_tracker_url, TrackerRef, TICKET_TEMPLATE};
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThoughtType {
+    Note,
+    Research,
+    Journal,
+    Ticket,
+    /// A category this build has no built-in variant for — most often a
+    /// team-configured one from [`ThoughtsLayout`] (`adr`, `retro`, ...).
+    /// Kept by name rather than being dropped or misclassified, mirroring
+    /// [`crate::matter::MatterType::Custom`].
+    Custom(String),
+}
+
+impl ThoughtType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ThoughtType::Note => "note",
+            ThoughtType::Research => "research",
+            ThoughtType::Journal => "journal",
+            ThoughtType::Ticket => "ticket",
+            ThoughtType::Custom(name) => name,
+        }
+    }
+
+    /// Parse a frontmatter `type:` value, falling back to `Custom`
+    /// instead of failing, so a document using a team-configured category
+    /// still shows up in listings and search rather than vanishing.
+    pub fn from_frontmatter(s: &str) -> ThoughtType {
+        match s {
+            "note" => ThoughtType::Note,
+            "research" => ThoughtType::Research,
+            "journal" => ThoughtType::Journal,
+            "ticket" => ThoughtType::Ticket,
+            other => ThoughtType::Custom(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ThoughtType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
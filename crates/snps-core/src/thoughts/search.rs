@@ -0,0 +1,94 @@
+//! `snps thoughts search`, built on the shared [`crate::search`] engine so
+//! it keeps working without `rg` installed.
+
+use crate::error::CoreResult;
+use crate::search::{search_dir, SearchMatch, SearchOptions};
+use std::path::{Path, PathBuf};
+
+pub struct ThoughtsSearchOptions {
+    pub paths_only: bool,
+    pub doc_type: Option<String>,
+    pub search: SearchOptions,
+}
+
+impl Default for ThoughtsSearchOptions {
+    fn default() -> Self {
+        Self {
+            paths_only: false,
+            doc_type: None,
+            search: SearchOptions::default(),
+        }
+    }
+}
+
+pub enum ThoughtsSearchResult {
+    Paths(Vec<PathBuf>),
+    Matches(Vec<RankedMatch>),
+}
+
+pub struct RankedMatch {
+    pub search_match: SearchMatch,
+    pub score: u32,
+}
+
+/// Score a match: a hit in the title (first `# heading` or frontmatter
+/// `title:` line) outweighs a body hit, and files with more matches rank
+/// higher within the same tier.
+fn score(path: &Path, query: &str, body_hits: usize) -> u32 {
+    let title_hit = std::fs::read_to_string(path)
+        .map(|contents| {
+            let query_lower = query.to_lowercase();
+            contents
+                .lines()
+                .take(20)
+                .any(|line| {
+                    let lower = line.to_lowercase();
+                    (lower.starts_with("title:") || lower.starts_with('#')) && lower.contains(&query_lower)
+                })
+        })
+        .unwrap_or(false);
+
+    let base = if title_hit { 1_000 } else { 0 };
+    base + body_hits as u32
+}
+
+/// Search the thoughts directory, filtering by doc type subdirectory when
+/// requested and collapsing to a path list for `--paths-only`. Matches
+/// are ranked by relevance: title/frontmatter hits first, then by number
+/// of body matches within a file.
+pub fn thoughts_search(
+    thoughts_dir: &Path,
+    query: &str,
+    options: &ThoughtsSearchOptions,
+) -> CoreResult<ThoughtsSearchResult> {
+    let root = match &options.doc_type {
+        Some(doc_type) => thoughts_dir.join(doc_type),
+        None => thoughts_dir.to_path_buf(),
+    };
+
+    let matches = search_dir(&root, query, &options.search);
+
+    if options.paths_only {
+        let mut paths: Vec<PathBuf> = matches.into_iter().map(|m| m.path).collect();
+        paths.sort();
+        paths.dedup();
+        return Ok(ThoughtsSearchResult::Paths(paths));
+    }
+
+    let mut hits_per_path: std::collections::HashMap<PathBuf, usize> = std::collections::HashMap::new();
+    for m in &matches {
+        *hits_per_path.entry(m.path.clone()).or_default() += 1;
+    }
+
+    let mut ranked: Vec<RankedMatch> = matches
+        .into_iter()
+        .map(|m| {
+            let hits = hits_per_path.get(&m.path).copied().unwrap_or(1);
+            let score = score(&m.path, query, hits);
+            RankedMatch { search_match: m, score }
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.score.cmp(&a.score));
+
+    Ok(ThoughtsSearchResult::Matches(ranked))
+}
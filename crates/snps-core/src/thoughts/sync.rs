@@ -0,0 +1,130 @@
+//! `snps thoughts sync`: commit a scoped slice of `thoughts/` instead of
+//! staging the whole tree, so a commit message doesn't end up claiming
+//! changes to categories (or, for a sub-project, subtrees) the caller
+//! never touched.
+//!
+//! There's no "central repo" shared across projects/profiles in this
+//! codebase to worry about beyond that — [`crate::git::GitRepo`] is
+//! opened at the workspace root, the same repo every other command here
+//! commits into (see `config/shadow.rs`'s shadow-repo pushes for the only
+//! other place this crate commits on a caller's behalf). "Scope" here is
+//! a configured [`super::ThoughtsLayout`] category name (or `None` for
+//! the whole `thoughts/` tree) rather than a fixed `shared`/`personal`/
+//! `global` enum — there's no such three-way split anywhere in this
+//! tree, and categories are already the real unit teams configure and
+//! filter by (`thoughts new <category>`, `thoughts list --doc-type`).
+
+use super::layout::ThoughtsLayout;
+use crate::error::{CoreError, CoreResult};
+use crate::git::GitRepo;
+use crate::repository::sync::git_push;
+use crate::search_index::SearchIndex;
+use std::path::{Path, PathBuf};
+
+/// What to restrict a sync to, beyond the whole `thoughts/` tree.
+pub struct ThoughtsSyncScope<'a> {
+    /// A configured category name (`layout.find`), or `None` for every
+    /// category.
+    pub category: Option<&'a str>,
+    /// Further restrict to this subpath under the resolved category (or
+    /// under `thoughts_dir` itself when `category` is `None`).
+    pub path: Option<&'a Path>,
+}
+
+/// What [`sync_thoughts`] did.
+pub struct ThoughtsSyncSummary {
+    /// The scope actually committed, repo-root-relative, for the caller
+    /// to fold into a commit message or status line.
+    pub scope_label: String,
+    /// Files the search index re-parsed while rebuilding against the
+    /// scoped subtree.
+    pub reparsed: usize,
+    /// `false` when the scope had nothing to commit — the index is still
+    /// rebuilt either way, since the index cache and git history aren't
+    /// the same "up to date" question.
+    pub committed: bool,
+    /// Dirty `thoughts/` paths outside the requested scope, left
+    /// untouched. Report the count so the caller knows other work is
+    /// still pending without this command silently sweeping it in.
+    pub other_dirty_count: usize,
+}
+
+fn resolve_scope_dir(thoughts_dir: &Path, layout: &ThoughtsLayout, scope: &ThoughtsSyncScope<'_>) -> CoreResult<PathBuf> {
+    let category_dir = match scope.category {
+        None => thoughts_dir.to_path_buf(),
+        Some(name) => {
+            let config = layout.find(name).ok_or_else(|| CoreError::InvalidInput(format!("unknown thoughts category '{name}'")))?;
+            match &config.dir {
+                Some(dir) => thoughts_dir.join(dir),
+                None => thoughts_dir.to_path_buf(),
+            }
+        }
+    };
+    Ok(match scope.path {
+        Some(path) => category_dir.join(path),
+        None => category_dir,
+    })
+}
+
+fn repo_relative(repo_root: &Path, path: &Path) -> String {
+    path.strip_prefix(repo_root).unwrap_or(path).to_string_lossy().replace('\\', "/")
+}
+
+/// Rebuild the search index against `target_dir` only, stage and commit
+/// just that subtree (via git2 pathspec matching, so new/removed files
+/// under it are covered without listing them by hand), and report how
+/// many other `thoughts/` files were left dirty. Fails the same way
+/// [`crate::config::shadow::config_push`] does for a repo in an unsafe
+/// state to commit into (mid-merge, mid-rebase, detached HEAD, shallow
+/// with no remote) — see [`crate::git::RepoHazard`].
+pub fn sync_thoughts(
+    workspace_root: &Path,
+    thoughts_dir: &Path,
+    layout: &ThoughtsLayout,
+    scope: &ThoughtsSyncScope<'_>,
+    index_path: &Path,
+    search_exclude_patterns: &[String],
+    push: bool,
+) -> CoreResult<ThoughtsSyncSummary> {
+    let target_dir = resolve_scope_dir(thoughts_dir, layout, scope)?;
+
+    let git = GitRepo::open(workspace_root)?;
+    if let Some(hazard) = git.commit_hazard()? {
+        return Err(CoreError::InvalidInput(hazard.describe(workspace_root)));
+    }
+
+    let relative_thoughts = repo_relative(workspace_root, thoughts_dir);
+    let relative_target = repo_relative(workspace_root, &target_dir);
+
+    let other_dirty_count = git
+        .porcelain_status(&[&relative_thoughts])?
+        .into_iter()
+        .filter(|line| {
+            let path = line.get(3..).unwrap_or_default();
+            !(path == relative_target || path.starts_with(&format!("{relative_target}/")))
+        })
+        .count();
+
+    let mut index = SearchIndex::load(index_path)?;
+    let reparsed = index.rebuild_incremental(&target_dir, search_exclude_patterns, |p| super::index::thought_title(p))?;
+    index.save(index_path)?;
+
+    let in_scope = !git.porcelain_status(&[&relative_target])?.is_empty();
+    let committed = if in_scope {
+        let scope_label = scope
+            .category
+            .map(|c| format!("category '{c}'"))
+            .unwrap_or_else(|| "all categories".to_string());
+        let path_label = scope.path.map(|p| format!(", path '{}'", p.display())).unwrap_or_default();
+        let message = format!("thoughts: sync {scope_label}{path_label}");
+        git.commit_pathspecs(&[&relative_target], &message)?;
+        if push {
+            git_push(workspace_root)?;
+        }
+        true
+    } else {
+        false
+    };
+
+    Ok(ThoughtsSyncSummary { scope_label: relative_target, reparsed, committed, other_dirty_count })
+}
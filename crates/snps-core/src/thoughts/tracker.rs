@@ -0,0 +1,179 @@
+//! `snps thoughts link-ticket` — parsing external tracker URLs and
+//! writing them into a ticket document's frontmatter.
+
+use crate::error::CoreResult;
+use std::path::Path;
+
+/// The template `snps thoughts new ticket` seeds a fresh ticket document
+/// from (see [`super::layout`]); `link-ticket` later fills in the
+/// `tracker`/`ticket_id`/`url` fields left blank here.
+pub const TICKET_TEMPLATE: &str = "---\ntitle: \"{{title}}\"\ntype: ticket\ntracker: \"\"\nticket_id: \"\"\nurl: \"\"\ncreated: {{date}}\n---\n\n";
+
+/// A ticket reference resolved from a tracker URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackerRef {
+    /// `"github"`, `"jira"`, or `"gitlab"`.
+    pub tracker: String,
+    /// The tracker's own id format: `owner/repo#N` for GitHub/GitLab
+    /// issues, `KEY-N` for Jira.
+    pub id: String,
+    pub url: String,
+}
+
+/// Parse a ticket URL into a [`TrackerRef`]. Supports the three common
+/// shapes teams actually paste around: GitHub issues
+/// (`github.com/owner/repo/issues/N`), GitLab issues
+/// (`gitlab.com/owner/repo/-/issues/N`), and Jira Cloud
+/// (`*.atlassian.net/browse/KEY-N`). Returns `None` for anything else
+/// rather than guessing.
+pub fn parse_tracker_url(url: &str) -> Option<TrackerRef> {
+    let trimmed = url.trim_end_matches('/');
+    let path = trimmed.strip_prefix("https://").or_else(|| trimmed.strip_prefix("http://"))?;
+
+    if let Some(rest) = path.strip_prefix("github.com/") {
+        let parts: Vec<&str> = rest.splitn(4, '/').collect();
+        if let [owner, repo, "issues", number] = parts[..] {
+            if number.chars().all(|c| c.is_ascii_digit()) && !number.is_empty() {
+                return Some(TrackerRef { tracker: "github".to_string(), id: format!("{owner}/{repo}#{number}"), url: url.to_string() });
+            }
+        }
+        return None;
+    }
+
+    if let Some(rest) = path.strip_prefix("gitlab.com/") {
+        let parts: Vec<&str> = rest.splitn(5, '/').collect();
+        if let [owner, repo, "-", "issues", number] = parts[..] {
+            if number.chars().all(|c| c.is_ascii_digit()) && !number.is_empty() {
+                return Some(TrackerRef { tracker: "gitlab".to_string(), id: format!("{owner}/{repo}#{number}"), url: url.to_string() });
+            }
+        }
+        return None;
+    }
+
+    if let Some((site, rest)) = path.split_once(".atlassian.net/") {
+        if !site.is_empty() {
+            if let Some(key) = rest.strip_prefix("browse/") {
+                if is_jira_key(key) {
+                    return Some(TrackerRef { tracker: "jira".to_string(), id: key.to_string(), url: url.to_string() });
+                }
+            }
+        }
+        return None;
+    }
+
+    None
+}
+
+/// A Jira issue key is an uppercase project prefix, a dash, and digits
+/// (`PROJ-123`).
+fn is_jira_key(s: &str) -> bool {
+    let Some((project, number)) = s.split_once('-') else { return false };
+    !project.is_empty()
+        && project.chars().all(|c| c.is_ascii_uppercase())
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Write `tracker_ref`'s fields into `path`'s frontmatter, replacing any
+/// existing `tracker`/`ticket_id`/`url` lines in place and appending the
+/// rest before the closing `---` — or writing a fresh frontmatter block if
+/// the file has none.
+pub fn link_ticket(path: &Path, tracker_ref: &TrackerRef) -> CoreResult<()> {
+    let contents = std::fs::read_to_string(path)?;
+    let fields = [("tracker", tracker_ref.tracker.as_str()), ("ticket_id", tracker_ref.id.as_str()), ("url", tracker_ref.url.as_str())];
+
+    let updated = if let Some(rest) = contents.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---\n") {
+            let mut lines: Vec<String> = rest[..end].lines().map(str::to_string).collect();
+            let body = &rest[end + "\n---\n".len()..];
+            for (key, value) in fields {
+                let line = format!("{key}: \"{value}\"");
+                match lines.iter().position(|l| l.split_once(':').map(|(k, _)| k.trim()) == Some(key)) {
+                    Some(i) => lines[i] = line,
+                    None => lines.push(line),
+                }
+            }
+            format!("---\n{}\n---\n{body}", lines.join("\n"))
+        } else {
+            prepend_frontmatter(&fields, &contents)
+        }
+    } else {
+        prepend_frontmatter(&fields, &contents)
+    };
+
+    std::fs::write(path, updated)?;
+    Ok(())
+}
+
+fn prepend_frontmatter(fields: &[(&str, &str); 3], contents: &str) -> String {
+    let mut block = String::from("---\n");
+    for (key, value) in fields {
+        block.push_str(&format!("{key}: \"{value}\"\n"));
+    }
+    block.push_str("---\n");
+    block.push_str(contents);
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_github_issue_url() {
+        let r = parse_tracker_url("https://github.com/acme/widgets/issues/42").unwrap();
+        assert_eq!(r.tracker, "github");
+        assert_eq!(r.id, "acme/widgets#42");
+    }
+
+    #[test]
+    fn parses_jira_cloud_url() {
+        let r = parse_tracker_url("https://acme.atlassian.net/browse/PROJ-123").unwrap();
+        assert_eq!(r.tracker, "jira");
+        assert_eq!(r.id, "PROJ-123");
+    }
+
+    #[test]
+    fn parses_gitlab_issue_url() {
+        let r = parse_tracker_url("https://gitlab.com/acme/widgets/-/issues/7").unwrap();
+        assert_eq!(r.tracker, "gitlab");
+        assert_eq!(r.id, "acme/widgets#7");
+    }
+
+    #[test]
+    fn rejects_unrecognized_urls() {
+        assert!(parse_tracker_url("https://example.com/not-a-ticket").is_none());
+        assert!(parse_tracker_url("not a url at all").is_none());
+    }
+
+    #[test]
+    fn link_ticket_updates_existing_frontmatter_in_place() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("ticket.md");
+        std::fs::write(&path, "---\ntitle: \"Fix the thing\"\ntype: ticket\n---\n\nbody text\n").unwrap();
+
+        let tracker_ref = TrackerRef { tracker: "github".to_string(), id: "acme/widgets#42".to_string(), url: "https://github.com/acme/widgets/issues/42".to_string() };
+        link_ticket(&path, &tracker_ref).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("title: \"Fix the thing\""));
+        assert!(contents.contains("tracker: \"github\""));
+        assert!(contents.contains("ticket_id: \"acme/widgets#42\""));
+        assert!(contents.contains("body text"));
+    }
+
+    #[test]
+    fn link_ticket_adds_frontmatter_when_missing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("ticket.md");
+        std::fs::write(&path, "just some notes\n").unwrap();
+
+        let tracker_ref = TrackerRef { tracker: "jira".to_string(), id: "PROJ-1".to_string(), url: "https://acme.atlassian.net/browse/PROJ-1".to_string() };
+        link_ticket(&path, &tracker_ref).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("---\n"));
+        assert!(contents.contains("ticket_id: \"PROJ-1\""));
+        assert!(contents.contains("just some notes"));
+    }
+}
@@ -0,0 +1,75 @@
+//! Minimal clock helpers. Kept dependency-free (no `chrono`) since only
+//! calendar-date and hour:minute formatting is needed.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Current Unix timestamp in seconds (UTC), for record `created_at`/
+/// `updated_at` fields.
+pub fn now_unix() -> u64 {
+    now_secs()
+}
+
+/// Today's date as `YYYY-MM-DD` (UTC).
+pub fn today_string() -> String {
+    date_string(now_secs())
+}
+
+pub fn date_string(secs: u64) -> String {
+    let days = secs / 86_400;
+    let z = days as i64 + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = if m <= 2 { y + 1 } else { y };
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+/// Current time as `HH:MM` (UTC).
+pub fn time_string() -> String {
+    let secs = now_secs() % 86_400;
+    format!("{:02}:{:02}", secs / 3600, (secs % 3600) / 60)
+}
+
+/// Coarse relative age (`"3m ago"`, `"5h ago"`, `"2d ago"`), for listings
+/// where an exact timestamp is more precision than the column has room
+/// for. Takes `now` explicitly rather than reading the clock, so it's
+/// testable without a real elapsed wait.
+pub fn age_string(secs: u64, now: u64) -> String {
+    let elapsed = now.saturating_sub(secs);
+    if elapsed < 60 {
+        "just now".to_string()
+    } else if elapsed < 3_600 {
+        format!("{}m ago", elapsed / 60)
+    } else if elapsed < 86_400 {
+        format!("{}h ago", elapsed / 3_600)
+    } else {
+        format!("{}d ago", elapsed / 86_400)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn age_string_buckets_by_magnitude() {
+        assert_eq!(age_string(1_000, 1_030), "just now");
+        assert_eq!(age_string(1_000, 1_000 + 5 * 60), "5m ago");
+        assert_eq!(age_string(1_000, 1_000 + 3 * 3_600), "3h ago");
+        assert_eq!(age_string(1_000, 1_000 + 2 * 86_400), "2d ago");
+    }
+
+    #[test]
+    fn age_string_never_underflows_when_now_precedes_secs() {
+        assert_eq!(age_string(2_000, 1_000), "just now");
+    }
+}
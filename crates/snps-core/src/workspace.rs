@@ -0,0 +1,99 @@
+//! Workspace root discovery, shared by every `cmd_*` function so that
+//! running a command from a nested directory behaves the same as running
+//! it at the project root.
+
+use crate::error::{CoreError, CoreResult};
+use std::path::{Path, PathBuf};
+
+const MARKER: &str = ".pmsynapse";
+
+/// A resolved `.pmsynapse` workspace: the root directory plus the paths
+/// commands read and write within it.
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    pub root: PathBuf,
+}
+
+impl Workspace {
+    /// Walk up from `start` looking for a `.pmsynapse` directory.
+    pub fn discover(start: &Path) -> CoreResult<Self> {
+        let mut dir = start
+            .canonicalize()
+            .unwrap_or_else(|_| start.to_path_buf());
+        loop {
+            if dir.join(MARKER).is_dir() {
+                return Ok(Self { root: dir });
+            }
+            match dir.parent() {
+                Some(parent) => dir = parent.to_path_buf(),
+                None => {
+                    return Err(CoreError::NotFound(format!(
+                        "no {MARKER} directory found above {}",
+                        start.display()
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Discover starting from the current working directory.
+    pub fn discover_from_cwd() -> CoreResult<Self> {
+        let cwd = std::env::current_dir()?;
+        Self::discover(&cwd)
+    }
+
+    pub fn pmsynapse_dir(&self) -> PathBuf {
+        self.root.join(MARKER)
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        self.pmsynapse_dir().join("config.yaml")
+    }
+
+    pub fn repositories_path(&self) -> PathBuf {
+        self.pmsynapse_dir().join("repositories.yaml")
+    }
+
+    pub fn teams_dir(&self) -> PathBuf {
+        self.pmsynapse_dir().join("teams")
+    }
+
+    pub fn knowledge_dir(&self) -> PathBuf {
+        self.root.join("knowledge")
+    }
+
+    pub fn thoughts_dir(&self) -> PathBuf {
+        self.root.join("thoughts")
+    }
+
+    pub fn matter_dir(&self) -> PathBuf {
+        self.root.join("matter")
+    }
+
+    pub fn graph_db_path(&self) -> PathBuf {
+        self.pmsynapse_dir().join("synapse.db")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn discovers_root_from_nested_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        let nested = tmp.path().join("matter").join("specs");
+        fs::create_dir_all(&nested).unwrap();
+
+        let ws = Workspace::discover(&nested).unwrap();
+        assert_eq!(ws.root, tmp.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn errors_when_no_marker_present() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(Workspace::discover(tmp.path()).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+//! Bearer-token gating for the daemon's HTTP API. Loopback-only daemons
+//! never needed this (only the same machine's `snps` and desktop app could
+//! reach them); binding to a LAN interface changes that, so `serve` refuses
+//! to do so unless a token is configured.
+
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+
+/// Environment variable holding the token. Read once at startup rather than
+/// per-request, so rotating it requires restarting the daemon — consistent
+/// with how `PMSYNAPSE_PROFILE` and other daemon env vars are handled.
+pub const TOKEN_ENV: &str = "PMSYNAPSE_DAEMON_TOKEN";
+
+#[derive(Clone)]
+pub struct RequiredToken(pub String);
+
+/// Rejects any request whose `Authorization: Bearer <token>` header doesn't
+/// match. `/health` is deliberately not exempted: a remote-bound daemon
+/// shouldn't leak even its liveness to an unauthenticated LAN client.
+pub async fn require_token(
+    State(expected): State<RequiredToken>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let presented = request
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    match presented {
+        Some(token) if token == expected.0 => Ok(next.run(request).await),
+        _ => Err(StatusCode::UNAUTHORIZED),
+    }
+}
@@ -0,0 +1,938 @@
+//! HTTP API for the PMSynapse daemon, used by the desktop app and by
+//! external agents that want to submit proposals or read the graph
+//! without shelling out to `snps`. Deliberately small: routes get added
+//! as each front end needs them, always backed by the same `snps-core`
+//! logic the CLI uses.
+
+pub mod auth;
+pub mod logging;
+mod metrics;
+pub mod pid;
+mod projects;
+mod scheduler;
+mod writer;
+
+use auth::RequiredToken;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use snps_core::claude::{list_claude_projects, list_sessions_for_project, resolved_title, titles_path, ClaudeProjectSummary, TitleStore};
+use snps_core::config::load_merged_config;
+use snps_core::dedup::{find_similar_titles, DuplicateCandidate, DEFAULT_THRESHOLD};
+use snps_core::embeddings::embedder_for;
+use snps_core::graph::{search_similar, GraphExport, KnowledgeGraph, Node, NodeOrderBy, NodeType, Provenance, ProvenanceSource};
+use snps_core::idlc::{IdlcItem, IdlcItemStore, ItemLink, LinkKind};
+use snps_core::matter::{self, MatterIndex, MatterItem, MatterType, NewMatter};
+use snps_core::proposals::{NewProposal, Proposal, ProposalStore, ProposedChange};
+use snps_core::repository::{self, Repository};
+use snps_core::Workspace;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{Notify, RwLock};
+use tower_http::cors::CorsLayer;
+
+/// There's no multi-user context config yet — same stand-in the CLI uses
+/// for `--include-private` gating (`commands::matter::CURRENT_CONTEXT`).
+const CURRENT_CONTEXT: &str = "project";
+
+/// How often the idle-eviction task in [`serve`] sweeps [`projects`] for
+/// projects that haven't been touched in [`PROJECT_IDLE_TIMEOUT`].
+const PROJECT_EVICTION_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+/// A project opened via [`projects::resolve_project_root`] that hasn't
+/// been touched for this long gets its `Workspace` dropped, closing its
+/// database handle until the next request for it reopens it.
+const PROJECT_IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+
+#[derive(Clone)]
+struct AppState {
+    workspace: Arc<Workspace>,
+    shutdown: Arc<Notify>,
+    /// Held for reading by the writer task while it applies a batch, and
+    /// for writing (exclusively) while a backup snapshot is taken, so a
+    /// backup never observes a batch import half-applied.
+    graph_write_lock: Arc<RwLock<()>>,
+    /// Loaded once at startup for the `matter` routes' visibility
+    /// filtering. `repositories.yaml` isn't watched for changes — every
+    /// other route already treats its config as fixed for the process
+    /// lifetime, so this matches. Global config isn't loaded yet since
+    /// no route needs it; that lands with whichever request first does.
+    repos: Arc<Vec<Repository>>,
+    /// Funnels all graph mutations through a single task so concurrent
+    /// writers never open competing sqlite connections. See [`writer`].
+    writer: writer::WriteHandle,
+    /// Front end to the background task that runs `sync.schedules` jobs.
+    /// See [`scheduler`].
+    scheduler: scheduler::SchedulerHandle,
+    /// Per-route request counts, latency histograms, and error counts.
+    /// See [`metrics`].
+    metrics: Arc<metrics::Metrics>,
+    /// The same log `serve` writes its own "daemon started"/"daemon
+    /// stopped" lines to, shared so the request-metrics middleware can
+    /// interleave per-request debug lines into it. `None` for `router()`'s
+    /// lifecycle-free test state, and whenever `DaemonLog::open` failed.
+    request_log: Arc<Mutex<Option<logging::DaemonLog>>>,
+    /// Other projects opened via `X-Pmsynapse-Project`/`?project=` (see
+    /// [`projects::resolve_project_root`]), lazily populated. Only
+    /// `list_nodes` and `GET /api/v1/projects` route through this so
+    /// far — every other handler still only ever sees `workspace` above.
+    /// See the module doc comment on [`projects`] for the full scope.
+    projects: projects::ProjectRegistry,
+}
+
+#[derive(Deserialize)]
+struct CreateProposalRequest {
+    agent: String,
+    title: String,
+    description: String,
+    #[serde(default)]
+    changes: Vec<ProposedChange>,
+}
+
+fn build_router(state: AppState) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        // Deliberately not instrumented via `route_layer` below: a scrape
+        // request hitting the metrics endpoint doesn't need to appear in
+        // its own output.
+        .route("/metrics", get(metrics_endpoint))
+        .route("/api/v1/shutdown", post(shutdown))
+        .route("/api/v1/proposals", post(create_proposal))
+        .route("/api/v1/nodes", get(list_nodes))
+        .route("/api/v1/nodes/similar", get(similar_nodes))
+        .route("/api/v1/projects", get(list_projects))
+        .route("/api/v1/graph/batch", post(graph_batch))
+        .route("/api/v1/graph/backup", post(graph_backup))
+        .route("/api/v1/graph/restore", post(graph_restore))
+        .route("/api/v1/status", get(daemon_status))
+        .route("/api/v1/matter", get(list_matter).post(create_matter))
+        .route("/api/v1/matter/:id", get(show_matter))
+        .route("/api/v1/matter/search", get(search_matter))
+        .route("/api/v1/idlc/items", get(list_idlc_items))
+        .route("/api/v1/idlc/items/:id", get(show_idlc_item))
+        .route("/api/v1/claude/projects", get(list_claude_project_summaries))
+        .route("/api/v1/sessions", get(list_sessions))
+        .route("/api/v1/jobs", get(list_jobs))
+        .route("/api/v1/jobs/:name/run", post(run_job))
+        // No PUT here yet: an update route needs a frontmatter versioning
+        // scheme (concurrent edits from the CLI and the desktop app both
+        // rewriting a file) that doesn't exist anywhere in this codebase
+        // yet, so it's left for a follow-up rather than faked.
+        //
+        // `route_layer` rather than `layer`: the latter wraps the whole
+        // router before dispatch, so `MatchedPath` (needed for a
+        // normalized `route` label instead of one literal path per id)
+        // isn't in the request's extensions yet when the middleware runs.
+        .route_layer(axum::middleware::from_fn_with_state(state.clone(), metrics::record_request))
+        .with_state(state)
+}
+
+async fn metrics_endpoint(State(state): State<AppState>) -> (HeaderMap, String) {
+    let mut headers = HeaderMap::new();
+    headers.insert(axum::http::header::CONTENT_TYPE, HeaderValue::from_static("text/plain; version=0.0.4"));
+    (headers, state.metrics.render_prometheus())
+}
+
+/// Build the `CorsLayer` for `allow_origins`. Empty means no cross-origin
+/// access, the historical default (only same-machine callers could reach a
+/// loopback-bound daemon). Axum answers preflight `OPTIONS` for every route
+/// automatically once this is layered on; there's no WebSocket route yet
+/// for a browser client to upgrade, so honoring the origin list there is
+/// deferred until that route exists.
+fn cors_layer(allow_origins: &[String]) -> Option<CorsLayer> {
+    if allow_origins.is_empty() {
+        return None;
+    }
+    let origins: Vec<HeaderValue> = allow_origins.iter().filter_map(|o| o.parse().ok()).collect();
+    Some(
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+            .allow_headers([axum::http::header::AUTHORIZATION, axum::http::header::CONTENT_TYPE]),
+    )
+}
+
+#[derive(Serialize)]
+struct MatterItemResponse {
+    id: String,
+    matter_type: String,
+    title: String,
+    author: Option<String>,
+    tags: Vec<String>,
+    body: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    history: Option<Vec<HistoryEntryResponse>>,
+}
+
+impl From<MatterItem> for MatterItemResponse {
+    fn from(item: MatterItem) -> Self {
+        MatterItemResponse {
+            id: item.id,
+            matter_type: item.matter_type.to_string(),
+            title: item.title,
+            author: item.author,
+            tags: item.tags,
+            body: item.body,
+            history: None,
+        }
+    }
+}
+
+/// Wire form of [`snps_core::git::FileHistoryEntry`] — kept as a separate
+/// type rather than deriving `Serialize` on the core struct, same as
+/// `MatterItemResponse` does for `MatterItem`.
+#[derive(Serialize)]
+struct HistoryEntryResponse {
+    commit_id: String,
+    author: String,
+    date_unix: i64,
+    subject: String,
+    insertions: usize,
+    deletions: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    renamed_from: Option<String>,
+}
+
+impl From<snps_core::git::FileHistoryEntry> for HistoryEntryResponse {
+    fn from(entry: snps_core::git::FileHistoryEntry) -> Self {
+        HistoryEntryResponse {
+            commit_id: entry.commit_id,
+            author: entry.author,
+            date_unix: entry.date_unix,
+            subject: entry.subject,
+            insertions: entry.insertions,
+            deletions: entry.deletions,
+            renamed_from: entry.renamed_from,
+        }
+    }
+}
+
+/// Mirrors `commands::matter::visible` in the CLI (repo + frontmatter
+/// visibility), duplicated here since the CLI and daemon crates don't
+/// share a caller for it.
+fn matter_visible(repos: &[Repository], item: &MatterItem, include_private: bool) -> bool {
+    let Some(repo) = Repository::owning(repos, &item.path) else {
+        return true;
+    };
+    repository::visibility::is_visible(repo, item, include_private, CURRENT_CONTEXT)
+}
+
+#[derive(Deserialize)]
+struct ListMatterQuery {
+    query: Option<String>,
+    #[serde(rename = "type")]
+    matter_type: Option<String>,
+    tags: Option<String>,
+    limit: Option<usize>,
+    #[serde(default)]
+    include_private: bool,
+}
+
+const DEFAULT_MATTER_PAGE_LIMIT: usize = 50;
+
+async fn list_matter(
+    State(state): State<AppState>,
+    Query(q): Query<ListMatterQuery>,
+) -> Result<Json<Vec<MatterItemResponse>>, (StatusCode, String)> {
+    let index = MatterIndex::build(&state.workspace.root).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let wanted_type: Option<MatterType> = q
+        .matter_type
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(|e: snps_core::error::CoreError| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let wanted_tags: Vec<String> = q.tags.map(|t| t.split(',').map(|s| s.trim().to_string()).collect()).unwrap_or_default();
+    let limit = q.limit.unwrap_or(DEFAULT_MATTER_PAGE_LIMIT);
+
+    let matched: Vec<&MatterItem> = match q.query.as_deref() {
+        Some(query) if !query.is_empty() => index.search(query),
+        _ => index.items.iter().collect(),
+    };
+
+    let items: Vec<MatterItemResponse> = matched
+        .into_iter()
+        .filter(|item| wanted_type.as_ref().map(|t| &item.matter_type == t).unwrap_or(true))
+        .filter(|item| wanted_tags.is_empty() || wanted_tags.iter().any(|t| item.tags.contains(t)))
+        .filter(|item| matter_visible(&state.repos, item, q.include_private))
+        .take(limit)
+        .cloned()
+        .map(MatterItemResponse::from)
+        .collect();
+
+    Ok(Json(items))
+}
+
+#[derive(Deserialize)]
+struct ShowMatterQuery {
+    #[serde(default)]
+    history: bool,
+}
+
+async fn show_matter(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(q): Query<ShowMatterQuery>,
+) -> Result<Json<MatterItemResponse>, (StatusCode, String)> {
+    let index = MatterIndex::build(&state.workspace.root).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let item = index
+        .items
+        .into_iter()
+        .find(|i| i.id == id)
+        .filter(|i| matter_visible(&state.repos, i, false))
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("no matter document with id '{id}'")))?;
+
+    let history = if q.history {
+        // No git history (not a repo, or no commits yet) just means an
+        // empty list here — the CLI's richer "not available" message
+        // doesn't have an obvious JSON equivalent worth inventing.
+        Some(
+            matter::history(&state.workspace.root, &item, true)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                .unwrap_or_default()
+                .into_iter()
+                .map(HistoryEntryResponse::from)
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    let mut response = MatterItemResponse::from(item);
+    response.history = history;
+    Ok(Json(response))
+}
+
+#[derive(Deserialize)]
+struct SearchMatterQuery {
+    query: String,
+    #[serde(default)]
+    include_private: bool,
+    limit: Option<usize>,
+}
+
+/// A [`matter::MatterSearchHit`] on the wire: the item plus why it matched,
+/// so the desktop app can tell apart similarly titled documents without a
+/// second request per result. `snippet` carries the query terms wrapped in
+/// `**...**` markers (via [`matter::highlight`]) since JSON has no ANSI
+/// equivalent — same convention the CLI's `matter search` uses for its own
+/// JSON output.
+#[derive(Serialize)]
+struct MatterSearchHitResponse {
+    id: String,
+    matter_type: String,
+    title: String,
+    line_number: Option<usize>,
+    score: f32,
+    snippet: String,
+}
+
+/// `GET /api/v1/matter/search?query=...` — a dedicated route rather than
+/// folding this into `/api/v1/matter?query=` because that route's response
+/// shape (full `MatterItemResponse`, including the whole `body`) predates
+/// snippets and score entirely; adding them there would mean every
+/// existing caller of the plain list/filter route pays for a search-only
+/// computation it never asked for.
+async fn search_matter(
+    State(state): State<AppState>,
+    Query(q): Query<SearchMatterQuery>,
+) -> Result<Json<Vec<MatterSearchHitResponse>>, (StatusCode, String)> {
+    let index = MatterIndex::build(&state.workspace.root).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let limit = q.limit.unwrap_or(DEFAULT_MATTER_PAGE_LIMIT);
+
+    let hits: Vec<MatterSearchHitResponse> = index
+        .search_with_snippets(&q.query)
+        .into_iter()
+        .filter(|hit| matter_visible(&state.repos, hit.item, q.include_private))
+        .take(limit)
+        .map(|hit| MatterSearchHitResponse {
+            id: hit.item.id.clone(),
+            matter_type: hit.item.matter_type.to_string(),
+            title: hit.item.title.clone(),
+            line_number: hit.line_number,
+            score: hit.score,
+            snippet: matter::highlight(&hit.snippet, &q.query, "**", "**"),
+        })
+        .collect();
+
+    Ok(Json(hits))
+}
+
+#[derive(Deserialize)]
+struct ListIdlcItemsQuery {
+    team: Option<String>,
+}
+
+fn idlc_team(state: &AppState, team: Option<String>) -> String {
+    team.unwrap_or_else(|| snps_core::team::active_team_id(&state.workspace))
+}
+
+/// Wire form of [`ItemLink`], with a graph node link's title resolved
+/// inline (the desktop app's whole reason for asking for an item is
+/// usually to show what it's connected to, not to make a second request
+/// per link to find out).
+#[derive(Serialize)]
+struct ItemLinkResponse {
+    kind: String,
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved_title: Option<String>,
+}
+
+fn resolve_link(graph: &KnowledgeGraph, link: &ItemLink) -> ItemLinkResponse {
+    let resolved_title = match link.kind {
+        LinkKind::Node => graph.get_node(&link.id).ok().flatten().map(|n| n.title),
+        // Matter and thought links aren't graph nodes, so there's nothing
+        // for this handler (backed by the graph db) to resolve them
+        // against; the id alone is still useful to a caller that already
+        // knows how to look those up.
+        LinkKind::Matter | LinkKind::Thought => None,
+    };
+    ItemLinkResponse { kind: link.kind.as_str().to_string(), id: link.id.clone(), resolved_title }
+}
+
+#[derive(Serialize)]
+struct IdlcItemResponse {
+    id: String,
+    title: String,
+    stage: String,
+    status: String,
+    links: Vec<ItemLinkResponse>,
+    content: String,
+    assignee: Option<String>,
+    source_url: Option<String>,
+    source_issue_number: Option<u64>,
+}
+
+fn idlc_item_response(item: IdlcItem, graph: &KnowledgeGraph) -> IdlcItemResponse {
+    IdlcItemResponse {
+        links: item.links.iter().map(|l| resolve_link(graph, l)).collect(),
+        id: item.id,
+        title: item.title,
+        stage: item.stage,
+        status: item.status,
+        content: item.content,
+        assignee: item.assignee,
+        source_url: item.source_url,
+        source_issue_number: item.source_issue_number,
+    }
+}
+
+async fn list_idlc_items(
+    State(state): State<AppState>,
+    Query(q): Query<ListIdlcItemsQuery>,
+) -> Result<Json<Vec<IdlcItemResponse>>, (StatusCode, String)> {
+    let team = idlc_team(&state, q.team);
+    let store = IdlcItemStore::load(&state.workspace, &team).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let graph = KnowledgeGraph::init(&state.workspace.graph_db_path()).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(store.items.into_iter().map(|item| idlc_item_response(item, &graph)).collect()))
+}
+
+async fn show_idlc_item(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(q): Query<ListIdlcItemsQuery>,
+) -> Result<Json<IdlcItemResponse>, (StatusCode, String)> {
+    let team = idlc_team(&state, q.team);
+    let store = IdlcItemStore::load(&state.workspace, &team).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let item = store.get(&id).cloned().ok_or_else(|| (StatusCode::NOT_FOUND, format!("no IDLC item '{id}' for team '{team}'")))?;
+    let graph = KnowledgeGraph::init(&state.workspace.graph_db_path()).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(idlc_item_response(item, &graph)))
+}
+
+#[derive(Serialize)]
+struct ClaudeProjectResponse {
+    path: PathBuf,
+    session_count: usize,
+    total_size_bytes: u64,
+    most_recent_activity: Option<u64>,
+}
+
+impl From<ClaudeProjectSummary> for ClaudeProjectResponse {
+    fn from(summary: ClaudeProjectSummary) -> Self {
+        ClaudeProjectResponse {
+            path: summary.path,
+            session_count: summary.session_count,
+            total_size_bytes: summary.total_size_bytes,
+            most_recent_activity: summary.most_recent_activity,
+        }
+    }
+}
+
+/// Same scan `snps claude projects` runs, exposed so the desktop app can
+/// show it without shelling out to the CLI. Reads `~/.claude/projects`
+/// directly rather than anything workspace-scoped, so unlike the other
+/// routes here it doesn't touch `AppState`.
+async fn list_claude_project_summaries() -> Result<Json<Vec<ClaudeProjectResponse>>, (StatusCode, String)> {
+    let summaries = list_claude_projects().map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(summaries.into_iter().map(ClaudeProjectResponse::from).collect()))
+}
+
+#[derive(Serialize)]
+struct SessionRecordResponse {
+    id: String,
+    title: String,
+    project: PathBuf,
+    modified_unix: Option<u64>,
+    size_bytes: u64,
+    is_agent_session: bool,
+}
+
+#[derive(Deserialize)]
+struct ListSessionsQuery {
+    /// Restrict to one project's sessions; scans every recorded project
+    /// otherwise. There's no persisted "session record" collection to
+    /// query yet (see the module doc comment on why), so this is a live
+    /// scan same as `/api/v1/claude/projects`, capped by `limit` the same
+    /// way `/api/v1/matter` and `/api/v1/nodes` cap theirs.
+    project: Option<PathBuf>,
+    #[serde(default)]
+    include_agent_sessions: bool,
+    limit: Option<usize>,
+}
+
+const DEFAULT_SESSION_PAGE_LIMIT: usize = 100;
+
+/// List Claude Code sessions across every recorded project (or one, with
+/// `?project=`), each with its resolved title if one was ever assigned.
+/// Backed by the same on-disk scan as `snps claude list`, not a
+/// database — there's no write path that persists session metadata into
+/// the graph today (`snps claude import`/`--register` don't exist in this
+/// tree; sessions are Claude Code's own transcripts, discovered by
+/// scanning `~/.claude/projects` rather than something this codebase
+/// ever writes). A real "SessionRecord" collection queryable by the
+/// desktop without a live filesystem scan would need that write path
+/// built first — this route gets the desktop the read-only piece of the
+/// ask without inventing one.
+async fn list_sessions(Query(q): Query<ListSessionsQuery>) -> Result<Json<Vec<SessionRecordResponse>>, (StatusCode, String)> {
+    let projects = match q.project {
+        Some(project) => vec![project],
+        None => list_claude_projects().map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?.into_iter().map(|p| p.path).collect(),
+    };
+    let titles = titles_path().map(TitleStore::load).transpose().map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let limit = q.limit.unwrap_or(DEFAULT_SESSION_PAGE_LIMIT);
+
+    let mut rows = Vec::new();
+    for project in projects {
+        let sessions = list_sessions_for_project(&project).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        for session in sessions {
+            if !q.include_agent_sessions && session.is_agent_session {
+                continue;
+            }
+            let title = match &titles {
+                Some(store) => resolved_title(store, &session.id, &session.id).to_string(),
+                None => session.id.clone(),
+            };
+            rows.push(SessionRecordResponse {
+                id: session.id,
+                title,
+                project: project.clone(),
+                modified_unix: session.modified_unix,
+                size_bytes: session.size_bytes,
+                is_agent_session: session.is_agent_session,
+            });
+        }
+    }
+
+    rows.sort_by(|a, b| b.modified_unix.cmp(&a.modified_unix));
+    rows.truncate(limit);
+    Ok(Json(rows))
+}
+
+/// Current state of every configured `sync.schedules` job: last run,
+/// outcome, and failure streak. Doesn't itself trigger anything — see
+/// `run_job` for that.
+async fn list_jobs(State(state): State<AppState>) -> Result<Json<Vec<scheduler::JobStatusRow>>, (StatusCode, String)> {
+    state.scheduler.status().await.map(Json).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Run one configured job immediately, regardless of whether it's due,
+/// and report the outcome. Backs `snps daemon jobs run <name>`.
+async fn run_job(State(state): State<AppState>, Path(name): Path<String>) -> Result<Json<snps_core::scheduler::JobRun>, (StatusCode, String)> {
+    state.scheduler.run_now(name).await.map(Json).map_err(|e| (StatusCode::NOT_FOUND, e.to_string()))
+}
+
+fn default_matter_context() -> String {
+    "project".to_string()
+}
+
+#[derive(Deserialize)]
+struct CreateMatterRequest {
+    #[serde(rename = "type")]
+    matter_type: String,
+    title: String,
+    author: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default = "default_matter_context")]
+    context: String,
+    /// Body content in place of the type's default template sections,
+    /// same as `snps matter create --content`.
+    content: Option<String>,
+    /// Skip the near-duplicate title scan. The CLI's `--force` blocks a
+    /// prompt with this; over HTTP there's no prompt to block, so this
+    /// just avoids the extra `MatterIndex::build` when a caller has
+    /// already checked (or doesn't care).
+    #[serde(default)]
+    skip_duplicate_check: bool,
+}
+
+#[derive(Serialize)]
+struct CreateMatterResponse {
+    #[serde(flatten)]
+    item: MatterItemResponse,
+    /// Existing documents with a similar title, most similar first — see
+    /// [`snps_core::dedup::find_similar_titles`]. Creation always
+    /// proceeds; callers decide what to do with these (the CLI prompts,
+    /// an API caller might just surface a warning).
+    duplicates: Vec<DuplicateCandidate>,
+}
+
+async fn create_matter(
+    State(state): State<AppState>,
+    Json(req): Json<CreateMatterRequest>,
+) -> Result<Json<CreateMatterResponse>, (StatusCode, String)> {
+    let matter_type: MatterType = req
+        .matter_type
+        .parse()
+        .map_err(|e: snps_core::error::CoreError| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let duplicates = if req.skip_duplicate_check {
+        Vec::new()
+    } else {
+        let index = MatterIndex::build(&state.workspace.root).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        find_similar_titles(&req.title, index.items.iter().map(|i| (i.title.as_str(), i.path.as_path())), DEFAULT_THRESHOLD)
+    };
+
+    let item = matter::matter_create(
+        &state.workspace.root,
+        NewMatter {
+            matter_type,
+            title: &req.title,
+            author: req.author.as_deref(),
+            tags: req.tags,
+            context: &req.context,
+            body: req.content.as_deref(),
+        },
+    )
+    .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    Ok(Json(CreateMatterResponse { item: item.into(), duplicates }))
+}
+
+#[derive(Serialize)]
+struct BatchResponse {
+    ids: std::collections::BTreeMap<String, String>,
+}
+
+/// Client identity header for graph writes that go through the daemon
+/// (the CLI's `snps graph import`, the desktop app, external agents). Its
+/// value is either a plain source name (`desktop`, `cli`) or `agent:<name>`;
+/// unset or unrecognized falls back to a bare `daemon-api` source with an
+/// `"unknown"` actor rather than rejecting the request.
+const CLIENT_HEADER: &str = "x-pmsynapse-client";
+
+fn provenance_from_headers(headers: &HeaderMap) -> Provenance {
+    let raw = headers.get(CLIENT_HEADER).and_then(|v| v.to_str().ok());
+    let source = raw.and_then(|s| s.parse::<ProvenanceSource>().ok()).unwrap_or(ProvenanceSource::DaemonApi);
+    Provenance::new(source, raw.unwrap_or("unknown"), env!("CARGO_PKG_VERSION"))
+}
+
+async fn graph_batch(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(mut batch): Json<GraphExport>,
+) -> Result<Json<BatchResponse>, (StatusCode, String)> {
+    let provenance = provenance_from_headers(&headers);
+    for node in &mut batch.nodes {
+        if node.provenance.is_none() {
+            node.provenance = Some(provenance.clone());
+        }
+    }
+    let ids = state.writer.batch(batch).await.map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    state.metrics.record_graph_write();
+    Ok(Json(BatchResponse { ids }))
+}
+
+#[derive(Serialize)]
+struct DaemonStatusResponse {
+    /// Graph writes currently queued in or being coalesced by the writer
+    /// task (see [`writer`]) — sustained non-zero values mean handlers are
+    /// producing writes faster than sqlite can absorb them.
+    write_queue_depth: usize,
+    /// Summarized from [`metrics::Metrics`] — see `GET /metrics` for the
+    /// full per-route breakdown.
+    requests_total: u64,
+    request_errors_total: u64,
+    graph_writes_total: u64,
+    /// Always zero today: nothing populates it yet, since there's no
+    /// filesystem watcher in this codebase (see the module doc on
+    /// `writer.rs`). Exposed now so a future watcher doesn't need a wire
+    /// format change to report through it.
+    watcher_events_total: u64,
+}
+
+async fn daemon_status(State(state): State<AppState>) -> Json<DaemonStatusResponse> {
+    Json(DaemonStatusResponse {
+        write_queue_depth: state.writer.queue_depth(),
+        requests_total: state.metrics.requests_total(),
+        request_errors_total: state.metrics.request_errors_total(),
+        graph_writes_total: state.metrics.graph_writes_total(),
+        watcher_events_total: state.metrics.watcher_events_total(),
+    })
+}
+
+async fn graph_backup(State(state): State<AppState>) -> Result<Json<GraphExport>, (StatusCode, String)> {
+    // Exclusive lock: no batch import can land mid-snapshot.
+    let _quiesce = state.graph_write_lock.write().await;
+    let graph = KnowledgeGraph::init(&state.workspace.graph_db_path()).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let export = GraphExport::dump(&graph).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(export))
+}
+
+#[derive(Deserialize)]
+struct RestoreRequest {
+    export: GraphExport,
+    #[serde(default)]
+    force: bool,
+}
+
+async fn graph_restore(State(state): State<AppState>, Json(req): Json<RestoreRequest>) -> Result<StatusCode, (StatusCode, String)> {
+    state.writer.restore(req.export, req.force).await.map_err(|e| (StatusCode::CONFLICT, e.to_string()))?;
+    state.metrics.record_graph_write();
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Header a caller sends to route a request at a project other than the
+/// daemon's default — see [`projects::resolve_project_root`]. Public so
+/// the CLI/desktop HTTP clients can attach it without hardcoding the
+/// literal a second time.
+pub const PROJECT_HEADER: &str = "X-Pmsynapse-Project";
+
+#[derive(Deserialize)]
+struct ListNodesQuery {
+    limit: Option<usize>,
+    offset: Option<usize>,
+    order_by: Option<String>,
+    node_type: Option<String>,
+    /// `cli`, `daemon-api`, `desktop`, `sync`, `analyze`, or `agent:<name>`
+    /// — see [`ProvenanceSource`]. There's no `snps graph query` CLI
+    /// command to expose this from yet, so today it's only reachable by
+    /// calling this endpoint directly.
+    source: Option<String>,
+    /// Same project selector as the [`PROJECT_HEADER`] header, for
+    /// callers (like a browser address bar) that would rather not set a
+    /// custom header. The header wins if both are present.
+    project: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ListNodesResponse {
+    nodes: Vec<Node>,
+    total: usize,
+    next_offset: Option<usize>,
+}
+
+const DEFAULT_NODE_PAGE_LIMIT: usize = 50;
+const MAX_NODE_PAGE_LIMIT: usize = 500;
+
+/// The first (and so far only) route migrated onto [`projects`] — see its
+/// module doc comment for why every other route still reads `state.workspace`
+/// directly.
+async fn list_nodes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(q): Query<ListNodesQuery>,
+) -> Result<Json<ListNodesResponse>, (StatusCode, String)> {
+    let node_type: Option<NodeType> =
+        q.node_type.as_deref().map(str::parse).transpose().map_err(|e: snps_core::error::CoreError| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let order_by: NodeOrderBy = q
+        .order_by
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(|e: snps_core::error::CoreError| (StatusCode::BAD_REQUEST, e.to_string()))?
+        .unwrap_or(NodeOrderBy::CreatedAt);
+    let limit = q.limit.unwrap_or(DEFAULT_NODE_PAGE_LIMIT).min(MAX_NODE_PAGE_LIMIT);
+    let offset = q.offset.unwrap_or(0);
+    let source: Option<ProvenanceSource> =
+        q.source.as_deref().map(str::parse).transpose().map_err(|e: snps_core::error::CoreError| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let header = headers.get(PROJECT_HEADER).and_then(|v| v.to_str().ok());
+    let root = projects::resolve_project_root(header, q.project.as_deref(), &state.workspace.root);
+    let workspace = state.projects.get_or_open(&root).await.map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let graph = KnowledgeGraph::init(&workspace.graph_db_path()).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let page = graph
+        .query_page(node_type, source.as_ref(), order_by, limit, offset)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(ListNodesResponse { nodes: page.nodes, total: page.total, next_offset: page.next_offset }))
+}
+
+/// `GET /api/v1/projects` — every project opened so far via `list_nodes`'s
+/// header/query routing, plus the daemon's own default (opened eagerly at
+/// startup so it always shows up even if no scoped request has landed yet).
+async fn list_projects(State(state): State<AppState>) -> Json<Vec<projects::ProjectSummary>> {
+    Json(state.projects.list().await)
+}
+
+#[derive(Deserialize)]
+struct SimilarNodesQuery {
+    q: String,
+    k: Option<usize>,
+    node_type: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SimilarNodeResponse {
+    node: Node,
+    distance: f32,
+}
+
+const DEFAULT_SIMILAR_K: usize = 10;
+
+/// The first route to need `GlobalConfig` (see the comment on
+/// `AppState::repos`) — it's loaded fresh on every call rather than
+/// cached on `AppState`, same as `list_nodes` re-opens the graph on every
+/// call instead of caching a handle.
+async fn similar_nodes(
+    State(state): State<AppState>,
+    Query(q): Query<SimilarNodesQuery>,
+) -> Result<Json<Vec<SimilarNodeResponse>>, (StatusCode, String)> {
+    let node_type: Option<NodeType> =
+        q.node_type.as_deref().map(str::parse).transpose().map_err(|e: snps_core::error::CoreError| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let k = q.k.unwrap_or(DEFAULT_SIMILAR_K);
+
+    let merged = load_merged_config(&state.workspace).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let embedder = embedder_for(&merged.config.llm_default_provider, merged.config.llm_api_key.clone())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let graph = KnowledgeGraph::init(&state.workspace.graph_db_path()).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let results = search_similar(&graph, embedder.as_ref(), &q.q, k, node_type)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(results.into_iter().map(|(node, distance)| SimilarNodeResponse { node, distance }).collect()))
+}
+
+/// A `Router` over a fresh, lifecycle-free `AppState` — useful for tests
+/// and embedding, but `serve` is what manages the PID file.
+pub fn router(workspace: Workspace) -> Router {
+    let repos = Repository::load_all(&workspace).unwrap_or_default();
+    let graph_write_lock = Arc::new(RwLock::new(()));
+    let workspace = Arc::new(workspace);
+    let writer = writer::spawn(workspace.graph_db_path(), graph_write_lock.clone());
+    let scheduler = scheduler::spawn(workspace.clone());
+    build_router(AppState {
+        workspace,
+        shutdown: Arc::new(Notify::new()),
+        graph_write_lock,
+        repos: Arc::new(repos),
+        writer,
+        scheduler,
+        metrics: Arc::new(metrics::Metrics::new()),
+        request_log: Arc::new(Mutex::new(None)),
+        // No eviction loop here: `router()` is documented as
+        // lifecycle-free, and a registry that's only ever touched by the
+        // request it's asked to serve doesn't need one.
+        projects: projects::ProjectRegistry::new(),
+    })
+}
+
+async fn health() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "status": "ok" }))
+}
+
+async fn shutdown(State(state): State<AppState>) -> StatusCode {
+    state.shutdown.notify_one();
+    StatusCode::ACCEPTED
+}
+
+async fn create_proposal(
+    State(state): State<AppState>,
+    Json(req): Json<CreateProposalRequest>,
+) -> Result<Json<Proposal>, (StatusCode, String)> {
+    let new = NewProposal { agent: &req.agent, title: &req.title, description: &req.description, changes: req.changes };
+    ProposalStore::create(&state.workspace, new)
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))
+}
+
+/// Bind to `<bind>:<port>`, write the PID file, and serve until a
+/// `POST /api/v1/shutdown` request or the process is killed. The PID
+/// file is removed on either exit path. `profile` names the PID file
+/// (`daemon-<profile>.pid` instead of the default `daemon.pid`) so a
+/// second daemon can serve the same workspace without clobbering the
+/// first one's PID file.
+///
+/// `allow_origins` enables CORS for the listed origins (empty disables it,
+/// same as before this parameter existed). Binding to anything but
+/// loopback requires [`auth::TOKEN_ENV`] to be set — an unauthenticated API
+/// reachable from the LAN is a much bigger exposure than one reachable only
+/// from the same machine — and every request is then required to present
+/// it as a bearer token.
+pub async fn serve(
+    workspace: Workspace,
+    port: u16,
+    profile: Option<String>,
+    bind: std::net::IpAddr,
+    allow_origins: Vec<String>,
+) -> anyhow::Result<()> {
+    let token = std::env::var(auth::TOKEN_ENV).ok();
+    if !bind.is_loopback() && token.is_none() {
+        anyhow::bail!(
+            "refusing to bind {bind} (not loopback) without {} set — the daemon's API has no other access control",
+            auth::TOKEN_ENV
+        );
+    }
+
+    let pid_lock = pid::DaemonPidFile::new(&workspace.pmsynapse_dir(), profile.as_deref())
+        .acquire(port)
+        .map_err(|e| anyhow::anyhow!("{e} (is another daemon already running for this profile?)"))?;
+
+    let daemon_log = Arc::new(Mutex::new(logging::DaemonLog::open(&workspace.pmsynapse_dir(), profile.as_deref()).ok()));
+    if let Some(log) = daemon_log.lock().unwrap().as_mut() {
+        log.line(&format!("daemon started, listening on http://{bind}:{port}"));
+    }
+
+    let repos = Repository::load_all(&workspace)?;
+    let shutdown_signal = Arc::new(Notify::new());
+    let graph_write_lock = Arc::new(RwLock::new(()));
+    let workspace = Arc::new(workspace);
+    let writer = writer::spawn(workspace.graph_db_path(), graph_write_lock.clone());
+    let scheduler = scheduler::spawn(workspace.clone());
+
+    let registry = projects::ProjectRegistry::new();
+    // Populated eagerly so the default project always shows up in
+    // `GET /api/v1/projects`, even before any scoped request opens it.
+    registry.get_or_open(&workspace.root).await.ok();
+    projects::spawn_eviction(registry.clone(), PROJECT_EVICTION_CHECK_INTERVAL, PROJECT_IDLE_TIMEOUT);
+
+    let state = AppState {
+        workspace,
+        shutdown: shutdown_signal.clone(),
+        graph_write_lock,
+        repos: Arc::new(repos),
+        writer,
+        scheduler,
+        metrics: Arc::new(metrics::Metrics::new()),
+        request_log: daemon_log.clone(),
+        projects: registry,
+    };
+    let mut app = build_router(state);
+    if let Some(cors) = cors_layer(&allow_origins) {
+        app = app.layer(cors);
+    }
+    if let Some(token) = token {
+        app = app.layer(axum::middleware::from_fn_with_state(RequiredToken(token), auth::require_token));
+    }
+
+    let addr = std::net::SocketAddr::from((bind, port));
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let result = axum::serve(listener, app).with_graceful_shutdown(async move { shutdown_signal.notified().await }).await;
+
+    if let Some(log) = daemon_log.lock().unwrap().as_mut() {
+        log.line("daemon stopped");
+    }
+    drop(pid_lock);
+    result.map_err(Into::into)
+}
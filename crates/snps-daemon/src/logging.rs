@@ -0,0 +1,190 @@
+//! Minimal structured logging for the daemon process: one line per event,
+//! timestamped with a Unix second count (kept dependency-free, matching
+//! `snps_core::time`, rather than pulling in a full logging crate for a
+//! handful of lines per run). `snps daemon logs` reads these files back.
+//!
+//! Log files live at `<pmsynapse_dir>/logs/daemon.log` (or
+//! `daemon-<profile>.log`), created lazily on first write so a workspace
+//! that has never started a daemon simply has no log file yet rather than
+//! an empty `logs/` directory left behind by something else.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub fn logs_dir(pmsynapse_dir: &Path) -> PathBuf {
+    pmsynapse_dir.join("logs")
+}
+
+pub fn log_file_path(pmsynapse_dir: &Path, profile: Option<&str>) -> PathBuf {
+    let name = match profile {
+        Some(profile) => format!("daemon-{profile}.log"),
+        None => "daemon.log".to_string(),
+    };
+    logs_dir(pmsynapse_dir).join(name)
+}
+
+/// Severity of a log line, ordered so `level >= threshold` filters work
+/// with a plain comparison (`Debug` is the least severe, `Error` the most).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Warn => "WARN",
+            Level::Error => "ERROR",
+        }
+    }
+}
+
+impl std::str::FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "debug" => Ok(Level::Debug),
+            "info" => Ok(Level::Info),
+            "warn" | "warning" => Ok(Level::Warn),
+            "error" => Ok(Level::Error),
+            other => Err(format!("unknown log level '{other}' (expected debug, info, warn, or error)")),
+        }
+    }
+}
+
+/// An append-only handle to one profile's log file, opened lazily.
+pub struct DaemonLog {
+    file: fs::File,
+}
+
+impl DaemonLog {
+    pub fn open(pmsynapse_dir: &Path, profile: Option<&str>) -> std::io::Result<Self> {
+        let path = log_file_path(pmsynapse_dir, profile);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(DaemonLog { file })
+    }
+
+    /// Append one `Info`-level line. A thin wrapper over [`Self::line_at`]
+    /// kept around since most call sites (daemon start/stop) don't care
+    /// about level.
+    pub fn line(&mut self, message: &str) {
+        self.line_at(Level::Info, message);
+    }
+
+    /// Append one line at the given level, stamped with the current Unix
+    /// time so `--since` can filter without depending on a particular log
+    /// line format beyond this prefix.
+    pub fn line_at(&mut self, level: Level, message: &str) {
+        let _ = writeln!(self.file, "[{}] [{}] {message}", snps_core::time::now_unix(), level.as_str());
+    }
+}
+
+/// The Unix timestamp a log line starts with, if it has one — lines
+/// written before this format existed, or corrupted lines, just don't
+/// match `--since` rather than erroring the whole read.
+pub fn line_timestamp(line: &str) -> Option<u64> {
+    let rest = line.strip_prefix('[')?;
+    let (ts, _) = rest.split_once(']')?;
+    ts.parse().ok()
+}
+
+/// The level a log line was written at, if it has one — lines written
+/// before levels existed just don't match a `--level` filter rather than
+/// erroring the whole read.
+pub fn line_level(line: &str) -> Option<Level> {
+    let rest = line.strip_prefix('[')?;
+    let (_, rest) = rest.split_once(']')?;
+    let rest = rest.trim_start().strip_prefix('[')?;
+    let (level, _) = rest.split_once(']')?;
+    level.parse().ok()
+}
+
+/// Parse a duration like `30s`, `10m`, `2h`, or `1d` (no suffix defaults
+/// to seconds), for `--since`.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let (num, unit) = match s.chars().last() {
+        Some(c) if c.is_ascii_alphabetic() => (&s[..s.len() - 1], c),
+        _ => (s, 's'),
+    };
+    let value: u64 = num.parse().ok()?;
+    let secs = match unit {
+        's' => value,
+        'm' => value * 60,
+        'h' => value * 3_600,
+        'd' => value * 86_400,
+        _ => return None,
+    };
+    Some(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_suffixed_and_bare_durations() {
+        assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration("10m"), Some(Duration::from_secs(600)));
+        assert_eq!(parse_duration("2h"), Some(Duration::from_secs(7_200)));
+        assert_eq!(parse_duration("1d"), Some(Duration::from_secs(86_400)));
+        assert_eq!(parse_duration("45"), Some(Duration::from_secs(45)));
+    }
+
+    #[test]
+    fn rejects_unknown_units_and_garbage() {
+        assert_eq!(parse_duration("5x"), None);
+        assert_eq!(parse_duration("not-a-duration"), None);
+    }
+
+    #[test]
+    fn extracts_leading_bracketed_timestamp() {
+        assert_eq!(line_timestamp("[1700000000] daemon started"), Some(1_700_000_000));
+        assert_eq!(line_timestamp("no timestamp here"), None);
+    }
+
+    #[test]
+    fn log_creates_directory_and_appends_lines() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut log = DaemonLog::open(tmp.path(), Some("dev")).unwrap();
+        log.line("daemon started");
+        log.line("daemon stopped");
+
+        let contents = fs::read_to_string(log_file_path(tmp.path(), Some("dev"))).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("daemon started"));
+        assert!(lines[1].ends_with("daemon stopped"));
+    }
+
+    #[test]
+    fn level_parses_case_insensitively_and_orders_by_severity() {
+        assert_eq!("debug".parse::<Level>(), Ok(Level::Debug));
+        assert_eq!("WARNING".parse::<Level>(), Ok(Level::Warn));
+        assert!("bogus".parse::<Level>().is_err());
+        assert!(Level::Debug < Level::Error);
+    }
+
+    #[test]
+    fn line_at_tags_the_level_and_line_level_recovers_it() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut log = DaemonLog::open(tmp.path(), None).unwrap();
+        log.line_at(Level::Debug, "GET /api/v1/status 200 1.2ms");
+
+        let contents = fs::read_to_string(log_file_path(tmp.path(), None)).unwrap();
+        let line = contents.lines().next().unwrap();
+        assert!(line.ends_with("GET /api/v1/status 200 1.2ms"));
+        assert_eq!(line_level(line), Some(Level::Debug));
+    }
+}
@@ -0,0 +1,217 @@
+//! Hand-rolled Prometheus-format metrics: per-route request counts,
+//! latency histograms, and error counts, plus a couple of counters for
+//! background work the HTTP layer doesn't see directly. No metrics crate
+//! — this is a handful of counters, the same reasoning `logging.rs` gives
+//! for not pulling in a full logging stack.
+
+use axum::body::Body;
+use axum::extract::{MatchedPath, State};
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Upper bound (inclusive) of each latency bucket, in milliseconds — every
+/// observation also counts toward the implicit `+Inf` bucket, Prometheus's
+/// own convention for histograms.
+const LATENCY_BUCKETS_MS: [f64; 8] = [5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0];
+
+struct RouteMetrics {
+    requests: AtomicU64,
+    errors: AtomicU64,
+    /// One cumulative counter per entry in `LATENCY_BUCKETS_MS`, plus one
+    /// more for `+Inf` — each observation increments every bucket whose
+    /// bound is at or above it, so these are already cumulative and need
+    /// no further summing when rendered.
+    bucket_counts: Vec<AtomicU64>,
+    sum_ms: AtomicU64,
+}
+
+impl RouteMetrics {
+    fn new() -> Self {
+        RouteMetrics {
+            requests: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            bucket_counts: (0..=LATENCY_BUCKETS_MS.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum_ms: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, duration_ms: f64, is_error: bool) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        if is_error {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.sum_ms.fetch_add(duration_ms.round() as u64, Ordering::Relaxed);
+        for (i, bound) in LATENCY_BUCKETS_MS.iter().enumerate() {
+            if duration_ms <= *bound {
+                self.bucket_counts[i].fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.bucket_counts[LATENCY_BUCKETS_MS.len()].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Process-lifetime metrics registry, shared via `AppState`.
+#[derive(Default)]
+pub struct Metrics {
+    routes: Mutex<HashMap<String, RouteMetrics>>,
+    /// Graph mutations applied by [`crate::writer`]. Incremented from
+    /// `graph_batch`/`graph_restore` since those are the only paths that
+    /// hand work to the writer task.
+    graph_writes: AtomicU64,
+    /// Filesystem watcher events observed. There's no watcher yet (see the
+    /// module doc comment on `writer.rs`) — this counter is plumbed ahead
+    /// of that so the metric name doesn't have to be invented again later.
+    watcher_events: AtomicU64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record_request(&self, route: &str, status: u16, duration_ms: f64) {
+        let mut routes = self.routes.lock().unwrap();
+        let entry = routes.entry(route.to_string()).or_insert_with(RouteMetrics::new);
+        entry.record(duration_ms, status >= 400);
+    }
+
+    pub fn record_graph_write(&self) {
+        self.graph_writes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_watcher_event(&self) {
+        self.watcher_events.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn graph_writes_total(&self) -> u64 {
+        self.graph_writes.load(Ordering::Relaxed)
+    }
+
+    pub fn watcher_events_total(&self) -> u64 {
+        self.watcher_events.load(Ordering::Relaxed)
+    }
+
+    pub fn requests_total(&self) -> u64 {
+        self.routes.lock().unwrap().values().map(|r| r.requests.load(Ordering::Relaxed)).sum()
+    }
+
+    pub fn request_errors_total(&self) -> u64 {
+        self.routes.lock().unwrap().values().map(|r| r.errors.load(Ordering::Relaxed)).sum()
+    }
+
+    /// Render every counter in Prometheus text exposition format.
+    pub fn render_prometheus(&self) -> String {
+        let routes = self.routes.lock().unwrap();
+        let mut route_names: Vec<&String> = routes.keys().collect();
+        route_names.sort();
+
+        let mut out = String::new();
+        out.push_str("# HELP snps_daemon_requests_total Total HTTP requests handled, per route.\n");
+        out.push_str("# TYPE snps_daemon_requests_total counter\n");
+        for route in &route_names {
+            let m = &routes[*route];
+            out.push_str(&format!("snps_daemon_requests_total{{route=\"{route}\"}} {}\n", m.requests.load(Ordering::Relaxed)));
+        }
+
+        out.push_str("# HELP snps_daemon_request_errors_total Requests that returned a 4xx/5xx status, per route.\n");
+        out.push_str("# TYPE snps_daemon_request_errors_total counter\n");
+        for route in &route_names {
+            let m = &routes[*route];
+            out.push_str(&format!("snps_daemon_request_errors_total{{route=\"{route}\"}} {}\n", m.errors.load(Ordering::Relaxed)));
+        }
+
+        out.push_str("# HELP snps_daemon_request_duration_ms Request latency in milliseconds, per route.\n");
+        out.push_str("# TYPE snps_daemon_request_duration_ms histogram\n");
+        for route in &route_names {
+            let m = &routes[*route];
+            for (i, bound) in LATENCY_BUCKETS_MS.iter().enumerate() {
+                out.push_str(&format!(
+                    "snps_daemon_request_duration_ms_bucket{{route=\"{route}\",le=\"{bound}\"}} {}\n",
+                    m.bucket_counts[i].load(Ordering::Relaxed)
+                ));
+            }
+            out.push_str(&format!(
+                "snps_daemon_request_duration_ms_bucket{{route=\"{route}\",le=\"+Inf\"}} {}\n",
+                m.bucket_counts[LATENCY_BUCKETS_MS.len()].load(Ordering::Relaxed)
+            ));
+            out.push_str(&format!("snps_daemon_request_duration_ms_sum{{route=\"{route}\"}} {}\n", m.sum_ms.load(Ordering::Relaxed)));
+            out.push_str(&format!("snps_daemon_request_duration_ms_count{{route=\"{route}\"}} {}\n", m.requests.load(Ordering::Relaxed)));
+        }
+
+        out.push_str("# HELP snps_daemon_graph_writes_total Graph mutations applied by the writer task.\n");
+        out.push_str("# TYPE snps_daemon_graph_writes_total counter\n");
+        out.push_str(&format!("snps_daemon_graph_writes_total {}\n", self.graph_writes_total()));
+
+        out.push_str("# HELP snps_daemon_watcher_events_total Filesystem watcher events observed.\n");
+        out.push_str("# TYPE snps_daemon_watcher_events_total counter\n");
+        out.push_str(&format!("snps_daemon_watcher_events_total {}\n", self.watcher_events_total()));
+
+        out
+    }
+}
+
+/// Middleware recording per-route request counts, latency, and error
+/// counts, plus a debug-level line through the daemon's request log (see
+/// `logging::DaemonLog::line_at`). Registered via `Router::route_layer` (not
+/// `Router::layer`) so `MatchedPath` is populated — routing happens before
+/// a `layer`-wrapped middleware runs, but after a `route_layer`-wrapped one.
+pub async fn record_request(
+    State(state): State<crate::AppState>,
+    matched_path: Option<MatchedPath>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let route = matched_path.as_ref().map(|p| p.as_str().to_string()).unwrap_or_else(|| request.uri().path().to_string());
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+    let status = response.status().as_u16();
+    state.metrics.record_request(&route, status, elapsed_ms);
+
+    if let Ok(mut guard) = state.request_log.lock() {
+        if let Some(log) = guard.as_mut() {
+            log.line_at(crate::logging::Level::Debug, &format!("{method} {route} {status} {elapsed_ms:.1}ms"));
+        }
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_request_accumulates_counts_and_buckets() {
+        let metrics = Metrics::new();
+        metrics.record_request("/api/v1/matter", 200, 3.0);
+        metrics.record_request("/api/v1/matter", 500, 2000.0);
+
+        assert_eq!(metrics.requests_total(), 2);
+        assert_eq!(metrics.request_errors_total(), 1);
+
+        let rendered = metrics.render_prometheus();
+        assert!(rendered.contains("snps_daemon_requests_total{route=\"/api/v1/matter\"} 2"));
+        assert!(rendered.contains("snps_daemon_request_errors_total{route=\"/api/v1/matter\"} 1"));
+        assert!(rendered.contains("le=\"5\""));
+        assert!(rendered.contains("le=\"+Inf\"} 2"));
+    }
+
+    #[test]
+    fn graph_write_and_watcher_counters_are_independent() {
+        let metrics = Metrics::new();
+        metrics.record_graph_write();
+        metrics.record_graph_write();
+        metrics.record_watcher_event();
+        assert_eq!(metrics.graph_writes_total(), 2);
+        assert_eq!(metrics.watcher_events_total(), 1);
+    }
+}
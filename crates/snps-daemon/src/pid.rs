@@ -0,0 +1,231 @@
+//! PID-file bookkeeping shared by anything that starts, stops, or checks
+//! on the daemon — `snps daemon start` today, and the desktop app's
+//! `start_daemon`/`stop_daemon` Tauri commands — so there's exactly one
+//! place that knows the file's format, location, and locking.
+//!
+//! A daemon normally runs unprofiled (`daemon.pid`), but a dev daemon
+//! serving a second workspace alongside it needs its own file so the two
+//! don't clobber each other (`daemon-<profile>.pid`). The `_for_profile`
+//! functions take that explicitly; `read_pid_file`/`remove_pid_file` are
+//! the unprofiled convenience wrappers most callers still want.
+//!
+//! [`DaemonPidFile::acquire`] is the only writer: it takes an advisory
+//! lock and writes pid+port atomically (temp file + rename), so a daemon
+//! restarting concurrently with a reader can't observe a half-written
+//! file, and a second daemon for the same profile fails to start instead
+//! of silently overwriting the first one's file.
+//!
+//! This stays in `snps-daemon` rather than `snps-core`: it's daemon
+//! lifecycle bookkeeping, not shared domain data, and all three
+//! consumers (CLI, the daemon itself, the desktop shim) already depend
+//! on this crate for it.
+
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DaemonPid {
+    pub pid: u32,
+    pub port: u16,
+}
+
+fn pid_file_name(profile: Option<&str>) -> String {
+    match profile {
+        Some(profile) => format!("daemon-{profile}.pid"),
+        None => "daemon.pid".to_string(),
+    }
+}
+
+fn pid_file_path(pmsynapse_dir: &Path, profile: Option<&str>) -> PathBuf {
+    pmsynapse_dir.join(pid_file_name(profile))
+}
+
+/// Parse either the current JSON body or the older plain `pid:port` text
+/// format, so a workspace whose pid file predates the JSON format (or
+/// one written by an older `snps` binary during a mixed-version
+/// upgrade) is still read correctly instead of silently failing to
+/// parse and reporting "not running".
+fn parse_pid_contents(contents: &str) -> Option<DaemonPid> {
+    if let Ok(daemon) = serde_json::from_str::<DaemonPid>(contents) {
+        return Some(daemon);
+    }
+    let (pid, port) = contents.trim().split_once(':')?;
+    Some(DaemonPid { pid: pid.parse().ok()?, port: port.parse().ok()? })
+}
+
+/// Advisory-locked, atomically-written pid:port bookkeeping for one
+/// profile's daemon.
+pub struct DaemonPidFile {
+    path: PathBuf,
+}
+
+impl DaemonPidFile {
+    pub fn new(pmsynapse_dir: &Path, profile: Option<&str>) -> Self {
+        DaemonPidFile { path: pid_file_path(pmsynapse_dir, profile) }
+    }
+
+    fn lock_path(&self) -> PathBuf {
+        self.path.with_extension("lock")
+    }
+
+    /// Take the advisory lock and atomically write `pid`/`port`. Meant to
+    /// be called once, by the daemon process itself, and the returned
+    /// guard held for the daemon's lifetime — dropping it releases the
+    /// lock and removes the pid file. An already-running daemon for this
+    /// profile makes this fail rather than clobbering its file.
+    pub fn acquire(&self, port: u16) -> io::Result<DaemonPidLock> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let lock_file = fs::OpenOptions::new().create(true).write(true).open(self.lock_path())?;
+        lock_file.try_lock_exclusive().map_err(|_| {
+            io::Error::new(io::ErrorKind::WouldBlock, "another daemon already holds the pid file lock for this profile")
+        })?;
+
+        let contents = serde_json::to_string(&DaemonPid { pid: std::process::id(), port }).unwrap_or_default();
+        let tmp_path = self.path.with_extension("pid.tmp");
+        fs::write(&tmp_path, contents)?;
+        fs::rename(&tmp_path, &self.path)?;
+
+        Ok(DaemonPidLock { _lock_file: lock_file, pid_path: self.path.clone(), lock_path: self.lock_path() })
+    }
+
+    pub fn read(&self) -> Option<DaemonPid> {
+        let contents = fs::read_to_string(&self.path).ok()?;
+        parse_pid_contents(&contents)
+    }
+
+    /// Whether the recorded pid is both running and actually an `snps`
+    /// process, not just any process that happens to have reused the
+    /// pid after a crash — a bare `kill -0` liveness check can't tell
+    /// those apart.
+    pub fn is_live(&self) -> bool {
+        self.read().is_some_and(|daemon| is_daemon_process(daemon.pid))
+    }
+}
+
+/// Holds the advisory lock and owns cleanup: dropping it (including on
+/// panic/unwind) removes the pid and lock files so the next start doesn't
+/// see stale state.
+pub struct DaemonPidLock {
+    _lock_file: fs::File,
+    pid_path: PathBuf,
+    lock_path: PathBuf,
+}
+
+impl Drop for DaemonPidLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.pid_path);
+        let _ = fs::remove_file(&self.lock_path);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn is_daemon_process(pid: u32) -> bool {
+    fs::read_to_string(format!("/proc/{pid}/comm")).map(|comm| comm.trim() == "snps").unwrap_or(false)
+}
+
+/// No portable way to inspect another process's identity outside
+/// `/proc`, so this falls back to a plain liveness probe rather than
+/// refusing to ever recognize a live daemon.
+#[cfg(not(target_os = "linux"))]
+fn is_daemon_process(pid: u32) -> bool {
+    snps_core::doctor::process_is_running(pid)
+}
+
+pub fn read_pid_file_for_profile(pmsynapse_dir: &Path, profile: Option<&str>) -> Option<DaemonPid> {
+    DaemonPidFile::new(pmsynapse_dir, profile).read()
+}
+
+pub fn read_pid_file(pmsynapse_dir: &Path) -> Option<DaemonPid> {
+    read_pid_file_for_profile(pmsynapse_dir, None)
+}
+
+/// Every `daemon*.pid` file under `pmsynapse_dir`, paired with the
+/// profile name each belongs to (`None` for the unprofiled default), for
+/// a profile picker to enumerate.
+pub fn list_pid_files(pmsynapse_dir: &Path) -> Vec<(Option<String>, DaemonPid)> {
+    let mut found = Vec::new();
+    let Ok(entries) = std::fs::read_dir(pmsynapse_dir) else { return found };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+        let Some(profile) = profile_from_file_name(file_name) else { continue };
+        let Ok(contents) = std::fs::read_to_string(&path) else { continue };
+        let Some(pid) = parse_pid_contents(&contents) else { continue };
+        found.push((profile, pid));
+    }
+
+    found
+}
+
+/// Parse `daemon.pid` as the unprofiled default (`Some(None)`) or
+/// `daemon-<profile>.pid` as that profile (`Some(Some(profile))`);
+/// anything else isn't one of ours (`None`).
+fn profile_from_file_name(file_name: &str) -> Option<Option<String>> {
+    if file_name == "daemon.pid" {
+        return Some(None);
+    }
+    file_name
+        .strip_prefix("daemon-")
+        .and_then(|rest| rest.strip_suffix(".pid"))
+        .map(|profile| Some(profile.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_from_file_name_distinguishes_default_from_named_from_unrelated() {
+        assert_eq!(profile_from_file_name("daemon.pid"), Some(None));
+        assert_eq!(profile_from_file_name("daemon-dev.pid"), Some(Some("dev".to_string())));
+        assert_eq!(profile_from_file_name("config.yaml"), None);
+    }
+
+    #[test]
+    fn profiled_and_unprofiled_pid_files_round_trip_independently() {
+        let tmp = tempfile::tempdir().unwrap();
+        let _default_lock = DaemonPidFile::new(tmp.path(), None).acquire(4884).unwrap();
+        let _dev_lock = DaemonPidFile::new(tmp.path(), Some("dev")).acquire(4885).unwrap();
+
+        assert_eq!(read_pid_file_for_profile(tmp.path(), None).unwrap().port, 4884);
+        assert_eq!(read_pid_file_for_profile(tmp.path(), Some("dev")).unwrap().port, 4885);
+
+        let mut listed = list_pid_files(tmp.path());
+        listed.sort_by_key(|(profile, _)| profile.clone());
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].0, None);
+        assert_eq!(listed[1].0, Some("dev".to_string()));
+    }
+
+    #[test]
+    fn acquire_fails_while_another_lock_is_held() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = DaemonPidFile::new(tmp.path(), None);
+        let _held = file.acquire(4884).unwrap();
+        assert!(file.acquire(4885).is_err());
+    }
+
+    #[test]
+    fn dropping_the_lock_removes_the_pid_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = DaemonPidFile::new(tmp.path(), None);
+        let lock = file.acquire(4884).unwrap();
+        assert!(file.read().is_some());
+        drop(lock);
+        assert!(file.read().is_none());
+    }
+
+    #[test]
+    fn parses_legacy_pid_colon_port_format() {
+        assert_eq!(parse_pid_contents("1234:7878").unwrap().pid, 1234);
+        assert_eq!(parse_pid_contents("1234:7878").unwrap().port, 7878);
+        assert!(parse_pid_contents("not a pid file").is_none());
+    }
+}
@@ -0,0 +1,188 @@
+//! Multi-project routing: a registry of `Workspace`s opened lazily by
+//! project root, so one daemon can serve requests for more than one
+//! project instead of needing a separate process (and port) per project.
+//!
+//! Only read routes (`list_nodes`, the new `GET /api/v1/projects`) are
+//! wired through [`ProjectRegistry`] so far — writes, [`crate::writer`],
+//! and [`crate::scheduler`] still only ever touch `AppState::workspace`,
+//! the daemon's configured default project. Giving every open project
+//! its own writer/scheduler task is a bigger piece of work than one
+//! route's worth of routing, so it's left for a follow-up rather than
+//! rushed through here.
+
+use serde::Serialize;
+use snps_core::error::CoreResult;
+use snps_core::graph::{KnowledgeGraph, NodeOrderBy};
+use snps_core::Workspace;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+struct OpenProject {
+    workspace: Arc<Workspace>,
+    last_used: Instant,
+}
+
+#[derive(Serialize)]
+pub struct ProjectSummary {
+    pub root: PathBuf,
+    pub node_count: usize,
+}
+
+/// Registry of opened project `Workspace`s, keyed by project root.
+/// Cloning shares the same underlying map — the same cheap-handle shape
+/// as [`crate::scheduler::SchedulerHandle`] and [`crate::writer::WriteHandle`]
+/// — so it can be held on `AppState` and passed into the idle-eviction task.
+#[derive(Clone)]
+pub struct ProjectRegistry {
+    projects: Arc<Mutex<HashMap<PathBuf, OpenProject>>>,
+}
+
+impl ProjectRegistry {
+    pub fn new() -> Self {
+        ProjectRegistry { projects: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    /// The `Workspace` for `root`, opening (and caching) it on first use.
+    /// `root` should already be a workspace root rather than an arbitrary
+    /// subdirectory — callers with only a cwd resolve it via
+    /// `Workspace::discover` before reaching here (see
+    /// [`resolve_project_root`]'s doc comment).
+    pub async fn get_or_open(&self, root: &Path) -> CoreResult<Arc<Workspace>> {
+        let mut projects = self.projects.lock().await;
+        if let Some(existing) = projects.get_mut(root) {
+            existing.last_used = Instant::now();
+            return Ok(existing.workspace.clone());
+        }
+        let workspace = Arc::new(Workspace::discover(root)?);
+        projects.insert(root.to_path_buf(), OpenProject { workspace: workspace.clone(), last_used: Instant::now() });
+        Ok(workspace)
+    }
+
+    /// Every currently-open project's root and node count, for
+    /// `GET /api/v1/projects`. Opening the graph to count nodes costs the
+    /// same as `list_nodes` already pays per request — just once per open
+    /// project here, rather than once per request.
+    pub async fn list(&self) -> Vec<ProjectSummary> {
+        let projects = self.projects.lock().await;
+        projects
+            .iter()
+            .map(|(root, open)| ProjectSummary { root: root.clone(), node_count: node_count(&open.workspace) })
+            .collect()
+    }
+
+    /// Drop any project untouched for longer than `max_idle`, so a daemon
+    /// driven across many projects over a long session doesn't keep every
+    /// one of them open forever.
+    pub async fn evict_idle(&self, max_idle: Duration) {
+        let mut projects = self.projects.lock().await;
+        retain_active(&mut projects, Instant::now(), max_idle);
+    }
+}
+
+impl Default for ProjectRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn node_count(workspace: &Workspace) -> usize {
+    KnowledgeGraph::init(&workspace.graph_db_path())
+        .and_then(|g| g.query_page(None, None, NodeOrderBy::CreatedAt, 1, 0))
+        .map(|page| page.total)
+        .unwrap_or(0)
+}
+
+/// The actual eviction rule, taking `now` explicitly so it's testable
+/// without a real clock or a background task.
+fn retain_active(projects: &mut HashMap<PathBuf, OpenProject>, now: Instant, max_idle: Duration) {
+    projects.retain(|_, open| now.saturating_duration_since(open.last_used) < max_idle);
+}
+
+/// Spawn the background loop that evicts idle projects from `registry`
+/// every `check_interval`, dropping any untouched for longer than
+/// `max_idle`. Mirrors [`crate::scheduler::spawn`]'s "wake up periodically,
+/// log and move on if something goes wrong" shape, except eviction can't
+/// fail the way a scheduled job can.
+pub fn spawn_eviction(registry: ProjectRegistry, check_interval: Duration, max_idle: Duration) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(check_interval).await;
+            registry.evict_idle(max_idle).await;
+        }
+    });
+}
+
+/// Which project a request is for: the `X-Pmsynapse-Project` header if
+/// present, else `?project=`, else `default_root`. Both forms carry a
+/// project root path rather than a bare id/name — resolving a name
+/// against some registry of known projects isn't needed yet, since the
+/// CLI and desktop app already know their own workspace root and can
+/// send it directly.
+pub fn resolve_project_root(header: Option<&str>, query_param: Option<&str>, default_root: &Path) -> PathBuf {
+    header.or(query_param).map(PathBuf::from).unwrap_or_else(|| default_root.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_project_root_prefers_header_then_query_then_default() {
+        let default_root = Path::new("/default");
+        assert_eq!(resolve_project_root(Some("/from-header"), Some("/from-query"), default_root), PathBuf::from("/from-header"));
+        assert_eq!(resolve_project_root(None, Some("/from-query"), default_root), PathBuf::from("/from-query"));
+        assert_eq!(resolve_project_root(None, None, default_root), PathBuf::from("/default"));
+    }
+
+    fn workspace_at(dir: &Path) -> Arc<Workspace> {
+        std::fs::create_dir_all(dir.join(".pmsynapse")).unwrap();
+        Arc::new(Workspace::discover(dir).unwrap())
+    }
+
+    #[test]
+    fn retain_active_evicts_only_projects_idle_past_the_threshold() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ws = workspace_at(tmp.path());
+        let base = Instant::now();
+
+        let mut projects = HashMap::new();
+        projects.insert(PathBuf::from("/a"), OpenProject { workspace: ws.clone(), last_used: base });
+        projects.insert(PathBuf::from("/b"), OpenProject { workspace: ws, last_used: base + Duration::from_secs(20) });
+
+        retain_active(&mut projects, base + Duration::from_secs(30), Duration::from_secs(15));
+
+        assert!(!projects.contains_key(Path::new("/a")));
+        assert!(projects.contains_key(Path::new("/b")));
+    }
+
+    #[tokio::test]
+    async fn get_or_open_caches_the_workspace_across_calls() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".pmsynapse")).unwrap();
+        let registry = ProjectRegistry::new();
+
+        let first = registry.get_or_open(tmp.path()).await.unwrap();
+        let second = registry.get_or_open(tmp.path()).await.unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[tokio::test]
+    async fn list_reports_every_opened_project() {
+        let tmp_a = tempfile::tempdir().unwrap();
+        let tmp_b = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(tmp_a.path().join(".pmsynapse")).unwrap();
+        std::fs::create_dir_all(tmp_b.path().join(".pmsynapse")).unwrap();
+        let registry = ProjectRegistry::new();
+
+        registry.get_or_open(tmp_a.path()).await.unwrap();
+        registry.get_or_open(tmp_b.path()).await.unwrap();
+
+        let summaries = registry.list().await;
+        assert_eq!(summaries.len(), 2);
+        assert!(summaries.iter().any(|s| s.root == tmp_a.path()));
+        assert!(summaries.iter().any(|s| s.root == tmp_b.path()));
+    }
+}
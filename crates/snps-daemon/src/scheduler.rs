@@ -0,0 +1,203 @@
+//! Background execution of `sync.schedules` jobs (`thoughts-sync`,
+//! `knowledge-pull`, `repo-sync <id>`, `index-rebuild`). Each job runs
+//! through the same `snps-core` library functions the CLI uses — nothing
+//! here shells out to `snps`. A job's failure is caught and recorded, never
+//! propagated: one broken job (a repo with a stale remote, say) must not
+//! take the daemon down or block the others.
+//!
+//! `knowledge-pull` runs the same scan/plan/apply pipeline as `snps know
+//! sync --apply`, scoped to every configured repo and never forced — a
+//! conflict is left for a human to settle with `snps know resolve` rather
+//! than picked automatically. It applies against the workspace's top-level
+//! `knowledge_dir()`, since a daemon job has no per-subproject `cwd` the
+//! way a CLI invocation does.
+
+use snps_core::config::load_merged_config;
+use snps_core::git::{Divergence, GitRepo};
+use snps_core::graph::{sync_markdown_to_graph, KnowledgeGraph};
+use snps_core::knowledge::state::SyncStateStore;
+use snps_core::knowledge::{apply_plan, build_sync_plan, local_snapshot, scan_repos, ProvenanceManifest, RegistrationManifest};
+use snps_core::repository::Repository;
+use snps_core::scheduler::{parse_operation, JobOutcome, JobRun, JobStateStore, ScheduleConfig, ScheduledOperation};
+use snps_core::search_index::SearchIndex;
+use snps_core::time::now_unix;
+use snps_core::Workspace;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often the scheduler wakes up to check for due jobs. Independent of
+/// any individual job's own interval — this only bounds how late a job
+/// can start after becoming due.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(serde::Serialize)]
+pub struct JobStatusRow {
+    pub name: String,
+    pub operation: String,
+    pub interval: String,
+    pub last_run: Option<JobRun>,
+    pub consecutive_failures: u32,
+}
+
+/// A cloneable front end to the background scheduler task, held on
+/// `AppState` for the `/api/v1/jobs` routes.
+#[derive(Clone)]
+pub struct SchedulerHandle {
+    workspace: Arc<Workspace>,
+}
+
+/// Spawn the background loop that checks for and runs due jobs every
+/// [`POLL_INTERVAL`], reloading config on each tick so `sync.schedules`
+/// edits take effect without a daemon restart.
+pub fn spawn(workspace: Arc<Workspace>) -> SchedulerHandle {
+    let handle = SchedulerHandle { workspace };
+    let loop_handle = handle.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            if let Err(e) = loop_handle.tick().await {
+                eprintln!("scheduler: {e}");
+            }
+        }
+    });
+    handle
+}
+
+impl SchedulerHandle {
+    async fn tick(&self) -> anyhow::Result<()> {
+        let workspace = self.workspace.clone();
+        tokio::task::spawn_blocking(move || run_due_jobs(&workspace)).await??;
+        Ok(())
+    }
+
+    /// Run one named job immediately, regardless of whether it's due, for
+    /// `POST /api/v1/jobs/:name/run` / `snps daemon jobs run <name>`.
+    pub async fn run_now(&self, name: String) -> anyhow::Result<JobRun> {
+        let workspace = self.workspace.clone();
+        tokio::task::spawn_blocking(move || run_one_by_name(&workspace, &name)).await?
+    }
+
+    pub async fn status(&self) -> anyhow::Result<Vec<JobStatusRow>> {
+        let workspace = self.workspace.clone();
+        tokio::task::spawn_blocking(move || status_rows(&workspace)).await?
+    }
+}
+
+fn schedules(workspace: &Workspace) -> anyhow::Result<Vec<ScheduleConfig>> {
+    Ok(load_merged_config(workspace)?.config.sync_schedules)
+}
+
+fn status_rows(workspace: &Workspace) -> anyhow::Result<Vec<JobStatusRow>> {
+    let configs = schedules(workspace)?;
+    let store = JobStateStore::load(&workspace.pmsynapse_dir())?;
+    Ok(configs
+        .into_iter()
+        .map(|c| {
+            let record = store.jobs.get(&c.name).cloned().unwrap_or_default();
+            JobStatusRow {
+                name: c.name,
+                operation: c.operation,
+                interval: c.interval,
+                last_run: record.last_run,
+                consecutive_failures: record.consecutive_failures,
+            }
+        })
+        .collect())
+}
+
+fn run_due_jobs(workspace: &Workspace) -> anyhow::Result<()> {
+    let configs = schedules(workspace)?;
+    if configs.is_empty() {
+        return Ok(());
+    }
+    let mut store = JobStateStore::load(&workspace.pmsynapse_dir())?;
+    let now = now_unix();
+    let due: Vec<ScheduleConfig> = store.due_jobs(&configs, now)?.into_iter().cloned().collect();
+    for job in due {
+        let run = execute(workspace, &job);
+        store.record(&job.name, run);
+    }
+    store.save(&workspace.pmsynapse_dir())?;
+    Ok(())
+}
+
+fn run_one_by_name(workspace: &Workspace, name: &str) -> anyhow::Result<JobRun> {
+    let configs = schedules(workspace)?;
+    let job = configs.into_iter().find(|j| j.name == name).ok_or_else(|| anyhow::anyhow!("no scheduled job named '{name}'"))?;
+    let run = execute(workspace, &job);
+    let mut store = JobStateStore::load(&workspace.pmsynapse_dir())?;
+    store.record(&job.name, run.clone());
+    store.save(&workspace.pmsynapse_dir())?;
+    Ok(run)
+}
+
+/// Run one job, catching any error as a `Failed` outcome instead of
+/// propagating it — a scheduled job failing must never be fatal to the
+/// background loop or an immediate `run now` request.
+fn execute(workspace: &Workspace, job: &ScheduleConfig) -> JobRun {
+    let started = Instant::now();
+    let started_unix = now_unix();
+    let result = run_operation(workspace, job);
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(()) => JobRun { started_unix, duration_ms, outcome: JobOutcome::Success, error: None },
+        Err(e) => JobRun { started_unix, duration_ms, outcome: JobOutcome::Failed, error: Some(e.to_string()) },
+    }
+}
+
+fn run_operation(workspace: &Workspace, job: &ScheduleConfig) -> anyhow::Result<()> {
+    match parse_operation(&job.operation)? {
+        ScheduledOperation::ThoughtsSync => {
+            let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+            sync_markdown_to_graph(
+                &graph,
+                &workspace.root,
+                &workspace.knowledge_dir(),
+                &workspace.thoughts_dir().join("shared"),
+                None,
+                false,
+            )?;
+        }
+        ScheduledOperation::IndexRebuild => {
+            let merged = load_merged_config(workspace)?;
+            let index_path = workspace.root.join(&merged.config.search_index_db);
+            let mut index = SearchIndex::load(&index_path)?;
+            index.rebuild_incremental(&workspace.thoughts_dir(), &merged.config.search_exclude_patterns, |p| {
+                snps_core::thoughts::thought_title(p)
+            })?;
+            index.save(&index_path)?;
+        }
+        ScheduledOperation::KnowledgePull => {
+            let repos = Repository::load_all(workspace)?;
+            let selected: Vec<&Repository> = repos.iter().collect();
+            let order = load_merged_config(workspace)?.config.knowledge_precedence;
+            let knowledge_dir = workspace.knowledge_dir();
+
+            let mut state = SyncStateStore::load(workspace)?;
+            let scanned = scan_repos(&selected, &order);
+            let (local_hashes, local_newer) = local_snapshot(&knowledge_dir, &scanned);
+            let plan = build_sync_plan(&scanned, &local_hashes, &local_newer, &state, false);
+            let strategy_for = |repo_id: &str| selected.iter().find(|r| r.id == repo_id).map(|r| r.sync_strategy).unwrap_or_default();
+
+            let mut provenance = ProvenanceManifest::load(workspace)?;
+            let mut registration = RegistrationManifest::load_or_migrate(workspace, &state, &repos)?;
+            apply_plan(&plan, &knowledge_dir, &mut state, &mut provenance, &mut registration, now_unix(), strategy_for)?;
+
+            state.save(workspace)?;
+            provenance.save(workspace)?;
+            registration.save(workspace)?;
+        }
+        ScheduledOperation::RepoSync { repo_id } => {
+            let repos = Repository::load_all(workspace)?;
+            let repo = repos.iter().find(|r| r.id == repo_id).ok_or_else(|| anyhow::anyhow!("no repository with id '{repo_id}'"))?;
+            let git = GitRepo::open(&repo.path)?;
+            git.fetch()?;
+            if git.divergence()? == Divergence::FastForwardable {
+                git.fast_forward()?;
+            }
+        }
+    }
+    Ok(())
+}
+
@@ -0,0 +1,127 @@
+//! Single writer task owning the `KnowledgeGraph` handle for graph
+//! mutations, so `graph_batch`/`graph_restore` requests from the CLI,
+//! desktop app, and (eventually) a file watcher never open competing
+//! sqlite connections. Handlers send a command through this task and await
+//! a oneshot response instead of blocking the tokio runtime on database IO
+//! themselves. Pending writes coalesce into one `KnowledgeGraph::init` per
+//! batch: up to [`MAX_BATCH`] commands or [`MAX_DELAY`], whichever comes
+//! first.
+//!
+//! Matter creation is plain markdown file IO with no shared mutable state
+//! to serialize, so it isn't routed through here — only the sqlite-backed
+//! graph has real lock contention to avoid.
+
+use snps_core::error::{CoreError, CoreResult};
+use snps_core::graph::{GraphExport, KnowledgeGraph};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, RwLock};
+
+const MAX_BATCH: usize = 32;
+const MAX_DELAY: Duration = Duration::from_millis(25);
+
+enum WriteCommand {
+    Batch { export: GraphExport, respond: oneshot::Sender<CoreResult<BTreeMap<String, String>>> },
+    Restore { export: GraphExport, force: bool, respond: oneshot::Sender<CoreResult<()>> },
+}
+
+/// A cloneable front end to the writer task. Every HTTP handler that
+/// mutates the graph gets one of these instead of a `KnowledgeGraph`.
+#[derive(Clone)]
+pub struct WriteHandle {
+    tx: mpsc::Sender<WriteCommand>,
+    queue_depth: Arc<AtomicUsize>,
+}
+
+impl WriteHandle {
+    /// Commands currently queued or being coalesced, for the `/api/v1/status` metric.
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth.load(Ordering::Relaxed)
+    }
+
+    pub async fn batch(&self, export: GraphExport) -> CoreResult<BTreeMap<String, String>> {
+        let (respond, rx) = oneshot::channel();
+        self.send(WriteCommand::Batch { export, respond }).await;
+        rx.await.unwrap_or_else(|_| Err(writer_gone()))
+    }
+
+    pub async fn restore(&self, export: GraphExport, force: bool) -> CoreResult<()> {
+        let (respond, rx) = oneshot::channel();
+        self.send(WriteCommand::Restore { export, force, respond }).await;
+        rx.await.unwrap_or_else(|_| Err(writer_gone()))
+    }
+
+    async fn send(&self, command: WriteCommand) {
+        self.queue_depth.fetch_add(1, Ordering::Relaxed);
+        let _ = self.tx.send(command).await;
+    }
+}
+
+fn writer_gone() -> CoreError {
+    CoreError::InvalidInput("daemon writer task is no longer running".to_string())
+}
+
+/// Spawn the writer task, holding `write_lock` for the duration of each
+/// coalesced batch so a `graph_backup` snapshot (which takes it
+/// exclusively) never observes a write half-applied — the same contract
+/// the write lock already had before this task existed.
+pub fn spawn(graph_db_path: PathBuf, write_lock: Arc<RwLock<()>>) -> WriteHandle {
+    let (tx, mut rx) = mpsc::channel::<WriteCommand>(256);
+    let queue_depth = Arc::new(AtomicUsize::new(0));
+    let handle = WriteHandle { tx, queue_depth: queue_depth.clone() };
+
+    tokio::spawn(async move {
+        loop {
+            let Some(first) = rx.recv().await else { break };
+            let mut pending = vec![first];
+            let deadline = tokio::time::sleep(MAX_DELAY);
+            tokio::pin!(deadline);
+            while pending.len() < MAX_BATCH {
+                tokio::select! {
+                    _ = &mut deadline => break,
+                    maybe = rx.recv() => match maybe {
+                        Some(command) => pending.push(command),
+                        None => break,
+                    },
+                }
+            }
+
+            let _quiesce = write_lock.read().await;
+            match KnowledgeGraph::init(&graph_db_path) {
+                Ok(graph) => {
+                    for command in pending {
+                        queue_depth.fetch_sub(1, Ordering::Relaxed);
+                        match command {
+                            WriteCommand::Batch { export, respond } => {
+                                let _ = respond.send(graph.add_batch(&export.nodes, &export.edges));
+                            }
+                            WriteCommand::Restore { export, force, respond } => {
+                                let _ = respond.send(export.restore(&graph, force));
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    let message = e.to_string();
+                    for command in pending {
+                        queue_depth.fetch_sub(1, Ordering::Relaxed);
+                        let error = CoreError::InvalidInput(message.clone());
+                        match command {
+                            WriteCommand::Batch { respond, .. } => {
+                                let _ = respond.send(Err(error));
+                            }
+                            WriteCommand::Restore { respond, .. } => {
+                                let _ = respond.send(Err(error));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    handle
+}
@@ -0,0 +1,111 @@
+//! Pure, allocation-only formatting for exported Claude session threads,
+//! shared between `snps-core` (CLI/daemon export) and `snps-wasm` (the
+//! browser viewer) so the same markdown/HTML comes out of both. No I/O,
+//! no panics on malformed input — callers own parsing and validation.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadMessage {
+    pub role: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadData {
+    pub session_id: String,
+    pub title: String,
+    pub messages: Vec<ThreadMessage>,
+}
+
+impl ThreadData {
+    pub fn message_count(&self) -> usize {
+        self.messages.len()
+    }
+}
+
+/// Render a thread as markdown. Iterates the message list rather than
+/// recursing, so a 10k-message session doesn't blow the (wasm) stack.
+pub fn thread_to_markdown(data: &ThreadData) -> String {
+    let mut out = markdown_header(&data.title);
+    for message in &data.messages {
+        out.push_str(&markdown_message(&message.role, &message.content));
+    }
+    out
+}
+
+/// Render a thread as a standalone HTML fragment (no `<html>`/`<head>` —
+/// callers embed it in their own page shell).
+pub fn thread_to_html(data: &ThreadData) -> String {
+    let mut out = html_header(&data.title);
+    for message in &data.messages {
+        out.push_str(&html_message(&message.role, &message.content));
+    }
+    out.push_str(&html_footer());
+    out
+}
+
+/// The markdown title line [`thread_to_markdown`] starts with. Split out so
+/// a caller streaming messages straight to a file (rather than building a
+/// [`ThreadData`] first) can write the same header without a fake
+/// one-message `ThreadData`.
+pub fn markdown_header(title: &str) -> String {
+    format!("# {title}\n\n")
+}
+
+/// One message's markdown, as [`thread_to_markdown`] would render it.
+pub fn markdown_message(role: &str, content: &str) -> String {
+    format!("## {role}\n\n{content}\n\n")
+}
+
+/// The HTML fragment's opening tag and title, matching [`thread_to_html`]'s
+/// prologue. Pair with [`html_message`] per message and [`html_footer`] to
+/// close the fragment when streaming instead of building a [`ThreadData`].
+pub fn html_header(title: &str) -> String {
+    format!("<article>\n<h1>{}</h1>\n", escape_html(title))
+}
+
+/// One message's HTML `<section>`, as [`thread_to_html`] would render it.
+pub fn html_message(role: &str, content: &str) -> String {
+    format!(
+        "<section class=\"message {role}\">\n<h2>{role}</h2>\n<p>{content}</p>\n</section>\n",
+        role = escape_html(role),
+        content = escape_html(content)
+    )
+}
+
+/// Closes the fragment opened by [`html_header`].
+pub fn html_footer() -> String {
+    "</article>\n".to_string()
+}
+
+/// Wrap a pre-rendered HTML fragment (e.g. from [`thread_to_html`], or any
+/// other caller's own markup) in a minimal standalone document, so writing
+/// an HTML file doesn't require a second, ad hoc `<html>` shell per caller.
+pub fn html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{}</title></head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        body
+    )
+}
+
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,207 @@
+//! Browser-side mirror of `snps_core::graph`'s node/edge model, kept as
+//! a plain in-memory store so the web app can build and query a graph
+//! without a daemon connection, then persist it into browser storage via
+//! [`WasmGraph::to_json`]/[`WasmGraph::from_json`].
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use wasm_bindgen::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeType {
+    Document,
+    Assumption,
+    Question,
+    Insight,
+}
+
+impl NodeType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            NodeType::Document => "document",
+            NodeType::Assumption => "assumption",
+            NodeType::Question => "question",
+            NodeType::Insight => "insight",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeType {
+    Describes,
+    RelatesTo,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    pub id: String,
+    pub node_type: NodeType,
+    pub title: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub edge_type: EdgeType,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct GraphData {
+    nodes: HashMap<String, Node>,
+    edges: Vec<Edge>,
+}
+
+/// Structured error returned to JS: `{ code, message }` instead of a bare
+/// string, so callers can branch on `code` without parsing text.
+fn js_error(code: &str, message: impl Into<String>) -> JsValue {
+    #[derive(Serialize)]
+    struct WasmError {
+        code: String,
+        message: String,
+    }
+    serde_wasm_bindgen::to_value(&WasmError { code: code.to_string(), message: message.into() }).unwrap_or(JsValue::NULL)
+}
+
+#[wasm_bindgen]
+pub struct WasmGraph {
+    data: GraphData,
+}
+
+#[wasm_bindgen]
+impl WasmGraph {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmGraph {
+        WasmGraph { data: GraphData::default() }
+    }
+
+    #[wasm_bindgen(js_name = addNode)]
+    pub fn add_node(&mut self, node_json: &str) -> Result<(), JsValue> {
+        let node: Node = serde_json::from_str(node_json).map_err(|e| js_error("invalid_node", e.to_string()))?;
+        self.data.nodes.insert(node.id.clone(), node);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = addEdge)]
+    pub fn add_edge(&mut self, edge_json: &str) -> Result<(), JsValue> {
+        let edge: Edge = serde_json::from_str(edge_json).map_err(|e| js_error("invalid_edge", e.to_string()))?;
+        if !self.data.nodes.contains_key(&edge.from) || !self.data.nodes.contains_key(&edge.to) {
+            return Err(js_error("unknown_node", "edge references a node that hasn't been added yet"));
+        }
+        self.data.edges.push(edge);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = removeNode)]
+    pub fn remove_node(&mut self, id: &str) -> Result<(), JsValue> {
+        if self.data.nodes.remove(id).is_none() {
+            return Err(js_error("not_found", format!("no node with id '{id}'")));
+        }
+        self.data.edges.retain(|e| e.from != id && e.to != id);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = queryByType)]
+    pub fn query_by_type(&self, node_type: &str) -> Result<JsValue, JsValue> {
+        let matches: Vec<&Node> = self.data.nodes.values().filter(|n| n.node_type.as_str() == node_type).collect();
+        serde_wasm_bindgen::to_value(&matches).map_err(|e| js_error("serialize_failed", e.to_string()))
+    }
+
+    /// BFS over the in-memory store starting at `id`, following edges in
+    /// either direction, up to `max_depth` hops.
+    #[wasm_bindgen(js_name = findRelated)]
+    pub fn find_related(&self, id: &str, max_depth: u32) -> Result<JsValue, JsValue> {
+        if !self.data.nodes.contains_key(id) {
+            return Err(js_error("not_found", format!("no node with id '{id}'")));
+        }
+
+        let mut visited: HashSet<String> = HashSet::new();
+        visited.insert(id.to_string());
+        let mut queue: VecDeque<(String, u32)> = VecDeque::new();
+        queue.push_back((id.to_string(), 0));
+        let mut related: Vec<&Node> = Vec::new();
+
+        while let Some((current, depth)) = queue.pop_front() {
+            if depth >= max_depth {
+                continue;
+            }
+            for edge in &self.data.edges {
+                let neighbor = if edge.from == current {
+                    Some(&edge.to)
+                } else if edge.to == current {
+                    Some(&edge.from)
+                } else {
+                    None
+                };
+                if let Some(neighbor) = neighbor {
+                    if visited.insert(neighbor.clone()) {
+                        if let Some(node) = self.data.nodes.get(neighbor) {
+                            related.push(node);
+                        }
+                        queue.push_back((neighbor.clone(), depth + 1));
+                    }
+                }
+            }
+        }
+
+        serde_wasm_bindgen::to_value(&related).map_err(|e| js_error("serialize_failed", e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.data).map_err(|e| js_error("serialize_failed", e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<WasmGraph, JsValue> {
+        let data: GraphData = serde_json::from_str(json).map_err(|e| js_error("invalid_graph", e.to_string()))?;
+        Ok(WasmGraph { data })
+    }
+}
+
+impl Default for WasmGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    fn node(id: &str, node_type: &str) -> String {
+        format!(r#"{{"id":"{id}","node_type":"{node_type}","title":"t","content":"c"}}"#)
+    }
+
+    #[wasm_bindgen_test]
+    fn find_related_follows_edges_within_depth() {
+        let mut graph = WasmGraph::new();
+        graph.add_node(&node("a", "document")).unwrap();
+        graph.add_node(&node("b", "insight")).unwrap();
+        graph.add_node(&node("c", "insight")).unwrap();
+        graph.add_edge(r#"{"from":"a","to":"b","edge_type":"relates_to"}"#).unwrap();
+        graph.add_edge(r#"{"from":"b","to":"c","edge_type":"relates_to"}"#).unwrap();
+
+        let related = graph.find_related("a", 1).unwrap();
+        let related: Vec<Node> = serde_wasm_bindgen::from_value(related).unwrap();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].id, "b");
+    }
+
+    #[wasm_bindgen_test]
+    fn round_trips_through_json() {
+        let mut graph = WasmGraph::new();
+        graph.add_node(&node("a", "document")).unwrap();
+        let json = graph.to_json().unwrap();
+
+        let restored = WasmGraph::from_json(&json).unwrap();
+        let matches = restored.query_by_type("document").unwrap();
+        let matches: Vec<Node> = serde_wasm_bindgen::from_value(matches).unwrap();
+        assert_eq!(matches.len(), 1);
+    }
+}
@@ -0,0 +1,103 @@
+//! IDLC schema mirrored from `snps_core::idlc` so the browser bundle can
+//! validate a drag-and-drop status change without a daemon round-trip.
+//! Kept in sync with core via the shared fixture in `fixtures/`.
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transition {
+    pub from: String,
+    pub to: String,
+    #[serde(default)]
+    pub except: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdlcConfig {
+    pub stages: Vec<String>,
+    #[serde(default)]
+    pub statuses: Vec<String>,
+    #[serde(default)]
+    pub transitions: Vec<Transition>,
+}
+
+impl Default for IdlcConfig {
+    fn default() -> Self {
+        let statuses = vec!["backlog".to_string(), "in_progress".to_string(), "review".to_string(), "done".to_string()];
+        IdlcConfig {
+            stages: statuses.clone(),
+            transitions: vec![
+                Transition { from: "backlog".into(), to: "in_progress".into(), except: vec![] },
+                Transition { from: "in_progress".into(), to: "review".into(), except: vec![] },
+                Transition { from: "review".into(), to: "done".into(), except: vec![] },
+                Transition { from: "review".into(), to: "in_progress".into(), except: vec![] },
+                Transition { from: "*".into(), to: "backlog".into(), except: vec!["done".into()] },
+            ],
+            statuses,
+        }
+    }
+}
+
+fn can_transition_impl(config: &IdlcConfig, from: &str, to: &str) -> bool {
+    config.transitions.iter().any(|t| {
+        (t.from == from || t.from == "*") && (t.to == to || t.to == "*") && !t.except.iter().any(|e| e == to)
+    })
+}
+
+fn js_error(code: &str, message: impl Into<String>) -> JsValue {
+    #[derive(Serialize)]
+    struct WasmError {
+        code: String,
+        message: String,
+    }
+    serde_wasm_bindgen::to_value(&WasmError { code: code.to_string(), message: message.into() }).unwrap_or(JsValue::NULL)
+}
+
+#[wasm_bindgen(js_name = getDefaultIdlcConfig)]
+pub fn get_default_idlc_config() -> JsValue {
+    serde_wasm_bindgen::to_value(&IdlcConfig::default()).unwrap_or(JsValue::NULL)
+}
+
+/// Parse an `IdlcConfig` from either YAML or JSON text.
+#[wasm_bindgen(js_name = parseIdlcConfig)]
+pub fn parse_idlc_config(yaml_or_json: &str) -> Result<JsValue, JsValue> {
+    let config = serde_yaml::from_str::<IdlcConfig>(yaml_or_json)
+        .or_else(|_| serde_json::from_str::<IdlcConfig>(yaml_or_json))
+        .map_err(|e| js_error("invalid_idlc_config", e.to_string()))?;
+    serde_wasm_bindgen::to_value(&config).map_err(|e| js_error("serialize_failed", e.to_string()))
+}
+
+/// Whether the given config (as YAML or JSON) allows moving directly
+/// from `from` to `to`.
+#[wasm_bindgen(js_name = canTransition)]
+pub fn can_transition(config_json: &str, from: &str, to: &str) -> Result<bool, JsValue> {
+    let config = serde_yaml::from_str::<IdlcConfig>(config_json)
+        .or_else(|_| serde_json::from_str::<IdlcConfig>(config_json))
+        .map_err(|e| js_error("invalid_idlc_config", e.to_string()))?;
+    Ok(can_transition_impl(&config, from, to))
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Case {
+        from: String,
+        to: String,
+        expected: bool,
+    }
+
+    #[wasm_bindgen_test]
+    fn matches_shared_transition_fixture() {
+        let fixture = include_str!("../../../fixtures/idlc_transitions.json");
+        let cases: Vec<Case> = serde_json::from_str(fixture).unwrap();
+        let config = IdlcConfig::default();
+        for case in cases {
+            assert_eq!(can_transition_impl(&config, &case.from, &case.to), case.expected, "from {} to {}", case.from, case.to);
+        }
+    }
+}
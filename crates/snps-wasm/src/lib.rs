@@ -0,0 +1,11 @@
+//! `wasm-bindgen` bindings consumed by the desktop/web frontend when it
+//! needs to model IDLC state or a knowledge graph without round-tripping
+//! through the daemon.
+
+mod graph;
+mod idlc;
+mod session;
+
+pub use graph::WasmGraph;
+pub use idlc::{can_transition, get_default_idlc_config, parse_idlc_config, IdlcConfig};
+pub use session::{parse_thread_data, thread_to_html, thread_to_markdown};
@@ -0,0 +1,72 @@
+//! Render exported Claude session threads in the browser, using the same
+//! pure formatters as `snps-core`'s session exporter so a session looks
+//! identical whether it's viewed via the CLI-generated HTML or here.
+
+use serde::{Deserialize, Serialize};
+use snps_thread_format::{thread_to_html as format_html, thread_to_markdown as format_markdown, ThreadData};
+use wasm_bindgen::prelude::*;
+
+fn js_error(code: &str, message: impl Into<String>) -> JsValue {
+    #[derive(Serialize)]
+    struct WasmError {
+        code: String,
+        message: String,
+    }
+    serde_wasm_bindgen::to_value(&WasmError { code: code.to_string(), message: message.into() }).unwrap_or(JsValue::NULL)
+}
+
+#[derive(Serialize, Deserialize)]
+struct ThreadSummary {
+    session_id: String,
+    title: String,
+    message_count: usize,
+}
+
+fn parse(json: &str) -> Result<ThreadData, JsValue> {
+    serde_json::from_str(json).map_err(|e| js_error("invalid_thread_data", e.to_string()))
+}
+
+/// Validate an exported session and return summary metadata for the
+/// viewer's header (title, session id, message count) without rendering
+/// the full transcript.
+#[wasm_bindgen(js_name = parseThreadData)]
+pub fn parse_thread_data(json: &str) -> Result<JsValue, JsValue> {
+    let data = parse(json)?;
+    let summary = ThreadSummary { session_id: data.session_id.clone(), title: data.title.clone(), message_count: data.message_count() };
+    serde_wasm_bindgen::to_value(&summary).map_err(|e| js_error("serialize_failed", e.to_string()))
+}
+
+#[wasm_bindgen(js_name = threadToMarkdown)]
+pub fn thread_to_markdown(json: &str) -> Result<String, JsValue> {
+    Ok(format_markdown(&parse(json)?))
+}
+
+#[wasm_bindgen(js_name = threadToHtml)]
+pub fn thread_to_html(json: &str) -> Result<String, JsValue> {
+    Ok(format_html(&parse(json)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    fn sample_json() -> String {
+        r#"{"session_id":"abc","title":"Add a test","messages":[{"role":"user","content":"add a test"},{"role":"assistant","content":"done"}]}"#.to_string()
+    }
+
+    #[wasm_bindgen_test]
+    fn parses_summary_metadata() {
+        let summary = parse_thread_data(&sample_json()).unwrap();
+        let summary: ThreadSummary = serde_wasm_bindgen::from_value(summary).unwrap();
+        assert_eq!(summary.session_id, "abc");
+        assert_eq!(summary.message_count, 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn malformed_json_is_a_descriptive_error() {
+        let err = parse_thread_data("not json").unwrap_err();
+        assert!(err.is_object() || err.is_truthy());
+    }
+}
@@ -0,0 +1,54 @@
+//! `snps analyze` — extract candidate assumptions and open questions from
+//! the project source into the knowledge graph.
+
+use clap::Args;
+use snps_core::config::load_merged_config;
+use snps_core::graph::{scan_deep, scan_quick, write_candidates, KnowledgeGraph};
+use snps_core::llm::provider_for;
+use snps_core::Workspace;
+
+#[derive(Args)]
+pub struct AnalyzeArgs {
+    /// Limit to comments, READMEs, and public API surface (the default).
+    #[arg(long)]
+    quick: bool,
+    /// Also chunk source files and ask the configured LLM provider for
+    /// assumptions/open questions it can infer. Requires an LLM API key
+    /// in config; falls back to --quick if none is set.
+    #[arg(long)]
+    deep: bool,
+    /// Print candidates without writing them to the graph.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+pub fn run(args: AnalyzeArgs) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+
+    let mut candidates = scan_quick(&workspace.root);
+
+    if args.deep {
+        let merged = load_merged_config(&workspace)?;
+        match merged.config.llm_api_key.clone() {
+            None => eprintln!("warning: --deep needs an LLM API key in config; running --quick only"),
+            Some(api_key) => {
+                let provider = provider_for(&merged.config.llm_default_provider, api_key)?;
+                let runtime = tokio::runtime::Runtime::new()?;
+                candidates.extend(runtime.block_on(scan_deep(&workspace.root, provider.as_ref()))?);
+            }
+        }
+    }
+
+    if args.dry_run {
+        for candidate in &candidates {
+            println!("{} {}:{} {}", candidate.node_type, candidate.source_path, candidate.source_line, candidate.title);
+        }
+        println!("{} candidate(s) (dry run, nothing written)", candidates.len());
+        return Ok(());
+    }
+
+    let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+    let written = write_candidates(&graph, &candidates)?;
+    println!("{written} node(s) created");
+    Ok(())
+}
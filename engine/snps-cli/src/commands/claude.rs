@@ -0,0 +1,745 @@
+//! `snps claude` — tools that read Claude Code's own session transcripts.
+
+use crate::output::{OutputFormat, OutputWriter};
+use anyhow::{bail, Context};
+use clap::{Subcommand, ValueEnum};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
+use ratatui::Terminal;
+use serde::Serialize;
+use snps_core::claude::{
+    export_session_jsonl_chat, extractive_summary, has_flat_sessions, list_claude_projects, list_sessions_for_project,
+    migrate_flat_sessions, parse_session_file, parse_session_file_strict, project_slug, render_summary_markdown,
+    resolve_project_dir_for_cwd, resolved_title, session_file_path, title_from_summary, titles_path, write_session_export,
+    write_session_export_streaming, write_session_summary, ExportFormat, JsonlChatOptions, ParseOptions, Session, SessionExporter,
+    SessionStatistics, SessionSummary, TitleStore,
+};
+use snps_core::config::load_merged_config;
+use snps_core::llm::provider_for;
+use snps_core::search_index::SearchIndex;
+use snps_core::Workspace;
+use std::path::{Path, PathBuf};
+
+#[derive(Subcommand)]
+pub enum ClaudeCommand {
+    /// Summarize a session into a research-type thought document.
+    Summarize {
+        session: String,
+        /// Project path the session belongs to; defaults to the current directory.
+        #[arg(long)]
+        project: Option<PathBuf>,
+        /// Skip the LLM narrative and produce a purely extractive summary.
+        #[arg(long)]
+        no_llm: bool,
+        /// Fail on the first malformed or unrecognized transcript line
+        /// instead of skipping it and warning.
+        #[arg(long)]
+        strict: bool,
+        /// Skip the one-time move of pre-existing flat
+        /// `thoughts/shared/sessions/{exports,summaries}/` files into their
+        /// per-project subdirectories.
+        #[arg(long)]
+        no_migrate: bool,
+    },
+    /// Assign a title to a session, stored in a sidecar keyed by session
+    /// id so it survives the session being moved between project
+    /// directories. Session listings and exporters prefer this over the
+    /// raw first user message.
+    Title {
+        /// Session ids to title. With `--from-summary`, each gets its own
+        /// derived title instead of sharing `title`.
+        sessions: Vec<String>,
+        /// Literal title to assign. Required unless `--from-summary` is set.
+        #[arg(long)]
+        title: Option<String>,
+        /// Derive the title from the extractive summary of each session's
+        /// first exchange instead of taking a literal title.
+        #[arg(long)]
+        from_summary: bool,
+        /// Project path the sessions belong to; defaults to the current directory.
+        #[arg(long)]
+        project: Option<PathBuf>,
+        /// Fail on the first malformed or unrecognized transcript line
+        /// instead of skipping it and warning. Only relevant with
+        /// `--from-summary`, which has to parse the session.
+        #[arg(long)]
+        strict: bool,
+    },
+    /// List every project Claude Code has recorded sessions for, with
+    /// session counts, total size on disk, and most recent activity.
+    Projects {
+        /// Only show projects with at least this many sessions.
+        #[arg(long, default_value_t = 0)]
+        min_sessions: usize,
+    },
+    /// Render a session as JSON, Markdown, or HTML, with secret-shaped
+    /// text (API keys, bearer tokens, private key blocks) redacted by
+    /// default before it's printed or saved.
+    ///
+    /// There's no `snps claude parse` or `convert` command in this tree —
+    /// this is named `export` to match the `SessionExporter`/`claude::export`
+    /// machinery it's built on, and `--format jsonl-chat` (curated-session
+    /// fine-tuning data, see `--include-tools`/`--system`/`--min-turn-length`
+    /// below) lives here as one more `--format` value rather than under a
+    /// command name that doesn't exist anywhere else in this CLI.
+    Export {
+        session: String,
+        /// Project path the session belongs to; defaults to the current directory.
+        #[arg(long)]
+        project: Option<PathBuf>,
+        #[arg(long, value_enum, default_value_t = ExportFormatArg::Json)]
+        format: ExportFormatArg,
+        /// Write the rendered export under
+        /// `thoughts/shared/sessions/exports/` instead of printing it to
+        /// stdout. Redaction defaults to on when this is set.
+        #[arg(long)]
+        save: bool,
+        /// Force redaction on even without `--save`.
+        #[arg(long, conflicts_with = "no_redact")]
+        redact: bool,
+        /// Skip redaction entirely, including with `--save`.
+        #[arg(long)]
+        no_redact: bool,
+        /// Fail on the first malformed or unrecognized transcript line
+        /// instead of skipping it and warning.
+        #[arg(long)]
+        strict: bool,
+        /// Format the transcript straight to disk one message at a time
+        /// instead of building the render in memory first. Requires
+        /// `--save` and a markdown/html `--format`; skips redaction (see
+        /// `SessionExporter::write_streaming`). For transcripts too large
+        /// to comfortably materialize.
+        #[arg(long, requires = "save", conflicts_with_all = ["redact", "no_redact"])]
+        stream: bool,
+        /// With `--stream`, truncate any single tool result past this many
+        /// bytes to a `"(truncated N bytes)"` marker. Unset means no limit.
+        #[arg(long)]
+        max_tool_output_bytes: Option<usize>,
+        /// Skip the one-time move of pre-existing flat
+        /// `thoughts/shared/sessions/{exports,summaries}/` files into their
+        /// per-project subdirectories. Only relevant with `--save`.
+        #[arg(long)]
+        no_migrate: bool,
+        /// With `--format jsonl-chat`, render tool calls as
+        /// `function_call` messages and tool results as `role: "tool"`
+        /// messages instead of dropping both from the assistant's turn.
+        #[arg(long)]
+        include_tools: bool,
+        /// With `--format jsonl-chat`, read this file and prepend its
+        /// contents as a `role: "system"` message on every exchange.
+        #[arg(long)]
+        system: Option<PathBuf>,
+        /// With `--format jsonl-chat`, drop an exchange whose combined
+        /// user/assistant text is shorter than this many bytes.
+        #[arg(long)]
+        min_turn_length: Option<usize>,
+    },
+    /// Print per-role message counts for a session without holding the
+    /// whole transcript in memory — the streaming counterpart to
+    /// `summarize`/`export`, for transcripts too large to materialize.
+    Stats {
+        session: String,
+        /// Project path the session belongs to; defaults to the current directory.
+        #[arg(long)]
+        project: Option<PathBuf>,
+        /// Truncate any single tool result past this many bytes before
+        /// counting it towards the total text size. Unset means no limit.
+        #[arg(long)]
+        max_tool_output_bytes: Option<usize>,
+    },
+    /// List every session recorded for a project, most recently modified
+    /// first.
+    List {
+        /// Project path the sessions belong to; defaults to the current directory.
+        #[arg(long)]
+        project: Option<PathBuf>,
+        /// Include sessions that are (at least partly) subagent
+        /// transcripts, hidden by default.
+        #[arg(long)]
+        include_agent_sessions: bool,
+        /// Render an HTML report (a sortable session table, with agent
+        /// sessions collapsed into their own section) instead of the
+        /// usual text/JSON listing. Requires `--save`.
+        #[arg(long, requires = "save")]
+        html: bool,
+        /// Write the report to this path. Only meaningful with `--html`.
+        #[arg(long)]
+        save: Option<PathBuf>,
+    },
+    /// Interactive TUI for browsing sessions: a list pane, a preview of
+    /// the selected session's first/last message, and keybindings to
+    /// export to markdown (`e`), open the export in `$EDITOR` (`o`),
+    /// copy the session id (`y`), and toggle agent sessions (`a`). Falls
+    /// back to `list` when stdout isn't a terminal.
+    Browse {
+        /// Project path the sessions belong to; defaults to the current directory.
+        #[arg(long)]
+        project: Option<PathBuf>,
+    },
+}
+
+/// Parse a session's transcript and, in the tolerant (default) case, warn
+/// about any line that had to be skipped — a summary is more useful to a
+/// human at the command line than silence, even though the parse itself
+/// already tolerated the bad line.
+fn parse_session_or_warn(path: &std::path::Path, strict: bool) -> anyhow::Result<Session> {
+    let session = if strict { parse_session_file_strict(path)? } else { parse_session_file(path)? };
+
+    if !session.parse_issues.is_empty() {
+        eprintln!(
+            "warning: skipped {} unparseable line(s) in {}",
+            session.parse_issues.len(),
+            session.source_path.display()
+        );
+        for issue in &session.parse_issues {
+            eprintln!("  line {} (byte {}): {}", issue.line, issue.byte_offset, issue.message);
+        }
+    }
+
+    Ok(session)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExportFormatArg {
+    Json,
+    Markdown,
+    Html,
+    /// One JSON object per user/assistant exchange in the OpenAI chat
+    /// fine-tuning shape, handled entirely separately from the other
+    /// three below (see the `ClaudeCommand::Export` match arm) since
+    /// `export_session_jsonl_chat` produces several independent
+    /// documents rather than one rendered one and so doesn't fit
+    /// `ExportFormat`/`SessionExporter`.
+    JsonlChat,
+}
+
+impl From<ExportFormatArg> for ExportFormat {
+    fn from(value: ExportFormatArg) -> Self {
+        match value {
+            ExportFormatArg::Json => ExportFormat::Json,
+            ExportFormatArg::Markdown => ExportFormat::Markdown,
+            ExportFormatArg::Html => ExportFormat::Html,
+            ExportFormatArg::JsonlChat => unreachable!("ClaudeCommand::Export branches on jsonl-chat before converting"),
+        }
+    }
+}
+
+/// Resolve `project`, defaulting to the current directory and warning
+/// (rather than silently proceeding) if the encoded project directory
+/// that maps to doesn't actually check out against a recorded session
+/// `cwd` — the `/` -> `-` encoding is ambiguous for paths that already
+/// contain dashes. Also accepts the `project_slug` form (as printed by
+/// `snps claude projects` sessions subdirectories) by matching it against
+/// every recorded project, since a slug alone can't be turned back into a
+/// path the way the raw encoded directory name can.
+fn resolve_project_arg(project: Option<PathBuf>) -> anyhow::Result<PathBuf> {
+    match project {
+        Some(project) if !project.exists() => {
+            let slug = project.to_string_lossy().to_string();
+            match list_claude_projects()?.into_iter().find(|p| project_slug(&p.path) == slug) {
+                Some(matched) => Ok(matched.path),
+                None => Ok(project),
+            }
+        }
+        Some(project) => Ok(project),
+        None => {
+            let cwd = std::env::current_dir()?;
+            let resolution = resolve_project_dir_for_cwd(&cwd);
+            if !resolution.verified {
+                eprintln!(
+                    "warning: {} may not be {}'s Claude project directory (a recorded session cwd disagrees) — pass --project to be explicit",
+                    resolution.dir.display(),
+                    cwd.display()
+                );
+            }
+            Ok(cwd)
+        }
+    }
+}
+
+/// Run the flat-to-per-project session migration once, unless the caller
+/// opted out or there's nothing left to migrate. Called from the two
+/// commands that write under `thoughts/shared/sessions/` (`summarize`,
+/// `export --save`) rather than eagerly on every invocation, so read-only
+/// commands like `list`/`stats` never touch disk for this.
+fn maybe_migrate_flat_sessions(workspace: &Workspace, no_migrate: bool) -> anyhow::Result<()> {
+    if no_migrate || !has_flat_sessions(&workspace.thoughts_dir()) {
+        return Ok(());
+    }
+    let stats = migrate_flat_sessions(&workspace.thoughts_dir())?;
+    println!("migrated {} session file(s) into per-project subdirectories", stats.moved);
+    if !stats.unresolved.is_empty() {
+        eprintln!("warning: could not determine the owning project for {} file(s), left in place:", stats.unresolved.len());
+        for path in &stats.unresolved {
+            eprintln!("  {path}");
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct SessionRow {
+    id: String,
+    title: String,
+    age: String,
+    message_count: usize,
+    is_agent_session: bool,
+}
+
+fn session_row(session: &SessionSummary, titles: Option<&TitleStore>, now: u64) -> SessionRow {
+    let title = match titles {
+        Some(store) => resolved_title(store, &session.id, &session.id).to_string(),
+        None => session.id.clone(),
+    };
+    let message_count = SessionStatistics::compute(&session.path, ParseOptions::default()).map(|s| s.total_messages()).unwrap_or(0);
+    let age = session.modified_unix.map(|m| snps_core::time::age_string(m, now)).unwrap_or_else(|| "-".to_string());
+    SessionRow { id: session.id.clone(), title, age, message_count, is_agent_session: session.is_agent_session }
+}
+
+#[derive(Serialize)]
+struct ProjectRow {
+    path: String,
+    session_count: usize,
+    total_size_bytes: u64,
+    most_recent_activity: Option<u64>,
+}
+
+pub fn run(command: ClaudeCommand, output: OutputFormat) -> anyhow::Result<()> {
+    match command {
+        ClaudeCommand::Summarize { session, project, no_llm, strict, no_migrate } => {
+            let workspace = Workspace::discover_from_cwd()?;
+            let project = resolve_project_arg(project)?;
+            maybe_migrate_flat_sessions(&workspace, no_migrate)?;
+            let path = session_file_path(&project, &session);
+            let parsed = parse_session_or_warn(&path, strict)?;
+            let extractive = extractive_summary(&parsed);
+
+            let narrative = if no_llm {
+                None
+            } else {
+                let merged = load_merged_config(&workspace)?;
+                match merged.config.llm_api_key.clone() {
+                    None => None,
+                    Some(api_key) => {
+                        let provider = provider_for(&merged.config.llm_default_provider, api_key)?;
+                        let runtime = tokio::runtime::Runtime::new()?;
+                        Some(runtime.block_on(snps_core::claude::narrative_summary(&parsed, provider.as_ref()))?)
+                    }
+                }
+            };
+
+            let body = render_summary_markdown(&parsed, &extractive, narrative.as_deref());
+            let today = snps_core::time::today_string();
+            let output_path = write_session_summary(&workspace.thoughts_dir(), &project, &today, &parsed, &body)?;
+
+            let merged = load_merged_config(&workspace)?;
+            let index_path = workspace.root.join(&merged.config.search_index_db);
+            let mut index = SearchIndex::load(&index_path)?;
+            index.rebuild_incremental(&workspace.thoughts_dir(), &merged.config.search_exclude_patterns, |p| {
+                snps_core::thoughts::thought_title(p)
+            })?;
+            index.save(&index_path)?;
+
+            println!("{}", output_path.display());
+        }
+        ClaudeCommand::Title { sessions, title, from_summary, project, strict } => {
+            if sessions.is_empty() {
+                bail!("provide at least one session id");
+            }
+            if from_summary == title.is_some() {
+                bail!("pass exactly one of a literal title or --from-summary");
+            }
+
+            let path = titles_path().context("could not determine home directory for the title sidecar")?;
+            let mut store = TitleStore::load(&path)?;
+            let project = resolve_project_arg(project)?;
+
+            for session in &sessions {
+                let resolved = if from_summary {
+                    let parsed = parse_session_or_warn(&session_file_path(&project, session), strict)?;
+                    title_from_summary(extractive_summary(&parsed).first_user_message.as_deref())
+                } else {
+                    title.clone().expect("checked above")
+                };
+                println!("{session}: {resolved}");
+                store.set(session, &resolved);
+            }
+
+            store.save(&path)?;
+        }
+        ClaudeCommand::Projects { min_sessions } => {
+            let rows: Vec<ProjectRow> = list_claude_projects()?
+                .into_iter()
+                .filter(|p| p.session_count >= min_sessions)
+                .map(|p| ProjectRow {
+                    path: p.path.display().to_string(),
+                    session_count: p.session_count,
+                    total_size_bytes: p.total_size_bytes,
+                    most_recent_activity: p.most_recent_activity,
+                })
+                .collect();
+
+            OutputWriter::new(output).emit(&rows, |rows| {
+                if rows.is_empty() {
+                    println!("no Claude Code projects found");
+                }
+                for row in rows {
+                    let activity = row.most_recent_activity.map(snps_core::time::date_string).unwrap_or_else(|| "never".to_string());
+                    println!("{}  sessions={} size={}B last_active={}", row.path, row.session_count, row.total_size_bytes, activity);
+                }
+            })?;
+        }
+        ClaudeCommand::Export {
+            session,
+            project,
+            format,
+            save,
+            redact,
+            no_redact,
+            strict,
+            stream,
+            max_tool_output_bytes,
+            no_migrate,
+            include_tools,
+            system,
+            min_turn_length,
+        } => {
+            let workspace = Workspace::discover_from_cwd()?;
+            let project = resolve_project_arg(project)?;
+
+            if format == ExportFormatArg::JsonlChat {
+                if save {
+                    bail!("--save isn't supported yet with --format jsonl-chat; redirect stdout instead");
+                }
+                let parsed = parse_session_or_warn(&session_file_path(&project, &session), strict)?;
+                let system_prompt = system.map(std::fs::read_to_string).transpose()?;
+                let options = JsonlChatOptions { include_tools, system_prompt, min_turn_length };
+                println!("{}", export_session_jsonl_chat(&parsed, &options));
+                return Ok(());
+            }
+
+            if save {
+                maybe_migrate_flat_sessions(&workspace, no_migrate)?;
+            }
+            let format: ExportFormat = format.into();
+
+            if stream {
+                if format == ExportFormat::Json {
+                    bail!("--stream only supports --format markdown or html");
+                }
+                let source_path = session_file_path(&project, &session);
+                // Deriving a title the usual way (from the first user
+                // message) means parsing that far into the session, which
+                // is exactly what `--stream` exists to avoid — fall back
+                // to the session id itself when there's no stored title.
+                let title = match titles_path() {
+                    Some(path) => resolved_title(&TitleStore::load(&path)?, &session, &session).to_string(),
+                    None => session.clone(),
+                };
+                let (path, stats) = write_session_export_streaming(
+                    &workspace.thoughts_dir(),
+                    &project,
+                    &source_path,
+                    &session,
+                    &title,
+                    format,
+                    max_tool_output_bytes,
+                )?;
+                if stats.parse_issues > 0 {
+                    eprintln!("warning: skipped {} unparseable line(s) in {}", stats.parse_issues, source_path.display());
+                }
+                println!("{} ({} message(s) written)", path.display(), stats.messages_written);
+                return Ok(());
+            }
+
+            let parsed = parse_session_or_warn(&session_file_path(&project, &session), strict)?;
+
+            let merged = load_merged_config(&workspace)?;
+            let effective_redact = if no_redact { false } else { redact || save };
+            let exporter = SessionExporter::new(effective_redact, merged.config.redaction_patterns.clone());
+
+            let inferred_title = title_from_summary(extractive_summary(&parsed).first_user_message.as_deref());
+            let title = match titles_path() {
+                Some(path) => resolved_title(&TitleStore::load(&path)?, &session, &inferred_title).to_string(),
+                None => inferred_title,
+            };
+            let (rendered, summary) = exporter.render(&parsed, Some(&title), format)?;
+
+            if summary.total() > 0 {
+                eprintln!("redacted {} match(es): {:?}", summary.total(), summary.counts);
+            }
+
+            if save {
+                let path = write_session_export(&workspace.thoughts_dir(), &project, &session, format, &rendered)?;
+                println!("{}", path.display());
+            } else {
+                println!("{rendered}");
+            }
+        }
+        ClaudeCommand::Stats { session, project, max_tool_output_bytes } => {
+            let project = resolve_project_arg(project)?;
+            let path = session_file_path(&project, &session);
+            let options = ParseOptions { strict: false, max_tool_output_bytes };
+            let stats = SessionStatistics::compute(&path, options)?;
+
+            println!(
+                "{} message(s): {} user, {} assistant, {} system; {} file change(s); {} unparseable line(s); {} byte(s) of text",
+                stats.total_messages(),
+                stats.user_messages,
+                stats.assistant_messages,
+                stats.system_messages,
+                stats.file_changes,
+                stats.parse_issues,
+                stats.total_text_bytes
+            );
+        }
+        ClaudeCommand::List { project, include_agent_sessions, html, save } => {
+            let project = resolve_project_arg(project)?;
+            let titles = titles_path().map(TitleStore::load).transpose()?;
+            let now = snps_core::time::now_unix();
+
+            // Agent sessions are always included for an HTML report (they
+            // get their own collapsed section there) — `--include-agent-sessions`
+            // only controls whether the flat text/JSON listing shows them.
+            let rows: Vec<SessionRow> = list_sessions_for_project(&project)?
+                .iter()
+                .filter(|s| html || include_agent_sessions || !s.is_agent_session)
+                .map(|s| session_row(s, titles.as_ref(), now))
+                .collect();
+
+            if html {
+                let report_rows: Vec<_> = rows
+                    .iter()
+                    .map(|r| snps_core::claude::SessionReportRow {
+                        id: r.id.clone(),
+                        title: r.title.clone(),
+                        message_count: r.message_count,
+                        age: r.age.clone(),
+                        is_agent_session: r.is_agent_session,
+                    })
+                    .collect();
+                let rendered = snps_core::claude::render_sessions_report(&project.display().to_string(), &report_rows);
+                let path = save.expect("clap enforces --save with --html");
+                std::fs::write(&path, rendered)?;
+                println!("{}", path.display());
+                return Ok(());
+            }
+
+            OutputWriter::new(output).emit(&rows, |rows| {
+                if rows.is_empty() {
+                    println!("no sessions found for {}", project.display());
+                }
+                for row in rows {
+                    let marker = if row.is_agent_session { "  [agent]" } else { "" };
+                    println!("{}  {:<40} {:>5} msg(s)  {}{}", row.id, row.title, row.message_count, row.age, marker);
+                }
+            })?;
+        }
+        ClaudeCommand::Browse { project } => {
+            let project = resolve_project_arg(project)?;
+            if !crate::ui::stdout_is_terminal() {
+                eprintln!("stdout isn't a terminal; falling back to `snps claude list`");
+                return run(ClaudeCommand::List { project: Some(project), include_agent_sessions: false, html: false, save: None }, output);
+            }
+            browse::run(&project)?;
+        }
+    }
+    Ok(())
+}
+
+/// The `snps claude browse` TUI. Kept in its own module within this file
+/// (rather than a `Session`/list-command style separate file) since
+/// nothing else needs `ratatui`/`crossterm`/`arboard`.
+mod browse {
+    use super::*;
+    use crossterm::event::{Event, KeyCode, KeyEventKind};
+    use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+    use crossterm::{execute, ExecutableCommand};
+    use ratatui::style::Stylize;
+
+    type Backend = CrosstermBackend<std::io::Stdout>;
+
+    /// Terminals narrower than this get a single list pane instead of a
+    /// side-by-side split, so the layout never has to hand a widget a
+    /// negative-width area.
+    const MIN_WIDTH_FOR_PREVIEW: u16 = 80;
+
+    struct App {
+        project: PathBuf,
+        sessions: Vec<SessionSummary>,
+        show_agent_sessions: bool,
+        selected: usize,
+        last_export: Option<PathBuf>,
+        status: Option<String>,
+    }
+
+    impl App {
+        fn load(project: &Path) -> anyhow::Result<Self> {
+            Ok(App {
+                project: project.to_path_buf(),
+                sessions: list_sessions_for_project(project)?,
+                show_agent_sessions: false,
+                selected: 0,
+                last_export: None,
+                status: None,
+            })
+        }
+
+        fn visible(&self) -> Vec<&SessionSummary> {
+            self.sessions.iter().filter(|s| self.show_agent_sessions || !s.is_agent_session).collect()
+        }
+
+        fn selected_session(&self) -> Option<SessionSummary> {
+            self.visible().get(self.selected).map(|s| (*s).clone())
+        }
+
+        fn move_selection(&mut self, delta: i64) {
+            let count = self.visible().len();
+            if count == 0 {
+                self.selected = 0;
+                return;
+            }
+            let next = self.selected as i64 + delta;
+            self.selected = next.clamp(0, count as i64 - 1) as usize;
+        }
+
+        fn toggle_agent_sessions(&mut self) {
+            self.show_agent_sessions = !self.show_agent_sessions;
+            self.selected = 0;
+        }
+
+        fn export_selected(&mut self) {
+            self.status = Some(match self.try_export_selected() {
+                Ok(path) => {
+                    let message = format!("exported to {}", path.display());
+                    self.last_export = Some(path);
+                    message
+                }
+                Err(e) => format!("export failed: {e}"),
+            });
+        }
+
+        fn try_export_selected(&self) -> anyhow::Result<PathBuf> {
+            let session = self.selected_session().ok_or_else(|| anyhow::anyhow!("no session selected"))?;
+            let workspace = Workspace::discover_from_cwd()?;
+            let parsed = parse_session_file(&session.path)?;
+            let exporter = SessionExporter::new(true, Vec::new());
+            let (rendered, _) = exporter.render(&parsed, None, ExportFormat::Markdown)?;
+            Ok(write_session_export(&workspace.thoughts_dir(), &self.project, &session.id, ExportFormat::Markdown, &rendered)?)
+        }
+
+        fn open_last_export(&mut self) {
+            let Some(path) = self.last_export.clone() else {
+                self.status = Some("nothing exported yet; press 'e' first".to_string());
+                return;
+            };
+            let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+
+            // The editor needs the real terminal, not the alternate
+            // screen this TUI is drawing into.
+            let _ = disable_raw_mode();
+            let _ = std::io::stdout().execute(LeaveAlternateScreen);
+            let outcome = std::process::Command::new(&editor).arg(&path).status();
+            let _ = std::io::stdout().execute(EnterAlternateScreen);
+            let _ = enable_raw_mode();
+
+            self.status = Some(match outcome {
+                Ok(status) if status.success() => format!("opened {} in {editor}", path.display()),
+                Ok(status) => format!("{editor} exited with {status}"),
+                Err(e) => format!("could not launch {editor}: {e}"),
+            });
+        }
+
+        fn copy_selected_id(&mut self) {
+            let Some(session) = self.selected_session() else { return };
+            self.status = Some(match arboard::Clipboard::new().and_then(|mut c| c.set_text(session.id.clone())) {
+                Ok(()) => format!("copied session id {}", session.id),
+                Err(e) => format!("clipboard unavailable: {e}"),
+            });
+        }
+
+        fn draw(&self, frame: &mut ratatui::Frame) {
+            let area = frame.size();
+            let show_preview = area.width >= MIN_WIDTH_FOR_PREVIEW;
+            let columns = if show_preview {
+                Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+                    .split(area)
+            } else {
+                Layout::default().direction(Direction::Vertical).constraints([Constraint::Min(0)]).split(area)
+            };
+
+            let items: Vec<ListItem> = self
+                .visible()
+                .iter()
+                .enumerate()
+                .map(|(i, s)| {
+                    let marker = if s.is_agent_session { " [agent]" } else { "" };
+                    let line = format!("{}{marker}", s.id);
+                    if i == self.selected { ListItem::new(line).style(Style::default().add_modifier(Modifier::REVERSED)) } else { ListItem::new(line) }
+                })
+                .collect();
+            let list = List::new(items).block(Block::default().borders(Borders::ALL).title("sessions (a: toggle agent, q: quit)"));
+            frame.render_widget(list, columns[0]);
+
+            if show_preview {
+                let body = match self.selected_session() {
+                    Some(session) => match parse_session_file(&session.path) {
+                        Ok(parsed) => format!(
+                            "first user message:\n{}\n\nlast assistant message:\n{}",
+                            parsed.first_user_message().unwrap_or("(none)"),
+                            parsed.final_assistant_message().unwrap_or("(none)")
+                        ),
+                        Err(e) => format!("could not parse session: {e}"),
+                    },
+                    None => "no sessions".to_string(),
+                };
+                let title = self.status.clone().unwrap_or_else(|| "preview (e: export, o: open export, y: copy id)".to_string());
+                let paragraph = Paragraph::new(body).wrap(Wrap { trim: false }).block(Block::default().borders(Borders::ALL).title(title.bold()));
+                frame.render_widget(paragraph, columns[1]);
+            }
+        }
+    }
+
+    pub fn run(project: &Path) -> anyhow::Result<()> {
+        let mut app = App::load(project)?;
+
+        enable_raw_mode()?;
+        let mut stdout = std::io::stdout();
+        execute!(stdout, EnterAlternateScreen)?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+        let result = event_loop(&mut app, &mut terminal);
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+
+        result
+    }
+
+    fn event_loop(app: &mut App, terminal: &mut Terminal<Backend>) -> anyhow::Result<()> {
+        loop {
+            terminal.draw(|frame| app.draw(frame))?;
+
+            let Event::Key(key) = crossterm::event::read()? else { continue };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Down | KeyCode::Char('j') => app.move_selection(1),
+                KeyCode::Up | KeyCode::Char('k') => app.move_selection(-1),
+                KeyCode::Char('a') => app.toggle_agent_sessions(),
+                KeyCode::Char('e') => app.export_selected(),
+                KeyCode::Char('o') => app.open_last_export(),
+                KeyCode::Char('y') => app.copy_selected_id(),
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! `snps completions` and `snps manpages` — shell completion scripts and
+//! man pages generated straight from the clap command tree, so they can
+//! never drift from the actual CLI surface.
+//!
+//! clap's static completion can't see workspace state, so values like
+//! template names need a runtime helper: the hidden `snps
+//! __complete-templates` subcommand prints the current workspace's
+//! template names, one per line, and the generated bash script wraps
+//! clap's own completer to call out to it for `--template`/`--type`.
+//! The same helper backs zsh in principle, but wiring it into zsh's
+//! `_arguments`-based completer is deferred — it needs more than a
+//! drop-in wrapper function.
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::path::Path;
+
+pub fn run(shell: Shell) -> anyhow::Result<()> {
+    let mut cmd = crate::Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+
+    if shell == Shell::Bash {
+        print!("{BASH_DYNAMIC_TEMPLATE_WRAPPER}");
+    }
+
+    Ok(())
+}
+
+/// Print the active workspace's template names, one per line, for shell
+/// completion to `compgen -W` against. Silent (not an error) outside a
+/// workspace, since a completion helper shouldn't ever print to stderr
+/// mid-keystroke.
+pub fn run_complete_templates() -> anyhow::Result<()> {
+    if let Ok(workspace) = snps_core::Workspace::discover_from_cwd() {
+        for template in snps_core::templates::list_templates(&workspace) {
+            println!("{}", template.name);
+        }
+    }
+    Ok(())
+}
+
+const BASH_DYNAMIC_TEMPLATE_WRAPPER: &str = r#"
+# Dynamic completion for workspace-dependent values (template names),
+# layered on top of the static completer generated above.
+_snps_dynamic_wrapper() {
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    case "$prev" in
+        --template|--type)
+            COMPREPLY=( $(compgen -W "$(snps __complete-templates 2>/dev/null)" -- "${COMP_WORDS[COMP_CWORD]}") )
+            ;;
+        *)
+            _snps "$@"
+            ;;
+    esac
+}
+complete -F _snps_dynamic_wrapper -o bashdefault -o default snps
+"#;
+
+pub fn run_manpages(dir: &Path) -> anyhow::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let cmd = crate::Cli::command();
+    write_man_page(&cmd, dir, &[])
+}
+
+fn write_man_page(cmd: &clap::Command, dir: &Path, ancestors: &[String]) -> anyhow::Result<()> {
+    let mut name_parts = ancestors.to_vec();
+    name_parts.push(cmd.get_name().to_string());
+    let file_name = format!("{}.1", name_parts.join("-"));
+
+    let man = clap_mangen::Man::new(cmd.clone());
+    let mut buffer = Vec::new();
+    man.render(&mut buffer)?;
+    std::fs::write(dir.join(file_name), buffer)?;
+
+    for sub in cmd.get_subcommands() {
+        write_man_page(sub, dir, &name_parts)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,159 @@
+//! `snps config` — inspect and (eventually) edit layered configuration.
+
+use clap::Subcommand;
+use snps_core::config::{config_push, config_sync, get_value, load_merged_config, parse_value, set_value, validate_config, ConfigScope, PushOutcome};
+use snps_core::repository::Repository;
+use snps_core::Workspace;
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Print the merged configuration.
+    Show {
+        /// Also print which layer each value came from.
+        #[arg(long)]
+        source: bool,
+    },
+    /// Pull team/project config from the owning shadow repository.
+    Sync {
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Push local team-level config edits into the shadow repository.
+    Push {
+        #[arg(long)]
+        dry_run: bool,
+        #[arg(long)]
+        push: bool,
+        /// Sync the file into the shadow repo's working tree and print
+        /// its `git status --porcelain`-style state, without committing.
+        #[arg(long, conflicts_with = "dry_run")]
+        status_only: bool,
+    },
+    /// Read a single dotted key from one config scope.
+    Get {
+        key: String,
+        #[arg(long, default_value = "project")]
+        scope: String,
+    },
+    /// Write a single dotted key in one config scope.
+    Set {
+        key: String,
+        value: String,
+        #[arg(long, default_value = "project")]
+        scope: String,
+    },
+    /// Check every config layer for unknown keys and unusable paths.
+    Validate,
+}
+
+pub fn run(command: ConfigCommand) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+
+    match command {
+        ConfigCommand::Show { source } => {
+            let merged = load_merged_config(&workspace)?;
+            let c = &merged.config;
+            println!("defaults.editor = {}", c.defaults_editor);
+            println!(
+                "defaults.notify_after_seconds = {}",
+                c.notify_after_seconds.map(|s| s.to_string()).unwrap_or_else(|| "(unset)".to_string())
+            );
+            println!("search.index_db = {}", c.search_index_db);
+            println!("search.exclude_patterns = {:?}", c.search_exclude_patterns);
+            println!("llm.default_provider = {}", c.llm_default_provider);
+            println!("llm.api_key = {}", c.llm_api_key.as_ref().map(|_| "***").unwrap_or("(unset)"));
+            println!("repositories_root = {}", c.repositories_root);
+            println!("active_team = {}", c.active_team);
+            println!("require_share_review = {}", c.require_share_review);
+
+            if source {
+                println!();
+                let mut keys: Vec<_> = merged.sources.keys().collect();
+                keys.sort();
+                for key in keys {
+                    println!("{key} <- {}", merged.sources[key]);
+                }
+            }
+        }
+        ConfigCommand::Sync { dry_run } => {
+            let repos = Repository::load_all(&workspace)?;
+            let changed = config_sync(&workspace, &repos, dry_run)?;
+            if changed.is_empty() {
+                println!("already up to date");
+            }
+            for file in changed {
+                println!(
+                    "{}{}: {}",
+                    file.path.display(),
+                    if dry_run { " (dry run)" } else { "" },
+                    file.changed_keys.join(", ")
+                );
+            }
+        }
+        ConfigCommand::Push { dry_run, push, status_only } => {
+            let repos = Repository::load_all(&workspace)?;
+            match config_push(&workspace, &repos, dry_run, push, status_only)? {
+                PushOutcome::NoChanges => println!("no team config changes to push"),
+                PushOutcome::Status(lines) => {
+                    if lines.is_empty() {
+                        println!("nothing to commit");
+                    } else {
+                        for line in lines {
+                            println!("{line}");
+                        }
+                    }
+                }
+                PushOutcome::Pushed(file) => println!(
+                    "{}{}: {}",
+                    file.path.display(),
+                    if dry_run { " (dry run)" } else { "" },
+                    file.changed_keys.join(", ")
+                ),
+            }
+        }
+        ConfigCommand::Get { key, scope } => {
+            let scope: ConfigScope = scope.parse()?;
+            match get_value(&workspace, scope, &key)? {
+                Some(value) => println!("{}", render_yaml_value(&value)),
+                None => println!("(unset)"),
+            }
+        }
+        ConfigCommand::Set { key, value, scope } => {
+            let scope: ConfigScope = scope.parse()?;
+            let parsed = parse_value(&key, &value)?;
+            let old = set_value(&workspace, scope, &key, parsed.clone())?;
+            println!(
+                "{key}: {} -> {}",
+                old.as_ref().map(render_yaml_value).unwrap_or_else(|| "(unset)".to_string()),
+                render_yaml_value(&parsed)
+            );
+        }
+        ConfigCommand::Validate => {
+            let issues = validate_config(&workspace)?;
+            if issues.is_empty() {
+                println!("config is valid");
+                return Ok(());
+            }
+
+            let mut by_file: std::collections::BTreeMap<String, Vec<&str>> = std::collections::BTreeMap::new();
+            for issue in &issues {
+                by_file.entry(issue.file.display().to_string()).or_default().push(&issue.message);
+            }
+            for (file, messages) in by_file {
+                println!("{file}:");
+                for message in messages {
+                    println!("  {message}");
+                }
+            }
+            return Err(anyhow::anyhow!("{} config issue(s) found", issues.len()));
+        }
+    }
+    Ok(())
+}
+
+fn render_yaml_value(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+    }
+}
@@ -0,0 +1,318 @@
+//! `snps daemon` — run the HTTP API used by the desktop app and external
+//! agents. Fuller lifecycle management (`stop`, restart) lands with
+//! later daemon work; today `start` just runs in the foreground.
+
+use crate::output::{OutputFormat, OutputWriter};
+use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+use snps_core::graph::KnowledgeGraph;
+use snps_core::scheduler::JobRun;
+use snps_core::Workspace;
+
+#[derive(Subcommand)]
+pub enum DaemonCommand {
+    /// Run the HTTP API in the foreground.
+    Start {
+        #[arg(long, default_value_t = 4884)]
+        port: u16,
+        /// Name this daemon instance (`daemon-<profile>.pid` instead of
+        /// the default `daemon.pid`), so it can run alongside another
+        /// daemon serving the same workspace. Defaults to
+        /// `PMSYNAPSE_PROFILE` if set.
+        #[arg(long)]
+        profile: Option<String>,
+        /// Interface to bind to. Defaults to loopback; binding elsewhere
+        /// requires `PMSYNAPSE_DAEMON_TOKEN` to be set.
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+        /// Origin to allow via CORS, e.g. `http://localhost:5173` for a
+        /// browser-based client. Repeatable; omit for no cross-origin
+        /// access at all.
+        #[arg(long = "allow-origin")]
+        allow_origin: Vec<String>,
+    },
+    /// Report whether a daemon is running for this workspace.
+    Status {
+        /// Also open the local graph db to report its schema version.
+        #[arg(long)]
+        detailed: bool,
+        /// Check the named profile's PID file instead of the default.
+        /// Defaults to `PMSYNAPSE_PROFILE` if set.
+        #[arg(long)]
+        profile: Option<String>,
+    },
+    /// Print (and optionally follow) a profile's daemon log.
+    Logs {
+        /// Named profile's log instead of the default. Defaults to
+        /// `PMSYNAPSE_PROFILE` if set.
+        #[arg(long)]
+        profile: Option<String>,
+        /// Keep printing new lines as they're written, until interrupted
+        /// (Ctrl+C).
+        #[arg(long)]
+        follow: bool,
+        /// Only show lines from within this long ago, e.g. `30s`, `10m`,
+        /// `2h`, `1d`.
+        #[arg(long)]
+        since: Option<String>,
+        /// Only show lines at this level or more severe: `debug`, `info`,
+        /// `warn`, `error`. Per-request lines (method, path, status,
+        /// duration) are written at `debug`, so `--level debug` is what
+        /// shows HTTP traffic; lines written before levels existed never
+        /// match a filter.
+        #[arg(long)]
+        level: Option<String>,
+    },
+    /// Inspect and trigger `sync.schedules` background jobs. Requires a
+    /// running daemon — jobs only execute inside its background loop.
+    Jobs {
+        #[command(subcommand)]
+        command: JobsCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum JobsCommand {
+    /// Show every configured job's last run and failure streak.
+    List,
+    /// Run one job immediately, regardless of whether it's due.
+    Run { name: String },
+}
+
+#[derive(Serialize)]
+struct StatusReport {
+    running: bool,
+    port: Option<u16>,
+    pid: Option<u32>,
+    graph_schema_version: Option<u32>,
+}
+
+fn effective_profile(profile: Option<String>) -> Option<String> {
+    profile.or_else(|| std::env::var("PMSYNAPSE_PROFILE").ok())
+}
+
+pub fn run(command: DaemonCommand, output: OutputFormat) -> anyhow::Result<()> {
+    match command {
+        DaemonCommand::Start { port, profile, bind, allow_origin } => {
+            let workspace = Workspace::discover_from_cwd()?;
+            let profile = effective_profile(profile);
+            let bind: std::net::IpAddr = bind.parse().map_err(|_| anyhow::anyhow!("invalid --bind address '{bind}'"))?;
+            eprintln!("snps daemon listening on http://{bind}:{port}");
+            tokio::runtime::Runtime::new()?.block_on(snps_daemon::serve(workspace, port, profile, bind, allow_origin))?;
+        }
+        DaemonCommand::Status { detailed, profile } => {
+            let workspace = Workspace::discover_from_cwd()?;
+            let profile = effective_profile(profile);
+            let daemon = snps_daemon::pid::read_pid_file_for_profile(&workspace.pmsynapse_dir(), profile.as_deref());
+
+            let graph_schema_version = if detailed {
+                KnowledgeGraph::init(&workspace.graph_db_path()).and_then(|g| g.schema_version()).ok()
+            } else {
+                None
+            };
+
+            let report = StatusReport {
+                running: daemon.is_some(),
+                port: daemon.as_ref().map(|d| d.port),
+                pid: daemon.as_ref().map(|d| d.pid),
+                graph_schema_version,
+            };
+
+            OutputWriter::new(output).emit(&report, |r| {
+                match (r.running, r.port, r.pid) {
+                    (true, Some(port), Some(pid)) => println!("daemon running on http://127.0.0.1:{port} (pid {pid})"),
+                    _ => println!("daemon not running"),
+                }
+                if detailed {
+                    match r.graph_schema_version {
+                        Some(v) => println!("graph schema version: {v}"),
+                        None => println!("graph: unavailable"),
+                    }
+                }
+            })?;
+        }
+        DaemonCommand::Logs { profile, follow, since, level } => {
+            let workspace = Workspace::discover_from_cwd()?;
+            let profile = effective_profile(profile);
+            let path = snps_daemon::logging::log_file_path(&workspace.pmsynapse_dir(), profile.as_deref());
+
+            let since_threshold = since
+                .map(|s| {
+                    snps_daemon::logging::parse_duration(&s)
+                        .ok_or_else(|| anyhow::anyhow!("invalid --since duration '{s}' (expected e.g. 30s, 10m, 2h, 1d)"))
+                })
+                .transpose()?
+                .map(|d| snps_core::time::now_unix().saturating_sub(d.as_secs()));
+            let level_threshold = level.map(|l| l.parse::<snps_daemon::logging::Level>()).transpose().map_err(|e| anyhow::anyhow!(e))?;
+
+            if !path.exists() {
+                let label = profile.as_deref().unwrap_or("default");
+                println!("no logs yet for profile '{label}' ({})", path.display());
+                if !follow {
+                    return Ok(());
+                }
+            } else {
+                print_existing_lines(&path, since_threshold, level_threshold)?;
+            }
+
+            if follow {
+                follow_log(&path, level_threshold)?;
+            }
+        }
+        DaemonCommand::Jobs { command } => run_jobs(command, output)?,
+    }
+    Ok(())
+}
+
+/// Mirrors `snps_daemon`'s (private) `JobStatusRow` response shape — the
+/// daemon crate doesn't export it, so this is the CLI's own copy of the
+/// wire format, same as `graph.rs` deserializes `GraphExport` responses
+/// without importing daemon-internal types.
+#[derive(Deserialize, Serialize)]
+struct JobStatusRow {
+    name: String,
+    operation: String,
+    interval: String,
+    last_run: Option<JobRun>,
+    consecutive_failures: u32,
+}
+
+fn require_daemon(workspace: &Workspace) -> anyhow::Result<snps_daemon::pid::DaemonPid> {
+    snps_daemon::pid::read_pid_file(&workspace.pmsynapse_dir())
+        .ok_or_else(|| anyhow::anyhow!("no daemon running for this workspace — scheduled jobs only run inside `snps daemon start`"))
+}
+
+fn run_jobs(command: JobsCommand, output: OutputFormat) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+    let daemon = require_daemon(&workspace)?;
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    match command {
+        JobsCommand::List => {
+            let rows: Vec<JobStatusRow> = runtime.block_on(list_jobs_via_daemon(daemon.port, &workspace.root))?;
+            OutputWriter::new(output).emit(&rows, |rows| {
+                if rows.is_empty() {
+                    println!("no scheduled jobs configured (see `sync.schedules` in config.yaml)");
+                }
+                for row in rows {
+                    let last = match &row.last_run {
+                        Some(run) => format!("{:?} {}ms ago", run.outcome, run.duration_ms),
+                        None => "never run".to_string(),
+                    };
+                    println!("{}\t{}\t{}\t{last}\t{} consecutive failure(s)", row.name, row.operation, row.interval, row.consecutive_failures);
+                }
+            })?;
+        }
+        JobsCommand::Run { name } => {
+            let run: JobRun = runtime.block_on(run_job_via_daemon(daemon.port, &workspace.root, &name))?;
+            OutputWriter::new(output).emit(&run, |run| {
+                println!("{name}: {:?}{}", run.outcome, run.error.as_ref().map(|e| format!(" ({e})")).unwrap_or_default());
+            })?;
+        }
+    }
+    Ok(())
+}
+
+async fn list_jobs_via_daemon(port: u16, workspace_root: &std::path::Path) -> anyhow::Result<Vec<JobStatusRow>> {
+    let response = reqwest::Client::new()
+        .get(format!("http://127.0.0.1:{port}/api/v1/jobs"))
+        .header(snps_daemon::PROJECT_HEADER, workspace_root.to_string_lossy().into_owned())
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("daemon rejected the jobs request: {body}");
+    }
+    Ok(response.json().await?)
+}
+
+async fn run_job_via_daemon(port: u16, workspace_root: &std::path::Path, name: &str) -> anyhow::Result<JobRun> {
+    let response = reqwest::Client::new()
+        .post(format!("http://127.0.0.1:{port}/api/v1/jobs/{name}/run"))
+        .header(snps_daemon::PROJECT_HEADER, workspace_root.to_string_lossy().into_owned())
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("daemon rejected running job '{name}': {body}");
+    }
+    Ok(response.json().await?)
+}
+
+/// Whether a log line should be shown under `threshold` (`None` means no
+/// `--level` filter was given). Lines from before levels existed, or that
+/// otherwise don't parse, are dropped once a filter is active — same
+/// "don't match rather than error" treatment `line_timestamp` gets for
+/// `--since`.
+fn passes_level_filter(line: &str, threshold: Option<snps_daemon::logging::Level>) -> bool {
+    match threshold {
+        None => true,
+        Some(min) => snps_daemon::logging::line_level(line).is_some_and(|level| level >= min),
+    }
+}
+
+fn print_existing_lines(path: &std::path::Path, since: Option<u64>, level: Option<snps_daemon::logging::Level>) -> anyhow::Result<()> {
+    let contents = std::fs::read_to_string(path)?;
+    for line in contents.lines() {
+        if !passes_level_filter(line, level) {
+            continue;
+        }
+        match since {
+            Some(threshold) if !snps_daemon::logging::line_timestamp(line).is_some_and(|ts| ts >= threshold) => {}
+            _ => println!("{line}"),
+        }
+    }
+    Ok(())
+}
+
+/// Poll the log file for growth, printing whatever was appended since the
+/// last check. A shrinking length means the file was truncated or
+/// replaced (log rotation) — reopen from the start rather than treating
+/// it as an error. Runs until interrupted; there's no state to clean up
+/// beyond the open file handle, so the platform's default Ctrl+C handling
+/// (process termination) already exits cleanly.
+///
+/// With no `--level` filter, appended bytes are printed raw as they
+/// arrive, same as before this flag existed. With a filter, printing has
+/// to happen line-by-line instead so unwanted lines can be dropped, which
+/// means a trailing partial line (no `\n` yet) is held back until the
+/// rest of it arrives on a later poll rather than printed early.
+fn follow_log(path: &std::path::Path, level: Option<snps_daemon::logging::Level>) -> anyhow::Result<()> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut pos = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    loop {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+
+        let Ok(metadata) = std::fs::metadata(path) else {
+            // The directory or file disappeared; wait for it to reappear
+            // rather than exiting follow mode.
+            pos = 0;
+            continue;
+        };
+
+        if metadata.len() < pos {
+            pos = 0;
+        }
+        if metadata.len() == pos {
+            continue;
+        }
+
+        let Ok(mut file) = std::fs::File::open(path) else { continue };
+        file.seek(SeekFrom::Start(pos))?;
+        let mut buf = String::new();
+        file.read_to_string(&mut buf)?;
+
+        match level {
+            None => print!("{buf}"),
+            Some(min) => {
+                for line in buf.lines() {
+                    if passes_level_filter(line, Some(min)) {
+                        println!("{line}");
+                    }
+                }
+            }
+        }
+        pos = metadata.len();
+    }
+}
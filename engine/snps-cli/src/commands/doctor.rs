@@ -0,0 +1,85 @@
+//! `snps doctor` — run environment diagnostics and report pass/warn/fail
+//! per check, with a suggested fix for anything that isn't a clean pass.
+//! Each check's decision logic lives in `snps_core::doctor` so the
+//! desktop app's first-run wizard can reuse it.
+
+use snps_core::doctor::{self, CheckStatus, DoctorCheck};
+use snps_core::Workspace;
+use std::path::PathBuf;
+
+pub fn run() -> anyhow::Result<()> {
+    let mut checks = Vec::new();
+
+    match home_dir() {
+        Some(home) => {
+            checks.push(doctor::check_directory_writable("home directory", &home));
+            let pmsynapse = home.join(".pmsynapse");
+            checks.push(doctor::check_pmsynapse_layout(pmsynapse.exists(), pmsynapse.is_dir(), &pmsynapse));
+            checks.push(doctor::check_symlink_capability(&home));
+        }
+        None => checks.push(missing_home_check()),
+    }
+
+    for tool in ["git", "rg", "pnpm", "tail"] {
+        checks.push(doctor::check_optional_tool(tool, doctor::tool_on_path(tool)));
+    }
+
+    match Workspace::discover_from_cwd() {
+        Ok(workspace) => {
+            let pid_file = snps_daemon::pid::DaemonPidFile::new(&workspace.pmsynapse_dir(), None);
+            let recorded = pid_file.read();
+            let alive = pid_file.is_live();
+            checks.push(doctor::check_daemon_pid_consistency(recorded.map(|d| d.pid), alive));
+
+            let open_result = snps_core::graph::KnowledgeGraph::init(&workspace.graph_db_path())
+                .map(|_| ())
+                .map_err(|e| e.to_string());
+            checks.push(doctor::check_graph_db_openable(open_result));
+
+            let issues = snps_core::config::validate_config(&workspace);
+            checks.extend(doctor::check_config_validation(issues.as_deref().map_err(|e| e.to_string())));
+        }
+        Err(_) => {
+            // Outside a workspace, only environment-level checks apply;
+            // workspace-scoped checks (daemon, graph db, config) are
+            // skipped rather than reported as failures.
+        }
+    }
+
+    print_report(&checks);
+
+    if let Some(check) = checks.iter().find(|c| c.status == CheckStatus::Fail) {
+        return Err(crate::error::CliError::Validation(format!("{}: {}", check.name, check.message)).into());
+    }
+    Ok(())
+}
+
+fn print_report(checks: &[DoctorCheck]) {
+    for check in checks {
+        let symbol = match check.status {
+            CheckStatus::Pass => "pass",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "fail",
+        };
+        println!("[{symbol}] {}: {}", check.name, check.message);
+        if let Some(fix) = &check.fix {
+            println!("       fix: {fix}");
+        }
+    }
+}
+
+fn missing_home_check() -> DoctorCheck {
+    // Constructing this directly (rather than through snps_core::doctor)
+    // since it doesn't need a stable check name or fix on this axis; the
+    // HOME environment variable itself is the whole story.
+    DoctorCheck {
+        name: "home directory".to_string(),
+        status: CheckStatus::Fail,
+        message: "HOME is not set".to_string(),
+        fix: Some("set the HOME environment variable".to_string()),
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
@@ -0,0 +1,280 @@
+//! `snps graph` — bulk import/export, backup, and restore of the
+//! knowledge graph.
+
+use clap::Subcommand;
+use snps_core::config::load_merged_config;
+use snps_core::embeddings::{embedder_for, Embedder};
+use snps_core::graph::{
+    backfill_missing, export_vault, ingest_markdown_to_graph, search_similar, GraphExport, KnowledgeGraph, NodeType, Provenance, ProvenanceSource,
+};
+use snps_core::Workspace;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum GraphCommand {
+    /// Bulk-load nodes and edges from a graph export file, in one
+    /// transaction. Goes through the daemon if one is running for this
+    /// workspace, otherwise writes directly to the local db.
+    Import { file: PathBuf },
+    /// Write a consistent point-in-time snapshot of the graph to `path`,
+    /// as a schema-versioned JSON dump. Goes through the daemon if one is
+    /// running, so the snapshot can't land mid-write; otherwise reads the
+    /// local db directly.
+    Backup { path: PathBuf },
+    /// Restore a snapshot produced by `backup` into the local database.
+    /// Refuses to touch a non-empty database unless `--force`, which
+    /// wipes it first.
+    Restore {
+        path: PathBuf,
+        #[arg(long)]
+        force: bool,
+    },
+    /// Chunk a markdown file (or every `.md` file under a directory) by
+    /// heading and map each chunk to a `Document` node, finer-grained
+    /// than `snps sync`'s one-node-per-file mapping. Like `sync` and
+    /// `analyze`, this always writes to the local db directly — it isn't
+    /// routed through the daemon.
+    Ingest {
+        path: PathBuf,
+        /// Only split on headings at or above this depth (an `h1` is
+        /// depth 1); deeper headings stay inside their parent chunk.
+        #[arg(long, default_value_t = 2)]
+        max_depth: u32,
+        /// Further split a section larger than this many bytes on
+        /// paragraph breaks. `0` means no limit.
+        #[arg(long, default_value_t = 4000)]
+        max_chunk_size: usize,
+        /// Print chunk counts without writing anything to the graph.
+        #[arg(long)]
+        stats_only: bool,
+    },
+    /// Backfill embeddings for nodes that don't have one yet. There's no
+    /// automatic embedding on node write today (see
+    /// `snps_core::graph::embed`'s doc comment), so this is how a
+    /// workspace's embeddings actually get kept current.
+    Embed {
+        /// The only mode so far — embed every node missing one.
+        #[arg(long)]
+        missing: bool,
+        /// Use the deterministic offline embedder instead of the
+        /// configured LLM provider. Produces vectors with no real
+        /// semantic meaning; useful for trying the feature out (or for
+        /// CI) without an API key.
+        #[arg(long)]
+        offline: bool,
+    },
+    /// Find nodes whose embedding is closest to `query`, nearest first.
+    /// Requires embeddings to already exist (see `snps graph embed`) —
+    /// nodes with none are simply never returned.
+    Similar {
+        query: String,
+        #[arg(long, default_value_t = 10)]
+        k: usize,
+        #[arg(long)]
+        node_type: Option<String>,
+        #[arg(long)]
+        offline: bool,
+    },
+    /// Export the graph as an Obsidian-compatible vault: one markdown
+    /// file per node plus an index note per node type. Re-running
+    /// against the same directory only rewrites files that changed and
+    /// removes files for nodes no longer in the graph, so this is safe
+    /// to run on a schedule to keep a vault in sync.
+    ExportVault { dir: PathBuf },
+}
+
+pub fn run(command: GraphCommand) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+
+    match command {
+        GraphCommand::Import { file } => {
+            let contents = std::fs::read_to_string(&file)?;
+            let mut export: GraphExport = serde_json::from_str(&contents)?;
+            let node_count = export.nodes.len();
+            let edge_count = export.edges.len();
+
+            match snps_daemon::pid::read_pid_file(&workspace.pmsynapse_dir()) {
+                Some(daemon) => {
+                    let runtime = tokio::runtime::Runtime::new()?;
+                    runtime.block_on(import_via_daemon(daemon.port, &workspace.root, &export))?;
+                    println!("imported {node_count} node(s), {edge_count} edge(s) via daemon on port {}", daemon.port);
+                }
+                None => {
+                    // No daemon (and so no `x-pmsynapse-client` header) to
+                    // derive provenance from — stamp it here instead, so a
+                    // direct import behaves the same as one routed through
+                    // the daemon.
+                    for node in &mut export.nodes {
+                        if node.provenance.is_none() {
+                            node.provenance = Some(Provenance::new(ProvenanceSource::Cli, "snps graph import", env!("CARGO_PKG_VERSION")));
+                        }
+                    }
+                    let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+                    graph.add_batch(&export.nodes, &export.edges)?;
+                    println!("imported {node_count} node(s), {edge_count} edge(s) directly into {}", workspace.graph_db_path().display());
+                }
+            }
+        }
+        GraphCommand::Backup { path } => {
+            let export = match snps_daemon::pid::read_pid_file(&workspace.pmsynapse_dir()) {
+                Some(daemon) => {
+                    let runtime = tokio::runtime::Runtime::new()?;
+                    runtime.block_on(backup_via_daemon(daemon.port, &workspace.root))?
+                }
+                None => {
+                    let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+                    GraphExport::dump(&graph)?
+                }
+            };
+            std::fs::write(&path, serde_json::to_string_pretty(&export)?)?;
+            println!("backed up {} node(s), {} edge(s) to {}", export.nodes.len(), export.edges.len(), path.display());
+        }
+        GraphCommand::Restore { path, force } => {
+            let contents = std::fs::read_to_string(&path)?;
+            let export: GraphExport = serde_json::from_str(&contents)?;
+
+            match snps_daemon::pid::read_pid_file(&workspace.pmsynapse_dir()) {
+                Some(daemon) => {
+                    let runtime = tokio::runtime::Runtime::new()?;
+                    runtime.block_on(restore_via_daemon(daemon.port, &workspace.root, &export, force))?;
+                }
+                None => {
+                    let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+                    export.restore(&graph, force)?;
+                }
+            }
+            println!("restored {} node(s), {} edge(s) from {}", export.nodes.len(), export.edges.len(), path.display());
+        }
+        GraphCommand::Ingest { path, max_depth, max_chunk_size, stats_only } => {
+            let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+            let stats = ingest_markdown_to_graph(&graph, &workspace.root, &path, max_depth, max_chunk_size, stats_only)?;
+
+            if stats_only {
+                println!(
+                    "{} document(s), {} chunk(s) to add, {} to update, {} to orphan, {} unchanged (stats only, nothing written)",
+                    stats.documents, stats.added, stats.updated, stats.orphaned, stats.unchanged
+                );
+            } else {
+                println!(
+                    "ingested {} document(s): {} chunk(s) added, {} updated, {} orphaned, {} unchanged",
+                    stats.documents, stats.added, stats.updated, stats.orphaned, stats.unchanged
+                );
+            }
+        }
+        GraphCommand::Embed { missing, offline } => {
+            if !missing {
+                anyhow::bail!("`snps graph embed` currently only supports `--missing`");
+            }
+            let embedder = resolve_embedder(&workspace, offline)?;
+            let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+            let runtime = tokio::runtime::Runtime::new()?;
+            let embedded = runtime.block_on(backfill_missing(&graph, embedder.as_ref()))?;
+            println!("embedded {embedded} node(s) using '{}'", embedder.name());
+        }
+        GraphCommand::Similar { query, k, node_type, offline } => {
+            let node_type: Option<NodeType> = node_type.as_deref().map(str::parse).transpose()?;
+            let embedder = resolve_embedder(&workspace, offline)?;
+            let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+            let runtime = tokio::runtime::Runtime::new()?;
+            let results = runtime.block_on(search_similar(&graph, embedder.as_ref(), &query, k, node_type))?;
+            if results.is_empty() {
+                println!("no similar nodes found (has `snps graph embed --missing` been run?)");
+            } else {
+                for (node, distance) in results {
+                    println!("{:.4}  {}  {}", distance, node.id, node.title);
+                }
+            }
+        }
+        GraphCommand::ExportVault { dir } => {
+            let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+            let stats = export_vault(&graph, &dir)?;
+            println!(
+                "vault at {}: {} created, {} updated, {} unchanged, {} deleted",
+                dir.display(),
+                stats.created,
+                stats.updated,
+                stats.unchanged,
+                stats.deleted
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve an embedder for `graph embed`/`graph similar`: the offline
+/// hash embedder if asked for, otherwise whatever `llm.default_provider`
+/// is configured with an api key for — the same resolution `snps llm
+/// test` uses, since embeddings and completions share the one configured
+/// provider slot.
+fn resolve_embedder(workspace: &Workspace, offline: bool) -> anyhow::Result<Box<dyn Embedder>> {
+    if offline {
+        return Ok(embedder_for("hash", None)?);
+    }
+    let merged = load_merged_config(workspace)?;
+    let api_key = merged
+        .config
+        .llm_api_key
+        .clone()
+        .or_else(|| std::env::var(env_var_for(&merged.config.llm_default_provider)).ok());
+    Ok(embedder_for(&merged.config.llm_default_provider, api_key)?)
+}
+
+fn env_var_for(provider: &str) -> &'static str {
+    match provider {
+        "openai" => "OPENAI_API_KEY",
+        _ => "ANTHROPIC_API_KEY",
+    }
+}
+
+async fn import_via_daemon(port: u16, workspace_root: &std::path::Path, export: &GraphExport) -> anyhow::Result<()> {
+    // Tells the daemon to credit any node in this batch that arrives
+    // without its own provenance to the CLI, rather than a bare
+    // "daemon-api" source.
+    let response = reqwest::Client::new()
+        .post(format!("http://127.0.0.1:{port}/api/v1/graph/batch"))
+        .header("x-pmsynapse-client", "cli")
+        .header(snps_daemon::PROJECT_HEADER, workspace_root.to_string_lossy().into_owned())
+        .json(export)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("daemon rejected the batch import: {body}");
+    }
+
+    Ok(())
+}
+
+async fn backup_via_daemon(port: u16, workspace_root: &std::path::Path) -> anyhow::Result<GraphExport> {
+    let response = reqwest::Client::new()
+        .post(format!("http://127.0.0.1:{port}/api/v1/graph/backup"))
+        .header(snps_daemon::PROJECT_HEADER, workspace_root.to_string_lossy().into_owned())
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("daemon rejected the backup request: {body}");
+    }
+
+    Ok(response.json().await?)
+}
+
+async fn restore_via_daemon(port: u16, workspace_root: &std::path::Path, export: &GraphExport, force: bool) -> anyhow::Result<()> {
+    let body = serde_json::json!({ "export": export, "force": force });
+    let response = reqwest::Client::new()
+        .post(format!("http://127.0.0.1:{port}/api/v1/graph/restore"))
+        .header(snps_daemon::PROJECT_HEADER, workspace_root.to_string_lossy().into_owned())
+        .json(&body)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!("daemon rejected the restore request: {body}");
+    }
+
+    Ok(())
+}
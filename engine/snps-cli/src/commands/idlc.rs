@@ -0,0 +1,350 @@
+//! `snps idlc` — inspect the active team's IDLC stage/status
+//! configuration, render it as a status report, and manage item links to
+//! graph nodes, matter documents, and thoughts. Bulk board commands
+//! (create-from-template, stage transitions with history) still land with
+//! their own dedicated backlog item.
+
+use crate::output::{OutputFormat, OutputWriter};
+use clap::Subcommand;
+use serde::Serialize;
+use snps_core::graph::{Edge, EdgeType, KnowledgeGraph};
+use snps_core::idlc::{github_import, HttpGithubClient, IdlcItemStore, ItemLink, LinkKind};
+use snps_core::matter::MatterIndex;
+use snps_core::team::active_team_id;
+use snps_core::thoughts::thought_title;
+use snps_core::Workspace;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum IdlcCommand {
+    /// Parse the active team's `idlc.yaml` and report whether it's valid.
+    Validate,
+    /// Render a status report for a team's IDLC board. This still renders
+    /// the stage diagram only — a per-item breakdown (grouping by stage,
+    /// honoring `since`/`stuck_after_days`) is separate work for once
+    /// board commands exist to populate item stage/status in bulk.
+    Report {
+        /// Team id; defaults to the active team.
+        #[arg(long)]
+        team: Option<String>,
+        #[arg(long)]
+        output: PathBuf,
+        #[arg(long, default_value = "markdown")]
+        format: String,
+        /// Reserved for once item storage exists: only items moved since
+        /// this date would be highlighted. Accepted now so the flag
+        /// doesn't need to change when that lands.
+        #[arg(long)]
+        since: Option<String>,
+        /// Reserved for the same reason: flag items that have sat in a
+        /// stage longer than this many days.
+        #[arg(long)]
+        stuck_after_days: Option<u32>,
+    },
+    /// Link an IDLC item to a graph node, matter document, or thought.
+    /// Linking to a node also adds an `implements` edge from a synthetic
+    /// `idlc:<team>:<item>` id, so graph traversal sees the connection.
+    Link {
+        /// Team id; defaults to the active team.
+        #[arg(long)]
+        team: Option<String>,
+        item: String,
+        #[arg(long)]
+        node: Option<String>,
+        #[arg(long)]
+        matter: Option<String>,
+        /// Path to the thought file, relative to the workspace's thoughts
+        /// directory.
+        #[arg(long)]
+        thought: Option<String>,
+    },
+    /// Show an item and the titles of anything it's linked to.
+    Show {
+        /// Team id; defaults to the active team.
+        #[arg(long)]
+        team: Option<String>,
+        item: String,
+    },
+    /// Import items from an external backlog.
+    Import {
+        #[command(subcommand)]
+        command: ImportCommand,
+    },
+    /// Preview and apply an `idlc.yaml`, e.g. one regenerated from a
+    /// newer template, in place of the active team's current one. Shows
+    /// what would change and refuses to overwrite when a stage or status
+    /// disappears out from under items still sitting in it, unless
+    /// `--force` or `--map` accounts for every one of them.
+    Apply {
+        /// Team id; defaults to the active team.
+        #[arg(long)]
+        team: Option<String>,
+        file: PathBuf,
+        /// Apply even if items would be orphaned by a removed status.
+        #[arg(long)]
+        force: bool,
+        /// Reassign items in a status this file removes to another
+        /// status, e.g. `--map archived=done`. Repeatable.
+        #[arg(long = "map", value_name = "OLD=NEW")]
+        map: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ImportCommand {
+    /// Pull issues from a GitHub repository and upsert them as IDLC
+    /// items. Re-running is safe: an issue already imported (matched by
+    /// its number) is updated rather than duplicated. Label -> status
+    /// mapping is read from `teams/<team>/github-import.yaml`.
+    Github {
+        /// Team id; defaults to the active team.
+        #[arg(long)]
+        team: Option<String>,
+        #[arg(long)]
+        repo: String,
+        #[arg(long)]
+        label: Option<String>,
+        /// Environment variable holding the GitHub API token.
+        #[arg(long, default_value = "GITHUB_TOKEN")]
+        token_env: String,
+        /// `open`, `closed`, or `all`, passed straight through to GitHub.
+        #[arg(long, default_value = "open")]
+        state: String,
+        /// Fetch and map issues without writing the item store.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Serialize)]
+struct ValidateReport {
+    team: String,
+    stages: Vec<String>,
+    statuses: Vec<String>,
+    transition_count: usize,
+}
+
+pub fn run(command: IdlcCommand, output: OutputFormat) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+
+    match command {
+        IdlcCommand::Validate => {
+            let team = active_team_id(&workspace);
+            let idlc_path = workspace.teams_dir().join(&team).join("idlc.yaml");
+            let contents = std::fs::read_to_string(&idlc_path)
+                .map_err(|_| crate::error::CliError::Config(format!("no idlc.yaml for team '{team}' at {}", idlc_path.display())))?;
+            let config = snps_core::idlc::parse_idlc_config(&contents)?;
+            if config.stages.is_empty() {
+                return Err(crate::error::CliError::Validation(format!("idlc.yaml for team '{team}' has no stages")).into());
+            }
+
+            let report = ValidateReport {
+                team,
+                stages: config.stages,
+                statuses: config.statuses,
+                transition_count: config.transitions.len(),
+            };
+            OutputWriter::new(output).emit(&report, |r| {
+                println!("team '{}' idlc.yaml is valid", r.team);
+                println!("stages: {}", r.stages.join(", "));
+                println!("transitions: {}", r.transition_count);
+            })?;
+        }
+        IdlcCommand::Report { team, output, format, since: _, stuck_after_days: _ } => {
+            let team = team.unwrap_or_else(|| active_team_id(&workspace));
+            let idlc_path = workspace.teams_dir().join(&team).join("idlc.yaml");
+            let contents = std::fs::read_to_string(&idlc_path)
+                .map_err(|_| anyhow::anyhow!("no idlc.yaml for team '{team}' at {}", idlc_path.display()))?;
+            let config = snps_core::idlc::parse_idlc_config(&contents)?;
+            let diagram = snps_core::idlc::idlc_visualize(&config);
+
+            let rendered = match format.as_str() {
+                "markdown" | "md" => render_report_markdown(&team, &diagram),
+                "html" => render_report_html(&team, &diagram),
+                other => anyhow::bail!("unknown --format '{other}' (expected markdown or html)"),
+            };
+            std::fs::write(&output, rendered)?;
+            println!("wrote {}", output.display());
+        }
+        IdlcCommand::Link { team, item, node, matter, thought } => {
+            let team = team.unwrap_or_else(|| active_team_id(&workspace));
+            let mut store = IdlcItemStore::load(&workspace, &team)?;
+            store.get(&item).ok_or_else(|| crate::error::CliError::NotFound(format!("no IDLC item '{item}' for team '{team}'")))?;
+
+            let links = [
+                node.map(|id| ItemLink { kind: LinkKind::Node, id }),
+                matter.map(|id| ItemLink { kind: LinkKind::Matter, id }),
+                thought.map(|id| ItemLink { kind: LinkKind::Thought, id }),
+            ];
+            let links: Vec<ItemLink> = links.into_iter().flatten().collect();
+            if links.is_empty() {
+                return Err(crate::error::CliError::Usage("pass at least one of --node, --matter, or --thought".to_string()).into());
+            }
+
+            for link in links {
+                if link.kind == LinkKind::Node {
+                    let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+                    let synthetic_item_id = format!("idlc:{team}:{item}");
+                    graph.add_edge(&Edge::new(synthetic_item_id, link.id.clone(), EdgeType::Implements))?;
+                }
+                store.add_link(&item, link)?;
+            }
+            store.save(&workspace, &team)?;
+            println!("linked item '{item}'");
+        }
+        IdlcCommand::Show { team, item } => {
+            let team = team.unwrap_or_else(|| active_team_id(&workspace));
+            let store = IdlcItemStore::load(&workspace, &team)?;
+            let item = store.get(&item).ok_or_else(|| crate::error::CliError::NotFound(format!("no IDLC item '{item}' for team '{team}'")))?;
+
+            println!("{} ({}/{})", item.title, item.stage, item.status);
+            if item.links.is_empty() {
+                println!("no links");
+            } else {
+                for link in &item.links {
+                    let title = resolve_link_title(&workspace, link);
+                    println!("- {}: {} ({})", link.kind, title.as_deref().unwrap_or("<unresolved>"), link.id);
+                }
+            }
+        }
+        IdlcCommand::Import { command: ImportCommand::Github { team, repo, label, token_env, state, dry_run } } => {
+            let team = team.unwrap_or_else(|| active_team_id(&workspace));
+            let token = std::env::var(&token_env).map_err(|_| anyhow::anyhow!("environment variable '{token_env}' is not set"))?;
+            let client = HttpGithubClient::new(token);
+
+            let runtime = tokio::runtime::Runtime::new()?;
+            let summary = runtime.block_on(github_import::import_github_issues(
+                &workspace,
+                &team,
+                &client,
+                github_import::ImportOptions { repo: &repo, label: label.as_deref(), state: &state, dry_run },
+            ))?;
+
+            if dry_run {
+                println!("would create {} item(s), update {} item(s)", summary.created, summary.updated);
+            } else {
+                println!("created {} item(s), updated {} item(s)", summary.created, summary.updated);
+            }
+        }
+        IdlcCommand::Apply { team, file, force, map } => {
+            let team = team.unwrap_or_else(|| active_team_id(&workspace));
+            let status_map = parse_status_map(&map)?;
+
+            let idlc_path = workspace.teams_dir().join(&team).join("idlc.yaml");
+            let old = match std::fs::read_to_string(&idlc_path) {
+                Ok(contents) => snps_core::idlc::parse_idlc_config(&contents)?,
+                Err(_) => snps_core::idlc::IdlcConfig { stages: vec![], statuses: vec![], transitions: vec![] },
+            };
+
+            let new_contents = std::fs::read_to_string(&file)
+                .map_err(|e| anyhow::anyhow!("failed to read {}: {e}", file.display()))?;
+            let new = snps_core::idlc::parse_idlc_config(&new_contents)?;
+            let issues = snps_core::idlc::validate_references(&new);
+            if !issues.is_empty() {
+                anyhow::bail!("{} has invalid transitions:\n  {}", file.display(), issues.join("\n  "));
+            }
+
+            let diff = snps_core::idlc::diff_idlc_configs(&old, &new);
+            print_diff(&diff);
+
+            let mut store = IdlcItemStore::load(&workspace, &team)?;
+            let orphaned = snps_core::idlc::orphaned_items(&store, &diff);
+            let unmapped: Vec<_> = orphaned.iter().filter(|o| !status_map.contains_key(&o.status)).collect();
+
+            if !unmapped.is_empty() && !force {
+                println!("\n{} item(s) sit in a status this file removes:", unmapped.len());
+                for item in &unmapped {
+                    println!("  {} ({}): {}", item.id, item.status, item.title);
+                }
+                anyhow::bail!("re-run with --force, or --map <status>=<new status> for each one, to apply anyway");
+            }
+
+            for item in &orphaned {
+                if let Some(new_status) = status_map.get(&item.status) {
+                    if let Some(stored) = store.items.iter_mut().find(|i| i.id == item.id) {
+                        stored.status = new_status.clone();
+                    }
+                }
+            }
+            store.save(&workspace, &team)?;
+
+            std::fs::write(&idlc_path, &new_contents)?;
+            println!("\napplied {} to team '{}'", file.display(), team);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse `--map old=new` flags into a lookup from old status to new.
+fn parse_status_map(map: &[String]) -> anyhow::Result<std::collections::HashMap<String, String>> {
+    map.iter()
+        .map(|entry| {
+            entry
+                .split_once('=')
+                .map(|(old, new)| (old.trim().to_string(), new.trim().to_string()))
+                .ok_or_else(|| anyhow::anyhow!("--map expects OLD=NEW, got '{entry}'"))
+        })
+        .collect()
+}
+
+fn print_diff(diff: &snps_core::idlc::IdlcConfigDiff) {
+    let mut changed = false;
+    let mut show = |label: &str, names: &[String]| {
+        if !names.is_empty() {
+            changed = true;
+            println!("{label}: {}", names.join(", "));
+        }
+    };
+    show("stages added", &diff.stages_added);
+    show("stages removed", &diff.stages_removed);
+    show("statuses added", &diff.statuses_added);
+    show("statuses removed", &diff.statuses_removed);
+    if !diff.transitions_added.is_empty() || !diff.transitions_removed.is_empty() {
+        changed = true;
+        println!("transitions added: {}, removed: {}", diff.transitions_added.len(), diff.transitions_removed.len());
+    }
+    if !changed {
+        println!("no changes");
+    }
+}
+
+/// Best-effort title lookup for a link's display in `idlc show`. Returns
+/// `None` rather than erroring so one dangling link doesn't stop the rest
+/// of the item from displaying.
+fn resolve_link_title(workspace: &Workspace, link: &ItemLink) -> Option<String> {
+    match link.kind {
+        LinkKind::Node => {
+            let graph = KnowledgeGraph::init(&workspace.graph_db_path()).ok()?;
+            graph.get_node(&link.id).ok().flatten().map(|n| n.title)
+        }
+        LinkKind::Matter => {
+            let index = MatterIndex::build(&workspace.root).ok()?;
+            index.items.iter().find(|i| i.id == link.id).map(|i| i.title.clone())
+        }
+        LinkKind::Thought => thought_title(&workspace.thoughts_dir().join(&link.id)),
+    }
+}
+
+/// Report body shared by both formats: the diagram, plus an explicit
+/// statement instead of a per-item table — see the `Report` doc comment
+/// for why that's still separate work.
+const NO_ITEMS_NOTE: &str = "This report is diagram-only for now — a per-item breakdown is a separate, larger piece of work.";
+
+fn render_report_markdown(team: &str, diagram: &str) -> String {
+    format!("# IDLC report: {team}\n\n```mermaid\n{diagram}```\n\n{NO_ITEMS_NOTE}\n")
+}
+
+fn render_report_html(team: &str, diagram: &str) -> String {
+    let body = format!(
+        "<h1>IDLC report: {}</h1>\n<pre class=\"mermaid\">\n{}</pre>\n<p>{}</p>\n",
+        escape_html(team),
+        escape_html(diagram),
+        NO_ITEMS_NOTE,
+    );
+    snps_core::claude::html_document(&format!("IDLC report: {team}"), &body)
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -0,0 +1,427 @@
+//! `snps know` (alias `knowledge`) — sync and browse shadow repositories.
+
+use crate::output::{OutputFormat, OutputWriter};
+use clap::Subcommand;
+use serde::Serialize;
+use snps_core::config::load_merged_config;
+use snps_core::knowledge::state::SyncStateStore;
+use snps_core::knowledge::SyncScope;
+use snps_core::repository::Repository;
+use snps_core::subproject;
+use snps_core::Workspace;
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Subcommand)]
+pub enum KnowCommand {
+    /// Scan configured shadow repos and reconcile them with the local
+    /// working copy. Without `--apply`, only prints the plan; with it,
+    /// pulls/pushes every non-conflicting file.
+    Sync {
+        #[arg(long)]
+        apply: bool,
+        #[arg(long)]
+        force: bool,
+        /// Restrict sync to repositories of this context (user/team/project).
+        #[arg(long)]
+        context: Option<String>,
+        /// Restrict sync to a single repository id.
+        #[arg(long)]
+        repo: Option<String>,
+        #[arg(long)]
+        dry_run: bool,
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+    /// Inspect a single knowledge file's provenance and drift.
+    File {
+        #[command(subcommand)]
+        command: FileCommand,
+    },
+    /// Search across merged knowledge, without requiring `rg`.
+    Search { query: String },
+    /// Show every configured repo that provides `path`, their content
+    /// hashes, and which one wins under the current precedence.
+    Explain { path: String },
+    /// List configured shadow repositories with their tracked file counts
+    /// and sync strategy (copy/symlink/hardlink).
+    List,
+    /// Summarize sync state: repo count and tracked/conflicted file counts.
+    Status,
+    /// Settle a file left in `Conflict` state by a previous sync.
+    Resolve {
+        path: String,
+        #[arg(long, conflicts_with_all = ["theirs", "merge"])]
+        ours: bool,
+        #[arg(long, conflicts_with_all = ["ours", "merge"])]
+        theirs: bool,
+        #[arg(long, conflicts_with_all = ["ours", "theirs"])]
+        merge: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum FileCommand {
+    /// Show which repo a knowledge file came from and whether it drifted.
+    Info { path: String },
+    /// Register a knowledge file's owning repo, so later lookups (`file
+    /// remove`, sync) don't have to guess it by scanning repo contents.
+    Add {
+        path: String,
+        #[arg(long)]
+        repo: String,
+    },
+    /// Drop a knowledge file's registration and delete its working copy.
+    /// Consults the registration manifest instead of scanning every
+    /// configured repo for the path.
+    Remove { path: String },
+    /// List registered knowledge files with their repo and drift status.
+    /// A project that predates the registration manifest is migrated the
+    /// first time this (or any other manifest-consulting command) runs.
+    List,
+}
+
+#[derive(Serialize)]
+struct RepoFileCount {
+    id: String,
+    context: String,
+    tracked_files: usize,
+    sync_strategy: String,
+}
+
+#[derive(Serialize)]
+struct KnowStatus {
+    repo_count: usize,
+    tracked_file_count: usize,
+}
+
+#[derive(Serialize)]
+struct ExplainRow {
+    repo_id: String,
+    context: String,
+    content_hash: String,
+    wins: bool,
+    identical_to_winner: bool,
+    reason: String,
+}
+
+#[derive(Serialize)]
+struct FileRegistrationRow {
+    path: String,
+    repo_id: String,
+    drift: String,
+}
+
+pub fn run(command: KnowCommand, output: OutputFormat) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+    let knowledge_dir = subproject::knowledge_dir(&workspace, &std::env::current_dir()?)?;
+
+    match command {
+        KnowCommand::Sync {
+            apply,
+            force,
+            context,
+            repo,
+            dry_run,
+            format,
+        } => {
+            let scope = SyncScope {
+                context: context.clone(),
+                repo_id: repo,
+            };
+            let repos = Repository::load_all(&workspace)?;
+            let selected = scope.select(&repos)?;
+            let order = load_merged_config(&workspace)?.config.knowledge_precedence;
+
+            let mut state = SyncStateStore::load(&workspace)?;
+
+            let scanned = snps_core::knowledge::scan_repos(&selected, &order);
+            let (local_hashes, local_newer) = snps_core::knowledge::local_snapshot(&knowledge_dir, &scanned);
+            let plan = snps_core::knowledge::build_sync_plan(&scanned, &local_hashes, &local_newer, &state, force);
+            let strategy_for = |repo_id: &str| {
+                selected
+                    .iter()
+                    .find(|r| r.id == repo_id)
+                    .map(|r| r.sync_strategy)
+                    .unwrap_or_default()
+            };
+            let preview = plan.preview(strategy_for);
+
+            if dry_run || !apply {
+                match format.as_str() {
+                    "json" => println!("{}", serde_json::to_string_pretty(&preview)?),
+                    _ => print!("{}", preview.render_text()),
+                }
+            }
+            append_sync_log(&workspace, &selected, &scope)?;
+
+            if !preview.conflicts.is_empty() && !force {
+                anyhow::bail!("run `snps know resolve <path>` to settle conflicts before syncing");
+            }
+
+            if apply && !dry_run {
+                let mut provenance = snps_core::knowledge::ProvenanceManifest::load(&workspace)?;
+                let mut registration =
+                    snps_core::knowledge::RegistrationManifest::load_or_migrate(&workspace, &state, &repos)?;
+                let synced_at_unix = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+
+                let summary = snps_core::knowledge::apply_plan(
+                    &plan,
+                    &knowledge_dir,
+                    &mut state,
+                    &mut provenance,
+                    &mut registration,
+                    synced_at_unix,
+                    strategy_for,
+                )?;
+
+                state.save(&workspace)?;
+                provenance.save(&workspace)?;
+                registration.save(&workspace)?;
+                println!("pulled {}, pushed {}, {} conflict(s) left for `snps know resolve`", summary.pulled, summary.pushed, summary.skipped_conflicts);
+                if !summary.force_overwritten.is_empty() {
+                    println!("--force overwrote:");
+                    for path in &summary.force_overwritten {
+                        println!("  {path}");
+                    }
+                }
+            }
+        }
+        KnowCommand::Search { query } => {
+            let matches = snps_core::search::search_dir(
+                &knowledge_dir,
+                &query,
+                &snps_core::search::SearchOptions::default(),
+            );
+            for m in matches {
+                println!("{}:{}: {}", m.path.display(), m.line_number, m.line.trim());
+            }
+        }
+        KnowCommand::Explain { path } => {
+            let repos = Repository::load_all(&workspace)?;
+            let order = load_merged_config(&workspace)?.config.knowledge_precedence;
+            let entries = snps_core::knowledge::explain_precedence(&repos, Path::new(&path), &order)?;
+            let winner = entries[0].clone();
+
+            let rows: Vec<ExplainRow> = entries
+                .iter()
+                .map(|e| ExplainRow {
+                    repo_id: e.repo_id.clone(),
+                    context: e.context.clone(),
+                    content_hash: e.content_hash.clone(),
+                    wins: e.wins,
+                    identical_to_winner: e.identical_to_winner,
+                    reason: snps_core::knowledge::explain_reason(e, &winner, &order),
+                })
+                .collect();
+
+            OutputWriter::new(output).emit(&rows, |rows| {
+                for r in rows {
+                    let marker = if r.wins { "*" } else { " " };
+                    println!("{marker} {}\t{}\t{}\t{}", r.repo_id, r.context, r.content_hash, r.reason);
+                }
+            })?;
+        }
+        KnowCommand::List => {
+            let repos = Repository::load_all(&workspace)?;
+            let state = SyncStateStore::load(&workspace)?;
+            let manifest = snps_core::knowledge::RegistrationManifest::load_or_migrate(&workspace, &state, &repos)?;
+            let summaries: Vec<RepoFileCount> = repos
+                .iter()
+                .map(|r| RepoFileCount {
+                    id: r.id.clone(),
+                    context: r.context.clone(),
+                    tracked_files: manifest.registrations.values().filter(|id| *id == &r.id).count(),
+                    sync_strategy: r.sync_strategy.to_string(),
+                })
+                .collect();
+            OutputWriter::new(output).emit(&summaries, |summaries| {
+                for s in summaries {
+                    println!(
+                        "{}\t{}\t{} tracked file(s)\t{}",
+                        s.id, s.context, s.tracked_files, s.sync_strategy
+                    );
+                }
+            })?;
+        }
+        KnowCommand::Status => {
+            let repos = Repository::load_all(&workspace)?;
+            let state = SyncStateStore::load(&workspace)?;
+            let status = KnowStatus { repo_count: repos.len(), tracked_file_count: state.files.len() };
+            OutputWriter::new(output).emit(&status, |s| {
+                println!("{} repo(s) configured, {} file(s) tracked", s.repo_count, s.tracked_file_count);
+            })?;
+        }
+        KnowCommand::File { command: FileCommand::Info { path } } => {
+            let manifest = snps_core::knowledge::ProvenanceManifest::load(&workspace)?;
+            let Some(entry) = manifest.get(&path) else {
+                anyhow::bail!("no provenance recorded for '{path}'");
+            };
+            let current_hash = std::fs::read(knowledge_dir.join(&path))
+                .ok()
+                .map(|bytes| snps_core::knowledge::hash_contents(&bytes));
+            let drift = manifest.drift_status(&path, current_hash.as_deref());
+            println!("repo: {}", entry.repo_id);
+            println!("context: {}", entry.context);
+            println!("source hash: {}", entry.source_hash);
+            println!(
+                "drift: {}",
+                match drift {
+                    snps_core::knowledge::DriftStatus::Clean => "clean",
+                    snps_core::knowledge::DriftStatus::Modified => "modified",
+                    snps_core::knowledge::DriftStatus::Unknown => "unknown",
+                }
+            );
+        }
+        KnowCommand::File { command: FileCommand::Add { path, repo } } => {
+            let repos = Repository::load_all(&workspace)?;
+            if !repos.iter().any(|r| r.id == repo) {
+                anyhow::bail!("no repository with id '{repo}'");
+            }
+            let mut manifest = snps_core::knowledge::RegistrationManifest::load(&workspace)?;
+            manifest.register(&path, &repo);
+            manifest.save(&workspace)?;
+            println!("registered {path} -> {repo}");
+        }
+        KnowCommand::File { command: FileCommand::Remove { path } } => {
+            let repos = Repository::load_all(&workspace)?;
+            let state = SyncStateStore::load(&workspace)?;
+            let mut manifest = snps_core::knowledge::RegistrationManifest::load_or_migrate(&workspace, &state, &repos)?;
+            let Some(repo_id) = manifest.unregister(&path) else {
+                anyhow::bail!("'{path}' is not registered — run `snps know file add` first");
+            };
+            manifest.save(&workspace)?;
+            let _ = std::fs::remove_file(knowledge_dir.join(&path));
+            println!("removed {path} (was registered to {repo_id})");
+        }
+        KnowCommand::File { command: FileCommand::List } => {
+            let repos = Repository::load_all(&workspace)?;
+            let state = SyncStateStore::load(&workspace)?;
+            let manifest = snps_core::knowledge::RegistrationManifest::load_or_migrate(&workspace, &state, &repos)?;
+            let provenance = snps_core::knowledge::ProvenanceManifest::load(&workspace)?;
+
+            let rows: Vec<FileRegistrationRow> = manifest
+                .registrations
+                .iter()
+                .map(|(path, repo_id)| {
+                    let current_hash = std::fs::read(knowledge_dir.join(path))
+                        .ok()
+                        .map(|bytes| snps_core::knowledge::hash_contents(&bytes));
+                    let drift = provenance.drift_status(path, current_hash.as_deref());
+                    FileRegistrationRow {
+                        path: path.clone(),
+                        repo_id: repo_id.clone(),
+                        drift: match drift {
+                            snps_core::knowledge::DriftStatus::Clean => "clean",
+                            snps_core::knowledge::DriftStatus::Modified => "modified",
+                            snps_core::knowledge::DriftStatus::Unknown => "unknown",
+                        }
+                        .to_string(),
+                    }
+                })
+                .collect();
+
+            OutputWriter::new(output).emit(&rows, |rows| {
+                if rows.is_empty() {
+                    println!("no registered knowledge files");
+                }
+                for row in rows {
+                    println!("{}\t{}\t{}", row.path, row.repo_id, row.drift);
+                }
+            })?;
+        }
+        KnowCommand::Resolve {
+            path,
+            ours,
+            theirs,
+            merge,
+        } => {
+            let mut state = SyncStateStore::load(&workspace)?;
+            if state.files.get(&path).is_none() {
+                anyhow::bail!("no tracked sync state for '{path}'");
+            }
+
+            if merge {
+                let repos = Repository::load_all(&workspace)?;
+                let manifest = snps_core::knowledge::RegistrationManifest::load_or_migrate(&workspace, &state, &repos)?;
+                let repo = owning_repo(&repos, &manifest, &path)?;
+                let local_path = knowledge_dir.join(&path);
+                let ours_content = std::fs::read_to_string(&local_path)
+                    .map_err(|e| anyhow::anyhow!("reading local copy of '{path}': {e}"))?;
+                let theirs_content = std::fs::read_to_string(repo.path.join(&path))
+                    .map_err(|e| anyhow::anyhow!("reading '{path}' from repository '{}': {e}", repo.id))?;
+                std::fs::write(&local_path, conflict_markers(&ours_content, &theirs_content))?;
+                println!("wrote conflict markers to {}", local_path.display());
+                println!("edit the file to resolve them, then run `snps know resolve {path} --ours`");
+                return Ok(());
+            }
+
+            let resolved_hash = if ours {
+                let bytes = std::fs::read(knowledge_dir.join(&path))
+                    .map_err(|e| anyhow::anyhow!("reading local copy of '{path}': {e}"))?;
+                snps_core::knowledge::hash_contents(&bytes)
+            } else if theirs {
+                let repos = Repository::load_all(&workspace)?;
+                let manifest = snps_core::knowledge::RegistrationManifest::load_or_migrate(&workspace, &state, &repos)?;
+                let repo = owning_repo(&repos, &manifest, &path)?;
+                let bytes = std::fs::read(repo.path.join(&path))
+                    .map_err(|e| anyhow::anyhow!("reading '{path}' from repository '{}': {e}", repo.id))?;
+                std::fs::write(knowledge_dir.join(&path), &bytes)?;
+                snps_core::knowledge::hash_contents(&bytes)
+            } else {
+                anyhow::bail!("specify one of --ours, --theirs, or --merge");
+            };
+
+            state.set(
+                &path,
+                snps_core::knowledge::FileSyncState {
+                    source_hash: resolved_hash.clone(),
+                    destination_hash: resolved_hash.clone(),
+                    last_synced_hash: resolved_hash,
+                },
+            );
+            state.save(&workspace)?;
+            println!("resolved {path}");
+        }
+    }
+    Ok(())
+}
+
+/// Find the repository `path` is registered to. The registration manifest
+/// is the only reliable way to do this lookup — `SyncStateStore` keys are
+/// bare relative paths with no repo id prefix, so a resolver can't recover
+/// the owning repo from the path string alone.
+fn owning_repo<'a>(repos: &'a [Repository], manifest: &snps_core::knowledge::RegistrationManifest, path: &str) -> anyhow::Result<&'a Repository> {
+    let repo_id = manifest
+        .repo_for(path)
+        .ok_or_else(|| anyhow::anyhow!("'{path}' isn't registered to a repository — run `snps know file add` first"))?;
+    repos
+        .iter()
+        .find(|r| r.id == repo_id)
+        .ok_or_else(|| anyhow::anyhow!("'{path}' is registered to repository '{repo_id}', which is no longer configured"))
+}
+
+/// Render `ours`/`theirs` with the same `<<<<<<<`/`=======`/`>>>>>>>`
+/// convention as a git merge conflict, so `snps know resolve --merge`
+/// leaves something a user can resolve in their normal editor before
+/// re-running `snps know resolve --ours`.
+fn conflict_markers(ours: &str, theirs: &str) -> String {
+    format!("<<<<<<< ours (local)\n{ours}\n=======\n{theirs}\n>>>>>>> theirs (shadow repo)\n")
+}
+
+/// Append a line to `.pmsynapse/sync.log` recording which scope a sync
+/// invocation ran with, so filtered runs are auditable after the fact.
+fn append_sync_log(workspace: &Workspace, repos: &[&Repository], scope: &SyncScope) -> anyhow::Result<()> {
+    let path = workspace.pmsynapse_dir().join("sync.log");
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    let ids: Vec<&str> = repos.iter().map(|r| r.id.as_str()).collect();
+    writeln!(
+        file,
+        "context={:?} repo={:?} selected={:?}",
+        scope.context, scope.repo_id, ids
+    )?;
+    Ok(())
+}
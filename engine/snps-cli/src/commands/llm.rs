@@ -0,0 +1,56 @@
+//! `snps llm` — connectivity check and ad hoc prompting against the
+//! configured provider.
+
+use clap::Subcommand;
+use snps_core::config::load_merged_config;
+use snps_core::llm::{provider_for, CompletionRequest};
+use snps_core::Workspace;
+
+#[derive(Subcommand)]
+pub enum LlmCommand {
+    /// Send a one-off prompt to the configured provider and print the reply.
+    Test {
+        prompt: String,
+        #[arg(long)]
+        model: Option<String>,
+    },
+}
+
+pub fn run(command: LlmCommand) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+    let merged = load_merged_config(&workspace)?;
+
+    match command {
+        LlmCommand::Test { prompt, model } => {
+            let api_key = merged
+                .config
+                .llm_api_key
+                .clone()
+                .or_else(|| std::env::var(env_var_for(&merged.config.llm_default_provider)).ok())
+                .ok_or_else(|| anyhow::anyhow!("no API key configured for '{}'", merged.config.llm_default_provider))?;
+
+            let provider = provider_for(&merged.config.llm_default_provider, api_key)?;
+            let model = model.unwrap_or_else(|| default_model_for(&merged.config.llm_default_provider));
+            let request = CompletionRequest::new(prompt, model);
+
+            let runtime = tokio::runtime::Runtime::new()?;
+            let completion = runtime.block_on(provider.complete(&request))?;
+            println!("{}", completion.text);
+        }
+    }
+    Ok(())
+}
+
+fn env_var_for(provider: &str) -> &'static str {
+    match provider {
+        "openai" => "OPENAI_API_KEY",
+        _ => "ANTHROPIC_API_KEY",
+    }
+}
+
+fn default_model_for(provider: &str) -> String {
+    match provider {
+        "openai" => "gpt-4o-mini".to_string(),
+        _ => "claude-3-5-sonnet-20241022".to_string(),
+    }
+}
@@ -0,0 +1,556 @@
+//! `snps matter` — create, list, search, and show matter documents.
+
+use crate::output::{OutputFormat, OutputWriter};
+use anyhow::{bail, Context};
+use clap::Subcommand;
+use colored::Colorize;
+use serde::Serialize;
+use snps_core::config::load_merged_config;
+use snps_core::dedup::{find_similar_titles, DuplicateCandidate, DEFAULT_THRESHOLD};
+use snps_core::matter::export::{self as matter_export, ExportFilter, ExportFormat, ExportOptions};
+use snps_core::matter::{self, highlight, LinkTarget, MatterIndex, MatterItem, MatterType, NewMatter, TemplateSource};
+use snps_core::proposals::{NewProposal, ProposalStore, ProposedChange};
+use snps_core::repository::{self, Repository};
+use snps_core::Workspace;
+use std::io::Read;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// The context this CLI invocation is running as, for `--include-private`
+/// gating. Until multi-user contexts are configurable, the project the
+/// command runs in is always the "current user context".
+const CURRENT_CONTEXT: &str = "project";
+
+#[derive(Subcommand)]
+pub enum MatterCommand {
+    /// Create a new matter document from the type's template.
+    Create {
+        #[arg(long, default_value = "document")]
+        r#type: String,
+        title: String,
+        #[arg(long)]
+        author: Option<String>,
+        #[arg(long, value_delimiter = ',')]
+        tags: Vec<String>,
+        #[arg(long, default_value = "project")]
+        context: String,
+        /// Use this literal string as the document body instead of the
+        /// type's placeholder sections. Mutually exclusive with `--from-file`.
+        #[arg(long, conflicts_with = "from_file")]
+        content: Option<String>,
+        /// Read the document body from a file, or from stdin if the path is `-`.
+        #[arg(long, conflicts_with = "content")]
+        from_file: Option<PathBuf>,
+        /// Open the configured editor on the new document immediately after creation.
+        #[arg(long)]
+        edit: bool,
+        /// Print only the created path, for scripting.
+        #[arg(long)]
+        print_path: bool,
+        /// Skip the near-duplicate title check (and its confirmation
+        /// prompt) entirely.
+        #[arg(long, visible_alias = "yes")]
+        force: bool,
+    },
+    /// List matter documents, optionally filtered by type.
+    List {
+        #[arg(long)]
+        r#type: Option<String>,
+        #[arg(long)]
+        visibility: Option<String>,
+        #[arg(long)]
+        include_private: bool,
+    },
+    /// Search matter documents by title and body.
+    Search {
+        query: String,
+        #[arg(long)]
+        include_private: bool,
+    },
+    /// Show a single matter document by id.
+    Show {
+        id: String,
+        /// Render the document as of this revision (a SHA, branch, or
+        /// something like `HEAD~3`) instead of its current content.
+        #[arg(long)]
+        at: Option<String>,
+    },
+    /// Show a document's git commit history (date, author, subject, and a
+    /// short diff stat), following renames. Requires `repo_root` to be a
+    /// git repository with at least one commit.
+    History { id: String },
+    /// Change a document's visibility. Applies immediately unless the
+    /// active team's `require_share_review` config is set, in which case
+    /// this submits a proposal for `snps proposals approve` to apply
+    /// instead of touching the file.
+    Promote {
+        id: String,
+        #[arg(long, value_parser = ["shared", "private"])]
+        to: String,
+        #[arg(long)]
+        author: Option<String>,
+    },
+    /// Manage document templates.
+    Templates {
+        #[command(subcommand)]
+        command: TemplatesCommand,
+    },
+    /// Show outgoing links and backlinks for a document.
+    Links { id: String },
+    /// Report broken links across the repository's matter documents.
+    Validate,
+    /// Report every matter document whose frontmatter needed something
+    /// coerced (an unrecognized type, an unparseable date) to parse.
+    Lint {
+        /// Only lint documents under this path instead of the whole repo.
+        path: Option<PathBuf>,
+    },
+    /// Export documents to an archive or a single concatenated document.
+    Export {
+        #[arg(long)]
+        context: Option<String>,
+        #[arg(long = "type")]
+        matter_type: Option<String>,
+        #[arg(long, value_delimiter = ',')]
+        tags: Vec<String>,
+        #[arg(long, value_delimiter = ',')]
+        ids: Vec<String>,
+        #[arg(long, default_value = "archive")]
+        format: String,
+        #[arg(long)]
+        strip_frontmatter: bool,
+        #[arg(long)]
+        out: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TemplatesCommand {
+    /// Show which templates are built-in vs overridden per type.
+    List,
+}
+
+#[derive(Serialize)]
+struct MatterSummary {
+    matter_type: String,
+    id: String,
+    title: String,
+}
+
+/// A `matter search` hit. `snippet` carries the query terms wrapped in
+/// `**...**` markers here, since JSON output has no ANSI equivalent —
+/// `print_search_results` re-highlights with color for text output instead
+/// of reusing these markers, so scripts parsing JSON never see stray `**`.
+#[derive(Serialize)]
+struct MatterSearchResult {
+    matter_type: String,
+    id: String,
+    title: String,
+    line_number: Option<usize>,
+    score: f32,
+    snippet: String,
+}
+
+pub fn run(command: MatterCommand, output: OutputFormat) -> anyhow::Result<()> {
+    let repo_root = Workspace::discover_from_cwd()?.root;
+
+    match command {
+        MatterCommand::Create {
+            r#type,
+            title,
+            author,
+            tags,
+            context,
+            content,
+            from_file,
+            edit,
+            print_path,
+            force,
+        } => {
+            let matter_type = MatterType::from_str(&r#type)?;
+
+            if !force {
+                let index = MatterIndex::build(&repo_root)?;
+                let candidates = find_similar_titles(&title, index.items.iter().map(|i| (i.title.as_str(), i.path.as_path())), DEFAULT_THRESHOLD);
+                if !candidates.is_empty() && !confirm_despite_duplicates(&title, &candidates)? {
+                    return Ok(());
+                }
+            }
+
+            let body = resolve_body_content(content, from_file)?;
+            let item = matter::matter_create(
+                &repo_root,
+                NewMatter {
+                    matter_type,
+                    title: &title,
+                    author: author.as_deref(),
+                    tags,
+                    context: &context,
+                    body: body.as_deref(),
+                },
+            )?;
+
+            if edit {
+                let workspace = Workspace::discover_from_cwd()?;
+                let merged = load_merged_config(&workspace)?;
+                // Blocks until editing is actually done, including for GUI
+                // editors like `code` that fork into an existing window and
+                // would otherwise return before the file is even open — see
+                // `snps_core::editor`.
+                snps_core::editor::launch_editor(&merged.config.defaults_editor, &item.path)?;
+            }
+
+            if print_path {
+                println!("{}", item.path.display());
+            } else {
+                println!("created {}", item.path.display());
+            }
+        }
+        MatterCommand::List {
+            r#type,
+            visibility,
+            include_private,
+        } => {
+            let index = MatterIndex::build(&repo_root)?;
+            let workspace = Workspace::discover_from_cwd()?;
+            let repos = Repository::load_all(&workspace)?;
+            let filter = r#type.map(|t| MatterType::from_str(&t)).transpose()?;
+            let want = visibility
+                .map(|v| v.parse::<repository::Visibility>())
+                .transpose()?;
+            let summaries: Vec<MatterSummary> = index
+                .list(filter)
+                .into_iter()
+                .filter(|item| visible(&repos, item, include_private, want))
+                .map(|item| summarize(item))
+                .collect();
+            let skipped = index.warnings.len();
+            OutputWriter::new(output).emit(&summaries, |summaries| {
+                print_summaries(summaries);
+                if skipped > 0 {
+                    println!("({skipped} file(s) had parse warnings — see `snps matter lint`)");
+                }
+            })?;
+        }
+        MatterCommand::Search { query, include_private } => {
+            let index = MatterIndex::build(&repo_root)?;
+            let workspace = Workspace::discover_from_cwd()?;
+            let repos = Repository::load_all(&workspace)?;
+            let results: Vec<MatterSearchResult> = index
+                .search_with_snippets(&query)
+                .into_iter()
+                .filter(|hit| visible(&repos, hit.item, include_private, None))
+                .map(|hit| MatterSearchResult {
+                    matter_type: hit.item.matter_type.to_string(),
+                    id: hit.item.id.clone(),
+                    title: hit.item.title.clone(),
+                    line_number: hit.line_number,
+                    score: hit.score,
+                    snippet: highlight(&hit.snippet, &query, "**", "**"),
+                })
+                .collect();
+            OutputWriter::new(output).emit(&results, |results| print_search_results(results, &query))?;
+        }
+        MatterCommand::Show { id, at } => {
+            let index = MatterIndex::build(&repo_root)?;
+            let item = index
+                .items
+                .iter()
+                .find(|i| i.id == id)
+                .ok_or_else(|| anyhow::anyhow!("no matter document with id '{id}'"))?;
+            match at {
+                Some(revision) => println!("{}", matter::show_at(&repo_root, item, &revision)?),
+                None => println!("{}", item.body),
+            }
+        }
+        MatterCommand::History { id } => {
+            let index = MatterIndex::build(&repo_root)?;
+            let item = index
+                .items
+                .iter()
+                .find(|i| i.id == id)
+                .ok_or_else(|| anyhow::anyhow!("no matter document with id '{id}'"))?;
+            match matter::history(&repo_root, item, true)? {
+                None => println!("no git history available (repository has no commits, or isn't a git repository)"),
+                Some(entries) if entries.is_empty() => println!("no commits found for {}", relative(&repo_root, &item.path)),
+                Some(entries) => {
+                    for entry in &entries {
+                        let rename_note = entry
+                            .renamed_from
+                            .as_ref()
+                            .map(|old| format!(" (renamed from {old})"))
+                            .unwrap_or_default();
+                        println!(
+                            "{}\t{}\t{}\t+{}/-{}\t{}{}",
+                            &entry.commit_id[..12.min(entry.commit_id.len())],
+                            snps_core::time::date_string(entry.date_unix.max(0) as u64),
+                            entry.author,
+                            entry.insertions,
+                            entry.deletions,
+                            entry.subject,
+                            rename_note
+                        );
+                    }
+                }
+            }
+        }
+        MatterCommand::Promote { id, to, author } => {
+            let index = MatterIndex::build(&repo_root)?;
+            let item = index
+                .items
+                .iter()
+                .find(|i| i.id == id)
+                .ok_or_else(|| anyhow::anyhow!("no matter document with id '{id}'"))?;
+            let to: repository::Visibility = to.parse()?;
+
+            let workspace = Workspace::discover_from_cwd()?;
+            let require_review = load_merged_config(&workspace)?.config.require_share_review;
+
+            if require_review {
+                let proposal = ProposalStore::create(
+                    &workspace,
+                    NewProposal {
+                        agent: author.as_deref().unwrap_or("cli"),
+                        title: &format!("Promote '{}' to {to}", item.title),
+                        description: &format!("Change visibility of {} to {to}.", relative(&repo_root, &item.path)),
+                        changes: vec![ProposedChange::MatterVisibility { path: item.path.to_string_lossy().into_owned(), to }],
+                    },
+                )?;
+                println!("submitted proposal {} — pending review (snps proposals approve {})", proposal.id, proposal.id);
+            } else {
+                matter::set_visibility(&item.path, to)?;
+                println!("{} is now {to}", relative(&repo_root, &item.path));
+            }
+        }
+        MatterCommand::Templates { command } => match command {
+            TemplatesCommand::List => {
+                for matter_type in MatterType::ALL {
+                    let source = match matter::template_source(&repo_root, matter_type) {
+                        TemplateSource::BuiltIn => "built-in".to_string(),
+                        TemplateSource::Override(path) => {
+                            format!("override ({})", relative(&repo_root, &path))
+                        }
+                    };
+                    println!("{}\t{}", matter_type, source);
+                }
+            }
+        },
+        MatterCommand::Links { id } => {
+            let index = MatterIndex::build(&repo_root)?;
+            let graph = index.link_graph();
+            println!("outgoing:");
+            for link in graph.outgoing_for(&id) {
+                match &link.target {
+                    LinkTarget::Resolved(path) => {
+                        println!("  resolved\t{}", relative(&repo_root, path))
+                    }
+                    LinkTarget::Broken(target) => println!("  broken\t{target}"),
+                }
+            }
+            println!("backlinks:");
+            for from in graph.backlinks_for(&id) {
+                println!("  {from}");
+            }
+        }
+        MatterCommand::Validate => {
+            let index = MatterIndex::build(&repo_root)?;
+            let graph = index.link_graph();
+            let broken = graph.broken_links();
+            for (from, target) in &broken {
+                println!("broken\t{from}\t{target}");
+            }
+            if broken.is_empty() {
+                println!("no broken links");
+            } else {
+                anyhow::bail!("{} broken link(s) found", broken.len());
+            }
+        }
+        MatterCommand::Lint { path } => {
+            let index = MatterIndex::build(&repo_root)?;
+            let warnings: Vec<_> = index
+                .warnings
+                .iter()
+                .filter(|w| path.as_ref().map(|p| w.path.starts_with(p)).unwrap_or(true))
+                .collect();
+
+            for warning in &warnings {
+                println!("{}", relative(&repo_root, &warning.path));
+                for message in &warning.messages {
+                    println!("  {message}");
+                }
+            }
+            println!("{} file(s) with warnings", warnings.len());
+        }
+        MatterCommand::Export {
+            context,
+            matter_type,
+            tags,
+            ids,
+            format,
+            strip_frontmatter,
+            out,
+        } => {
+            let index = MatterIndex::build(&repo_root)?;
+            let filter = ExportFilter {
+                context,
+                matter_type: matter_type.map(|t| MatterType::from_str(&t)).transpose()?,
+                tags,
+                ids,
+            };
+            let format = match format.as_str() {
+                "archive" | "zip" => ExportFormat::Archive,
+                "markdown" | "md" => ExportFormat::ConcatenatedMarkdown,
+                "json" => ExportFormat::Json,
+                other => anyhow::bail!("unknown export format '{other}'"),
+            };
+            let manifest = matter_export::export(
+                &repo_root,
+                &repo_root.to_string_lossy(),
+                &index.items,
+                &filter,
+                &ExportOptions {
+                    format,
+                    strip_frontmatter,
+                    out_path: out.clone(),
+                },
+            )?;
+            println!(
+                "exported {} document(s) to {}",
+                manifest.entries.len(),
+                out.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn summarize(item: &MatterItem) -> MatterSummary {
+    MatterSummary { matter_type: item.matter_type.to_string(), id: item.id.clone(), title: item.title.clone() }
+}
+
+fn print_summaries(summaries: &[MatterSummary]) {
+    for s in summaries {
+        println!("{}\t{}\t{}", s.matter_type, s.id, s.title);
+    }
+}
+
+/// Print each search hit's summary line followed by its snippet, with the
+/// `**query**` markers [`MatterSearchResult::snippet`] carries for JSON
+/// output re-rendered as color here instead — see the doc comment on that
+/// struct for why the marker text is shared between the two formats.
+fn print_search_results(results: &[MatterSearchResult], query: &str) {
+    if results.is_empty() {
+        println!("no matches for '{query}'");
+        return;
+    }
+    for r in results {
+        let location = r.line_number.map(|n| format!(":{n}")).unwrap_or_default();
+        println!("{}\t{}\t{}{}\t{:.1}", r.matter_type, r.id, r.title, location, r.score);
+        println!("    {}", colorize_markers(&r.snippet));
+    }
+}
+
+/// Turn the `**...**` markers that [`snps_core::matter::highlight`] wraps
+/// matches in into terminal color, relying on `colored`'s global
+/// `--no-color`/`NO_COLOR` override (see `crate::ui::init_color`) to make
+/// `.yellow().bold()` a no-op when color is disabled.
+fn colorize_markers(snippet: &str) -> String {
+    let mut out = String::with_capacity(snippet.len());
+    let mut parts = snippet.split("**");
+    if let Some(first) = parts.next() {
+        out.push_str(first);
+    }
+    for (i, part) in parts.enumerate() {
+        if i % 2 == 0 {
+            out.push_str(&part.yellow().bold().to_string());
+        } else {
+            out.push_str(part);
+        }
+    }
+    out
+}
+
+/// Apply repository + frontmatter visibility rules, and an optional
+/// `--visibility` filter, to a single item.
+fn visible(
+    repos: &[Repository],
+    item: &MatterItem,
+    include_private: bool,
+    want: Option<repository::Visibility>,
+) -> bool {
+    let Some(repo) = Repository::owning(repos, &item.path) else {
+        // No repositories configured: treat everything as shared.
+        return true;
+    };
+    if !repository::visibility::is_visible(repo, item, include_private, CURRENT_CONTEXT) {
+        return false;
+    }
+    match want {
+        Some(want) => repository::effective_visibility(repo, item) == want,
+        None => true,
+    }
+}
+
+fn relative(root: &std::path::Path, path: &std::path::Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Resolve `--content`/`--from-file` into the document body, reading
+/// stdin when `--from-file -` is passed. `content` and `from_file` are
+/// already clap-enforced as mutually exclusive.
+fn resolve_body_content(content: Option<String>, from_file: Option<PathBuf>) -> anyhow::Result<Option<String>> {
+    let body = match (content, from_file) {
+        (Some(content), None) => Some(content),
+        (None, Some(path)) if path == PathBuf::from("-") => {
+            let mut buf = Vec::new();
+            std::io::stdin().read_to_end(&mut buf).context("failed to read stdin")?;
+            Some(String::from_utf8(buf).context("stdin input is not valid UTF-8")?)
+        }
+        (None, Some(path)) => Some(std::fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?),
+        (None, None) => None,
+    };
+
+    if let Some(body) = &body {
+        if body.trim().is_empty() {
+            bail!("document body is empty");
+        }
+    }
+
+    Ok(body)
+}
+
+/// Print near-duplicate title candidates and ask whether to continue
+/// anyway, open one of them instead, or abort. Returns `Ok(true)` for
+/// "continue creating the new document". Used by both `matter create` and
+/// `thoughts new`.
+pub(crate) fn confirm_despite_duplicates(title: &str, candidates: &[DuplicateCandidate]) -> anyhow::Result<bool> {
+    println!("'{title}' looks similar to {} existing document(s):", candidates.len());
+    for c in candidates {
+        println!("  {:.0}%  {}  {}", c.similarity * 100.0, c.title, c.path.display());
+    }
+    print!("Continue creating a new document anyway? [y/N/path to open instead]: ");
+    std::io::Write::flush(&mut std::io::stdout()).ok();
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).context("failed to read confirmation from stdin")?;
+    let answer = answer.trim();
+
+    match answer.to_lowercase().as_str() {
+        "y" | "yes" => Ok(true),
+        "" | "n" | "no" => {
+            println!("aborted");
+            Ok(false)
+        }
+        other => {
+            if let Some(candidate) = candidates.iter().find(|c| c.path.display().to_string() == other) {
+                println!("{}", candidate.path.display());
+                Ok(false)
+            } else {
+                bail!("'{other}' isn't 'y', 'n', or one of the listed paths");
+            }
+        }
+    }
+}
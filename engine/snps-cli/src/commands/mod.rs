@@ -0,0 +1,21 @@
+pub mod analyze;
+pub mod claude;
+pub mod completions;
+pub mod config;
+pub mod daemon;
+pub mod doctor;
+pub mod graph;
+pub mod idlc;
+pub mod know;
+pub mod llm;
+pub mod matter;
+pub mod project;
+pub mod proposals;
+pub mod publish;
+pub mod repo;
+pub mod stats;
+pub mod status;
+pub mod sync;
+pub mod team;
+pub mod templates;
+pub mod thoughts;
@@ -0,0 +1,58 @@
+//! `snps project` — sub-project registration for monorepos that share one
+//! `.pmsynapse` root. There's no `snps init` in this tree, so a sub-project
+//! is registered directly with `add`, the same way a team is registered
+//! with `snps team create`.
+
+use clap::Subcommand;
+use snps_core::subproject;
+use snps_core::team;
+use snps_core::Workspace;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum ProjectCommand {
+    /// Register a sub-project owning `path` (relative to the workspace
+    /// root); commands run from inside that subtree resolve to it instead
+    /// of the workspace root.
+    Add {
+        name: String,
+        path: PathBuf,
+        /// Team the sub-project's IDLC config comes from. Defaults to
+        /// `name`.
+        #[arg(long)]
+        team: Option<String>,
+        /// Create the team from the default template if it isn't
+        /// registered yet, same as `snps team switch --create`.
+        #[arg(long)]
+        create_team: bool,
+    },
+    /// Enumerate registered sub-projects.
+    List,
+}
+
+pub fn run(command: ProjectCommand) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+
+    match command {
+        ProjectCommand::Add { name, path, team, create_team } => {
+            let team_id = team.unwrap_or_else(|| name.clone());
+            if create_team && team::find_team(&team_id).is_err() {
+                team::create_team(&workspace, &team_id, &team_id)?;
+            }
+            let sub = subproject::register_sub_project(&workspace, &name, &path, &team_id)?;
+            println!("registered sub-project '{}' at {} (team: {})", sub.name, sub.path.display(), sub.team);
+        }
+        ProjectCommand::List => {
+            let projects = subproject::list_sub_projects(&workspace)?;
+            if projects.is_empty() {
+                println!("no sub-projects registered");
+                return Ok(());
+            }
+            for sub in projects {
+                println!("{} — {} (team: {})", sub.name, sub.path.display(), sub.team);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! `snps proposals` — review agent-submitted change proposals.
+
+use clap::Subcommand;
+use snps_core::graph::KnowledgeGraph;
+use snps_core::proposals::{ProposalStatus, ProposalStore};
+use snps_core::Workspace;
+
+#[derive(Subcommand)]
+pub enum ProposalsCommand {
+    /// List proposals, optionally filtered by agent and/or status.
+    List {
+        #[arg(long)]
+        agent: Option<String>,
+        #[arg(long)]
+        status: Option<String>,
+    },
+    /// Approve a proposal, applying any node/edge/visibility changes.
+    Approve { id: String },
+    /// Reject a proposal, recording why.
+    Reject {
+        id: String,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+}
+
+pub fn run(command: ProposalsCommand) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+
+    match command {
+        ProposalsCommand::List { agent, status } => {
+            let status = status.map(|s| s.parse::<ProposalStatus>()).transpose()?;
+            let proposals = ProposalStore::list(&workspace, agent.as_deref(), status)?;
+            if proposals.is_empty() {
+                println!("No pending proposals");
+                return Ok(());
+            }
+            for proposal in proposals {
+                println!("{} [{}] {} — {}", proposal.id, proposal.status.as_str(), proposal.agent, proposal.title);
+            }
+        }
+        ProposalsCommand::Approve { id } => {
+            let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+            let proposal = ProposalStore::approve(&workspace, &id, &graph)?;
+            println!("approved {} ({} change(s) applied)", proposal.id, proposal.changes.len());
+        }
+        ProposalsCommand::Reject { id, reason } => {
+            let proposal = ProposalStore::reject(&workspace, &id, reason)?;
+            println!("rejected {}", proposal.id);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! `snps publish` — render shared thoughts, knowledge, and matter into a
+//! static HTML site for stakeholders who won't install the CLI.
+
+use crate::ui::Console;
+use clap::Args;
+use snps_core::publish::{publish, PublishOptions};
+use snps_core::Workspace;
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct PublishArgs {
+    /// Directory to write the generated site into (created if missing).
+    output_dir: PathBuf,
+    /// Also publish matter items that would otherwise be excluded as
+    /// private. The published site has no access control of its own, so
+    /// this hands anyone who can reach `output_dir` the same content — off
+    /// by default for that reason.
+    #[arg(long)]
+    include_private: bool,
+}
+
+pub fn run(args: PublishArgs, console: &Console) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+    let options = PublishOptions { include_private: args.include_private };
+    let stats = publish(&workspace, &args.output_dir, options)?;
+
+    console.result(format!(
+        "site at {}: {} created, {} updated, {} unchanged, {} private skipped",
+        args.output_dir.display(),
+        stats.created,
+        stats.updated,
+        stats.unchanged,
+        stats.skipped_private
+    ));
+
+    Ok(())
+}
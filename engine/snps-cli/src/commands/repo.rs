@@ -0,0 +1,187 @@
+//! `snps repo` — shadow repository maintenance, and initializing a new one.
+
+use crate::error::CliError;
+use crate::output::{OutputFormat, OutputWriter};
+use clap::Subcommand;
+use serde::Serialize;
+use snps_core::git::GitRepo;
+use snps_core::repository::{
+    check_repositories, repair, save_repositories, scaffold, sync_repository, Repository, RepoIssue, RepoLayout, SyncOutcome, SyncStrategy,
+};
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Subcommand)]
+pub enum RepoCommand {
+    /// Create a `.pmsynapse` workspace at `path` (defaulting to the
+    /// current directory), git-init it if it isn't a repository already,
+    /// and scaffold a starting directory structure and README for it.
+    Init {
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Skip scaffolding directories/README/.gitignore — just create
+        /// `.pmsynapse/` and the git repository.
+        #[arg(long)]
+        bare: bool,
+        /// Which built-in layout to scaffold: `user`, `team`, or
+        /// `project` (see `snps_core::repository::RepoLayout`). Ignored
+        /// with `--bare`.
+        #[arg(long, default_value = "project")]
+        layout: String,
+    },
+    /// Validate `repositories.yaml` against the filesystem: dead paths,
+    /// missing `.pmsynapse` scaffolding, and duplicate ids.
+    Check {
+        /// Interactively repair what can be: relocate entries found under
+        /// `repositories_root`, re-derive ids for duplicates, and ask
+        /// before dropping entries whose path can't be found anywhere.
+        #[arg(long, conflicts_with = "prune")]
+        fix: bool,
+        /// Like `--fix`, but non-interactive: entries whose path can't be
+        /// found anywhere are dropped without asking.
+        #[arg(long)]
+        prune: bool,
+    },
+    /// Fetch and reconcile shadow repositories, reporting divergence
+    /// instead of blindly pulling and pushing.
+    Sync {
+        #[arg(long)]
+        no_push: bool,
+        #[arg(long, default_value = "fast-forward")]
+        strategy: String,
+    },
+    /// List configured shadow repositories.
+    List,
+}
+
+#[derive(Serialize)]
+struct RepoSummary {
+    id: String,
+    context: String,
+    visibility: String,
+    path: String,
+    path_exists: bool,
+}
+
+pub fn run(command: RepoCommand, output: OutputFormat) -> anyhow::Result<()> {
+    match command {
+        RepoCommand::Init { path, bare, layout } => {
+            std::fs::create_dir_all(&path)?;
+            let pmsynapse_dir = path.join(".pmsynapse");
+            std::fs::create_dir_all(&pmsynapse_dir)?;
+            let config_path = pmsynapse_dir.join("config.yaml");
+            if !config_path.exists() {
+                std::fs::write(&config_path, "")?;
+            }
+            GitRepo::open_or_init(&path)?;
+
+            if bare {
+                println!("{} (bare)", path.display());
+            } else {
+                let layout: RepoLayout = layout.parse()?;
+                scaffold(&path, layout)?;
+                println!("{} ({} layout)", path.display(), layout.as_str());
+            }
+        }
+        RepoCommand::Check { fix, prune } => {
+            let workspace = snps_core::Workspace::discover_from_cwd()?;
+            let repos = Repository::load_all(&workspace)?;
+            let findings = check_repositories(&workspace)?;
+
+            if findings.is_empty() {
+                println!("repositories.yaml: no issues found");
+                return Ok(());
+            }
+
+            for finding in &findings {
+                match &finding.relocated {
+                    Some(new_path) => println!("{}\t{}\t(found at {})", finding.id, finding.issue.description(), new_path.display()),
+                    None => println!("{}\t{}", finding.id, finding.issue.description()),
+                }
+            }
+
+            if !fix && !prune {
+                return Err(CliError::Validation(format!("{} issue(s) found in repositories.yaml", findings.len())).into());
+            }
+
+            let repaired = if prune {
+                repair(repos, &findings, true)
+            } else {
+                let mut repaired = repair(repos, &findings, false);
+                repaired.retain(|repo| {
+                    let unresolved_missing =
+                        findings.iter().any(|f| f.id == repo.id && f.issue == RepoIssue::MissingPath && f.relocated.is_none());
+                    if !unresolved_missing {
+                        return true;
+                    }
+                    print!("Remove '{}' ({}, not found)? [y/N]: ", repo.id, repo.path.display());
+                    std::io::stdout().flush().ok();
+                    let mut answer = String::new();
+                    std::io::stdin().read_line(&mut answer).ok();
+                    !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+                });
+                repaired
+            };
+
+            save_repositories(&workspace, &repaired)?;
+            println!("repositories.yaml updated ({} entr{})", repaired.len(), if repaired.len() == 1 { "y" } else { "ies" });
+        }
+        RepoCommand::Sync { no_push, strategy } => {
+            let strategy = SyncStrategy::from_str(&strategy)?;
+            let workspace = snps_core::Workspace::discover_from_cwd()?;
+            let repos = Repository::load_all(&workspace)?;
+
+            let mut any_conflict = false;
+            let mut any_diverged = false;
+            for repo in &repos {
+                let summary = sync_repository(repo, strategy, !no_push)?;
+                match &summary.outcome {
+                    SyncOutcome::UpToDate => println!("{}\tup-to-date", repo.id),
+                    SyncOutcome::FastForwarded => println!("{}\tfast-forwarded", repo.id),
+                    SyncOutcome::Rebased => println!("{}\trebased", repo.id),
+                    SyncOutcome::Diverged => {
+                        any_diverged = true;
+                        println!("{}\tdiverged (no fast-forward possible; rerun with --strategy rebase)", repo.id);
+                    }
+                    SyncOutcome::Conflicted { files } => {
+                        any_conflict = true;
+                        println!("{}\tconflicted", repo.id);
+                        for file in files {
+                            println!("  {file}");
+                        }
+                    }
+                }
+            }
+
+            if any_conflict {
+                std::process::exit(snps_core::repository::sync::CONFLICT_EXIT_CODE);
+            }
+            if any_diverged {
+                std::process::exit(snps_core::repository::sync::DIVERGED_EXIT_CODE);
+            }
+        }
+        RepoCommand::List => {
+            let workspace = snps_core::Workspace::discover_from_cwd()?;
+            let repos = Repository::load_all(&workspace)?;
+            let summaries: Vec<RepoSummary> = repos
+                .iter()
+                .map(|r| RepoSummary {
+                    id: r.id.clone(),
+                    context: r.context.clone(),
+                    visibility: r.visibility.to_string(),
+                    path: r.path.display().to_string(),
+                    path_exists: r.path.exists(),
+                })
+                .collect();
+
+            OutputWriter::new(output).emit(&summaries, |summaries| {
+                for s in summaries {
+                    let missing = if s.path_exists { "" } else { " (missing)" };
+                    println!("{}\t{}\t{}\t{}{missing}", s.id, s.context, s.visibility, s.path);
+                }
+            })?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//! `snps stats` / `snps stats clear` — summarize or wipe the local metrics
+//! file `telemetry` appends to when `defaults.telemetry_enabled` is on.
+
+use crate::telemetry;
+use crate::ui::Console;
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum StatsCommand {
+    /// Per-command p50/p95 duration, failure rate, and busiest days.
+    Show,
+    /// Delete the local metrics file.
+    Clear,
+}
+
+pub fn run(command: StatsCommand, console: &Console) -> anyhow::Result<()> {
+    let Some(path) = telemetry::metrics_path() else {
+        console.result("no metrics file (HOME is not set)");
+        return Ok(());
+    };
+
+    match command {
+        StatsCommand::Show => show(&path, console),
+        StatsCommand::Clear => {
+            telemetry::clear(&path)?;
+            console.result(format!("cleared {}", path.display()));
+            Ok(())
+        }
+    }
+}
+
+fn show(path: &std::path::Path, console: &Console) -> anyhow::Result<()> {
+    let records = telemetry::load_records(path);
+    if records.is_empty() {
+        console.result(format!(
+            "no metrics recorded yet at {} (enable with `snps config set defaults.telemetry_enabled true`)",
+            path.display()
+        ));
+        return Ok(());
+    }
+
+    console.result(format!("{} recorded invocations", records.len()));
+    for stats in telemetry::summarize(&records) {
+        console.result(format!(
+            "  {}: {} runs, p50 {}ms, p95 {}ms, {:.1}% failed",
+            stats.command,
+            stats.count,
+            stats.p50_ms,
+            stats.p95_ms,
+            stats.failure_rate * 100.0
+        ));
+    }
+
+    console.result("busiest days:");
+    for (day, count) in telemetry::busiest_days(&records, 5) {
+        console.result(format!("  {day}: {count}"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+//! `snps status` — a quick health check for the current workspace. Each
+//! probe degrades on its own (daemon down, graph db missing, ...)
+//! instead of failing the whole command, so one broken piece doesn't
+//! hide the rest.
+
+use clap::Args;
+use serde::Serialize;
+use snps_core::graph::KnowledgeGraph;
+use snps_core::proposals::{ProposalStatus, ProposalStore};
+use snps_core::repository::Repository;
+use snps_core::subproject;
+use snps_core::team::active_team_id;
+use snps_core::Workspace;
+
+#[derive(Args)]
+pub struct StatusArgs {
+    #[arg(long, default_value = "text")]
+    format: String,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Probe<T> {
+    Ok(T),
+    Unavailable { unavailable: String },
+}
+
+impl<T> Probe<T> {
+    fn from_result(result: Result<T, impl ToString>) -> Self {
+        match result {
+            Ok(value) => Probe::Ok(value),
+            Err(e) => Probe::Unavailable { unavailable: e.to_string() },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DaemonStatus {
+    running: bool,
+    port: Option<u16>,
+}
+
+#[derive(Serialize)]
+struct GraphStatus {
+    schema_version: u32,
+    node_count: usize,
+    edge_count: usize,
+}
+
+#[derive(Serialize)]
+struct StageCount {
+    stage: String,
+    /// Always 0 today: nothing in the workspace assigns a stage to an
+    /// item yet, so this is honestly zero rather than fabricated. Real
+    /// counts land with IDLC item/board storage.
+    count: usize,
+}
+
+#[derive(Serialize)]
+struct IdlcStatus {
+    team: String,
+    stages: Vec<StageCount>,
+}
+
+#[derive(Serialize)]
+struct MatterStatus {
+    repo_count: usize,
+    missing_paths: usize,
+}
+
+#[derive(Serialize)]
+struct ThoughtsStatus {
+    initialized: bool,
+    last_sync_unix: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct WorkspaceContext {
+    /// The registered sub-project's name, or `"root"` for the workspace
+    /// root context.
+    active: String,
+}
+
+#[derive(Serialize)]
+struct StatusReport {
+    version: String,
+    context: Probe<WorkspaceContext>,
+    daemon: DaemonStatus,
+    graph: Probe<GraphStatus>,
+    idlc: Probe<IdlcStatus>,
+    matter: Probe<MatterStatus>,
+    thoughts: ThoughtsStatus,
+    proposals: Probe<usize>,
+}
+
+pub fn run(args: StatusArgs) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+
+    let pid_file = snps_daemon::pid::DaemonPidFile::new(&workspace.pmsynapse_dir(), None);
+    let daemon = match pid_file.read() {
+        Some(pid) if pid_file.is_live() => DaemonStatus { running: true, port: Some(pid.port) },
+        _ => DaemonStatus { running: false, port: None },
+    };
+
+    let context = Probe::from_result(context_status(&workspace));
+    let graph = Probe::from_result(graph_status(&workspace));
+    let idlc = Probe::from_result(idlc_status(&workspace));
+    let matter = Probe::from_result(matter_status(&workspace));
+    let thoughts = thoughts_status(&workspace);
+    let proposals = Probe::from_result(ProposalStore::list(&workspace, None, Some(ProposalStatus::Pending)).map(|p| p.len()));
+
+    let report =
+        StatusReport { version: env!("CARGO_PKG_VERSION").to_string(), context, daemon, graph, idlc, matter, thoughts, proposals };
+
+    match args.format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+        _ => print_text(&report),
+    }
+
+    Ok(())
+}
+
+fn context_status(workspace: &Workspace) -> anyhow::Result<WorkspaceContext> {
+    let cwd = std::env::current_dir()?;
+    let active = match subproject::resolve_sub_project_for(workspace, &cwd)? {
+        Some(sub) => sub.name,
+        None => "root".to_string(),
+    };
+    Ok(WorkspaceContext { active })
+}
+
+fn graph_status(workspace: &Workspace) -> anyhow::Result<GraphStatus> {
+    let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+    Ok(GraphStatus { schema_version: graph.schema_version()?, node_count: graph.query(None)?.len(), edge_count: graph.all_edges()?.len() })
+}
+
+fn idlc_status(workspace: &Workspace) -> anyhow::Result<IdlcStatus> {
+    let team = active_team_id(workspace);
+    let idlc_path = workspace.teams_dir().join(&team).join("idlc.yaml");
+    let contents = std::fs::read_to_string(&idlc_path).map_err(|_| anyhow::anyhow!("no idlc.yaml for team '{team}'"))?;
+    let config = snps_core::idlc::parse_idlc_config(&contents)?;
+    Ok(IdlcStatus { team, stages: config.stages.into_iter().map(|stage| StageCount { stage, count: 0 }).collect() })
+}
+
+fn matter_status(workspace: &Workspace) -> anyhow::Result<MatterStatus> {
+    let repos = Repository::load_all(workspace)?;
+    let missing_paths = repos.iter().filter(|r| !r.path.exists()).count();
+    Ok(MatterStatus { repo_count: repos.len(), missing_paths })
+}
+
+fn thoughts_status(workspace: &Workspace) -> ThoughtsStatus {
+    ThoughtsStatus {
+        initialized: workspace.thoughts_dir().exists(),
+        last_sync_unix: snps_core::sync_log::last_sync(&workspace.pmsynapse_dir()).map(|s| s.unix_time),
+    }
+}
+
+fn print_text(report: &StatusReport) {
+    println!("snps {}", report.version);
+
+    match &report.context {
+        Probe::Ok(context) => println!("context: {}", context.active),
+        Probe::Unavailable { unavailable } => println!("context: unavailable ({unavailable})"),
+    }
+
+    match &report.daemon {
+        DaemonStatus { running: true, port: Some(port) } => println!("daemon: running on port {port}"),
+        _ => println!("daemon: not running"),
+    }
+
+    match &report.graph {
+        Probe::Ok(g) => println!("graph: schema v{}, {} node(s), {} edge(s)", g.schema_version, g.node_count, g.edge_count),
+        Probe::Unavailable { unavailable } => println!("graph: unavailable ({unavailable})"),
+    }
+
+    match &report.idlc {
+        Probe::Ok(idlc) => {
+            println!("idlc: team '{}'", idlc.team);
+            for stage in &idlc.stages {
+                println!("  {}: {}", stage.stage, stage.count);
+            }
+        }
+        Probe::Unavailable { unavailable } => println!("idlc: unavailable ({unavailable})"),
+    }
+
+    match &report.matter {
+        Probe::Ok(m) => println!("matter: {} repo(s) configured, {} with a missing path", m.repo_count, m.missing_paths),
+        Probe::Unavailable { unavailable } => println!("matter: unavailable ({unavailable})"),
+    }
+
+    match report.thoughts.last_sync_unix {
+        Some(t) => println!("thoughts: initialized={}, last sync at unix time {t}", report.thoughts.initialized),
+        None => println!("thoughts: initialized={}, never synced", report.thoughts.initialized),
+    }
+
+    match &report.proposals {
+        Probe::Ok(count) => println!("proposals: {count} pending"),
+        Probe::Unavailable { unavailable } => println!("proposals: unavailable ({unavailable})"),
+    }
+}
@@ -0,0 +1,71 @@
+//! `snps sync` — map `knowledge/` and `thoughts/shared/` markdown into the
+//! knowledge graph as `Document` nodes.
+
+use crate::telemetry::Telemetry;
+use crate::ui::Console;
+use clap::Args;
+use snps_core::graph::{sync_markdown_to_graph, DocOutcome, KnowledgeGraph};
+use snps_core::Workspace;
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct SyncArgs {
+    /// Show planned adds/updates/orphans without writing to the graph.
+    #[arg(long)]
+    dry_run: bool,
+    /// Restrict sync to a single markdown file.
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+pub fn run(args: SyncArgs, console: &Console, telemetry: &Telemetry) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+    let graph = KnowledgeGraph::init(&workspace.graph_db_path())?;
+
+    let results = sync_markdown_to_graph(
+        &graph,
+        &workspace.root,
+        &workspace.knowledge_dir(),
+        &workspace.thoughts_dir().join("shared"),
+        args.file.as_deref(),
+        args.dry_run,
+    )?;
+    telemetry.add("files_scanned", results.len() as u64);
+
+    let (mut added, mut updated, mut orphaned, mut unchanged) = (0, 0, 0, 0);
+    for result in &results {
+        match result.outcome {
+            DocOutcome::Added => added += 1,
+            DocOutcome::Updated => updated += 1,
+            DocOutcome::Orphaned => orphaned += 1,
+            DocOutcome::Unchanged => unchanged += 1,
+        }
+        if args.dry_run {
+            match result.outcome {
+                DocOutcome::Unchanged => {}
+                other => console.info(format!("{other:?} {} ({})", result.id, result.path.display())),
+            }
+        }
+    }
+
+    if args.dry_run {
+        if added == 0 && updated == 0 && orphaned == 0 {
+            console.result("Sync complete (no changes)");
+        } else {
+            console.result(format!(
+                "{added} to add, {updated} to update, {orphaned} to orphan, {unchanged} unchanged (dry run, nothing written)"
+            ));
+        }
+        return Ok(());
+    }
+
+    snps_core::sync_log::record_sync(&workspace.pmsynapse_dir())?;
+    telemetry.add("nodes_written", (added + updated) as u64);
+    if added == 0 && updated == 0 && orphaned == 0 {
+        console.result("Sync complete (no changes)");
+    } else {
+        console.result(format!("Sync complete ({added} added, {updated} updated, {orphaned} orphaned)"));
+    }
+
+    Ok(())
+}
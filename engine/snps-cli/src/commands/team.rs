@@ -0,0 +1,67 @@
+//! `snps team` — team registry and active-team switching.
+
+use clap::Subcommand;
+use snps_core::team;
+use snps_core::Workspace;
+
+#[derive(Subcommand)]
+pub enum TeamCommand {
+    /// Enumerate registered teams and whether they have an IDLC config yet.
+    List,
+    /// Print a team's IDLC stages and member-facing config.
+    Show { id: String },
+    /// Change the project's active team.
+    Switch {
+        id: String,
+        /// Create the team from the default template if it isn't registered.
+        #[arg(long)]
+        create: bool,
+    },
+    /// Register a new team.
+    Create {
+        id: String,
+        #[arg(long)]
+        name: String,
+    },
+}
+
+pub fn run(command: TeamCommand) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+
+    match command {
+        TeamCommand::List => {
+            let teams = team::list_teams(&workspace)?;
+            if teams.is_empty() {
+                println!("no teams registered");
+                return Ok(());
+            }
+            for summary in teams {
+                println!(
+                    "{}{} — {} (idlc: {})",
+                    summary.id,
+                    if summary.active { " (active)" } else { "" },
+                    summary.name,
+                    if summary.has_idlc_config { "configured" } else { "missing" }
+                );
+            }
+        }
+        TeamCommand::Show { id } => {
+            let (entry, idlc) = team::show_team(&workspace, &id)?;
+            println!("{} — {}", entry.id, entry.name);
+            match idlc {
+                Some(config) => println!("{config}"),
+                None => println!("(no IDLC config yet)"),
+            }
+        }
+        TeamCommand::Switch { id, create } => {
+            team::switch_team(&workspace, &id, create)?;
+            println!("active team: {id}");
+        }
+        TeamCommand::Create { id, name } => {
+            let entry = team::create_team(&workspace, &id, &name)?;
+            println!("created team {} ({})", entry.id, entry.name);
+        }
+    }
+
+    Ok(())
+}
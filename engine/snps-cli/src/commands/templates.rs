@@ -0,0 +1,98 @@
+//! `snps templates` — built-in and file-backed workflow templates.
+
+use clap::Subcommand;
+use snps_core::templates;
+use snps_core::Workspace;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum TemplatesCommand {
+    /// List built-in and `.pmsynapse/templates/`-defined templates.
+    List,
+    /// Copy a template's IDLC config and prompts into the active team.
+    Use {
+        name: String,
+        /// Overwrite the active team's existing idlc.yaml.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Check a template's IDLC config and prompt files for consistency.
+    Validate { name: String },
+    /// Copy a template out to a plain folder for sharing.
+    Export { name: String, dir: PathBuf },
+    /// Install the active team's `idlc.yaml` from a local file, an
+    /// `https://` URL, or a configured matter/knowledge repository id
+    /// (resolving `teams/<team>/idlc.yaml` inside it), instead of
+    /// copy-pasting a centrally maintained config by hand.
+    Import {
+        #[arg(long)]
+        from: String,
+        /// Overwrite the active team's existing idlc.yaml.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Re-fetch the active team's `idlc.yaml` from wherever `templates
+    /// import` installed it from, and show what would change.
+    Update {
+        /// Write the re-fetched config over the local one; without this,
+        /// only the diff is shown.
+        #[arg(long)]
+        apply: bool,
+    },
+}
+
+pub fn run(command: TemplatesCommand) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+
+    match command {
+        TemplatesCommand::List => {
+            let mut summaries = templates::list_templates(&workspace);
+            summaries.sort_by(|a, b| a.name.cmp(&b.name));
+            for summary in summaries {
+                println!("{}  ({})", summary.name, summary.source.as_str());
+            }
+        }
+        TemplatesCommand::Use { name, force } => {
+            let team_dir = templates::use_template(&workspace, &name, force)?;
+            println!("installed template '{name}' into {}", team_dir.display());
+        }
+        TemplatesCommand::Validate { name } => {
+            let issues = templates::validate_template(&workspace, &name)?;
+            if issues.is_empty() {
+                println!("template '{name}' is valid");
+                return Ok(());
+            }
+            for issue in &issues {
+                println!("  {}", issue.message);
+            }
+            return Err(anyhow::anyhow!("{} issue(s) found in template '{name}'", issues.len()));
+        }
+        TemplatesCommand::Export { name, dir } => {
+            templates::export_template(&workspace, &name, &dir)?;
+            println!("exported template '{name}' to {}", dir.display());
+        }
+        TemplatesCommand::Import { from, force } => {
+            let runtime = tokio::runtime::Runtime::new()?;
+            let dest = runtime.block_on(templates::import_idlc_config(&workspace, &from, force))?;
+            println!("installed idlc.yaml from '{from}' into {}", dest.display());
+        }
+        TemplatesCommand::Update { apply } => {
+            let runtime = tokio::runtime::Runtime::new()?;
+            let outcome = runtime.block_on(templates::update_idlc_config(&workspace, apply))?;
+            if !outcome.changed {
+                println!("idlc.yaml is already up to date with its recorded source");
+                return Ok(());
+            }
+            for line in &outcome.diff {
+                println!("{line}");
+            }
+            if outcome.applied {
+                println!("updated idlc.yaml");
+            } else {
+                println!("{} line(s) would change; rerun with --apply to overwrite", outcome.diff.len());
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,272 @@
+//! `snps thoughts` — notes system commands.
+
+use crate::commands::matter::confirm_despite_duplicates;
+use clap::Subcommand;
+use snps_core::config::load_merged_config;
+use snps_core::dedup::{find_similar_titles, DEFAULT_THRESHOLD};
+use snps_core::git::GitRepo;
+use snps_core::hooks::{self, HookState, InstallOptions};
+use snps_core::subproject;
+use snps_core::thoughts::{
+    list_thoughts, parse_tracker_url, sync_thoughts, thoughts_init, thoughts_new, thoughts_search, ThoughtType, ThoughtsLayout,
+    ThoughtsListFilter, ThoughtsSearchOptions, ThoughtsSearchResult, ThoughtsSyncScope,
+};
+use snps_core::Workspace;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum ThoughtsCommand {
+    /// Search thoughts documents, without requiring `rg`.
+    Search {
+        query: String,
+        #[arg(long)]
+        paths_only: bool,
+        #[arg(long)]
+        doc_type: Option<String>,
+    },
+    /// List thoughts documents, optionally filtered by type/tag/ticket id.
+    List {
+        #[arg(long = "doc-type", visible_alias = "type")]
+        thought_type: Option<String>,
+        #[arg(long)]
+        tag: Option<String>,
+        /// Filter to the ticket document with this `ticket_id`.
+        #[arg(long)]
+        ticket: Option<String>,
+    },
+    /// Move stale thoughts documents into `thoughts/archive/`.
+    Archive {
+        #[arg(long)]
+        older_than_days: Option<u64>,
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Append an entry to today's journal file, creating it on first use.
+    Journal {
+        entry: String,
+    },
+    /// Parse a tracker URL (GitHub/GitLab issues, Jira Cloud) and write
+    /// its tracker/ticket_id/url into a ticket document's frontmatter.
+    LinkTicket {
+        file: PathBuf,
+        url: String,
+    },
+    /// Seed a new thoughts document in a configured category. `category`
+    /// isn't a fixed set of clap values since teams can add their own via
+    /// `thoughts.categories` config — it's instead validated at runtime
+    /// against the effective [`ThoughtsLayout`], so a typo or unconfigured
+    /// name errors out the same way an unknown `--doc-type` does.
+    New {
+        category: String,
+        title: String,
+        /// Skip the near-duplicate title check (and its confirmation
+        /// prompt) entirely.
+        #[arg(long, visible_alias = "yes")]
+        force: bool,
+        /// Open the configured editor on the new document immediately
+        /// after creation, same as `matter create --edit`. Always blocks
+        /// until editing is done — see `snps_core::editor` — so there's no
+        /// separate `--wait`/`--no-wait` toggle to get wrong.
+        #[arg(long)]
+        open: bool,
+    },
+    /// Create the directory tree for every configured thoughts category.
+    Init,
+    /// Commit a scoped slice of `thoughts/`, instead of the whole tree,
+    /// so the commit message names only what actually changed. There's
+    /// no `shared`/`personal`/`global` split in this codebase (or a
+    /// separate central repo thoughts get pushed to) — scope is a
+    /// configured category name, the same unit `thoughts new <category>`
+    /// already uses, or omitted for every category.
+    Sync {
+        /// Restrict to one configured category (see `thoughts new`).
+        /// Omit to sync every category.
+        #[arg(long)]
+        category: Option<String>,
+        /// Further restrict to this subpath under the category (or under
+        /// `thoughts/` itself with no `--category`).
+        #[arg(long)]
+        path: Option<PathBuf>,
+        /// Push after committing, same as `snps config push --push`.
+        #[arg(long)]
+        push: bool,
+    },
+    /// Manage the git hooks that keep the graph in sync with thoughts and
+    /// knowledge documents as they're committed.
+    Hooks {
+        #[command(subcommand)]
+        command: HooksCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum HooksCommand {
+    /// Install the `pre-commit` sync preview hook (and, with
+    /// `--auto-sync`, the `post-commit` hook that runs the sync for
+    /// real).
+    Install {
+        #[arg(long)]
+        auto_sync: bool,
+        /// Append to an existing hook file even if it doesn't look like
+        /// husky or lefthook.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Remove PMSynapse's hooks, restoring any file they were embedded
+    /// in to its original bytes.
+    Uninstall,
+    /// Show whether each hook is installed, embedded in another tool's
+    /// hook, or left alone.
+    Status,
+}
+
+pub fn run(command: ThoughtsCommand) -> anyhow::Result<()> {
+    let workspace = Workspace::discover_from_cwd()?;
+    let thoughts_dir = subproject::thoughts_dir(&workspace, &std::env::current_dir()?)?;
+
+    match command {
+        ThoughtsCommand::Search {
+            query,
+            paths_only,
+            doc_type,
+        } => {
+            let options = ThoughtsSearchOptions {
+                paths_only,
+                doc_type,
+                ..ThoughtsSearchOptions::default()
+            };
+            match thoughts_search(&thoughts_dir, &query, &options)? {
+                ThoughtsSearchResult::Paths(paths) => {
+                    for path in paths {
+                        println!("{}", path.display());
+                    }
+                }
+                ThoughtsSearchResult::Matches(matches) => {
+                    for ranked in matches {
+                        let m = ranked.search_match;
+                        println!(
+                            "{}:{}: {} (score {})",
+                            m.path.display(),
+                            m.line_number,
+                            m.line.trim(),
+                            ranked.score
+                        );
+                    }
+                }
+            }
+        }
+        ThoughtsCommand::List { thought_type, tag, ticket } => {
+            let filter = ThoughtsListFilter {
+                thought_type: thought_type.map(|t| ThoughtType::from_frontmatter(&t)),
+                tag,
+                ticket,
+            };
+            for item in list_thoughts(&thoughts_dir, &filter) {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    item.thought_type.map(|t| t.to_string()).unwrap_or_default(),
+                    item.ticket_id.as_deref().unwrap_or("-"),
+                    item.title,
+                    item.path.display()
+                );
+            }
+        }
+        ThoughtsCommand::Archive { older_than_days, dry_run } => {
+            let options = snps_core::thoughts::ArchiveOptions {
+                older_than: older_than_days.map(|d| std::time::Duration::from_secs(d * 86_400)),
+                dry_run,
+            };
+            let moved = snps_core::thoughts::archive_thoughts(&thoughts_dir, &options)?;
+            for file in &moved {
+                println!("{} -> {}", file.from.display(), file.to.display());
+            }
+            println!("{} file(s){}", moved.len(), if dry_run { " (dry run)" } else { "" });
+        }
+        ThoughtsCommand::Journal { entry } => {
+            let today = snps_core::time::today_string();
+            let time = snps_core::time::time_string();
+            let path = snps_core::thoughts::append_journal_entry(&thoughts_dir, &today, &time, &entry)?;
+            println!("{}", path.display());
+        }
+        ThoughtsCommand::LinkTicket { file, url } => {
+            let tracker_ref = parse_tracker_url(&url)
+                .ok_or_else(|| anyhow::anyhow!("'{url}' doesn't look like a GitHub, GitLab, or Jira Cloud ticket URL"))?;
+            snps_core::thoughts::link_ticket(&file, &tracker_ref)?;
+            println!("linked {} to {} ({})", file.display(), tracker_ref.id, tracker_ref.tracker);
+        }
+        ThoughtsCommand::New { category, title, force, open } => {
+            if !force {
+                let existing = list_thoughts(&thoughts_dir, &ThoughtsListFilter::default());
+                let candidates = find_similar_titles(&title, existing.iter().map(|i| (i.title.as_str(), i.path.as_path())), DEFAULT_THRESHOLD);
+                if !candidates.is_empty() && !confirm_despite_duplicates(&title, &candidates)? {
+                    return Ok(());
+                }
+            }
+
+            let merged = load_merged_config(&workspace)?;
+            let layout = ThoughtsLayout::from_config(merged.config.thoughts_categories);
+            let today = snps_core::time::today_string();
+            let path = thoughts_new(&thoughts_dir, &layout, &category, &title, &today)?;
+
+            if open {
+                snps_core::editor::launch_editor(&merged.config.defaults_editor, &path)?;
+            }
+
+            println!("{}", path.display());
+        }
+        ThoughtsCommand::Init => {
+            let merged = load_merged_config(&workspace)?;
+            let layout = ThoughtsLayout::from_config(merged.config.thoughts_categories);
+            thoughts_init(&thoughts_dir, &layout)?;
+            println!("{}", thoughts_dir.display());
+        }
+        ThoughtsCommand::Sync { category, path, push } => {
+            let merged = load_merged_config(&workspace)?;
+            let layout = ThoughtsLayout::from_config(merged.config.thoughts_categories);
+            let scope = ThoughtsSyncScope { category: category.as_deref(), path: path.as_deref() };
+            let index_path = workspace.root.join(&merged.config.search_index_db);
+
+            let summary =
+                sync_thoughts(&workspace.root, &thoughts_dir, &layout, &scope, &index_path, &merged.config.search_exclude_patterns, push)?;
+
+            if summary.committed {
+                println!("committed {}", summary.scope_label);
+            } else {
+                println!("{}: nothing to commit", summary.scope_label);
+            }
+            println!("{} file(s) reindexed", summary.reparsed);
+            if summary.other_dirty_count > 0 {
+                println!(
+                    "{} other thoughts file(s) outside this scope are dirty and were left untouched",
+                    summary.other_dirty_count
+                );
+            }
+        }
+        ThoughtsCommand::Hooks { command } => run_hooks(command, &workspace)?,
+    }
+    Ok(())
+}
+
+fn run_hooks(command: HooksCommand, workspace: &Workspace) -> anyhow::Result<()> {
+    let repo = GitRepo::open(&workspace.root)?;
+    let hooks_dir = repo.hooks_dir();
+
+    let statuses = match command {
+        HooksCommand::Install { auto_sync, force } => {
+            hooks::install_thoughts_hooks(&hooks_dir, &workspace.root, InstallOptions { auto_sync, force })?
+        }
+        HooksCommand::Uninstall => hooks::uninstall_thoughts_hooks(&hooks_dir)?,
+        HooksCommand::Status => hooks::hooks_status(&hooks_dir)?,
+    };
+
+    for status in statuses {
+        let state = match status.state {
+            HookState::NotInstalled => "not installed",
+            HookState::Managed => "installed",
+            HookState::EmbeddedInExistingHook => "embedded in existing hook",
+            HookState::ForeignUnmanaged => "left alone (unmanaged foreign hook)",
+        };
+        println!("{}: {state}", status.kind.file_name());
+    }
+    Ok(())
+}
@@ -0,0 +1,125 @@
+//! Structured CLI errors with documented exit codes, so scripts can tell
+//! "config invalid" apart from "not found" instead of getting a bare
+//! exit 1 for everything. Most command functions still return
+//! `anyhow::Result<()>`; a command opts into a specific documented code
+//! by returning a `CliError` (it implements `std::error::Error`, so `?`
+//! and `.into()` work with `anyhow` as usual) and `main` downcasts for
+//! it when choosing the process exit code, falling back to 1 for
+//! anything else. Migrating every command over to construct these
+//! instead of `anyhow::bail!` is mechanical follow-up, the same way the
+//! `Console` rollout in `ui.rs` started with one command.
+//!
+//! Exit codes:
+//! - 2 usage — bad arguments clap itself didn't already reject
+//! - 3 config — missing or invalid on-disk configuration
+//! - 4 daemon-unreachable — a command needed the daemon and couldn't reach it
+//! - 5 not-found — the referenced item/file/entity doesn't exist
+//! - 6 validation — well-formed input that fails a domain rule
+
+use crate::output::OutputFormat;
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum CliError {
+    Usage(String),
+    Config(String),
+    /// Reserved for commands that call out to a running daemon over
+    /// HTTP; none do yet (`daemon start`/`daemon status` work against
+    /// the PID file and workspace directly), so nothing constructs this
+    /// today.
+    DaemonUnreachable(String),
+    NotFound(String),
+    Validation(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) => 2,
+            CliError::Config(_) => 3,
+            CliError::DaemonUnreachable(_) => 4,
+            CliError::NotFound(_) => 5,
+            CliError::Validation(_) => 6,
+        }
+    }
+
+    fn category(&self) -> &'static str {
+        match self {
+            CliError::Usage(_) => "usage",
+            CliError::Config(_) => "config",
+            CliError::DaemonUnreachable(_) => "daemon_unreachable",
+            CliError::NotFound(_) => "not_found",
+            CliError::Validation(_) => "validation",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            CliError::Usage(m) | CliError::Config(m) | CliError::DaemonUnreachable(m) | CliError::NotFound(m) | CliError::Validation(m) => m,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// `snps_core::CoreError` doesn't carry a CLI-facing category, so this
+/// maps its variants onto the closest one: `NotFound` is unambiguous,
+/// `InvalidInput`/`Parse` both mean "the data on disk failed a rule" and
+/// land on `Validation`/`Config` respectively depending on whether the
+/// input was a workspace config file or arbitrary user-supplied data.
+impl From<snps_core::CoreError> for CliError {
+    fn from(err: snps_core::CoreError) -> Self {
+        match err {
+            snps_core::CoreError::NotFound(what) => CliError::NotFound(what),
+            snps_core::CoreError::Parse { path, message } => {
+                CliError::Config(format!("failed to parse {}: {message}", path.display()))
+            }
+            snps_core::CoreError::InvalidInput(msg) => CliError::Validation(msg),
+            snps_core::CoreError::Io(err) => CliError::Usage(err.to_string()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorReport<'a> {
+    error: &'a str,
+    category: &'a str,
+    exit_code: i32,
+}
+
+/// Print `err` the way `--output` asks for: a plain `error: ...` line to
+/// stderr for text output, or a JSON object (still to stderr, since
+/// stdout is reserved for a command's actual result) carrying the
+/// category and exit code for scripts that want to branch on it without
+/// parsing prose.
+pub fn print_error(err: &anyhow::Error, output: OutputFormat) {
+    match err.downcast_ref::<CliError>() {
+        Some(cli_err) => match output {
+            OutputFormat::Json => {
+                let report = ErrorReport { error: &cli_err.message(), category: cli_err.category(), exit_code: cli_err.exit_code() };
+                eprintln!("{}", serde_json::to_string(&report).unwrap_or_else(|_| cli_err.to_string()));
+            }
+            OutputFormat::Text => eprintln!("error: {cli_err}"),
+        },
+        None => match output {
+            OutputFormat::Json => {
+                let report = ErrorReport { error: &err.to_string(), category: "unknown", exit_code: 1 };
+                eprintln!("{}", serde_json::to_string(&report).unwrap_or_else(|_| err.to_string()));
+            }
+            OutputFormat::Text => eprintln!("error: {err}"),
+        },
+    }
+}
+
+/// The process exit code for `err`: a `CliError`'s documented code, or 1
+/// for anything else.
+pub fn exit_code_for(err: &anyhow::Error) -> i32 {
+    err.downcast_ref::<CliError>().map(CliError::exit_code).unwrap_or(1)
+}
@@ -0,0 +1,240 @@
+//! `snps` — the PMSynapse command line tool.
+
+mod commands;
+mod error;
+mod notify;
+mod output;
+mod telemetry;
+mod ui;
+
+use clap::{Parser, Subcommand};
+use output::OutputFormat;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "snps", about = "AI-enabled project management with knowledge graphs")]
+pub(crate) struct Cli {
+    /// Render script-friendly output where the command supports it.
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    output: OutputFormat,
+    /// Disable colored output, regardless of terminal support.
+    #[arg(long, global = true)]
+    no_color: bool,
+    /// Suppress the banner and informational output; warnings and errors
+    /// still print to stderr.
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    /// Increase verbosity; stack for trace-level output (-vv).
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Manage matter documents (specs, research, plans, insights).
+    Matter {
+        #[command(subcommand)]
+        command: commands::matter::MatterCommand,
+    },
+    /// Manage shadow repositories.
+    Repo {
+        #[command(subcommand)]
+        command: commands::repo::RepoCommand,
+    },
+    /// Sync and browse knowledge from shadow repositories.
+    #[command(alias = "knowledge")]
+    Know {
+        #[command(subcommand)]
+        command: commands::know::KnowCommand,
+    },
+    /// Manage thoughts documents.
+    Thoughts {
+        #[command(subcommand)]
+        command: commands::thoughts::ThoughtsCommand,
+    },
+    /// Inspect and manage layered configuration.
+    Config {
+        #[command(subcommand)]
+        command: commands::config::ConfigCommand,
+    },
+    /// Talk to the configured LLM provider.
+    Llm {
+        #[command(subcommand)]
+        command: commands::llm::LlmCommand,
+    },
+    /// Work with Claude Code's own session transcripts.
+    Claude {
+        #[command(subcommand)]
+        command: commands::claude::ClaudeCommand,
+    },
+    /// Extract candidate assumptions and questions into the knowledge graph.
+    Analyze {
+        #[command(flatten)]
+        args: commands::analyze::AnalyzeArgs,
+    },
+    /// Map knowledge/thoughts markdown documents into the knowledge graph.
+    Sync {
+        #[command(flatten)]
+        args: commands::sync::SyncArgs,
+    },
+    /// Review agent-submitted change proposals.
+    Proposals {
+        #[command(subcommand)]
+        command: commands::proposals::ProposalsCommand,
+    },
+    /// Render shared thoughts, knowledge, and matter into a static site.
+    Publish {
+        #[command(flatten)]
+        args: commands::publish::PublishArgs,
+    },
+    /// Run the HTTP API used by the desktop app and external agents.
+    Daemon {
+        #[command(subcommand)]
+        command: commands::daemon::DaemonCommand,
+    },
+    /// Bulk import/export the knowledge graph.
+    Graph {
+        #[command(subcommand)]
+        command: commands::graph::GraphCommand,
+    },
+    /// Manage teams and the active team.
+    Team {
+        #[command(subcommand)]
+        command: commands::team::TeamCommand,
+    },
+    /// Manage sub-projects sharing this workspace's `.pmsynapse` root.
+    Project {
+        #[command(subcommand)]
+        command: commands::project::ProjectCommand,
+    },
+    /// Manage IDLC workflow templates.
+    Templates {
+        #[command(subcommand)]
+        command: commands::templates::TemplatesCommand,
+    },
+    /// Quick health check for the current workspace.
+    Status {
+        #[command(flatten)]
+        args: commands::status::StatusArgs,
+    },
+    /// Inspect the active team's IDLC stage/status configuration.
+    Idlc {
+        #[command(subcommand)]
+        command: commands::idlc::IdlcCommand,
+    },
+    /// Summarize or clear the local metrics file (opt-in, see
+    /// `defaults.telemetry_enabled`).
+    Stats {
+        #[command(subcommand)]
+        command: commands::stats::StatsCommand,
+    },
+    /// Print a shell completion script to stdout.
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Generate a man page per subcommand into a directory.
+    Manpages { dir: PathBuf },
+    /// Print the active workspace's template names, for shell completion.
+    #[command(hide = true, name = "__complete-templates")]
+    CompleteTemplates,
+    /// Diagnose common environment problems (permissions, missing tools,
+    /// stale daemon state, an unopenable graph database).
+    Doctor,
+}
+
+/// A short, human-readable label for the notification fired by
+/// [`notify::notify`] — one word per top-level subcommand, ignoring which
+/// nested subcommand or flags were used.
+fn command_label(command: &Command) -> &'static str {
+    match command {
+        Command::Matter { .. } => "matter",
+        Command::Repo { .. } => "repo",
+        Command::Know { .. } => "know",
+        Command::Thoughts { .. } => "thoughts",
+        Command::Config { .. } => "config",
+        Command::Llm { .. } => "llm",
+        Command::Claude { .. } => "claude",
+        Command::Analyze { .. } => "analyze",
+        Command::Sync { .. } => "sync",
+        Command::Proposals { .. } => "proposals",
+        Command::Publish { .. } => "publish",
+        Command::Daemon { .. } => "daemon",
+        Command::Graph { .. } => "graph",
+        Command::Team { .. } => "team",
+        Command::Project { .. } => "project",
+        Command::Templates { .. } => "templates",
+        Command::Status { .. } => "status",
+        Command::Idlc { .. } => "idlc",
+        Command::Stats { .. } => "stats",
+        Command::Completions { .. } => "completions",
+        Command::Manpages { .. } => "manpages",
+        Command::CompleteTemplates => "complete-templates",
+        Command::Doctor => "doctor",
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let output = cli.output;
+    let verbosity = ui::Verbosity::from_flags(cli.quiet, cli.verbose);
+    let console = ui::Console::new(verbosity);
+
+    ui::init_color(cli.no_color);
+    if verbosity > ui::Verbosity::Quiet && ui::should_print_banner(ui::stdout_is_terminal(), output) {
+        ui::print_banner();
+    }
+
+    if verbosity > ui::Verbosity::Quiet {
+        if let Ok(Some(stale)) = snps_core::global_state::check_global_schema() {
+            eprintln!(
+                "warning: ~/.pmsynapse was last written by a newer snps (schema v{}, this binary supports up to v{}) — upgrade snps",
+                stale.recorded_version, stale.supported_version
+            );
+        }
+    }
+
+    let notify_threshold = notify::threshold();
+    let notify_label = command_label(&cli.command);
+    let started = std::time::Instant::now();
+    let telemetry_handle = telemetry::Telemetry::new();
+
+    let result = match cli.command {
+        Command::Matter { command } => commands::matter::run(command, output),
+        Command::Repo { command } => commands::repo::run(command, output),
+        Command::Know { command } => commands::know::run(command, output),
+        Command::Thoughts { command } => commands::thoughts::run(command),
+        Command::Config { command } => commands::config::run(command),
+        Command::Llm { command } => commands::llm::run(command),
+        Command::Claude { command } => commands::claude::run(command, output),
+        Command::Analyze { args } => commands::analyze::run(args),
+        Command::Sync { args } => commands::sync::run(args, &console, &telemetry_handle),
+        Command::Proposals { command } => commands::proposals::run(command),
+        Command::Publish { args } => commands::publish::run(args, &console),
+        Command::Daemon { command } => commands::daemon::run(command, output),
+        Command::Graph { command } => commands::graph::run(command),
+        Command::Team { command } => commands::team::run(command),
+        Command::Project { command } => commands::project::run(command),
+        Command::Templates { command } => commands::templates::run(command),
+        Command::Status { args } => commands::status::run(args),
+        Command::Idlc { command } => commands::idlc::run(command, output),
+        Command::Stats { command } => commands::stats::run(command, &console),
+        Command::Completions { shell } => commands::completions::run(shell),
+        Command::Manpages { dir } => commands::completions::run_manpages(&dir),
+        Command::CompleteTemplates => commands::completions::run_complete_templates(),
+        Command::Doctor => commands::doctor::run(),
+    };
+
+    let elapsed = started.elapsed();
+    telemetry::record(notify_label, elapsed, result.is_ok(), &telemetry_handle);
+    if notify::should_notify(notify_threshold, elapsed, output, ui::stdout_is_terminal()) {
+        notify::notify(notify_label, result.is_ok(), elapsed);
+    }
+
+    if let Err(err) = result {
+        error::print_error(&err, output);
+        std::process::exit(error::exit_code_for(&err));
+    }
+}
@@ -0,0 +1,89 @@
+//! Desktop notification when a command finishes, opt-in via
+//! `defaults.notify_after_seconds`. Lives entirely in `main()`'s dispatch
+//! wrapper (see [`crate::main`]) rather than in individual `commands::*::run`
+//! functions, so this is the only place that needs to know about it. The
+//! desktop app doesn't go through this module at all — it fires its own
+//! notification via `tauri-plugin-notification` from wherever it invokes the
+//! equivalent operation, since `notify-rust` targets a CLI process, not a
+//! Tauri webview.
+
+use crate::output::OutputFormat;
+use std::time::Duration;
+
+/// Whether a just-finished command should fire a desktop notification: a
+/// threshold is configured, the command ran at least that long, and we're
+/// rendering human text to a real terminal. `--output json` and a
+/// non-interactive shell (no TTY — a script, a cron job, a pipe) must never
+/// pop a notification nobody asked for and nobody's there to see.
+pub fn should_notify(threshold: Option<Duration>, elapsed: Duration, output: OutputFormat, is_terminal: bool) -> bool {
+    let Some(threshold) = threshold else { return false };
+    is_terminal && output == OutputFormat::Text && elapsed >= threshold
+}
+
+/// The configured threshold, if any. A workspace that can't be discovered or
+/// a config layer that fails to load just means notifications stay off —
+/// same as if `defaults.notify_after_seconds` were never set. This must
+/// never be the reason a command fails.
+pub fn threshold() -> Option<Duration> {
+    let workspace = snps_core::Workspace::discover_from_cwd().ok()?;
+    let merged = snps_core::config::load_merged_config(&workspace).ok()?;
+    merged.config.notify_after_seconds.map(Duration::from_secs)
+}
+
+/// Fire the notification. Best-effort: a platform without a notification
+/// daemon (headless CI, a minimal container) shouldn't make the command
+/// itself look like it failed, so a send error is only ever warned about.
+pub fn notify(command: &str, succeeded: bool, elapsed: Duration) {
+    let summary = format!("snps {command}");
+    let body = if succeeded {
+        format!("finished in {}", format_elapsed(elapsed))
+    } else {
+        format!("failed after {}", format_elapsed(elapsed))
+    };
+
+    if let Err(e) = notify_rust::Notification::new().summary(&summary).body(&body).show() {
+        eprintln!("warning: couldn't send desktop notification: {e}");
+    }
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else {
+        format!("{}m{}s", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn never_notifies_without_a_configured_threshold() {
+        assert!(!should_notify(None, Duration::from_secs(1000), OutputFormat::Text, true));
+    }
+
+    #[test]
+    fn never_notifies_under_json_output() {
+        assert!(!should_notify(Some(Duration::from_secs(1)), Duration::from_secs(10), OutputFormat::Json, true));
+    }
+
+    #[test]
+    fn never_notifies_off_a_terminal() {
+        assert!(!should_notify(Some(Duration::from_secs(1)), Duration::from_secs(10), OutputFormat::Text, false));
+    }
+
+    #[test]
+    fn notifies_once_elapsed_reaches_the_threshold() {
+        let threshold = Some(Duration::from_secs(30));
+        assert!(!should_notify(threshold, Duration::from_secs(29), OutputFormat::Text, true));
+        assert!(should_notify(threshold, Duration::from_secs(30), OutputFormat::Text, true));
+    }
+
+    #[test]
+    fn formats_elapsed_time_for_the_notification_body() {
+        assert_eq!(format_elapsed(Duration::from_secs(42)), "42s");
+        assert_eq!(format_elapsed(Duration::from_secs(125)), "2m5s");
+    }
+}
@@ -0,0 +1,33 @@
+//! Shared plumbing for commands that support both human-readable text and
+//! machine-readable JSON via the global `--output` flag. Most commands
+//! still print prose directly; this is for the ones that have been
+//! converted so scripts can rely on their output.
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+pub struct OutputWriter {
+    format: OutputFormat,
+}
+
+impl OutputWriter {
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format }
+    }
+
+    /// Print `value` as pretty JSON in `Json` mode, or hand it to
+    /// `render_text` for a human-readable rendering in `Text` mode.
+    pub fn emit<T: Serialize>(&self, value: &T, render_text: impl FnOnce(&T)) -> anyhow::Result<()> {
+        match self.format {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(value)?),
+            OutputFormat::Text => render_text(value),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+//! Opt-in local metrics: one JSON line per invocation appended to
+//! `~/.pmsynapse/metrics.jsonl`, summarized by `snps stats`. Scoped the
+//! same way [`crate::notify`] is: generic command/duration/success capture
+//! lives entirely in `main()`'s dispatch wrapper (see [`crate::main`]), so
+//! most commands need no changes at all. A [`Telemetry`] handle lets a
+//! command attach its own counts (files scanned, nodes written, and so
+//! on) on top of that — only `sync` does so today, the same staged
+//! migration [`crate::ui::Console`]'s doc comment describes for itself.
+//!
+//! Off by default (`defaults.telemetry_enabled`, unset means `false`):
+//! nothing is read or written unless a config layer turns it on.
+
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Per-command counts a running command can accumulate before the
+/// dispatch wrapper records the finished invocation. Interior mutability
+/// so `commands::*::run` functions can take `&Telemetry` without needing
+/// `mut` threaded through every call in between.
+#[derive(Debug, Default)]
+pub struct Telemetry {
+    counts: RefCell<BTreeMap<String, u64>>,
+}
+
+impl Telemetry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&self, key: &str, n: u64) {
+        *self.counts.borrow_mut().entry(key.to_string()).or_insert(0) += n;
+    }
+
+    fn snapshot(&self) -> BTreeMap<String, u64> {
+        self.counts.borrow().clone()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricRecord {
+    pub command: String,
+    /// When the record was written, not when the command started —
+    /// there's no caller-supplied start timestamp to log against, and
+    /// finish time is close enough for the day-bucketed `busiest_days`
+    /// this feeds.
+    pub at_unix: u64,
+    pub duration_ms: u64,
+    pub success: bool,
+    #[serde(default)]
+    pub counts: BTreeMap<String, u64>,
+}
+
+/// Whether `defaults.telemetry_enabled` is on for the current workspace.
+/// A workspace that can't be discovered or a config layer that fails to
+/// load just means telemetry stays off — same as
+/// [`crate::notify::threshold`]'s reasoning: this must never be the
+/// reason a command fails.
+pub fn enabled() -> bool {
+    let Ok(workspace) = snps_core::Workspace::discover_from_cwd() else { return false };
+    snps_core::config::load_merged_config(&workspace).map(|m| m.config.telemetry_enabled).unwrap_or(false)
+}
+
+pub fn metrics_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".pmsynapse").join("metrics.jsonl"))
+}
+
+/// Append one record for a just-finished command. Best-effort, like
+/// [`crate::notify::notify`]: an unwritable home directory or a full disk
+/// must not make the command itself look like it failed.
+pub fn record(command: &str, elapsed: Duration, success: bool, telemetry: &Telemetry) {
+    let Some(path) = metrics_path() else { return };
+    let record = MetricRecord {
+        command: command.to_string(),
+        at_unix: snps_core::time::now_unix(),
+        duration_ms: elapsed.as_millis() as u64,
+        success,
+        counts: telemetry.snapshot(),
+    };
+    if let Err(e) = append_record_if_enabled(&path, enabled(), &record) {
+        eprintln!("warning: couldn't write telemetry record: {e}");
+    }
+}
+
+/// The gated part of [`record`], taking `telemetry_enabled` explicitly
+/// (rather than calling [`enabled`] itself) so the off-by-default
+/// behavior is testable without discovering a real workspace or mutating
+/// process-wide environment state.
+fn append_record_if_enabled(path: &Path, telemetry_enabled: bool, record: &MetricRecord) -> std::io::Result<()> {
+    if !telemetry_enabled {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(record)?;
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Read every well-formed line of `path`; a missing file or a line that
+/// doesn't parse (a hand-edited file, a future binary's added field) is
+/// skipped rather than failing the whole read.
+pub fn load_records(path: &Path) -> Vec<MetricRecord> {
+    let Ok(contents) = std::fs::read_to_string(path) else { return Vec::new() };
+    contents.lines().filter_map(|line| serde_json::from_str(line).ok()).collect()
+}
+
+pub fn clear(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandStats {
+    pub command: String,
+    pub count: u64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub failure_rate: f64,
+}
+
+/// Per-command duration percentiles and failure rate, sorted by command
+/// name for stable output.
+pub fn summarize(records: &[MetricRecord]) -> Vec<CommandStats> {
+    let mut by_command: BTreeMap<&str, Vec<&MetricRecord>> = BTreeMap::new();
+    for record in records {
+        by_command.entry(&record.command).or_default().push(record);
+    }
+
+    by_command
+        .into_iter()
+        .map(|(command, records)| {
+            let mut durations: Vec<u64> = records.iter().map(|r| r.duration_ms).collect();
+            durations.sort_unstable();
+            let failures = records.iter().filter(|r| !r.success).count();
+            CommandStats {
+                command: command.to_string(),
+                count: records.len() as u64,
+                p50_ms: percentile(&durations, 0.50),
+                p95_ms: percentile(&durations, 0.95),
+                failure_rate: failures as f64 / records.len() as f64,
+            }
+        })
+        .collect()
+}
+
+/// Nearest-rank percentile over an already-sorted slice. `0` for an empty
+/// slice rather than panicking — a command with a record but somehow no
+/// durations shouldn't crash the summary.
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((sorted.len() as f64) * p).ceil() as usize;
+    let index = rank.saturating_sub(1).min(sorted.len() - 1);
+    sorted[index]
+}
+
+/// The `top_n` days with the most recorded invocations, busiest first,
+/// ties broken by date so output is stable.
+pub fn busiest_days(records: &[MetricRecord], top_n: usize) -> Vec<(String, u64)> {
+    let mut by_day: BTreeMap<String, u64> = BTreeMap::new();
+    for record in records {
+        *by_day.entry(snps_core::time::date_string(record.at_unix)).or_insert(0) += 1;
+    }
+    let mut days: Vec<(String, u64)> = by_day.into_iter().collect();
+    days.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    days.truncate(top_n);
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(command: &str, at_unix: u64, duration_ms: u64, success: bool) -> MetricRecord {
+        MetricRecord { command: command.to_string(), at_unix, duration_ms, success, counts: BTreeMap::new() }
+    }
+
+    #[test]
+    fn append_record_writes_nothing_when_disabled() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("metrics.jsonl");
+        append_record_if_enabled(&path, false, &record("sync", 100, 50, true)).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn append_record_then_load_round_trips_when_enabled() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("metrics.jsonl");
+        append_record_if_enabled(&path, true, &record("sync", 100, 50, true)).unwrap();
+        append_record_if_enabled(&path, true, &record("sync", 200, 75, false)).unwrap();
+
+        let loaded = load_records(&path);
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].command, "sync");
+        assert_eq!(loaded[1].duration_ms, 75);
+    }
+
+    #[test]
+    fn load_records_skips_malformed_lines() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("metrics.jsonl");
+        std::fs::write(&path, "not json\n{\"command\":\"sync\",\"at_unix\":1,\"duration_ms\":1,\"success\":true}\n").unwrap();
+        assert_eq!(load_records(&path).len(), 1);
+    }
+
+    #[test]
+    fn load_records_returns_empty_for_missing_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(load_records(&tmp.path().join("nope.jsonl")).is_empty());
+    }
+
+    #[test]
+    fn clear_removes_the_file_and_is_idempotent() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("metrics.jsonl");
+        std::fs::write(&path, "{}\n").unwrap();
+        clear(&path).unwrap();
+        assert!(!path.exists());
+        clear(&path).unwrap();
+    }
+
+    #[test]
+    fn summarize_computes_percentiles_and_failure_rate_per_command() {
+        let records = vec![
+            record("sync", 1, 10, true),
+            record("sync", 2, 20, true),
+            record("sync", 3, 30, false),
+            record("status", 4, 5, true),
+        ];
+        let stats = summarize(&records);
+        assert_eq!(stats.len(), 2);
+        let sync = stats.iter().find(|s| s.command == "sync").unwrap();
+        assert_eq!(sync.count, 3);
+        assert_eq!(sync.p50_ms, 20);
+        assert_eq!(sync.p95_ms, 30);
+        assert!((sync.failure_rate - (1.0 / 3.0)).abs() < 1e-9);
+        let status = stats.iter().find(|s| s.command == "status").unwrap();
+        assert_eq!(status.failure_rate, 0.0);
+    }
+
+    #[test]
+    fn percentile_of_empty_slice_is_zero() {
+        assert_eq!(percentile(&[], 0.5), 0);
+    }
+
+    #[test]
+    fn busiest_days_ranks_by_count_then_date() {
+        let day1 = 0u64;
+        let day2 = 86_400u64;
+        let records = vec![
+            record("sync", day1, 1, true),
+            record("sync", day1, 1, true),
+            record("sync", day2, 1, true),
+        ];
+        let days = busiest_days(&records, 5);
+        assert_eq!(days[0], (snps_core::time::date_string(day1), 2));
+        assert_eq!(days[1], (snps_core::time::date_string(day2), 1));
+    }
+}
@@ -0,0 +1,171 @@
+//! Interactive-terminal presentation: the startup banner and color output,
+//! both suppressed when stdout isn't a TTY so piped/scripted invocations
+//! (`--output json`, `| jq`, etc.) get clean, parseable output.
+
+use crate::output::OutputFormat;
+use std::io::IsTerminal;
+
+/// Whether stdout is attached to a terminal right now. A thin wrapper so
+/// call sites don't reach for `std::io::stdout()` directly.
+pub fn stdout_is_terminal() -> bool {
+    std::io::stdout().is_terminal()
+}
+
+/// Whether color escape codes should be emitted, given the `--no-color`
+/// flag, the `NO_COLOR` convention (https://no-color.org), and whether
+/// stdout is a TTY at all. Takes every input explicitly so the decision
+/// itself is testable without mutating process environment or a real
+/// terminal.
+pub fn color_enabled(no_color_flag: bool, no_color_env_set: bool, is_terminal: bool) -> bool {
+    is_terminal && !no_color_flag && !no_color_env_set
+}
+
+/// Apply the `--no-color` flag and `NO_COLOR` env var to the `colored`
+/// crate's global override, based on the real environment.
+pub fn init_color(no_color_flag: bool) {
+    let enabled = color_enabled(
+        no_color_flag,
+        std::env::var_os("NO_COLOR").is_some(),
+        stdout_is_terminal(),
+    );
+    colored::control::set_override(enabled);
+}
+
+/// Whether the startup banner should print: only for an interactive
+/// terminal rendering text output. JSON output and piped/redirected
+/// stdout never see it, so a script's stdout carries only the command's
+/// primary output.
+pub fn should_print_banner(is_terminal: bool, output: OutputFormat) -> bool {
+    is_terminal && output == OutputFormat::Text
+}
+
+/// Print the greeting banner. Only call this after checking
+/// [`should_print_banner`].
+pub fn print_banner() {
+    println!("snps — AI-enabled project management with knowledge graphs");
+}
+
+/// How chatty a command's output should be, derived once from `-q` and
+/// stacked `-v` in `main()` and threaded down instead of each command
+/// checking flags itself. Ordered so `verbosity > Verbosity::Quiet` reads
+/// naturally as "not quiet".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+    Trace,
+}
+
+impl Verbosity {
+    /// `-q` wins outright; otherwise each `-v` steps up one level.
+    pub fn from_flags(quiet: bool, verbose_count: u8) -> Self {
+        if quiet {
+            return Verbosity::Quiet;
+        }
+        match verbose_count {
+            0 => Verbosity::Normal,
+            1 => Verbosity::Verbose,
+            _ => Verbosity::Trace,
+        }
+    }
+}
+
+/// A single place that enforces `Verbosity` so command modules stop
+/// deciding for themselves whether a `println!` should fire. Decorative
+/// and progress output goes through here; a command's actual result
+/// (the reason it was run) should still print unconditionally so `-q`
+/// silences chatter without silencing answers.
+///
+/// Only `sync`, the command called out in the request this shipped with,
+/// has been migrated so far. The rest of the CLI still prints directly —
+/// routing every command through `Console` is mechanical follow-up, not
+/// something to rush through in one pass without a compiler to check it.
+pub struct Console {
+    verbosity: Verbosity,
+}
+
+impl Console {
+    pub fn new(verbosity: Verbosity) -> Self {
+        Console { verbosity }
+    }
+
+    pub fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+
+    /// Progress/decorative chatter, suppressed by `-q`.
+    pub fn info(&self, message: impl std::fmt::Display) {
+        if self.verbosity > Verbosity::Quiet {
+            println!("{message}");
+        }
+    }
+
+    /// A positive outcome worth calling out, suppressed by `-q`.
+    pub fn success(&self, message: impl std::fmt::Display) {
+        if self.verbosity > Verbosity::Quiet {
+            println!("{message}");
+        }
+    }
+
+    /// Debug-style detail, shown only once `-v` (or higher) is set.
+    pub fn verbose(&self, message: impl std::fmt::Display) {
+        if self.verbosity >= Verbosity::Verbose {
+            println!("{message}");
+        }
+    }
+
+    /// Always printed to stderr: `-q` mutes decoration, not problems.
+    pub fn warn(&self, message: impl std::fmt::Display) {
+        eprintln!("warning: {message}");
+    }
+
+    /// Always printed to stderr: `-q` mutes decoration, not problems.
+    pub fn error(&self, message: impl std::fmt::Display) {
+        eprintln!("error: {message}");
+    }
+
+    /// A command's primary output — always printed, quiet or not, so `-q`
+    /// only strips chatter around the answer rather than the answer itself.
+    pub fn result(&self, message: impl std::fmt::Display) {
+        println!("{message}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn banner_only_prints_for_interactive_text_output() {
+        assert!(should_print_banner(true, OutputFormat::Text));
+        assert!(!should_print_banner(false, OutputFormat::Text));
+        assert!(!should_print_banner(true, OutputFormat::Json));
+        assert!(!should_print_banner(false, OutputFormat::Json));
+    }
+
+    #[test]
+    fn color_disabled_by_flag_env_or_non_terminal() {
+        assert!(color_enabled(false, false, true));
+        assert!(!color_enabled(true, false, true));
+        assert!(!color_enabled(false, true, true));
+        assert!(!color_enabled(false, false, false));
+    }
+
+    #[test]
+    fn verbosity_from_flags() {
+        assert_eq!(Verbosity::from_flags(false, 0), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(false, 1), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(false, 5), Verbosity::Trace);
+        assert_eq!(Verbosity::from_flags(true, 0), Verbosity::Quiet);
+        // `-q` wins even if `-v` was also passed.
+        assert_eq!(Verbosity::from_flags(true, 3), Verbosity::Quiet);
+    }
+
+    #[test]
+    fn verbosity_ordering_controls_gating() {
+        assert!(Verbosity::Quiet < Verbosity::Normal);
+        assert!(Verbosity::Normal < Verbosity::Verbose);
+        assert!(Verbosity::Verbose < Verbosity::Trace);
+    }
+}
@@ -0,0 +1,41 @@
+//! `snps idlc validate` and `snps idlc show`, ported onto the shared
+//! harness. `idlc report` and `idlc link` aren't covered here yet — they
+//! write files/graph state rather than just printing a summary, so they
+//! fit better alongside a future test that also checks the file/graph
+//! side effects rather than only stdout.
+
+mod support;
+
+use support::Harness;
+
+#[test]
+fn idlc_validate_reports_stages_and_transitions() {
+    let harness = Harness::new();
+    harness.init_workspace();
+    harness.write(
+        ".pmsynapse/teams/default/idlc.yaml",
+        "stages: [backlog, doing, done]\nstatuses: [open, closed]\ntransitions: []\n",
+    );
+
+    let output = harness.command(&["idlc", "validate"]).output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    support::assert_golden("idlc_validate.txt", &harness.normalize_output(&stdout));
+}
+
+#[test]
+fn idlc_show_prints_item_and_links() {
+    let harness = Harness::new();
+    harness.init_workspace();
+    harness.write(
+        ".pmsynapse/teams/default/idlc-items.yaml",
+        "items:\n  - id: itm-1\n    title: Investigate flaky test\n    stage: backlog\n    status: open\n",
+    );
+
+    let output = harness.command(&["idlc", "show", "itm-1"]).output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    support::assert_golden("idlc_show.txt", &harness.normalize_output(&stdout));
+}
@@ -0,0 +1,26 @@
+//! `snps matter create` and `snps matter list`, ported onto the shared
+//! harness. Run as one flow since `list` has nothing to show without a
+//! document already created.
+
+mod support;
+
+use support::Harness;
+
+#[test]
+fn matter_create_and_list_flow() {
+    let harness = Harness::new();
+    harness.init_workspace();
+
+    let create_output = harness
+        .command(&["matter", "create", "--type", "document", "Rollout plan for search index", "--force"])
+        .output()
+        .unwrap();
+    assert!(create_output.status.success());
+    let create_stdout = String::from_utf8(create_output.stdout).unwrap();
+    support::assert_golden("matter_create.txt", &harness.normalize_output(&create_stdout));
+
+    let list_output = harness.command(&["matter", "list"]).output().unwrap();
+    assert!(list_output.status.success());
+    let list_stdout = String::from_utf8(list_output.stdout).unwrap();
+    support::assert_golden("matter_list.txt", &harness.normalize_output(&list_stdout));
+}
@@ -0,0 +1,19 @@
+//! `snps status` against a freshly scaffolded, otherwise-empty workspace:
+//! every probe should degrade cleanly (no idlc.yaml yet) or report all
+//! zeros, rather than erroring the whole command.
+
+mod support;
+
+use support::Harness;
+
+#[test]
+fn status_text_on_empty_workspace() {
+    let harness = Harness::new();
+    harness.init_workspace();
+
+    let output = harness.command(&["status"]).output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    support::assert_golden("status_empty_workspace.txt", &harness.normalize_output(&stdout));
+}
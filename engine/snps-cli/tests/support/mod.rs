@@ -0,0 +1,158 @@
+//! Shared harness for CLI integration tests: a scratch `HOME`/cwd per
+//! test, a fake bin directory prepended to `PATH` for stubbing external
+//! tools (editors, `rg`, git hooks helpers), and golden-file comparison
+//! with ANSI codes and known-flaky substrings (ages, timestamps, the
+//! crate version) normalized out first.
+//!
+//! Every other integration test file should `mod support;` and build on
+//! [`Harness`] rather than shelling out to `std::process::Command`
+//! directly, so `HOME`/cwd isolation and PATH stubbing stay consistent
+//! across files.
+
+use assert_cmd::Command;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A fully isolated environment for one `snps` invocation (or a short
+/// sequence of them): its own `HOME`, its own current directory, and its
+/// own leading `PATH` entry for stub binaries. Kept alive for the
+/// lifetime of a test so the temp directories aren't cleaned up early.
+pub struct Harness {
+    pub home: TempDir,
+    pub cwd: TempDir,
+    pub bin: TempDir,
+}
+
+impl Harness {
+    /// Set up a fresh, empty environment. Does not scaffold a
+    /// `.pmsynapse` workspace — call [`Harness::init_workspace`] for that,
+    /// or scaffold your own layout for tests that need something
+    /// different.
+    pub fn new() -> Self {
+        Harness {
+            home: tempfile::tempdir().unwrap(),
+            cwd: tempfile::tempdir().unwrap(),
+            bin: tempfile::tempdir().unwrap(),
+        }
+    }
+
+    /// Write the same minimal `.pmsynapse` layout `exit_codes.rs` uses:
+    /// a `default` team directory and an empty root config. Individual
+    /// tests add whatever `idlc.yaml` / `idlc-items.yaml` / thoughts
+    /// config they need on top.
+    pub fn init_workspace(&self) -> &Self {
+        fs::create_dir_all(self.cwd.path().join(".pmsynapse/teams/default")).unwrap();
+        fs::write(self.cwd.path().join(".pmsynapse/config.yaml"), "").unwrap();
+        self
+    }
+
+    /// Write `contents` to `relative_path` inside the scratch cwd,
+    /// creating parent directories as needed.
+    pub fn write(&self, relative_path: &str, contents: &str) -> &Self {
+        let path = self.cwd.path().join(relative_path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+        self
+    }
+
+    /// Install a stub external tool: a `#!/bin/sh` script named `name` on
+    /// the fake bin directory prepended to `PATH`, so commands that shell
+    /// out (an editor, `rg`, ...) hit this instead of whatever happens to
+    /// be installed on the machine running the tests.
+    #[cfg(unix)]
+    pub fn stub_bin(&self, name: &str, script_body: &str) -> &Self {
+        use std::os::unix::fs::PermissionsExt;
+        let path = self.bin.path().join(name);
+        fs::write(&path, format!("#!/bin/sh\n{script_body}\n")).unwrap();
+        let mut perms = fs::metadata(&path).unwrap().permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&path, perms).unwrap();
+        self
+    }
+
+    /// Build a `snps` invocation scoped to this harness: `HOME` and the
+    /// current directory point at the scratch dirs, and `PATH` starts
+    /// with the fake bin dir so stubs shadow real binaries. The real
+    /// `PATH` is kept behind it so commands this harness doesn't stub
+    /// (like a shell used internally) still resolve.
+    pub fn command(&self, args: &[&str]) -> Command {
+        let mut cmd = Command::cargo_bin("snps").unwrap();
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let mut new_path = self.bin.path().as_os_str().to_owned();
+        new_path.push(":");
+        new_path.push(path);
+        cmd.env_clear()
+            .env("HOME", self.home.path())
+            .env("PATH", new_path)
+            .current_dir(self.cwd.path())
+            .args(args);
+        cmd
+    }
+
+    pub fn path(&self, relative_path: &str) -> PathBuf {
+        self.cwd.path().join(relative_path)
+    }
+
+    /// Run the full [`normalize`] pipeline on `output`, plus one more
+    /// substitution specific to this harness: the scratch `cwd`/`home`
+    /// paths are unique per test run (`tempfile` picks a fresh directory
+    /// name every time), so any command that prints one of them back
+    /// (`thoughts init`, `matter create`) would never match a golden file
+    /// literally. Replace both with stable placeholders first.
+    pub fn normalize_output(&self, output: &str) -> String {
+        let out = output.replace(&self.cwd.path().display().to_string(), "<CWD>");
+        let out = out.replace(&self.home.path().display().to_string(), "<HOME>");
+        normalize(&out)
+    }
+}
+
+/// Strip ANSI escape sequences (color, cursor movement) from captured
+/// output, so a test run against a real terminal's `colored` overrides
+/// doesn't diverge from one run headless.
+pub fn strip_ansi(input: &str) -> String {
+    let ansi = regex::Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").unwrap();
+    ansi.replace_all(input, "").into_owned()
+}
+
+/// Blank out substrings that are true but change from run to run:
+/// relative ages ("2h ago", "3 days ago"), Unix timestamps, and the
+/// crate's own version number (which golden files shouldn't need to be
+/// touched for on every release bump).
+pub fn normalize_flaky(input: &str) -> String {
+    let age = regex::Regex::new(r"\b\d+(\.\d+)? ?(second|minute|hour|day|week|month|year)s? ago\b").unwrap();
+    let unix_time = regex::Regex::new(r"\bunix time \d+\b").unwrap();
+    let version = regex::Regex::new(r"\bsnps \d+\.\d+\.\d+(-[0-9A-Za-z.-]+)?\b").unwrap();
+    let date = regex::Regex::new(r"\b\d{4}-\d{2}-\d{2}\b").unwrap();
+
+    let out = age.replace_all(input, "<AGE>").into_owned();
+    let out = unix_time.replace_all(&out, "unix time <TIMESTAMP>").into_owned();
+    let out = version.replace_all(&out, "snps <VERSION>").into_owned();
+    date.replace_all(&out, "<DATE>").into_owned()
+}
+
+/// Full normalization pipeline applied before every golden-file
+/// comparison: strip ANSI, then blank out flaky substrings.
+pub fn normalize(input: &str) -> String {
+    normalize_flaky(&strip_ansi(input))
+}
+
+/// Compare `actual` (already run through [`normalize`] by the caller)
+/// against the golden file at `tests/golden/<name>`. Set
+/// `SNPS_UPDATE_GOLDEN=1` to write `actual` as the new golden contents
+/// instead of asserting, for intentional output changes.
+pub fn assert_golden(name: &str, actual: &str) {
+    let path = golden_path(name);
+    if std::env::var_os("SNPS_UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("no golden file at {} (run with SNPS_UPDATE_GOLDEN=1 to create it)", path.display()));
+    assert_eq!(expected, actual, "output for '{name}' doesn't match its golden file at {}", path.display());
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(name)
+}
@@ -0,0 +1,32 @@
+//! `snps thoughts init`, `new`, and `list`, ported onto the shared
+//! harness. Run as one flow (init the tree, seed a note, list it back)
+//! since `list` has nothing to show without the other two having run
+//! first.
+
+mod support;
+
+use support::Harness;
+
+#[test]
+fn thoughts_init_new_and_list_flow() {
+    let harness = Harness::new();
+    harness.init_workspace();
+
+    let init_output = harness.command(&["thoughts", "init"]).output().unwrap();
+    assert!(init_output.status.success());
+    let init_stdout = String::from_utf8(init_output.stdout).unwrap();
+    support::assert_golden("thoughts_init.txt", &harness.normalize_output(&init_stdout));
+
+    let new_output = harness
+        .command(&["thoughts", "new", "note", "Investigate slow CI", "--force"])
+        .output()
+        .unwrap();
+    assert!(new_output.status.success());
+    let new_stdout = String::from_utf8(new_output.stdout).unwrap();
+    support::assert_golden("thoughts_new.txt", &harness.normalize_output(&new_stdout));
+
+    let list_output = harness.command(&["thoughts", "list"]).output().unwrap();
+    assert!(list_output.status.success());
+    let list_stdout = String::from_utf8(list_output.stdout).unwrap();
+    support::assert_golden("thoughts_list.txt", &harness.normalize_output(&list_stdout));
+}